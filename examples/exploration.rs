@@ -1,6 +1,7 @@
 use nalgebra::vector;
 use sembas::prelude::*;
-use sembas::search::global_search::{MonteCarloSearch, SearchFactory};
+use sembas::search::find_initial_boundary_pair;
+use sembas::search::global_search::MonteCarloSearch;
 use sembas::search::surfacing::binary_surface_search;
 use sembas::sps::Sphere;
 
@@ -13,10 +14,9 @@ fn main() {
 
     // Acquire an initial boundary pair using some global search solution
     println!("Finding initial boundary pair.");
-    //      v--Stores intermediate results
-    let mut history = vec![];
-
-    let b_pair = find_initial_boundary_pair(&mut classifier, &mut history, 256).unwrap();
+    let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
+    //           v--Stores intermediate results
+    let (b_pair, _history) = find_initial_boundary_pair(&mut search, &mut classifier, 256).unwrap();
     println!("Pair found!");
 
     // Use the boundary pair to find the surface using binary surface search
@@ -63,47 +63,3 @@ fn main() {
     println!("BLE Count: {ble_count}");
     println!("OOB Count: {oob_count}");
 }
-
-fn find_initial_boundary_pair<const N: usize, C: Classifier<N>>(
-    classifier: &mut C,
-    history: &mut Vec<Sample<N>>,
-    max_samples: i32,
-) -> Result<BoundaryPair<N>> {
-    let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
-    let mut take_sample = move || {
-        let p = search.sample();
-        classifier
-            .classify(p)
-            .expect("Invalid sample. Bad global search domain?")
-    };
-
-    let mut t0 = None;
-    let mut x0 = None;
-    let mut i = 0;
-
-    while (t0.is_none() || x0.is_none()) && i < max_samples {
-        let sample = take_sample();
-        match sample {
-            Sample::WithinMode(t) => {
-                if t0.is_none() {
-                    t0 = Some(t)
-                }
-            }
-            Sample::OutOfMode(x) => {
-                if x0.is_none() {
-                    x0 = Some(x)
-                }
-            }
-        }
-
-        history.push(sample);
-
-        i += 1;
-    }
-
-    if let (Some(t), Some(x)) = (t0, x0) {
-        Ok(BoundaryPair::new(t, x))
-    } else {
-        Err(SamplingError::MaxSamplesExceeded)
-    }
-}
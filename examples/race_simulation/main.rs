@@ -7,13 +7,13 @@ use std::{
 
 use sembas::{
     api::SembasSession,
-    boundary_tools::estimation::{approx_mc_volume, approx_surface, PredictionMode},
-    prelude::{bs_adherer::BinarySearchAdhererFactory, *},
-    search::{global_search::*, surfacing::binary_surface_search},
-    structs::{
-        messagse::{MSG_PHASE_BOUNDARY_EXPL, MSG_PHASE_GLOBAL_SEARCH, MSG_PHASE_SURFACE_SEARCH},
-        Classifier,
+    boundary_tools::{
+        estimation::{approx_mc_volume, approx_surface, PredictionMode},
+        io::BoundaryData,
     },
+    prelude::{bs_adherer::BinarySearchAdhererFactory, *},
+    search::{find_initial_boundary_pair, global_search::*, surfacing::binary_surface_search},
+    structs::messages::Phase,
 };
 use serde::{Deserialize, Serialize};
 
@@ -22,11 +22,6 @@ const NUM_BPOINTS: usize = 250;
 const JUMP_DIST: f64 = 0.07;
 const MAX_GS: u32 = 500;
 
-#[derive(Serialize, Deserialize)]
-struct BoundaryData {
-    boundary_points: Vec<Vec<f64>>,
-    boundary_surface: Vec<Vec<f64>>,
-}
 #[derive(Serialize, Deserialize)]
 struct Output {
     volume: Option<f64>,
@@ -36,7 +31,7 @@ fn main() {
     let domain = Domain::<NDIM>::normalized();
     // let mut classifier = RemoteClassifier::<NDIM>::bind("127.0.0.1:2000".to_string()).unwrap();
     let mut classifier =
-        SembasSession::<NDIM>::bind("127.0.0.1:2000".to_string(), MSG_PHASE_GLOBAL_SEARCH).unwrap();
+        SembasSession::<NDIM>::bind("127.0.0.1:2000".to_string(), Phase::GlobalSearch).unwrap();
 
     let mut i = 0;
     loop {
@@ -49,8 +44,9 @@ fn main() {
 fn run_test<const N: usize>(domain: &Domain<N>, classifier: &mut SembasSession<N>, i: u32) {
     println!("Finding initial pair...");
     // classifier
-    classifier.update_phase(MSG_PHASE_GLOBAL_SEARCH);
-    let bp = if let Ok(bp) = find_initial_boundary_pair(classifier, MAX_GS) {
+    classifier.update_phase(Phase::GlobalSearch);
+    let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
+    let bp = if let Ok((bp, _)) = find_initial_boundary_pair(&mut search, classifier, MAX_GS) {
         bp
     } else {
         println!("Ending early due to no boundary pair found during GS");
@@ -58,7 +54,7 @@ fn run_test<const N: usize>(domain: &Domain<N>, classifier: &mut SembasSession<N
     };
 
     println!("Establishing roots...");
-    classifier.update_phase(MSG_PHASE_SURFACE_SEARCH);
+    classifier.update_phase(Phase::SurfaceSearch);
 
     let root = binary_surface_search(JUMP_DIST, &bp, 100, classifier).unwrap();
 
@@ -71,21 +67,12 @@ fn run_test<const N: usize>(domain: &Domain<N>, classifier: &mut SembasSession<N
     println!("Starting boundary exploration");
 
     let mut expl = MeshExplorer::new(JUMP_DIST, root, JUMP_DIST * 0.8, adh_f);
-    classifier.update_phase(MSG_PHASE_BOUNDARY_EXPL);
-    while expl.boundary().len() < NUM_BPOINTS {
-        match expl.step(classifier) {
-            Ok(None) => {
-                println!("Ran out of boundary, ending exploration early.");
-                break;
-            }
-            Err(_) => (),
-            // Err(e) => println!("Got error: {e:?}"),
-            _ => (),
-        }
-    }
-
-    if expl.boundary().len() >= NUM_BPOINTS {
-        println!("Found desired number of boundary points!");
+    classifier.update_phase(Phase::BoundaryExploration);
+    let mut budget = Budget::new().with_max_boundary_points(NUM_BPOINTS).start();
+    match expl.explore(classifier, &mut budget) {
+        Ok(None) => println!("Ran out of boundary, ending exploration early."),
+        Ok(Some(_)) => println!("Found desired number of boundary points!"),
+        Err(e) => println!("Exploration failed: {e:?}"),
     }
 
     let volume = approx_mc_volume(
@@ -107,49 +94,6 @@ fn run_test<const N: usize>(domain: &Domain<N>, classifier: &mut SembasSession<N
     .expect("Unexpected failure while saving boundary");
 }
 
-fn find_initial_boundary_pair<const N: usize, C: Classifier<N>>(
-    classifier: &mut C,
-    max_samples: u32,
-) -> Result<BoundaryPair<N>> {
-    let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
-    let mut take_sample = move || {
-        let p = search.sample();
-        classifier
-            .classify(p)
-            .expect("Invalid sample. Bad global search domain?")
-    };
-
-    let mut t0 = None;
-    let mut x0 = None;
-    let mut i = 0;
-
-    while (t0.is_none() || x0.is_none()) && i < max_samples {
-        let sample = take_sample();
-        match sample {
-            Sample::WithinMode(t) => {
-                if t0.is_none() {
-                    println!("Found target");
-                    t0 = Some(t)
-                }
-            }
-            Sample::OutOfMode(x) => {
-                if x0.is_none() {
-                    println!("Found non-target");
-                    x0 = Some(x)
-                }
-            }
-        }
-
-        i += 1;
-    }
-
-    if let (Some(t), Some(x)) = (t0, x0) {
-        Ok(BoundaryPair::new(t, x))
-    } else {
-        Err(SamplingError::MaxSamplesExceeded)
-    }
-}
-
 fn save_boundary<const N: usize>(
     boundary: &Boundary<N>,
     path: &str,
@@ -165,23 +109,10 @@ fn save_boundary<const N: usize>(
         .truncate(true)
         .open(path)?;
 
-    let (boundary_points, boundary_surface): (Vec<Vec<f64>>, Vec<Vec<f64>>) = boundary
-        .iter()
-        .map(|hs| {
-            (
-                (*hs.b).iter().copied().collect(),
-                hs.n.iter().copied().collect(),
-            )
-        })
-        .unzip();
-
     f.write_all(
         serde_json::to_string_pretty(&Output {
             volume,
-            boundary: BoundaryData {
-                boundary_points,
-                boundary_surface,
-            },
+            boundary: BoundaryData::new(boundary),
         })?
         .as_bytes(),
     )?;
@@ -0,0 +1,58 @@
+use std::cell::Cell;
+
+use nalgebra::vector;
+use sembas::boundary_tools::reacquisition::{reacquire_all_binary, reacquire_all_incremental};
+use sembas::prelude::*;
+use sembas::search::find_initial_boundary_pair;
+use sembas::search::global_search::MonteCarloSearch;
+use sembas::search::surfacing::binary_surface_search;
+use sembas::sps::Sphere;
+
+fn main() {
+    let d = 0.05;
+    let max_err = d / 10.0;
+
+    // Explore the original FUT's boundary
+    let mut classifier = Sphere::new(vector![0.5, 0.5, 0.5], 0.2, Some(Domain::normalized()));
+    let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
+    let (b_pair, _) = find_initial_boundary_pair(&mut search, &mut classifier, 256).unwrap();
+    let root = binary_surface_search(d, &b_pair, 256, &mut classifier).unwrap();
+
+    let adherer_f = ConstantAdhererFactory::new(15.0f64.to_radians(), None);
+    let mut expl = MeshExplorer::new(d, root, d * 0.9, adherer_f);
+    while expl.boundary_count() < 200 {
+        if let Ok(None) = expl.step(&mut classifier) {
+            break;
+        }
+    }
+    println!("Explored {} boundary points.", expl.boundary_count());
+
+    // The FUT changes, growing its envelope well beyond @max_err
+    let n = Cell::new(0);
+    let mut updated = Sphere::new(vector![0.5, 0.5, 0.5], 0.3, Some(Domain::normalized()));
+    let mut classifier = FunctionClassifier::new(|p| {
+        n.set(n.get() + 1);
+        Ok(updated.classify(p)?.class())
+    });
+
+    // Incremental reacquisition steps each boundary point @max_err at a time, while
+    // binary reacquisition brackets and bisects the new boundary point.
+    let domain = Domain::normalized();
+    let (incremental, _) =
+        reacquire_all_incremental(&mut classifier, expl.boundary(), &domain, max_err, None)
+            .unwrap();
+    let n_incremental = n.replace(0);
+    let (binary, _) =
+        reacquire_all_binary(&mut classifier, expl.boundary(), &domain, max_err, None).unwrap();
+    let n_binary = n.get();
+
+    let count = |b: &[Option<Halfspace<3>>]| b.iter().flatten().count();
+    println!(
+        "Incremental: reacquired {} in {n_incremental} samples.",
+        count(&incremental)
+    );
+    println!(
+        "Binary: reacquired {} in {n_binary} samples.",
+        count(&binary)
+    );
+}
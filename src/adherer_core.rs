@@ -24,6 +24,96 @@ pub trait Adherer<const N: usize> {
     /// Returns the current state of the adherer, either Searching or
     /// FoundBoundary(hs) where hs is the resulting halfspace.
     fn get_state(&self) -> AdhererState<N>;
+
+    /// The samples taken so far, in order. Includes the final sample of a failed
+    /// adherence, e.g. the sample that exceeded the max rotation.
+    fn samples(&self) -> &[Sample<N>];
+
+    /// The current displacement vector from the pivot, i.e. the direction and
+    /// distance of the most recent sample relative to the pivot's boundary point.
+    fn displacement(&self) -> SVector<f64, N>;
+}
+
+/// The partial results of an adherence that failed, e.g. with BoundaryLost. Useful
+/// for tuning the jump distance and angles of an exploration. See
+/// `ExplorationObserver::on_adherence_failed()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdherenceFailure<const N: usize> {
+    /// The halfspace the adherer pivoted around.
+    pub pivot: Halfspace<N>,
+    /// The samples taken before the adherence failed, in order.
+    pub samples: Vec<Sample<N>>,
+    /// The displacement vector of the final sample from the pivot.
+    pub displacement: SVector<f64, N>,
+    /// The reason the adherence failed.
+    pub error: SamplingError,
+}
+
+/// Statistics of the adherence that acquired a boundary halfspace, e.g. for
+/// computing the boundary sampling efficiency (BSE) of a region of the boundary
+/// rather than of the whole exploration. See `Explorer::adherence_stats()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AdherenceStats {
+    /// The number of samples taken, including those of failed attempts that were
+    /// retried.
+    pub n_samples: usize,
+    /// The total angle in radians swept by the displacement vector between
+    /// consecutive samples.
+    pub rotation: f64,
+    /// The number of times the adherence was retried after losing the boundary.
+    pub n_retries: u32,
+}
+
+impl AdherenceStats {
+    /// Measures an adherence around @pivot from the @samples it took.
+    pub fn from_samples<const N: usize>(pivot: &Halfspace<N>, samples: &[Sample<N>]) -> Self {
+        let rotation = samples
+            .windows(2)
+            .map(|w| (*w[0] - *pivot.b).angle(&(*w[1] - *pivot.b)))
+            .sum();
+
+        AdherenceStats {
+            n_samples: samples.len(),
+            rotation,
+            n_retries: 0,
+        }
+    }
+
+    /// Combines the statistics of two adherences, e.g. a failed attempt and its
+    /// retry.
+    pub fn merge(&self, other: &AdherenceStats) -> AdherenceStats {
+        AdherenceStats {
+            n_samples: self.n_samples + other.n_samples,
+            rotation: self.rotation + other.rotation,
+            n_retries: self.n_retries + other.n_retries,
+        }
+    }
+
+    /// The boundary sampling efficiency of the adherence, i.e. 1 / n_samples, or 0
+    /// if no samples were taken (e.g. for the root).
+    pub fn bse(&self) -> f64 {
+        if self.n_samples == 0 {
+            0.0
+        } else {
+            1.0 / self.n_samples as f64
+        }
+    }
+}
+
+impl<const N: usize> AdherenceFailure<N> {
+    /// Captures the partial results of @adherer, which failed with @error.
+    pub fn from_adherer<A: Adherer<N>>(
+        pivot: Halfspace<N>,
+        adherer: &A,
+        error: SamplingError,
+    ) -> Self {
+        AdherenceFailure {
+            pivot,
+            samples: adherer.samples().to_vec(),
+            displacement: adherer.displacement(),
+            error,
+        }
+    }
 }
 
 /// Builds an Adherer and returns it. Provides a means of decoupling Explorers from
@@ -50,6 +140,9 @@ impl fmt::Debug for SamplingError {
             }
             SamplingError::MaxSamplesExceeded => write!(f, "Exceeded max samples."),
             SamplingError::InvalidClassifierResponse(msg) => write!(f, "{msg}"),
+            SamplingError::Disconnected => write!(f, "Lost connection to the classifier."),
+            SamplingError::Timeout => write!(f, "Classifier did not respond in time."),
+            SamplingError::Pending => write!(f, "Classification has not been provided."),
         }
     }
 }
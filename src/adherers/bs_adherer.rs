@@ -1,6 +1,9 @@
 use crate::{
     adherer_core::{Adherer, AdhererFactory, AdhererState},
-    structs::{Classifier, Halfspace, OutOfMode, Result, Sample, SamplingError, Span, WithinMode},
+    structs::{
+        Classifier, Halfspace, OutOfMode, ParameterError, Result, Sample, SamplingError, Span,
+        WithinMode,
+    },
 };
 use nalgebra::{Const, OMatrix, SVector};
 #[cfg(feature = "io")]
@@ -30,12 +33,20 @@ pub struct BinarySearchAdhererFactory<const N: usize> {
     n_iter: u32,
 }
 
+/// Builds a BinarySearchAdhererFactory, validating its parameters. See
+/// `BinarySearchAdhererFactory::builder()`.
+#[derive(Debug, Copy, Clone)]
+pub struct BinarySearchAdhererFactoryBuilder<const N: usize> {
+    init_angle: f64,
+    n_iter: u32,
+}
+
 impl<const N: usize> BinarySearchAdherer<N> {
     /// Creates a BinarySearchAdherer
     /// ## Arguments
     /// * pivot : The halfspace to rotate around for finding a neighboring halfspace.
     /// * v : The vector of travel, the direction along the surface to explore in.
-    ///     The length of this vector determines how far to travel.
+    ///   The length of this vector determines how far to travel.
     /// * init_angle : The initial angle to rotate by. Recommended values fall
     ///   between 90 and 120 degrees.
     /// * n_iter : The number of iterations to take before returning the acquired
@@ -85,10 +96,10 @@ impl<const N: usize> BinarySearchAdherer<N> {
     ) -> Result<Sample<N>> {
         let ccw = if prev_cls { 1.0 } else { -1.0 };
         let rot = (self.rot_factory)(ccw * self.angle);
-        self.v = rot * self.v;
+        let v = rot * self.v;
 
-        let cur = self.pivot.b + self.v;
-        let sample = classifier.classify(cur)?;
+        let sample = classifier.classify(self.pivot.b + v)?;
+        self.v = v;
         self.prev_cls = Some(sample.class());
 
         match sample {
@@ -108,12 +119,21 @@ impl<const N: usize> Adherer<N> for BinarySearchAdherer<N> {
         self.state
     }
 
+    fn samples(&self) -> &[Sample<N>] {
+        &self.samples
+    }
+
+    fn displacement(&self) -> SVector<f64, N> {
+        self.v
+    }
+
     fn sample_next<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<&Sample<N>> {
         let cur = if let Some(prev_cls) = self.prev_cls {
             self.take_sample(prev_cls, classifier)?
         } else {
             self.take_initial_sample(classifier)?
         };
+        self.samples.push(cur);
 
         if self.n_iter == 0 {
             if let (Some(t), Some(_)) = (self.t, self.x) {
@@ -127,8 +147,6 @@ impl<const N: usize> Adherer<N> for BinarySearchAdherer<N> {
             }
         }
 
-        self.samples.push(cur);
-
         Ok(self
             .samples
             .last()
@@ -142,9 +160,91 @@ impl<const N: usize> BinarySearchAdhererFactory<N> {
     }
 }
 
+impl<const N: usize> BinarySearchAdhererFactory<N> {
+    /// Creates a builder for a BinarySearchAdhererFactory, which defaults to an
+    /// init_angle of 110 degrees and 4 iterations. Unlike `new()`, `build()`
+    /// rejects parameters that would produce degenerate adherers.
+    pub fn builder() -> BinarySearchAdhererFactoryBuilder<N> {
+        BinarySearchAdhererFactoryBuilder {
+            init_angle: 110.0f64.to_radians(),
+            n_iter: 4,
+        }
+    }
+}
+
+impl<const N: usize> BinarySearchAdhererFactoryBuilder<N> {
+    /// The initial angle to rotate by, 0 < init_angle < PI.
+    pub fn with_init_angle(mut self, init_angle: f64) -> Self {
+        self.init_angle = init_angle;
+        self
+    }
+
+    /// The number of iterations to take before returning the acquired halfspace,
+    /// n_iter > 0.
+    pub fn with_n_iter(mut self, n_iter: u32) -> Self {
+        self.n_iter = n_iter;
+        self
+    }
+
+    /// Validates the parameters and builds the factory.
+    /// ## Error (Err)
+    /// * ParameterError::Invalid : If init_angle is not within (0, PI) or if n_iter
+    ///   is 0.
+    pub fn build(self) -> std::result::Result<BinarySearchAdhererFactory<N>, ParameterError> {
+        let init_angle = self.init_angle;
+        if !(init_angle > 0.0 && init_angle < PI) {
+            return Err(ParameterError::Invalid(format!(
+                "init_angle must be within (0, PI). Got: {init_angle}"
+            )));
+        }
+        if self.n_iter == 0 {
+            return Err(ParameterError::Invalid(
+                "n_iter must be non-zero!".to_string(),
+            ));
+        }
+
+        Ok(BinarySearchAdhererFactory {
+            init_angle,
+            n_iter: self.n_iter,
+        })
+    }
+}
+
 impl<const N: usize> AdhererFactory<N> for BinarySearchAdhererFactory<N> {
     type TargetAdherer = BinarySearchAdherer<N>;
     fn adhere_from(&self, hs: Halfspace<N>, v: SVector<f64, N>) -> BinarySearchAdherer<N> {
         BinarySearchAdherer::new(hs, v, self.init_angle, self.n_iter)
     }
 }
+
+#[cfg(test)]
+mod binary_search_adherer {
+    use std::f64::consts::PI;
+
+    use crate::prelude::ParameterError;
+
+    use super::BinarySearchAdhererFactory;
+
+    #[test]
+    fn builder_rejects_degenerate_parameters() {
+        let builder = BinarySearchAdhererFactory::<2>::builder();
+        assert!(builder.build().is_ok());
+        assert!(builder
+            .with_init_angle(PI / 2.0)
+            .with_n_iter(1)
+            .build()
+            .is_ok());
+
+        for invalid in [
+            builder.with_init_angle(0.0),
+            builder.with_init_angle(PI),
+            builder.with_init_angle(f64::NAN),
+            builder.with_n_iter(0),
+        ] {
+            assert!(
+                matches!(invalid.build(), Err(ParameterError::Invalid(_))),
+                "Accepted invalid parameters: {invalid:?}"
+            );
+        }
+    }
+}
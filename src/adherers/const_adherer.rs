@@ -1,11 +1,11 @@
 use crate::{
     adherer_core::{Adherer, AdhererFactory, AdhererState},
-    structs::{Classifier, Halfspace, Result, Sample, SamplingError, Span},
+    structs::{Classifier, Halfspace, ParameterError, Result, Sample, SamplingError, Span},
 };
 use nalgebra::{Const, OMatrix, SVector};
 #[cfg(feature = "io")]
 use serde::{Deserialize, Serialize};
-use std::f64::consts::PI;
+use std::{collections::VecDeque, f64::consts::PI};
 
 /// Pivots around a known boundary halfspace by taking fixed-angle rotations until
 /// the boundary is crossed.
@@ -19,6 +19,8 @@ pub struct ConstantAdherer<const N: usize> {
     angle: f64,
     delta_angle: f64,
     max_rotation: f64,
+    batch_size: usize,
+    pending: VecDeque<Sample<N>>,
     pub state: AdhererState<N>,
 }
 
@@ -28,6 +30,22 @@ pub struct ConstantAdherer<const N: usize> {
 pub struct ConstantAdhererFactory<const N: usize> {
     delta_angle: f64,
     max_rotation: Option<f64>,
+    #[cfg_attr(feature = "io", serde(default = "default_batch_size"))]
+    batch_size: usize,
+}
+
+/// Builds a ConstantAdhererFactory, validating its parameters. See
+/// `ConstantAdhererFactory::builder()`.
+#[derive(Debug, Copy, Clone)]
+pub struct ConstantAdhererFactoryBuilder<const N: usize> {
+    delta_angle: f64,
+    max_rotation: Option<f64>,
+    batch_size: usize,
+}
+
+#[cfg(feature = "io")]
+fn default_batch_size() -> usize {
+    1
 }
 
 impl<const N: usize> ConstantAdherer<N> {
@@ -35,7 +53,7 @@ impl<const N: usize> ConstantAdherer<N> {
     /// ## Arguments
     /// * pivot : The halfspace to rotate around for finding a neighboring halfspace.
     /// * v : The vector of travel, the direction along the surface to explore in.
-    ///     The length of this vector determines how far to travel.
+    ///   The length of this vector determines how far to travel.
     /// * delta_angle : The fixed-angle to rotate the displacement vector by to cross
     ///   and find the neighboring boundary.
     /// * max_rotation : The maximum total angle in radians to rotate by. Defaults to
@@ -64,10 +82,21 @@ impl<const N: usize> ConstantAdherer<N> {
             rot: None,
             samples: vec![],
             angle: 0.0,
+            batch_size: 1,
+            pending: VecDeque::new(),
             state: AdhererState::Searching,
         }
     }
 
+    /// Speculatively classifies up to @batch_size of the upcoming rotations in a
+    /// single request, reducing the number of round trips to the FUT at the cost
+    /// of samples taken past the boundary. Defaults to 1, i.e. no batching.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be non-zero!");
+        self.batch_size = batch_size;
+        self
+    }
+
     fn take_initial_sample<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<Sample<N>> {
         let cur = self.pivot.b + self.v;
         let sample = classifier.classify(cur)?;
@@ -86,11 +115,42 @@ impl<const N: usize> ConstantAdherer<N> {
         rot: OMatrix<f64, Const<N>, Const<N>>,
         classifier: &mut C,
     ) -> Result<Sample<N>> {
-        self.v = rot * self.v;
-        let cur = self.pivot.b + self.v;
+        if self.pending.is_empty() && self.batch_size > 1 {
+            self.queue_batch(rot, classifier);
+        }
+
+        // The rotation is only applied once classified, so that a failed request
+        // can be retried.
+        let v = rot * self.v;
+        let sample = match self.pending.pop_front() {
+            Some(sample) => sample,
+            None => classifier.classify(self.pivot.b + v)?,
+        };
+        self.v = v;
         self.angle += self.delta_angle;
 
-        classifier.classify(cur)
+        Ok(sample)
+    }
+
+    fn queue_batch<C: Classifier<N>>(
+        &mut self,
+        rot: OMatrix<f64, Const<N>, Const<N>>,
+        classifier: &mut C,
+    ) {
+        let remaining = ((self.max_rotation - self.angle) / self.delta_angle).floor() as usize + 1;
+        let mut v = self.v;
+        let points: Vec<SVector<f64, N>> = (0..self.batch_size.min(remaining))
+            .map(|_| {
+                v = rot * v;
+                self.pivot.b + v
+            })
+            .collect();
+
+        // On failure, fall back to sampling one at a time so that the error is
+        // returned for the sample that caused it.
+        if let Ok(samples) = classifier.classify_batch(&points) {
+            self.pending.extend(samples);
+        }
     }
 }
 
@@ -99,6 +159,14 @@ impl<const N: usize> Adherer<N> for ConstantAdherer<N> {
         self.state
     }
 
+    fn samples(&self) -> &[Sample<N>] {
+        &self.samples
+    }
+
+    fn displacement(&self) -> SVector<f64, N> {
+        self.v
+    }
+
     fn sample_next<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<&Sample<N>> {
         let cur = if let Some(rot) = self.rot {
             self.take_sample(rot, classifier)?
@@ -120,12 +188,12 @@ impl<const N: usize> Adherer<N> for ConstantAdherer<N> {
             }
         }
 
-        if matches!(self.state, AdhererState::Searching {}) && self.angle > self.max_rotation {
+        self.samples.push(cur);
+
+        if matches!(self.state, AdhererState::Searching) && self.angle > self.max_rotation {
             return Err(SamplingError::BoundaryLost);
         }
 
-        self.samples.push(cur);
-
         Ok(self
             .samples
             .last()
@@ -138,7 +206,82 @@ impl<const N: usize> ConstantAdhererFactory<N> {
         ConstantAdhererFactory {
             delta_angle,
             max_rotation,
+            batch_size: 1,
+        }
+    }
+
+    /// Sets the number of rotations the adherers classify per request. See
+    /// `ConstantAdherer::with_batch_size`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be non-zero!");
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl<const N: usize> ConstantAdhererFactory<N> {
+    /// Creates a builder for a ConstantAdhererFactory, which defaults to a
+    /// delta_angle of 5 degrees, a max_rotation of 180 degrees and no batching.
+    /// Unlike `new()`, `build()` rejects parameters that would produce degenerate
+    /// adherers.
+    pub fn builder() -> ConstantAdhererFactoryBuilder<N> {
+        ConstantAdhererFactoryBuilder {
+            delta_angle: 5.0f64.to_radians(),
+            max_rotation: None,
+            batch_size: 1,
+        }
+    }
+}
+
+impl<const N: usize> ConstantAdhererFactoryBuilder<N> {
+    /// The fixed-angle to rotate by, 0 < delta_angle < PI.
+    pub fn with_delta_angle(mut self, delta_angle: f64) -> Self {
+        self.delta_angle = delta_angle;
+        self
+    }
+
+    /// The maximum total angle in radians to rotate by, max_rotation >= delta_angle.
+    pub fn with_max_rotation(mut self, max_rotation: f64) -> Self {
+        self.max_rotation = Some(max_rotation);
+        self
+    }
+
+    /// The number of rotations classified per request, batch_size > 0. See
+    /// `ConstantAdherer::with_batch_size()`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Validates the parameters and builds the factory.
+    /// ## Error (Err)
+    /// * ParameterError::Invalid : If delta_angle is not within (0, PI), if
+    ///   max_rotation is less than delta_angle, or if batch_size is 0.
+    pub fn build(self) -> std::result::Result<ConstantAdhererFactory<N>, ParameterError> {
+        let delta_angle = self.delta_angle;
+        if !(delta_angle > 0.0 && delta_angle < PI) {
+            return Err(ParameterError::Invalid(format!(
+                "delta_angle must be within (0, PI). Got: {delta_angle}"
+            )));
+        }
+        if let Some(max_rotation) = self.max_rotation {
+            if max_rotation.is_nan() || max_rotation < delta_angle {
+                return Err(ParameterError::Invalid(format!(
+                    "max_rotation must be at least delta_angle. Got: {max_rotation} < {delta_angle}"
+                )));
+            }
+        }
+        if self.batch_size == 0 {
+            return Err(ParameterError::Invalid(
+                "batch_size must be non-zero!".to_string(),
+            ));
         }
+
+        Ok(ConstantAdhererFactory {
+            delta_angle,
+            max_rotation: self.max_rotation,
+            batch_size: self.batch_size,
+        })
     }
 }
 
@@ -146,16 +289,20 @@ impl<const N: usize> AdhererFactory<N> for ConstantAdhererFactory<N> {
     type TargetAdherer = ConstantAdherer<N>;
     fn adhere_from(&self, hs: Halfspace<N>, v: SVector<f64, N>) -> ConstantAdherer<N> {
         ConstantAdherer::new(hs, v, self.delta_angle, self.max_rotation)
+            .with_batch_size(self.batch_size)
     }
 }
 
 #[cfg(test)]
 mod constant_adherer {
-    use nalgebra::SVector;
+    use nalgebra::{vector, SVector};
 
-    use crate::prelude::{Adherer, AdhererState, FunctionClassifier, Halfspace, WithinMode};
+    use crate::prelude::{
+        Adherer, AdhererState, Classifier, FunctionClassifier, Halfspace, ParameterError, Result,
+        Sample, WithinMode,
+    };
 
-    use super::ConstantAdherer;
+    use super::{ConstantAdherer, ConstantAdhererFactory};
 
     #[test]
     fn displacement_vector_norm_never_changes() {
@@ -183,4 +330,95 @@ mod constant_adherer {
             );
         }
     }
+
+    struct BatchCounter<F: FnMut(SVector<f64, 2>) -> bool> {
+        f: F,
+        requests: usize,
+    }
+
+    impl<F: FnMut(SVector<f64, 2>) -> bool> Classifier<2> for BatchCounter<F> {
+        fn classify(&mut self, p: SVector<f64, 2>) -> Result<Sample<2>> {
+            self.requests += 1;
+            Ok(Sample::from_class(p, (self.f)(p)))
+        }
+
+        fn classify_batch(&mut self, points: &[SVector<f64, 2>]) -> Result<Vec<Sample<2>>> {
+            self.requests += 1;
+            Ok(points
+                .iter()
+                .map(|&p| Sample::from_class(p, (self.f)(p)))
+                .collect())
+        }
+    }
+
+    fn adhere<F: FnMut(SVector<f64, 2>) -> bool>(
+        batch_size: usize,
+        classifier: &mut BatchCounter<F>,
+    ) -> Halfspace<2> {
+        let pivot = Halfspace {
+            b: WithinMode(vector![0.5, 0.49]),
+            n: vector![0.0, 1.0],
+        };
+        let mut adh = ConstantAdherer::new(pivot, vector![0.05, 0.0], 5.0f64.to_radians(), None)
+            .with_batch_size(batch_size);
+
+        loop {
+            adh.sample_next(classifier)
+                .expect("Unexpected sampling error");
+            if let AdhererState::FoundBoundary(hs) = adh.get_state() {
+                return hs;
+            }
+        }
+    }
+
+    #[test]
+    fn batching_finds_same_boundary_with_fewer_requests() {
+        // A slanted plane, so that several rotations are required.
+        let plane = |p: SVector<f64, 2>| p[1] < 0.5 - 0.5 * (p[0] - 0.5);
+
+        let mut single = BatchCounter {
+            f: plane,
+            requests: 0,
+        };
+        let mut batched = BatchCounter {
+            f: plane,
+            requests: 0,
+        };
+        let hs_single = adhere(1, &mut single);
+        let hs_batched = adhere(8, &mut batched);
+
+        assert_eq!(hs_single, hs_batched);
+        assert!(
+            batched.requests < single.requests,
+            "Batching did not reduce requests: {} vs {}",
+            batched.requests,
+            single.requests
+        );
+    }
+
+    #[test]
+    fn builder_rejects_degenerate_parameters() {
+        let builder = ConstantAdhererFactory::<2>::builder();
+        assert!(builder.build().is_ok());
+        assert!(builder
+            .with_delta_angle(0.1)
+            .with_max_rotation(0.1)
+            .with_batch_size(4)
+            .build()
+            .is_ok());
+
+        for invalid in [
+            builder.with_delta_angle(0.0),
+            builder.with_delta_angle(-0.1),
+            builder.with_delta_angle(f64::NAN),
+            builder.with_delta_angle(std::f64::consts::PI),
+            builder.with_delta_angle(0.2).with_max_rotation(0.1),
+            builder.with_batch_size(0),
+        ] {
+            assert!(
+                matches!(invalid.build(), Err(ParameterError::Invalid(_))),
+                "Accepted invalid parameters: {invalid:?}"
+            );
+        }
+    }
 }
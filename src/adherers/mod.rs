@@ -1,4 +1,6 @@
 pub mod bs_adherer;
 pub mod const_adherer;
+pub mod noise_tolerant;
 
 pub use const_adherer::*;
+pub use noise_tolerant::*;
@@ -0,0 +1,341 @@
+use crate::{
+    adherer_core::{Adherer, AdhererFactory, AdhererState},
+    structs::{Classifier, Halfspace, Result, Sample},
+};
+use nalgebra::SVector;
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+/// An estimate of a stochastic FUT's label-noise rate, i.e. the probability that a
+/// single classification disagrees with the majority label of its point.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NoiseEstimate {
+    /// The number of classifications taken.
+    pub n_votes: u64,
+    /// The number of classifications that disagreed with the majority label.
+    pub n_dissenting: u64,
+}
+
+/// Wraps an adherer for use with a stochastic FUT, i.e. one where the same point
+/// may be classified differently between executions. Each probe made by the
+/// wrapped adherer is classified repeatedly and labeled by majority vote, so that
+/// a crossing is only declared once the probes on either side of it have been
+/// resolved.
+pub struct NoiseTolerantAdherer<const N: usize, A: Adherer<N>> {
+    adherer: A,
+    n_votes: u32,
+    confidence: f64,
+    max_votes: u32,
+    noise: NoiseEstimate,
+}
+
+/// Builds a NoiseTolerantAdherer instance, wrapping the adherers built by the
+/// given factory.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone)]
+pub struct NoiseTolerantAdhererFactory<const N: usize, F: AdhererFactory<N>> {
+    adherer_f: F,
+    n_votes: u32,
+    confidence: f64,
+    max_votes: u32,
+}
+
+/// Classifies each point by repeated sampling on behalf of the wrapped adherer.
+struct Voter<'a, C, const N: usize> {
+    classifier: &'a mut C,
+    n_votes: u32,
+    confidence: f64,
+    max_votes: u32,
+    noise: &'a mut NoiseEstimate,
+}
+
+impl NoiseEstimate {
+    /// The estimated probability of a classification being mislabeled. Slightly
+    /// underestimates the true rate, since a mislabeled majority is unobservable.
+    pub fn rate(&self) -> f64 {
+        if self.n_votes == 0 {
+            0.0
+        } else {
+            self.n_dissenting as f64 / self.n_votes as f64
+        }
+    }
+
+    /// Combines two estimates, e.g. from several adherers, into a single estimate.
+    pub fn merge(&self, other: &NoiseEstimate) -> NoiseEstimate {
+        NoiseEstimate {
+            n_votes: self.n_votes + other.n_votes,
+            n_dissenting: self.n_dissenting + other.n_dissenting,
+        }
+    }
+}
+
+impl<const N: usize, A: Adherer<N>> NoiseTolerantAdherer<N, A> {
+    /// Creates a NoiseTolerantAdherer.
+    /// ## Arguments
+    /// * adherer : The adherer whose probes are to be classified repeatedly.
+    /// * n_votes : The number of times each probe is classified per round of
+    ///   voting. An odd number avoids ties, which are labeled OutOfMode.
+    /// * confidence : 0.5 <= confidence <= 1, the fraction of votes the majority
+    ///   label must hold before the probe is labeled. Otherwise, another round of
+    ///   @n_votes is taken.
+    /// * max_votes : The maximum number of times a probe is classified. Once
+    ///   reached, the probe is labeled by majority regardless of @confidence.
+    pub fn new(adherer: A, n_votes: u32, confidence: f64, max_votes: u32) -> Self {
+        assert!(n_votes > 0, "n_votes must be positive non-zero!");
+        assert!(
+            (0.5..=1.0).contains(&confidence),
+            "Invalid confidence, must be between 0.5 and 1! Got: {confidence}"
+        );
+        assert!(
+            max_votes >= n_votes,
+            "max_votes must be at least n_votes! Got: {max_votes} < {n_votes}"
+        );
+
+        NoiseTolerantAdherer {
+            adherer,
+            n_votes,
+            confidence,
+            max_votes,
+            noise: NoiseEstimate::default(),
+        }
+    }
+
+    /// The label-noise rate observed by this adherer's votes so far.
+    pub fn noise(&self) -> NoiseEstimate {
+        self.noise
+    }
+
+    /// Returns the wrapped adherer.
+    pub fn into_inner(self) -> A {
+        self.adherer
+    }
+}
+
+impl<const N: usize, A: Adherer<N>> Adherer<N> for NoiseTolerantAdherer<N, A> {
+    fn get_state(&self) -> AdhererState<N> {
+        self.adherer.get_state()
+    }
+
+    fn samples(&self) -> &[Sample<N>] {
+        self.adherer.samples()
+    }
+
+    fn displacement(&self) -> SVector<f64, N> {
+        self.adherer.displacement()
+    }
+
+    fn sample_next<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<&Sample<N>> {
+        let mut voter = Voter {
+            classifier,
+            n_votes: self.n_votes,
+            confidence: self.confidence,
+            max_votes: self.max_votes,
+            noise: &mut self.noise,
+        };
+        self.adherer.sample_next(&mut voter)
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for Voter<'_, C, N>
+where
+    C: Classifier<N>,
+{
+    /// Votes are weighted by the confidence of the FUT, if reported, where a vote
+    /// with confidence c counts c towards its class and 1 - c towards the other.
+    /// A first vote that is at least as confident as required is accepted alone.
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let mut n_within = 0;
+        let mut w_within = 0.0;
+        let mut n = 0;
+        loop {
+            for _ in 0..self.n_votes.min(self.max_votes - n) {
+                let (sample, confidence) = self.classifier.classify_with_confidence(p)?;
+                if n == 0 && confidence.is_some_and(|c| c >= self.confidence) {
+                    self.noise.n_votes += 1;
+                    return Ok(sample);
+                }
+
+                let c = confidence.unwrap_or(1.0);
+                if sample.class() {
+                    n_within += 1;
+                    w_within += c;
+                } else {
+                    w_within += 1.0 - c;
+                }
+                n += 1;
+            }
+
+            let w_majority = w_within.max(n as f64 - w_within);
+            if w_majority >= self.confidence * n as f64 || n >= self.max_votes {
+                let cls = 2.0 * w_within > n as f64;
+                let n_dissenting = if cls { n - n_within } else { n_within };
+                self.noise.n_votes += n as u64;
+                self.noise.n_dissenting += n_dissenting as u64;
+                return Ok(Sample::from_class(p, cls));
+            }
+        }
+    }
+}
+
+impl<const N: usize, F: AdhererFactory<N>> NoiseTolerantAdhererFactory<N, F> {
+    /// See `NoiseTolerantAdherer::new()` for the parameters.
+    pub fn new(adherer_f: F, n_votes: u32, confidence: f64, max_votes: u32) -> Self {
+        NoiseTolerantAdhererFactory {
+            adherer_f,
+            n_votes,
+            confidence,
+            max_votes,
+        }
+    }
+}
+
+impl<const N: usize, F: AdhererFactory<N>> AdhererFactory<N> for NoiseTolerantAdhererFactory<N, F> {
+    type TargetAdherer = NoiseTolerantAdherer<N, F::TargetAdherer>;
+    fn adhere_from(&self, hs: Halfspace<N>, v: SVector<f64, N>) -> Self::TargetAdherer {
+        NoiseTolerantAdherer::new(
+            self.adherer_f.adhere_from(hs, v),
+            self.n_votes,
+            self.confidence,
+            self.max_votes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod noise_tolerant_adherer {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        classifiers::ProbabilisticAdapter,
+        prelude::{
+            bs_adherer::BinarySearchAdherer, Adherer, AdhererState, FunctionClassifier, Halfspace,
+            ProbabilisticClassifier, Result, Sample, WithinMode,
+        },
+    };
+
+    use super::{NoiseEstimate, NoiseTolerantAdherer};
+
+    const D: f64 = 0.05;
+    const FLIP_RATE: f64 = 0.1;
+
+    /// A plane at y = 0.5 whose labels are flipped at FLIP_RATE.
+    fn noisy_plane() -> impl FnMut(SVector<f64, 2>) -> Result<bool> {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        move |p| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let flip = ((state >> 11) as f64 / (1u64 << 53) as f64) < FLIP_RATE;
+            Ok((p[1] <= 0.5) != flip)
+        }
+    }
+
+    fn adhere<A: Adherer<2>>(adh: &mut A, f: impl FnMut(SVector<f64, 2>) -> Result<bool>) {
+        let mut classifier = FunctionClassifier::new(f);
+        while let AdhererState::Searching = adh.get_state() {
+            adh.sample_next(&mut classifier)
+                .expect("Unexpected sampling error");
+        }
+    }
+
+    #[test]
+    fn majority_vote_recovers_boundary_and_noise_rate() {
+        let mut noise = NoiseEstimate::default();
+        let mut classifier = noisy_plane();
+
+        for i in 0..20 {
+            let pivot = Halfspace {
+                b: WithinMode(vector![0.1 + 0.04 * i as f64, 0.499]),
+                n: vector![0.0, 1.0],
+            };
+            let mut adh = NoiseTolerantAdherer::new(
+                BinarySearchAdherer::new(pivot, vector![D, 0.0], PI / 2.0, 6),
+                9,
+                0.8,
+                45,
+            );
+            adhere(&mut adh, &mut classifier);
+
+            let AdhererState::FoundBoundary(hs) = adh.get_state() else {
+                unreachable!()
+            };
+            assert!(
+                (hs.b[1] - 0.5).abs() < 0.1 * D,
+                "Boundary point too far from plane: {:?}",
+                hs.b
+            );
+            noise = noise.merge(&adh.noise());
+        }
+
+        assert!(
+            (noise.rate() - FLIP_RATE).abs() < 0.03,
+            "Bad noise estimate: {noise:?}"
+        );
+    }
+
+    #[test]
+    fn deterministic_fut_takes_single_round() {
+        let pivot = Halfspace {
+            b: WithinMode(vector![0.5, 0.499]),
+            n: vector![0.0, 1.0],
+        };
+        let mut adh = NoiseTolerantAdherer::new(
+            BinarySearchAdherer::new(pivot, vector![D, 0.0], PI / 2.0, 6),
+            3,
+            1.0,
+            30,
+        );
+        adhere(&mut adh, |p| Ok(p[1] <= 0.5));
+
+        assert_eq!(adh.noise().n_votes, 3 * 6);
+        assert_eq!(adh.noise().rate(), 0.0);
+    }
+
+    /// The plane at y = 0.5, reporting a fixed confidence.
+    struct CalibratedPlane(f64);
+
+    impl ProbabilisticClassifier<2> for CalibratedPlane {
+        fn classify_probabilistic(&mut self, p: SVector<f64, 2>) -> Result<(Sample<2>, f64)> {
+            Ok((Sample::from_class(p, p[1] <= 0.5), self.0))
+        }
+    }
+
+    #[test]
+    fn confident_fut_takes_single_vote() {
+        let pivot = Halfspace {
+            b: WithinMode(vector![0.5, 0.499]),
+            n: vector![0.0, 1.0],
+        };
+        let mut classifier = ProbabilisticAdapter::new(CalibratedPlane(0.95));
+        let mut adh = NoiseTolerantAdherer::new(
+            BinarySearchAdherer::new(pivot, vector![D, 0.0], PI / 2.0, 6),
+            3,
+            0.9,
+            30,
+        );
+        while let AdhererState::Searching = adh.get_state() {
+            adh.sample_next(&mut classifier)
+                .expect("Unexpected sampling error");
+        }
+        assert_eq!(adh.noise().n_votes, 6);
+
+        // Unconfident votes are weighted down, requiring more votes to agree
+        let mut classifier = ProbabilisticAdapter::new(CalibratedPlane(0.6));
+        let mut adh = NoiseTolerantAdherer::new(
+            BinarySearchAdherer::new(pivot, vector![D, 0.0], PI / 2.0, 6),
+            3,
+            0.9,
+            30,
+        );
+        while let AdhererState::Searching = adh.get_state() {
+            adh.sample_next(&mut classifier)
+                .expect("Unexpected sampling error");
+        }
+        assert_eq!(adh.noise().n_votes, 30 * 6);
+        assert!(
+            matches!(adh.get_state(), AdhererState::FoundBoundary(hs) if (hs.b[1] - 0.5).abs() < 0.1 * D)
+        );
+    }
+}
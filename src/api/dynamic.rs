@@ -0,0 +1,262 @@
+use std::{io, net};
+
+use nalgebra::DVector;
+
+use super::{
+    exchange_batch, exchange_single, handshake, receive_text, send_ok, send_text, Encoding,
+    RemoteClassifier, Transport,
+};
+use crate::{
+    dynamic::{DClassifier, DSample},
+    structs::{error, messages::MSG_END, SamplingError},
+};
+
+/// A RemoteClassifier whose dimensionality is determined at runtime by the number
+/// of params the FUT announces during the handshake, rather than at compile time.
+/// Points are classified as DVectors within the normalized domain [0, 1]^n.
+///
+/// Use `into_fixed()` to drive the existing explorers once the dimensionality is
+/// known, e.g. by matching on `num_params()`, or use it directly as a DClassifier
+/// with the dynamic explorers (e.g. DMeshExplorer).
+pub struct RemoteClassifierDyn<S: Transport = net::TcpStream> {
+    stream: Option<S>,
+    num_params: usize,
+    max_batch: Option<usize>,
+    version: Option<u16>,
+    encoding: Encoding,
+}
+
+impl RemoteClassifierDyn {
+    /// Opens a socket to be connected to by a remote function under test (FUT),
+    /// following the connection sequence of `RemoteClassifier::bind()`, except
+    /// that any number of params is accepted.
+    pub fn bind(addr: String) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for client connection...");
+        let (stream, _) = listener.accept()?;
+        println!("Connection established.");
+
+        RemoteClassifierDyn::from_stream(stream)
+    }
+
+    /// Opens a socket to be connected to by a remote function under test (FUT)
+    /// that supports batch classification. See `RemoteClassifier::bind_batched()`.
+    pub fn bind_batched(addr: String) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for client connection...");
+        let (stream, _) = listener.accept()?;
+        println!("Connection established.");
+
+        RemoteClassifierDyn::from_stream_batched(stream)
+    }
+}
+
+impl<S: Transport> RemoteClassifierDyn<S> {
+    /// Completes the connection sequence over an already established connection
+    /// to the FUT.
+    pub fn from_stream(stream: S) -> io::Result<Self> {
+        Self::configure(stream, false)
+    }
+
+    /// Completes the batched connection sequence over an already established
+    /// connection to the FUT.
+    pub fn from_stream_batched(stream: S) -> io::Result<Self> {
+        Self::configure(stream, true)
+    }
+
+    fn configure(mut stream: S, batched: bool) -> io::Result<Self> {
+        let config = handshake(&mut stream, batched, None)?;
+        if config.num_params == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Number of params must be non-zero!",
+            ));
+        }
+        send_ok(&mut stream, &config)?;
+
+        Ok(RemoteClassifierDyn {
+            stream: Some(stream),
+            num_params: config.num_params,
+            max_batch: config.max_batch,
+            version: config.version,
+            encoding: config.encoding,
+        })
+    }
+
+    /// The number of params announced by the FUT, i.e. the dimensionality of the
+    /// input space.
+    pub fn num_params(&self) -> usize {
+        self.num_params
+    }
+
+    /// The max number of points the FUT can classify in one request, or None if
+    /// the FUT did not negotiate batch classification.
+    pub fn max_batch(&self) -> Option<usize> {
+        self.max_batch
+    }
+
+    /// The framed protocol version negotiated with the FUT, or None if the FUT uses
+    /// the legacy protocol.
+    pub fn protocol_version(&self) -> Option<u16> {
+        self.version
+    }
+
+    /// Converts into a RemoteClassifier of fixed dimensionality, allowing the
+    /// existing explorers and search algorithms to be used.
+    /// ## Return (Ok)
+    /// * classifier : The RemoteClassifier using this connection.
+    /// ## Error (Err)
+    /// * self : If N does not match the number of params announced by the FUT.
+    pub fn into_fixed<const N: usize>(mut self) -> Result<RemoteClassifier<N, S>, Self> {
+        if N != self.num_params {
+            return Err(self);
+        }
+
+        let stream = self.stream.take().expect("Connection was already moved?");
+        Ok(RemoteClassifier::new(
+            stream,
+            self.max_batch,
+            self.version,
+            self.encoding,
+        ))
+    }
+
+    /// Classifies a point.
+    /// ## Arguments
+    /// * p : The point to classify, with length equal to `num_params()`.
+    /// ## Return (Ok)
+    /// * cls : True if @p is within the target performance mode.
+    /// ## Error (Err)
+    /// * OutOfBounds : If @p is outside of [0, 1]^n.
+    pub fn classify(&mut self, p: &DVector<f64>) -> error::Result<bool> {
+        Ok(self.classify_batch(std::slice::from_ref(p))?[0])
+    }
+
+    /// Classifies several points, sending them in batches of at most the
+    /// negotiated max batch size.
+    /// ## Error (Err)
+    /// * OutOfBounds : If any point is outside of [0, 1]^n, including points that
+    ///   do not have n dimensions. No points are sent.
+    pub fn classify_batch(&mut self, points: &[DVector<f64>]) -> error::Result<Vec<bool>> {
+        if points
+            .iter()
+            .any(|p| p.len() != self.num_params || p.iter().any(|&x| !(0.0..=1.0).contains(&x)))
+        {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        let (max_batch, version, encoding) = (self.max_batch, self.version, self.encoding);
+        let stream = self.stream();
+        match max_batch {
+            Some(max_batch) => {
+                let mut classes = Vec::with_capacity(points.len());
+                for chunk in points.chunks(max_batch) {
+                    let batch: Vec<&[f64]> = chunk.iter().map(|p| p.as_slice()).collect();
+                    classes.extend(exchange_batch(stream, version, encoding, &batch)?);
+                }
+                Ok(classes)
+            }
+            None => points
+                .iter()
+                .map(|p| exchange_single(stream, p.as_slice()))
+                .collect(),
+        }
+    }
+
+    /// Send a message to the client. See `RemoteClassifier::send_msg()`.
+    pub fn send_msg(&mut self, msg: &str) -> io::Result<()> {
+        let (version, encoding) = (self.version, self.encoding);
+        send_text(self.stream(), version, encoding, msg)
+    }
+
+    /// Receive a message from the client. See `RemoteClassifier::receive_msg()`.
+    pub fn receive_msg(&mut self) -> io::Result<String> {
+        let (version, encoding) = (self.version, self.encoding);
+        receive_text(self.stream(), version, encoding)
+    }
+
+    fn stream(&mut self) -> &mut S {
+        self.stream.as_mut().expect("Connection was already moved?")
+    }
+}
+
+impl<S: Transport> DClassifier for RemoteClassifierDyn<S> {
+    fn classify(&mut self, p: &DVector<f64>) -> error::Result<DSample> {
+        Ok(DSample::from_class(
+            p.clone(),
+            RemoteClassifierDyn::classify(self, p)?,
+        ))
+    }
+}
+
+impl<S: Transport> Drop for RemoteClassifierDyn<S> {
+    fn drop(&mut self) {
+        if self.stream.is_some() {
+            let _ = self.send_msg(MSG_END);
+        }
+    }
+}
+
+#[cfg(test)]
+mod dynamic_dimension {
+    use std::{
+        io::{Read, Write},
+        thread,
+    };
+
+    use nalgebra::{dvector, vector};
+
+    use super::*;
+    use crate::structs::Classifier;
+
+    #[test]
+    fn accepts_announced_dimensionality() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+            stream.write_all(&3usize.to_be_bytes()).unwrap();
+            let mut ok = [0u8; 3];
+            stream.read_exact(&mut ok).unwrap();
+            assert_eq!(&ok, b"OK\n");
+
+            let mut n_requests = 0;
+            loop {
+                let mut buffer = [0u8; 24];
+                stream.read_exact(&mut buffer[..4]).unwrap();
+                if &buffer[..4] == b"END\n" {
+                    break;
+                }
+                stream.read_exact(&mut buffer[4..]).unwrap();
+                let x2 = f64::from_ne_bytes(buffer[16..].try_into().unwrap());
+                stream.write_all(&[(x2 < 0.5) as u8]).unwrap();
+                n_requests += 1;
+            }
+            n_requests
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifierDyn::from_stream(stream).unwrap();
+        assert_eq!(classifier.num_params(), 3);
+
+        assert!(classifier.classify(&dvector![0.5, 0.5, 0.25]).unwrap());
+        assert!(matches!(
+            classifier.classify(&dvector![0.5, 1.5, 0.25]),
+            Err(SamplingError::OutOfBounds)
+        ));
+        assert!(matches!(
+            classifier.classify_batch(&[dvector![0.5, 0.5, 0.25], dvector![0.5, 0.5]]),
+            Err(SamplingError::OutOfBounds)
+        ));
+
+        let classifier = classifier.into_fixed::<2>().err().unwrap();
+        let mut classifier = classifier.into_fixed::<3>().ok().unwrap();
+        assert!(!classifier
+            .classify(vector![0.5, 0.5, 0.75])
+            .unwrap()
+            .class());
+        drop(classifier);
+
+        assert_eq!(client.join().unwrap(), 2);
+    }
+}
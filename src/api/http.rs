@@ -0,0 +1,524 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use nalgebra::SVector;
+use serde_json::{json, Value};
+
+use crate::structs::{error, Classifier, Domain, Sample, SamplingError};
+
+/// The max number of idle connections kept open for reuse.
+const DEFAULT_MAX_IDLE: usize = 4;
+/// The largest response body SEMBAS will read from a service.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Classifies points by POSTing them to an FUT wrapped as a web service, e.g. an
+/// ML model server. Each request body is a JSON object `{"point": [x1, ..., xn]}`,
+/// and the response body must be either a JSON bool, a 0/1 number, or an object
+/// containing the class under the configured class field (default "class").
+///
+/// Connections are kept alive and pooled between requests. Only plain HTTP is
+/// supported; use a local TLS-terminating proxy for HTTPS services. Points outside
+/// of the normalized domain are OutOfBounds and are not sent to the service.
+pub struct HttpClassifier<const N: usize> {
+    domain: Domain<N>,
+    host: String,
+    addr: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    class_field: String,
+    timeout: Option<Duration>,
+    max_idle: usize,
+    pool: Vec<BufReader<TcpStream>>,
+}
+
+impl<const N: usize> HttpClassifier<N> {
+    /// Creates an HttpClassifier for the endpoint at @url.
+    /// ## Arguments
+    /// * url : The endpoint to POST points to, e.g. "http://127.0.0.1:8000/classify".
+    /// ## Error (Err)
+    /// * InvalidInput : If @url is not a valid http:// URL.
+    pub fn new(url: &str) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_string());
+
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("Only http:// URLs are supported"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        if authority.is_empty() {
+            return Err(invalid("URL is missing a host"));
+        }
+        let addr = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:80")
+        };
+
+        Ok(HttpClassifier {
+            domain: Domain::normalized(),
+            host: authority.to_string(),
+            addr,
+            path: path.to_string(),
+            headers: vec![],
+            class_field: "class".to_string(),
+            timeout: None,
+            max_idle: DEFAULT_MAX_IDLE,
+            pool: vec![],
+        })
+    }
+
+    /// Adds a header sent with every request, e.g. an authorization token.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        assert!(
+            !(name.contains(['\r', '\n']) || value.contains(['\r', '\n'])),
+            "Headers must not contain line breaks!"
+        );
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the field of a JSON object response that contains the class.
+    pub fn with_class_field(mut self, field: &str) -> Self {
+        self.class_field = field.to_string();
+        self
+    }
+
+    /// Sets how long to wait on the service before failing with
+    /// SamplingError::Timeout. None waits indefinitely.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the max number of idle connections kept open for reuse. 0 disables
+    /// connection reuse.
+    pub fn with_max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self.pool.truncate(max_idle);
+        self
+    }
+
+    /// The number of idle connections currently held in the pool.
+    pub fn idle_connections(&self) -> usize {
+        self.pool.len()
+    }
+
+    fn connect(&self) -> io::Result<BufReader<TcpStream>> {
+        let stream = TcpStream::connect(&self.addr)?;
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+        stream.set_nodelay(true)?;
+        Ok(BufReader::new(stream))
+    }
+
+    /// POSTs @body to the endpoint, returning the response body. A pooled
+    /// connection that was closed by the server before the request was sent is
+    /// replaced by a new connection. Any other failure, such as a timeout while
+    /// waiting on the response, is returned, since the request may have been
+    /// processed.
+    fn post(&mut self, body: &str) -> error::Result<Value> {
+        let request = self.format_request(body);
+
+        let (status, response, _) = match self.pool.pop() {
+            Some(mut conn) => match exchange(&mut conn, &request)? {
+                Some(result) => {
+                    self.release(conn, result.2);
+                    result
+                }
+                None => self.exchange_fresh(&request)?,
+            },
+            None => self.exchange_fresh(&request)?,
+        };
+
+        if !(200..300).contains(&status) {
+            return Err(SamplingError::InvalidClassifierResponse(format!(
+                "HTTP {status}: {}",
+                String::from_utf8_lossy(&response)
+            )));
+        }
+
+        serde_json::from_slice(&response).map_err(|e| {
+            SamplingError::InvalidClassifierResponse(format!("Invalid JSON response: {e}"))
+        })
+    }
+
+    fn exchange_fresh(&mut self, request: &[u8]) -> io::Result<(u16, Vec<u8>, bool)> {
+        let mut conn = self.connect()?;
+        let result = exchange(&mut conn, request)?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        self.release(conn, result.2);
+        Ok(result)
+    }
+
+    fn release(&mut self, conn: BufReader<TcpStream>, keep_alive: bool) {
+        if keep_alive && self.pool.len() < self.max_idle {
+            self.pool.push(conn);
+        }
+    }
+
+    fn format_request(&self, body: &str) -> Vec<u8> {
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Accept: application/json\r\nContent-Length: {}\r\n",
+            self.path,
+            self.host,
+            body.len()
+        );
+        if self.max_idle == 0 {
+            request.push_str("Connection: close\r\n");
+        }
+        for (name, value) in self.headers.iter() {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        request.into_bytes()
+    }
+
+    fn parse_class(&self, value: &Value) -> Option<bool> {
+        match value {
+            Value::Bool(cls) => Some(*cls),
+            Value::Number(x) => x
+                .as_f64()
+                .filter(|&x| x == 0.0 || x == 1.0)
+                .map(|x| x == 1.0),
+            Value::Object(fields) => fields
+                .get(&self.class_field)
+                .and_then(|v| self.parse_class(v)),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> Classifier<N> for HttpClassifier<N> {
+    fn classify(&mut self, p: SVector<f64, N>) -> error::Result<Sample<N>> {
+        if !self.domain.contains(&p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        let body = json!({ "point": p.as_slice() }).to_string();
+        let response = self.post(&body)?;
+        let cls = self.parse_class(&response).ok_or_else(|| {
+            SamplingError::InvalidClassifierResponse(format!(
+                "Response did not contain a class: {response}"
+            ))
+        })?;
+
+        Ok(Sample::from_class(p, cls))
+    }
+}
+
+/// Sends @request over @conn and reads the response.
+/// ## Return (Ok)
+/// * Some((status, body, keep_alive)) : Whether the connection may be reused.
+/// * None : If the connection was closed before any of @request was written, or
+///   before any of the response was read, i.e. the server closed an idle
+///   connection.
+/// ## Error (Err)
+/// * InvalidData : If the response is malformed, or its body is larger than
+///   `MAX_BODY_SIZE`.
+fn exchange(
+    conn: &mut BufReader<TcpStream>,
+    request: &[u8],
+) -> io::Result<Option<(u16, Vec<u8>, bool)>> {
+    let mut written = 0;
+    while written < request.len() {
+        match conn.get_mut().write(&request[written..]) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(k) => written += k,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) if is_timeout(&e) || written > 0 => return Err(e),
+            Err(_) => return Ok(None),
+        }
+    }
+    conn.get_mut().flush()?;
+
+    let mut line = String::new();
+    if conn.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let mut status_line = line.split_whitespace();
+    let version = status_line.next().unwrap_or_default().to_string();
+    let status: u16 = status_line
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid_data(&format!("Invalid HTTP status line: {line}")))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    let mut keep_alive = version == "HTTP/1.1";
+    loop {
+        line.clear();
+        if conn.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| invalid_data(&format!("Invalid HTTP header: {header}")))?;
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => {
+                content_length = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| invalid_data("Invalid Content-Length"))?,
+                )
+            }
+            "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+            "connection" => keep_alive = value.eq_ignore_ascii_case("keep-alive"),
+            _ => (),
+        }
+    }
+
+    let body = if chunked {
+        read_chunked(conn)?
+    } else if let Some(len) = content_length {
+        check_body_size(len)?;
+        let mut body = vec![0u8; len];
+        conn.read_exact(&mut body)?;
+        body
+    } else {
+        // The body is delimited by the server closing the connection.
+        keep_alive = false;
+        let mut body = vec![];
+        conn.take(MAX_BODY_SIZE as u64 + 1).read_to_end(&mut body)?;
+        check_body_size(body.len())?;
+        body
+    };
+
+    Ok(Some((status, body, keep_alive)))
+}
+
+fn read_chunked(conn: &mut BufReader<TcpStream>) -> io::Result<Vec<u8>> {
+    let mut body = vec![];
+    let mut line = String::new();
+    loop {
+        line.clear();
+        conn.read_line(&mut line)?;
+        let size = line.trim().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| invalid_data(&format!("Invalid chunk size: {line}")))?;
+
+        if size == 0 {
+            // Skip any trailers, up to the terminating empty line.
+            loop {
+                line.clear();
+                if conn.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                    return Ok(body);
+                }
+            }
+        }
+
+        let start = body.len();
+        check_body_size(start.saturating_add(size))?;
+        body.resize(start + size, 0);
+        conn.read_exact(&mut body[start..])?;
+        line.clear();
+        conn.read_line(&mut line)?;
+    }
+}
+
+fn check_body_size(len: usize) -> io::Result<()> {
+    if len > MAX_BODY_SIZE {
+        return Err(invalid_data(&format!(
+            "HTTP response body of {len} bytes exceeds the max of {MAX_BODY_SIZE}"
+        )));
+    }
+    Ok(())
+}
+
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod http_classifier {
+    use std::{net::TcpListener, sync::mpsc, thread};
+
+    use nalgebra::vector;
+
+    use super::*;
+
+    /// Serves @n_requests on a single keep-alive connection, classifying x < 0.5 as
+    /// in-mode. Alternates between Content-Length and chunked responses.
+    fn serve(listener: &TcpListener, n_requests: usize) -> Vec<String> {
+        let (stream, _) = listener.accept().unwrap();
+        let mut conn = BufReader::new(stream);
+        let mut auth_headers = vec![];
+
+        for i in 0..n_requests {
+            let mut len = 0;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                conn.read_line(&mut line).unwrap();
+                let header = line.trim_end().to_ascii_lowercase();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some(v) = header.strip_prefix("content-length:") {
+                    len = v.trim().parse().unwrap();
+                }
+                if header.starts_with("authorization:") {
+                    auth_headers.push(line.trim_end().to_string());
+                }
+            }
+            let mut body = vec![0u8; len];
+            conn.read_exact(&mut body).unwrap();
+            let request: Value = serde_json::from_slice(&body).unwrap();
+            let x = request["point"][0].as_f64().unwrap();
+            let response = json!({ "class": x < 0.5, "score": x }).to_string();
+
+            let stream = conn.get_mut();
+            if i % 2 == 0 {
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            } else {
+                let (a, b) = response.split_at(5);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{a}\r\n{:x}\r\n{b}\r\n0\r\n\r\n",
+                    a.len(),
+                    b.len()
+                )
+                .unwrap();
+            }
+        }
+
+        auth_headers
+    }
+
+    #[test]
+    fn classifies_over_pooled_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/classify", listener.local_addr().unwrap());
+        let server = thread::spawn(move || serve(&listener, 3));
+
+        let mut classifier = HttpClassifier::<2>::new(&url)
+            .unwrap()
+            .with_header("Authorization", "Bearer token");
+
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+        assert!(!classifier.classify(vector![0.75, 0.5]).unwrap().class());
+        assert!(classifier.classify(vector![0.1, 0.9]).unwrap().class());
+        assert_eq!(classifier.idle_connections(), 1);
+
+        let auth_headers = server.join().unwrap();
+        assert_eq!(auth_headers, vec!["Authorization: Bearer token"; 3]);
+    }
+
+    #[test]
+    fn replaces_pooled_connection_closed_by_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/classify", listener.local_addr().unwrap());
+        let (closed_tx, closed_rx) = mpsc::channel();
+        let server = thread::spawn(move || {
+            serve(&listener, 1);
+            closed_tx.send(()).unwrap();
+            serve(&listener, 1);
+        });
+
+        let mut classifier = HttpClassifier::<2>::new(&url).unwrap();
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+        closed_rx.recv().unwrap();
+        assert!(!classifier.classify(vector![0.75, 0.5]).unwrap().class());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reports_timeout_without_resending() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/classify", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut conn = BufReader::new(stream);
+            for i in 0..2 {
+                // Each request body is a JSON object, ending the request.
+                let mut request = vec![];
+                conn.read_until(b'}', &mut request).unwrap();
+                if i == 0 {
+                    let response = "true";
+                    write!(
+                        conn.get_mut(),
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{response}",
+                        response.len()
+                    )
+                    .unwrap();
+                }
+            }
+            // Hangs without responding, and no new connection should be made
+            thread::sleep(Duration::from_millis(300));
+            listener.set_nonblocking(true).unwrap();
+            listener.accept().is_err()
+        });
+
+        let mut classifier = HttpClassifier::<2>::new(&url)
+            .unwrap()
+            .with_timeout(Some(Duration::from_millis(50)));
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+        let result = classifier.classify(vector![0.25, 0.5]);
+        assert!(matches!(result, Err(SamplingError::Timeout)), "{result:?}");
+        assert!(server.join().unwrap(), "The request was resent");
+    }
+
+    #[test]
+    fn rejects_oversized_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/classify", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1];
+            stream.read_exact(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                u64::MAX
+            )
+            .unwrap();
+        });
+
+        let mut classifier = HttpClassifier::<2>::new(&url).unwrap();
+        assert!(matches!(
+            classifier.classify(vector![0.25, 0.5]),
+            Err(SamplingError::InvalidClassifierResponse(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_points() {
+        // Nothing listens on port 9, so any request would fail to connect
+        let mut classifier = HttpClassifier::<2>::new("http://127.0.0.1:9/classify").unwrap();
+        assert!(matches!(
+            classifier.classify(vector![1.5, 0.5]),
+            Err(SamplingError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_urls() {
+        assert!(HttpClassifier::<2>::new("https://example.com/classify").is_err());
+        assert!(HttpClassifier::<2>::new("http:///classify").is_err());
+        assert!(HttpClassifier::<2>::new("http://localhost").is_ok());
+    }
+}
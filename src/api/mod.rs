@@ -0,0 +1,1399 @@
+pub mod dynamic;
+#[cfg(feature = "io")]
+pub mod http;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod protocol;
+pub mod session;
+pub mod subprocess;
+pub mod websocket;
+
+pub use dynamic::RemoteClassifierDyn;
+#[cfg(feature = "io")]
+pub use http::HttpClassifier;
+pub use protocol::{Encoding, Frame, PROTOCOL_VERSION};
+pub use session::{ModeResult, SembasSession, SessionError, SessionState};
+pub use subprocess::SubprocessClassifier;
+pub use websocket::WebSocketStream;
+
+use crate::classifiers::TranscriptWriter;
+use crate::prelude::messages::{MSG_END, MSG_ERR, MSG_OK, MSG_PING, MSG_PONG};
+use crate::prelude::Sample;
+use crate::structs::RichSample;
+use crate::structs::SamplingError;
+use nalgebra::SVector;
+use std::io::{self, Read};
+use std::io::{BufRead, BufReader, Write};
+use std::net;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::structs::error;
+use crate::structs::Classifier;
+use crate::structs::Domain;
+
+const BUFFER_CONFIG_SIZE: usize = 8;
+/// The longest authentication token SEMBAS will read from a client.
+const MAX_TOKEN_SIZE: usize = 4096;
+
+/// A connection to a remote FUT.
+pub trait Transport: Read + Write {
+    /// Sets the read and write timeouts of the underlying connection. None blocks
+    /// indefinitely.
+    fn set_timeouts(&mut self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()> {
+        let _ = (read, write);
+        Ok(())
+    }
+}
+
+impl Transport for net::TcpStream {
+    fn set_timeouts(&mut self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()> {
+        self.set_read_timeout(read)?;
+        self.set_write_timeout(write)
+    }
+}
+
+/// Represents the communication session with the client FUT, providing a
+/// simpler way of handling complex interactions between an FUT and SEMBAS.
+///
+/// Allows for complex bi-directional communication through a standardized
+/// communication protocol.
+///
+/// Allows an external function under test to connect to SEMBAS and request
+/// where to sample next. The classifier can then be called just like any other
+/// classifier.
+pub struct RemoteClassifier<const N: usize, S: Transport = net::TcpStream> {
+    stream: S,
+    domain: Domain<N>,
+    max_batch: Option<usize>,
+    accept: Option<Acceptor<S>>,
+    connected: bool,
+    auto_reconnect: bool,
+    timeouts: (Option<Duration>, Option<Duration>),
+    heartbeat: Option<Duration>,
+    last_activity: Instant,
+    version: Option<u16>,
+    encoding: Encoding,
+    recorder: Option<TranscriptWriter>,
+    token: Option<String>,
+}
+
+/// Accepts a new connection from the FUT, used for reconnecting.
+type Acceptor<S> = Box<dyn FnMut() -> io::Result<S> + Send>;
+
+impl<const N: usize> RemoteClassifier<N> {
+    /// Opens a socket to be connected to by a remote function under test (FUT).  
+    /// Once a connection is established, the RemoteClassifier will send the points
+    /// to the FUT to be classified, and the FUT will return the resulting class
+    /// (bool).
+    /// ## Connection Sequence
+    /// 1. RemoteClassifier binds to TcpListener.
+    /// 2. FUT connects to socket.
+    /// 3. RemoteClassifier accepts connection.
+    /// 4. FUT sends config containing number of params info
+    /// 5. RemoteClassifier accepts configuration, throwing error if N != num params
+    /// 6. RemoteClassifier sends back 'OK\n'
+    /// 7. RemoteClassifier setup complete, ready to classify.
+    ///
+    /// FUTs that begin with `protocol::PROTOCOL_MAGIC` in place of the config use
+    /// the versioned, length-prefixed protocol instead. See `Frame`.
+    pub fn bind(addr: String) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for client connection...");
+        let (stream, _) = listener.accept()?;
+        println!("Connection established.");
+
+        RemoteClassifier::from_stream(stream)
+            .map(|c| c.with_acceptor(Box::new(move || Ok(listener.accept()?.0))))
+    }
+
+    /// Opens a socket to be connected to by a remote function under test (FUT)
+    /// that supports batch classification. Follows the same connection sequence as
+    /// `bind()`, except the config also contains the max batch size the FUT can
+    /// evaluate at once.
+    /// ## Batch Protocol
+    /// * Config : num_params (u64 BE), max_batch (u64 BE).
+    /// * Request : batch size K (u64 BE), followed by K points (K * N f64s).
+    /// * Response : K class bytes, in the order the points were sent.
+    pub fn bind_batched(addr: String) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for client connection...");
+        let (stream, _) = listener.accept()?;
+        println!("Connection established.");
+
+        RemoteClassifier::from_stream_batched(stream)
+            .map(|c| c.with_acceptor(Box::new(move || Ok(listener.accept()?.0))))
+    }
+
+    /// Opens a socket that only accepts FUTs presenting @token, e.g. when running
+    /// SEMBAS on a shared machine. Clients that fail to authenticate are
+    /// disconnected, and SEMBAS continues listening for an authorized client.
+    /// ## Authentication
+    /// Before sending its config (step 4 of `bind()`), the FUT sends the token as a
+    /// u32 BE length followed by the UTF-8 token. An invalid token is answered
+    /// with 'ERROR\n' and the connection is closed.
+    /// ## Arguments
+    /// * addr : The address to listen on.
+    /// * token : The shared secret that clients must present.
+    /// * batched : Whether the FUT uses the batched config. See `bind_batched()`.
+    pub fn bind_authenticated(addr: String, token: &str, batched: bool) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for authorized client connection...");
+        loop {
+            let (stream, peer) = listener.accept()?;
+            match RemoteClassifier::from_stream_authenticated(stream, token, batched) {
+                Ok(c) => {
+                    println!("Connection established.");
+                    return Ok(c.with_acceptor(Box::new(move || Ok(listener.accept()?.0))));
+                }
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                    println!("Rejected unauthorized client {peer}.");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<const N: usize, S: Transport> RemoteClassifier<N, S> {
+    /// Constructs a RemoteClassifer. Prefer using `bind()` unless you need
+    /// fine-grained control. This is used internally after socket setup, once
+    /// the OK signal has been sent to the client.
+    fn new(stream: S, max_batch: Option<usize>, version: Option<u16>, encoding: Encoding) -> Self {
+        let domain = Domain::<N>::normalized();
+        RemoteClassifier {
+            stream,
+            domain,
+            max_batch,
+            accept: None,
+            connected: true,
+            auto_reconnect: false,
+            timeouts: (None, None),
+            heartbeat: None,
+            last_activity: Instant::now(),
+            version,
+            encoding,
+            recorder: None,
+            token: None,
+        }
+    }
+
+    /// Completes the connection sequence (steps 4-7 of `bind()`) over an already
+    /// established connection to the FUT.
+    pub fn from_stream(stream: S) -> io::Result<Self> {
+        Self::configure(stream, false)
+    }
+
+    /// Completes the connection sequence of `bind_batched()` over an already
+    /// established connection to the FUT.
+    pub fn from_stream_batched(stream: S) -> io::Result<Self> {
+        Self::configure(stream, true)
+    }
+
+    /// Completes the connection sequence over an already established connection
+    /// to the FUT, requiring it to first present @token. See
+    /// `bind_authenticated()`.
+    /// ## Error (Err)
+    /// * PermissionDenied : If the FUT presented an invalid token.
+    pub fn from_stream_authenticated(stream: S, token: &str, batched: bool) -> io::Result<Self> {
+        let mut classifier = Self::configure_with(stream, batched, Some(token))?;
+        classifier.token = Some(token.to_string());
+        Ok(classifier)
+    }
+
+    fn configure(stream: S, batched: bool) -> io::Result<Self> {
+        Self::configure_with(stream, batched, None)
+    }
+
+    fn configure_with(mut stream: S, batched: bool, token: Option<&str>) -> io::Result<Self> {
+        if let Some(token) = token {
+            authenticate(&mut stream, token)?;
+        }
+        let config = handshake(&mut stream, batched, Some(N))?;
+        send_ok(&mut stream, &config)?;
+
+        Ok(RemoteClassifier::new(
+            stream,
+            config.max_batch,
+            config.version,
+            config.encoding,
+        ))
+    }
+
+    /// The framed protocol version negotiated with the FUT, or None if the FUT uses
+    /// the legacy protocol.
+    pub fn protocol_version(&self) -> Option<u16> {
+        self.version
+    }
+
+    /// The encoding selected by the FUT for the framed protocol.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Sets how new connections are accepted when reconnecting to the FUT.
+    pub fn with_acceptor(mut self, accept: Acceptor<S>) -> Self {
+        self.accept = Some(accept);
+        self
+    }
+
+    /// If enabled, a lost connection is automatically re-established and the
+    /// unanswered request is replayed, rather than returning
+    /// SamplingError::Disconnected.
+    pub fn set_auto_reconnect(&mut self, enabled: bool) {
+        self.auto_reconnect = enabled;
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Listens for the FUT to reconnect and repeats the connection sequence,
+    /// replacing the lost connection.
+    /// ## Error (Err)
+    /// * Unsupported : If the classifier was not created with `bind()`,
+    ///   `bind_batched()` or `with_acceptor()`.
+    /// * io::Error : If the FUT fails to reconnect or sends an invalid config.
+    pub fn reconnect(&mut self) -> io::Result<()> {
+        let accept = self.accept.as_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "RemoteClassifier has no listener to reconnect with",
+            )
+        })?;
+
+        println!("Listening for client reconnection...");
+        let stream = accept()?;
+        let mut fresh =
+            Self::configure_with(stream, self.max_batch.is_some(), self.token.as_deref())?;
+
+        std::mem::swap(&mut self.stream, &mut fresh.stream);
+        self.max_batch = fresh.max_batch;
+        self.version = fresh.version;
+        self.encoding = fresh.encoding;
+        self.connected = true;
+        fresh.connected = false;
+        self.stream.set_timeouts(self.timeouts.0, self.timeouts.1)?;
+        self.last_activity = Instant::now();
+
+        Ok(())
+    }
+
+    /// Sets how long to wait on the FUT before failing with SamplingError::Timeout.
+    /// After a timeout, the connection is considered lost, since a late response
+    /// would otherwise be mistaken for the answer to the next request.
+    /// ## Arguments
+    /// * read : The max time to wait for a response. None waits indefinitely.
+    /// * write : The max time to wait while sending a request. None waits
+    ///   indefinitely.
+    pub fn set_timeouts(
+        &mut self,
+        read: Option<Duration>,
+        write: Option<Duration>,
+    ) -> io::Result<()> {
+        self.timeouts = (read, write);
+        self.stream.set_timeouts(read, write)
+    }
+
+    /// Enables a keepalive check: if the connection has been idle for longer than
+    /// @interval, a PING is sent before the next request and the FUT must respond
+    /// with PONG. The FUT must support PING messages for this to be enabled.
+    pub fn set_heartbeat(&mut self, interval: Option<Duration>) {
+        self.heartbeat = interval;
+    }
+
+    /// Sends a PING to the FUT and waits for its PONG.
+    /// ## Return (Ok)
+    /// * rtt : The round trip time.
+    /// ## Error (Err)
+    /// * Timeout : If the FUT did not respond within the read timeout.
+    /// * InvalidClassifierResponse : If the FUT responded with anything but PONG.
+    pub fn ping(&mut self) -> error::Result<Duration> {
+        let start = Instant::now();
+        self.send_msg(MSG_PING)?;
+        let msg = self.receive_msg()?;
+        if msg != MSG_PONG {
+            return Err(SamplingError::InvalidClassifierResponse(format!(
+                "Expected {MSG_PONG} in response to {MSG_PING}, got '{msg}'"
+            )));
+        }
+
+        self.last_activity = Instant::now();
+        Ok(start.elapsed())
+    }
+
+    fn keep_alive(&mut self) -> error::Result<()> {
+        if self
+            .heartbeat
+            .is_some_and(|interval| self.last_activity.elapsed() >= interval)
+        {
+            self.ping()?;
+        }
+        Ok(())
+    }
+
+    /// Executes @request, handling a lost connection according to the
+    /// auto-reconnect setting.
+    fn with_reconnect<T>(
+        &mut self,
+        mut request: impl FnMut(&mut Self) -> error::Result<T>,
+    ) -> error::Result<T> {
+        if !self.connected {
+            if !self.auto_reconnect {
+                return Err(SamplingError::Disconnected);
+            }
+            self.reconnect()?;
+        }
+
+        let mut request = |c: &mut Self| {
+            let result = c.keep_alive().and_then(|_| request(c));
+            if result.is_ok() {
+                c.last_activity = Instant::now();
+            }
+            result
+        };
+
+        match request(self) {
+            Err(SamplingError::Timeout) => {
+                self.connected = false;
+                Err(SamplingError::Timeout)
+            }
+            Err(SamplingError::Disconnected) => {
+                self.connected = false;
+                if !self.auto_reconnect {
+                    return Err(SamplingError::Disconnected);
+                }
+                self.reconnect()?;
+                // The replay is not retried, so the connection is treated as lost.
+                request(self).inspect_err(|_| self.connected = false)
+            }
+            result => result,
+        }
+    }
+
+    /// The max number of points the FUT can classify in one request, or None if
+    /// the FUT did not negotiate batch classification.
+    pub fn max_batch(&self) -> Option<usize> {
+        self.max_batch
+    }
+
+    /// Records every classification made by the FUT to a transcript at @path,
+    /// which can be served offline by `ReplayClassifier`.
+    pub fn record_to<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.recorder = Some(TranscriptWriter::create(path)?);
+        Ok(())
+    }
+
+    /// Stops recording classifications, closing the transcript.
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
+    }
+
+    fn record(&mut self, sample: &Sample<N>) -> error::Result<()> {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.record(sample).map_err(|e| {
+                SamplingError::InvalidClassifierResponse(format!(
+                    "Failed to record transcript: {e}"
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Classifies several points, sending them in batches of at most the
+    /// negotiated max batch size. Points are classified one at a time if batching
+    /// was not negotiated.
+    /// ## Arguments
+    /// * points : The points to classify.
+    /// ## Returns
+    /// * samples : The result for each point, in order. Points outside of the
+    ///   domain are OutOfBounds and are not sent to the FUT.
+    pub fn classify_batch_partial(
+        &mut self,
+        points: &[SVector<f64, N>],
+    ) -> Vec<error::Result<Sample<N>>> {
+        let max_batch = if let Some(max_batch) = self.max_batch {
+            max_batch
+        } else {
+            return points.iter().map(|&p| self.classify(p)).collect();
+        };
+
+        let mut results: Vec<error::Result<Sample<N>>> = points
+            .iter()
+            .map(|_| Err(SamplingError::OutOfBounds))
+            .collect();
+        let valid: Vec<usize> = (0..points.len())
+            .filter(|&i| self.domain.contains(&points[i]))
+            .collect();
+
+        for chunk in valid.chunks(max_batch) {
+            let batch: Vec<SVector<f64, N>> = chunk.iter().map(|&i| points[i]).collect();
+            match self.with_reconnect(|c| c.send_batch(&batch)) {
+                Ok(classes) => {
+                    for (&i, cls) in chunk.iter().zip(classes) {
+                        let sample = Sample::from_class(points[i], cls);
+                        results[i] = self.record(&sample).map(|_| sample);
+                    }
+                }
+                Err(e) => {
+                    for &i in chunk {
+                        results[i] = Err(e.clone());
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    fn send_batch(&mut self, batch: &[SVector<f64, N>]) -> error::Result<Vec<bool>> {
+        Ok(self
+            .send_batch_rich(batch)?
+            .into_iter()
+            .map(|(cls, _)| cls)
+            .collect())
+    }
+
+    fn send_batch_rich(
+        &mut self,
+        batch: &[SVector<f64, N>],
+    ) -> error::Result<Vec<(bool, Vec<f64>)>> {
+        let points: Vec<&[f64]> = batch.iter().map(|p| p.as_slice()).collect();
+        exchange_batch_rich(&mut self.stream, self.version, self.encoding, &points)
+    }
+
+    /// Classifies a point, including any auxiliary scalars the FUT reports
+    /// alongside the class. Only the framed protocol supports metadata; other FUTs
+    /// produce samples with empty metadata.
+    pub fn classify_rich(&mut self, p: SVector<f64, N>) -> error::Result<RichSample<N>> {
+        Ok(self.classify_batch_rich(&[p])?.remove(0))
+    }
+
+    /// Classifies several points, including any auxiliary scalars the FUT reports
+    /// alongside each class.
+    /// ## Error (Err)
+    /// * OutOfBounds : If any point is outside of the domain. No points are sent.
+    pub fn classify_batch_rich(
+        &mut self,
+        points: &[SVector<f64, N>],
+    ) -> error::Result<Vec<RichSample<N>>> {
+        if points.iter().any(|p| !self.domain.contains(p)) {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        let max_batch = match self.max_batch {
+            Some(max_batch) => max_batch,
+            None => {
+                return points
+                    .iter()
+                    .map(|&p| Ok(RichSample::new(self.classify(p)?, vec![])))
+                    .collect()
+            }
+        };
+
+        let mut samples = Vec::with_capacity(points.len());
+        for batch in points.chunks(max_batch) {
+            let results = self.with_reconnect(|c| c.send_batch_rich(batch))?;
+            for (&p, (cls, metadata)) in batch.iter().zip(results) {
+                let sample = Sample::from_class(p, cls);
+                self.record(&sample)?;
+                samples.push(RichSample::new(sample, metadata));
+            }
+        }
+
+        Ok(samples)
+    }
+
+    /// Send a message to the client.
+    ///
+    /// Assertion Error: @msg must not contain a newline character.
+    ///     Do not include newline ('\n') characters in your messages,
+    ///     which is used to determine the end of the line and is automatically
+    ///     appended to your data. To prevent this from causing unexpected
+    ///     runtime defects,
+    ///
+    /// Provides a means of sending custom signals to the client. You can
+    /// send anything, but be sure that the client is prepared to receive these
+    /// messages. Pre-defined messages exist within structs/messages, which are
+    /// already implemented in the provided python api scripts (not yet on pep).
+    pub fn send_msg(&mut self, msg: &str) -> io::Result<()> {
+        send_text(&mut self.stream, self.version, self.encoding, msg)
+    }
+
+    /// Receive a message from the client.
+    ///
+    /// WARNING: Destructive, if recieve_msg is called on a classification response,
+    /// the class will be converted to a ASCII String.
+    ///
+    /// Provides a means of receiving custom signals from the client.
+    /// Pre-defined messages exist within structs/messages, which are
+    /// already implemented in the provided python api scripts (not yet on pep).
+    pub fn receive_msg(&mut self) -> io::Result<String> {
+        receive_text(&mut self.stream, self.version, self.encoding)
+    }
+}
+
+impl<const N: usize, S: Transport> Drop for RemoteClassifier<N, S> {
+    fn drop(&mut self) {
+        if self.connected {
+            // The FUT may have already hung up, in which case there is no one left
+            // to notify.
+            let _ = self.send_msg(MSG_END);
+        }
+    }
+}
+
+impl From<io::Error> for SamplingError {
+    fn from(value: io::Error) -> Self {
+        match value.kind() {
+            io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted => SamplingError::Disconnected,
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => SamplingError::Timeout,
+            _ => SamplingError::InvalidClassifierResponse(format!(
+                "Invalid client response message. IO Error: {value}"
+            )),
+        }
+    }
+}
+
+impl<const N: usize, S: Transport> Classifier<N> for RemoteClassifier<N, S> {
+    fn classify(&mut self, p: SVector<f64, N>) -> error::Result<Sample<N>> {
+        if !self.domain.contains(&p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        let sample = self.with_reconnect(|c| c.request(p))?;
+        self.record(&sample)?;
+        Ok(sample)
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> error::Result<Vec<Sample<N>>> {
+        if points.iter().any(|p| !self.domain.contains(p)) {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        self.classify_batch_partial(points).into_iter().collect()
+    }
+}
+
+impl<const N: usize, S: Transport> RemoteClassifier<N, S> {
+    fn request(&mut self, p: SVector<f64, N>) -> error::Result<Sample<N>> {
+        if self.max_batch.is_some() {
+            return self
+                .send_batch(&[p])
+                .map(|classes| Sample::from_class(p, classes[0]));
+        }
+
+        exchange_single(&mut self.stream, p.as_slice()).map(|cls| Sample::from_class(p, cls))
+    }
+}
+
+/// The connection parameters negotiated with the FUT during the handshake.
+struct Handshake {
+    num_params: usize,
+    max_batch: Option<usize>,
+    version: Option<u16>,
+    encoding: Encoding,
+}
+
+/// Receives the FUT's config (steps 4-5 of `RemoteClassifier::bind()`), detecting
+/// whether the legacy or framed protocol is used.
+/// ## Arguments
+/// * stream : The connection to the FUT.
+/// * batched : Whether the legacy config includes a max batch size.
+/// * expected : The number of params required, or None to accept any.
+fn handshake<S: Transport>(
+    stream: &mut S,
+    batched: bool,
+    expected: Option<usize>,
+) -> io::Result<Handshake> {
+    println!("Waiting for sim config...");
+    let mut buffer = [0u8; BUFFER_CONFIG_SIZE];
+    stream.read_exact(&mut buffer)?;
+
+    if buffer[..4] == protocol::PROTOCOL_MAGIC {
+        return handshake_framed(stream, buffer, expected);
+    }
+
+    let num_params = usize::from_be_bytes(buffer);
+
+    if let Some(n) = expected.filter(|&n| n != num_params) {
+        stream.write_all(format!("{n}\n").as_bytes())?;
+        stream.flush()?;
+
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid number of param names! Expected {n}, Got {num_params}"),
+        ));
+    }
+
+    let max_batch = if batched {
+        stream.read_exact(&mut buffer)?;
+        let max_batch = usize::from_be_bytes(buffer);
+        if max_batch == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Max batch size must be non-zero!",
+            ));
+        }
+        Some(max_batch)
+    } else {
+        None
+    };
+
+    println!("Got valid config. Ready.");
+
+    Ok(Handshake {
+        num_params,
+        max_batch,
+        version: None,
+        encoding: Encoding::Binary,
+    })
+}
+
+/// Completes the handshake of the framed protocol. See `Frame`.
+fn handshake_framed<S: Transport>(
+    stream: &mut S,
+    header: [u8; BUFFER_CONFIG_SIZE],
+    expected: Option<usize>,
+) -> io::Result<Handshake> {
+    let client_version = u16::from_be_bytes([header[4], header[5]]);
+    let version = client_version.min(PROTOCOL_VERSION);
+    let encoding_id = u16::from_be_bytes([header[6], header[7]]);
+
+    let reject = |stream: &mut S, encoding: Encoding, msg: String| {
+        Frame::Error(msg.clone()).write_encoded(stream, encoding)?;
+        Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+    };
+
+    let encoding = match Encoding::from_id(encoding_id) {
+        Some(encoding) => encoding,
+        None => {
+            return reject(
+                stream,
+                Encoding::Binary,
+                format!("Unsupported encoding {encoding_id}"),
+            )
+        }
+    };
+
+    if version == 0 {
+        return reject(
+            stream,
+            encoding,
+            "Unsupported protocol version 0".to_string(),
+        );
+    }
+
+    let (num_params, max_batch) = match Frame::read_encoded(stream, encoding)? {
+        Frame::Config {
+            num_params,
+            max_batch,
+        } => (num_params as usize, max_batch as usize),
+        frame => return reject(stream, encoding, format!("Expected config, got {frame:?}")),
+    };
+
+    if let Some(n) = expected.filter(|&n| n != num_params) {
+        return reject(
+            stream,
+            encoding,
+            format!("Invalid number of param names! Expected {n}, Got {num_params}"),
+        );
+    }
+
+    println!("Got valid config (protocol v{version}). Ready.");
+
+    Ok(Handshake {
+        num_params,
+        max_batch: Some(max_batch.max(1)),
+        version: Some(version),
+        encoding,
+    })
+}
+
+/// Reads the token presented by the FUT, rejecting the connection if it does not
+/// match @token.
+fn authenticate<S: Transport>(stream: &mut S, token: &str) -> io::Result<()> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+
+    let presented = if len <= MAX_TOKEN_SIZE {
+        let mut presented = vec![0u8; len];
+        stream.read_exact(&mut presented)?;
+        Some(presented)
+    } else {
+        None
+    };
+
+    if presented.is_some_and(|p| tokens_match(&p, token.as_bytes())) {
+        return Ok(());
+    }
+
+    send_text(stream, None, Encoding::Binary, MSG_ERR)?;
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "Client presented an invalid authentication token",
+    ))
+}
+
+/// Compares tokens in constant time with respect to their contents, so that
+/// response timing does not reveal how much of a guess was correct.
+fn tokens_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Accepts the FUT's config (step 6 of `RemoteClassifier::bind()`).
+fn send_ok<S: Transport>(stream: &mut S, config: &Handshake) -> io::Result<()> {
+    if let Some(version) = config.version {
+        Frame::Ok { version }.write_encoded(stream, config.encoding)
+    } else {
+        send_text(stream, None, config.encoding, MSG_OK)
+    }
+}
+
+/// Sends a single point to an FUT using the legacy, unbatched protocol.
+fn exchange_single<S: Transport>(stream: &mut S, p: &[f64]) -> error::Result<bool> {
+    stream.write_all(bytemuck::cast_slice(p))?;
+    stream.flush()?;
+
+    let mut buffer = [0; 1];
+    stream.read_exact(&mut buffer)?;
+    if buffer[0] > 1 {
+        Err(SamplingError::InvalidClassifierResponse(
+            "Remote Classifier received non-bool response?".to_string(),
+        ))
+    } else {
+        Ok(buffer[0] == 1)
+    }
+}
+
+/// Sends a batch of points to an FUT that negotiated batch classification.
+fn exchange_batch<S: Transport>(
+    stream: &mut S,
+    version: Option<u16>,
+    encoding: Encoding,
+    batch: &[&[f64]],
+) -> error::Result<Vec<bool>> {
+    Ok(exchange_batch_rich(stream, version, encoding, batch)?
+        .into_iter()
+        .map(|(cls, _)| cls)
+        .collect())
+}
+
+/// Sends a batch of points to an FUT that negotiated batch classification,
+/// returning each class with the auxiliary scalars reported by the FUT, if any.
+fn exchange_batch_rich<S: Transport>(
+    stream: &mut S,
+    version: Option<u16>,
+    encoding: Encoding,
+    batch: &[&[f64]],
+) -> error::Result<Vec<(bool, Vec<f64>)>> {
+    if version.is_some() {
+        let points = batch.iter().map(|p| p.to_vec()).collect();
+        Frame::Request(points).write_encoded(stream, encoding)?;
+
+        return match Frame::read_encoded(stream, encoding)? {
+            Frame::Response(classes) if classes.len() == batch.len() => {
+                Ok(classes.into_iter().map(|cls| (cls, vec![])).collect())
+            }
+            Frame::RichResponse { classes, metadata }
+                if classes.len() == batch.len() && metadata.len() == batch.len() =>
+            {
+                Ok(classes.into_iter().zip(metadata).collect())
+            }
+            Frame::Error(msg) => Err(SamplingError::InvalidClassifierResponse(msg)),
+            frame => Err(SamplingError::InvalidClassifierResponse(format!(
+                "Expected {} classes, got {frame:?}",
+                batch.len()
+            ))),
+        };
+    }
+
+    stream.write_all(&batch.len().to_be_bytes())?;
+    for p in batch {
+        stream.write_all(bytemuck::cast_slice(p))?;
+    }
+    stream.flush()?;
+
+    let mut buffer = vec![0; batch.len()];
+    stream.read_exact(&mut buffer)?;
+    if buffer.iter().any(|&b| b > 1) {
+        Err(SamplingError::InvalidClassifierResponse(
+            "Remote Classifier received non-bool response?".to_string(),
+        ))
+    } else {
+        Ok(buffer.into_iter().map(|b| (b == 1, vec![])).collect())
+    }
+}
+
+fn send_text<S: Transport>(
+    stream: &mut S,
+    version: Option<u16>,
+    encoding: Encoding,
+    msg: &str,
+) -> io::Result<()> {
+    assert!(!msg.contains("\n"));
+
+    if version.is_some() {
+        return Frame::Text(msg.to_string()).write_encoded(stream, encoding);
+    }
+
+    let message = format!("{}\n", msg);
+    stream.write_all(message.as_bytes())?;
+    stream.flush()?;
+
+    Ok(())
+}
+
+fn receive_text<S: Transport>(
+    stream: &mut S,
+    version: Option<u16>,
+    encoding: Encoding,
+) -> io::Result<String> {
+    if version.is_some() {
+        return match Frame::read_encoded(stream, encoding)? {
+            Frame::Text(msg) => Ok(msg),
+            frame => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Expected a text message, got {frame:?}"),
+            )),
+        };
+    }
+
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    line = line.trim().to_string();
+
+    Ok(line)
+}
+
+#[cfg(test)]
+mod batch_protocol {
+    use std::thread;
+
+    use nalgebra::{vector, SVector};
+
+    use super::*;
+
+    #[test]
+    fn classifies_in_negotiated_batches() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+            stream.write_all(&2usize.to_be_bytes()).unwrap();
+            stream.write_all(&3usize.to_be_bytes()).unwrap();
+
+            let mut ok = [0u8; 3];
+            stream.read_exact(&mut ok).unwrap();
+            assert_eq!(&ok, b"OK\n");
+
+            let mut batch_sizes = vec![];
+            loop {
+                let mut buffer = [0u8; 8];
+                stream.read_exact(&mut buffer[..4]).unwrap();
+                if &buffer[..4] == b"END\n" {
+                    break;
+                }
+                stream.read_exact(&mut buffer[4..]).unwrap();
+                let k = usize::from_be_bytes(buffer);
+                batch_sizes.push(k);
+
+                let mut points = vec![0u8; k * 2 * 8];
+                stream.read_exact(&mut points).unwrap();
+                let points: Vec<f64> = points
+                    .chunks(8)
+                    .map(|b| f64::from_ne_bytes(b.try_into().unwrap()))
+                    .collect();
+                let classes: Vec<u8> = points.chunks(2).map(|p| (p[0] < 0.5) as u8).collect();
+                stream.write_all(&classes).unwrap();
+            }
+
+            batch_sizes
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream_batched(stream).unwrap();
+        assert_eq!(classifier.max_batch(), Some(3));
+
+        let points: Vec<SVector<f64, 2>> = vec![
+            vector![0.1, 0.5],
+            vector![0.9, 0.5],
+            vector![1.5, 0.5],
+            vector![0.2, 0.5],
+            vector![0.8, 0.5],
+            vector![0.3, 0.5],
+        ];
+        let results = classifier.classify_batch_partial(&points);
+
+        assert_eq!(results.len(), points.len());
+        assert!(matches!(results[2], Err(SamplingError::OutOfBounds)));
+        for (p, r) in points.iter().zip(results.iter()) {
+            if let Ok(s) = r {
+                assert_eq!(s.class(), p[0] < 0.5);
+            }
+        }
+        drop(classifier);
+
+        assert_eq!(client.join().unwrap(), vec![3, 2]);
+    }
+}
+
+#[cfg(test)]
+mod reconnection {
+    use std::thread;
+
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn connect(addr: net::SocketAddr) -> net::TcpStream {
+        let mut stream = net::TcpStream::connect(addr).unwrap();
+        stream.write_all(&2usize.to_be_bytes()).unwrap();
+        let mut ok = [0u8; 3];
+        stream.read_exact(&mut ok).unwrap();
+        stream
+    }
+
+    #[test]
+    fn replays_request_after_reconnecting() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            // Crashes mid-request
+            let mut stream = connect(addr);
+            let mut request = [0u8; 16];
+            stream.read_exact(&mut request).unwrap();
+            drop(stream);
+
+            let mut stream = connect(addr);
+            stream.read_exact(&mut request).unwrap();
+            stream.write_all(&[1]).unwrap();
+
+            let mut end = [0u8; 4];
+            stream.read_exact(&mut end).unwrap();
+            assert_eq!(&end, b"END\n");
+            request
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream)
+            .unwrap()
+            .with_acceptor(Box::new(move || Ok(listener.accept()?.0)));
+        classifier.set_auto_reconnect(true);
+
+        let p = vector![0.25, 0.75];
+        let sample = classifier.classify(p).expect("Failed to replay request");
+        assert!(sample.class());
+        assert!(classifier.is_connected());
+        drop(classifier);
+
+        let request = client.join().unwrap();
+        assert_eq!(&request, bytemuck::cast_slice::<f64, u8>(p.as_slice()));
+    }
+
+    #[test]
+    fn disconnects_when_replay_fails() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            // Crashes mid-request, both before and after reconnecting
+            for _ in 0..2 {
+                let mut stream = net::TcpStream::connect(addr).unwrap();
+                stream.write_all(&2usize.to_be_bytes()).unwrap();
+                let mut ok = [0u8; 3];
+                stream.read_exact(&mut ok).unwrap();
+                let mut request = [0u8; 16];
+                stream.read_exact(&mut request).unwrap();
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream)
+            .unwrap()
+            .with_acceptor(Box::new(move || Ok(listener.accept()?.0)));
+        classifier.set_auto_reconnect(true);
+
+        let result = classifier.classify(vector![0.25, 0.75]);
+        assert!(
+            matches!(result, Err(SamplingError::Disconnected)),
+            "{result:?}"
+        );
+        assert!(!classifier.is_connected());
+
+        client.join().unwrap();
+    }
+
+    /// A connection whose writes fail once the FUT has hung up.
+    struct HungUp {
+        input: io::Cursor<Vec<u8>>,
+        writable: bool,
+    }
+
+    impl Read for HungUp {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for HungUp {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.writable {
+                Ok(buf.len())
+            } else {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Transport for HungUp {}
+
+    #[test]
+    fn drop_after_hang_up_does_not_panic() {
+        let stream = HungUp {
+            input: io::Cursor::new(2usize.to_be_bytes().to_vec()),
+            writable: true,
+        };
+        let mut classifier = RemoteClassifier::<2, _>::from_stream(stream).unwrap();
+        classifier.stream.writable = false;
+        drop(classifier);
+    }
+
+    #[test]
+    fn handshake_reports_hang_up() {
+        let stream = HungUp {
+            input: io::Cursor::new(3usize.to_be_bytes().to_vec()),
+            writable: false,
+        };
+        let result = RemoteClassifier::<2, _>::from_stream(stream);
+        assert_eq!(result.err().unwrap().kind(), io::ErrorKind::BrokenPipe);
+    }
+}
+
+#[cfg(test)]
+mod keepalive {
+    use std::{io::BufRead, thread};
+
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn connect(addr: net::SocketAddr) -> net::TcpStream {
+        let mut stream = net::TcpStream::connect(addr).unwrap();
+        stream.write_all(&2usize.to_be_bytes()).unwrap();
+        let mut ok = [0u8; 3];
+        stream.read_exact(&mut ok).unwrap();
+        stream
+    }
+
+    #[test]
+    fn reports_hung_fut_as_timeout() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = connect(addr);
+            let mut request = [0u8; 16];
+            stream.read_exact(&mut request).unwrap();
+            // Hangs without responding
+            thread::sleep(Duration::from_millis(500));
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        classifier
+            .set_timeouts(Some(Duration::from_millis(50)), None)
+            .unwrap();
+
+        let start = Instant::now();
+        let result = classifier.classify(vector![0.5, 0.5]);
+        assert!(matches!(result, Err(SamplingError::Timeout)), "{result:?}");
+        assert!(start.elapsed() < Duration::from_millis(400));
+        assert!(!classifier.is_connected());
+
+        drop(classifier);
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn pings_idle_connection_before_request() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let stream = connect(addr);
+            let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            assert_eq!(line, "PING\n");
+            writer.write_all(b"PONG\n").unwrap();
+
+            let mut request = [0u8; 16];
+            reader.read_exact(&mut request).unwrap();
+            writer.write_all(&[0]).unwrap();
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        classifier.set_heartbeat(Some(Duration::ZERO));
+
+        let sample = classifier.classify(vector![0.5, 0.5]).unwrap();
+        assert!(!sample.class());
+
+        client.join().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod authentication {
+    use std::thread;
+
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn connect(addr: net::SocketAddr, token: &str) -> net::TcpStream {
+        let mut stream = net::TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(&(token.len() as u32).to_be_bytes())
+            .unwrap();
+        stream.write_all(token.as_bytes()).unwrap();
+        stream
+    }
+
+    #[test]
+    fn rejects_clients_with_invalid_token() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut intruder = connect(addr, "guess");
+            let mut response = String::new();
+            BufReader::new(&mut intruder)
+                .read_line(&mut response)
+                .unwrap();
+            assert_eq!(response.trim(), MSG_ERR);
+
+            let mut stream = connect(addr, "secret");
+            stream.write_all(&2usize.to_be_bytes()).unwrap();
+            let mut ok = [0u8; 3];
+            stream.read_exact(&mut ok).unwrap();
+            assert_eq!(&ok, b"OK\n");
+
+            let mut buffer = [0u8; 16];
+            stream.read_exact(&mut buffer).unwrap();
+            stream.write_all(&[1]).unwrap();
+
+            let mut end = [0u8; 4];
+            stream.read_exact(&mut end).unwrap();
+            assert_eq!(&end, b"END\n");
+        });
+
+        let (intruder, _) = listener.accept().unwrap();
+        let rejected = RemoteClassifier::<2>::from_stream_authenticated(intruder, "secret", false);
+        assert_eq!(
+            rejected.err().unwrap().kind(),
+            io::ErrorKind::PermissionDenied
+        );
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier =
+            RemoteClassifier::<2>::from_stream_authenticated(stream, "secret", false).unwrap();
+        assert!(classifier.classify(vector![0.5, 0.5]).unwrap().class());
+        drop(classifier);
+
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn compares_tokens_exactly() {
+        assert!(tokens_match(b"secret", b"secret"));
+        assert!(!tokens_match(b"secreT", b"secret"));
+        assert!(!tokens_match(b"secret!", b"secret"));
+    }
+}
+
+#[cfg(test)]
+mod framed_protocol {
+    use std::thread;
+
+    use nalgebra::vector;
+
+    use super::*;
+
+    #[test]
+    fn negotiates_and_classifies_with_frames() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+
+            stream.write_all(&protocol::PROTOCOL_MAGIC).unwrap();
+            stream.write_all(&7u16.to_be_bytes()).unwrap();
+            stream.write_all(&0u16.to_be_bytes()).unwrap();
+            Frame::Config {
+                num_params: 2,
+                max_batch: 4,
+            }
+            .write_to(&mut stream)
+            .unwrap();
+            assert_eq!(
+                Frame::read_from(&mut stream).unwrap(),
+                Frame::Ok {
+                    version: PROTOCOL_VERSION
+                }
+            );
+
+            let mut n_requests = 0;
+            loop {
+                match Frame::read_from(&mut stream).unwrap() {
+                    Frame::Request(points) => {
+                        n_requests += 1;
+                        let classes = points.iter().map(|p| p[0] < 0.5).collect();
+                        Frame::Response(classes).write_to(&mut stream).unwrap();
+                    }
+                    Frame::Text(msg) if msg == MSG_END => break,
+                    Frame::Text(msg) => Frame::Text(format!("echo {msg}"))
+                        .write_to(&mut stream)
+                        .unwrap(),
+                    frame => panic!("Unexpected frame: {frame:?}"),
+                }
+            }
+            n_requests
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        assert_eq!(classifier.protocol_version(), Some(PROTOCOL_VERSION));
+        assert_eq!(classifier.max_batch(), Some(4));
+
+        let transcript = std::env::temp_dir().join("sembas_framed_transcript.csv");
+        classifier.record_to(&transcript).unwrap();
+
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+        let samples = classifier
+            .classify_batch(&[vector![0.75, 0.5], vector![0.1, 0.1]])
+            .unwrap();
+        assert!(!samples[0].class() && samples[1].class());
+
+        classifier.stop_recording();
+        let mut replay = crate::classifiers::ReplayClassifier::<2>::load(&transcript).unwrap();
+        std::fs::remove_file(&transcript).unwrap();
+        assert_eq!(replay.len(), 3);
+        assert_eq!(replay.classify(vector![0.75, 0.5]).unwrap(), samples[0]);
+
+        classifier.send_msg("GS").unwrap();
+        assert_eq!(classifier.receive_msg().unwrap(), "echo GS");
+        drop(classifier);
+
+        assert_eq!(client.join().unwrap(), 2);
+    }
+
+    #[test]
+    fn receives_metadata_with_classes() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+
+            stream.write_all(&protocol::PROTOCOL_MAGIC).unwrap();
+            stream.write_all(&PROTOCOL_VERSION.to_be_bytes()).unwrap();
+            stream.write_all(&0u16.to_be_bytes()).unwrap();
+            Frame::Config {
+                num_params: 2,
+                max_batch: 8,
+            }
+            .write_to(&mut stream)
+            .unwrap();
+            Frame::read_from(&mut stream).unwrap();
+
+            loop {
+                match Frame::read_from(&mut stream).unwrap() {
+                    Frame::Request(points) => Frame::RichResponse {
+                        classes: points.iter().map(|p| p[0] < 0.5).collect(),
+                        metadata: points.iter().map(|p| vec![p[0] * 10.0]).collect(),
+                    }
+                    .write_to(&mut stream)
+                    .unwrap(),
+                    Frame::Text(msg) if msg == MSG_END => break,
+                    frame => panic!("Unexpected frame: {frame:?}"),
+                }
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+
+        let samples = classifier
+            .classify_batch_rich(&[vector![0.25, 0.5], vector![0.75, 0.5]])
+            .unwrap();
+        assert!(samples[0].class() && !samples[1].class());
+        assert_eq!(samples[0].metadata, vec![2.5]);
+        assert_eq!(samples[1].metadata, vec![7.5]);
+
+        // Metadata is discarded by plain classification.
+        assert!(classifier.classify(vector![0.1, 0.1]).unwrap().class());
+        drop(classifier);
+
+        client.join().unwrap();
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn negotiates_msgpack_encoding() {
+        let encoding = Encoding::MessagePack;
+
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+
+            stream.write_all(&protocol::PROTOCOL_MAGIC).unwrap();
+            stream.write_all(&PROTOCOL_VERSION.to_be_bytes()).unwrap();
+            stream.write_all(&encoding.id().to_be_bytes()).unwrap();
+            Frame::Config {
+                num_params: 2,
+                max_batch: 2,
+            }
+            .write_encoded(&mut stream, encoding)
+            .unwrap();
+            assert!(matches!(
+                Frame::read_encoded(&mut stream, encoding).unwrap(),
+                Frame::Ok { .. }
+            ));
+
+            loop {
+                match Frame::read_encoded(&mut stream, encoding).unwrap() {
+                    Frame::Request(points) => {
+                        let classes = points.iter().map(|p| p[1] > 0.5).collect();
+                        Frame::Response(classes)
+                            .write_encoded(&mut stream, encoding)
+                            .unwrap();
+                    }
+                    Frame::Text(msg) if msg == MSG_END => break,
+                    frame => panic!("Unexpected frame: {frame:?}"),
+                }
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        assert_eq!(classifier.encoding(), Encoding::MessagePack);
+
+        let samples = classifier
+            .classify_batch(&[vector![0.1, 0.9], vector![0.9, 0.1]])
+            .unwrap();
+        assert!(samples[0].class() && !samples[1].class());
+        drop(classifier);
+
+        client.join().unwrap();
+    }
+}
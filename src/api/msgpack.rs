@@ -0,0 +1,276 @@
+use std::io;
+
+/// Protocol frames nest at most a few arrays deep (a batch of points), so
+/// anything deeper is rejected rather than recursed into.
+const MAX_DEPTH: usize = 16;
+
+/// The subset of MessagePack values used by the remote protocol.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::UInt(x) => Some(x),
+            Value::Int(x) if x >= 0 => Some(x as u64),
+            _ => None,
+        }
+    }
+
+    /// Converts any numeric value to f64, since clients may encode whole numbers
+    /// as integers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(x) => Some(x),
+            Value::UInt(x) => Some(x as f64),
+            Value::Int(x) => Some(x as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            Value::UInt(x) if x <= 1 => Some(x == 1),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::Nil => out.push(0xc0),
+            Value::Bool(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+            Value::UInt(x) => encode_uint(*x, out),
+            Value::Int(x) if *x >= 0 => encode_uint(*x as u64, out),
+            Value::Int(x) => {
+                if *x >= -32 {
+                    out.push(*x as i8 as u8);
+                } else {
+                    out.push(0xd3);
+                    out.extend_from_slice(&x.to_be_bytes());
+                }
+            }
+            Value::Float(x) => {
+                out.push(0xcb);
+                out.extend_from_slice(&x.to_be_bytes());
+            }
+            Value::Str(s) => {
+                let len = s.len();
+                if len < 32 {
+                    out.push(0xa0 | len as u8);
+                } else if len <= u8::MAX as usize {
+                    out.push(0xd9);
+                    out.push(len as u8);
+                } else if len <= u16::MAX as usize {
+                    out.push(0xda);
+                    out.extend_from_slice(&(len as u16).to_be_bytes());
+                } else {
+                    out.push(0xdb);
+                    out.extend_from_slice(&(len as u32).to_be_bytes());
+                }
+                out.extend_from_slice(s.as_bytes());
+            }
+            Value::Array(values) => {
+                let len = values.len();
+                if len < 16 {
+                    out.push(0x90 | len as u8);
+                } else if len <= u16::MAX as usize {
+                    out.push(0xdc);
+                    out.extend_from_slice(&(len as u16).to_be_bytes());
+                } else {
+                    out.push(0xdd);
+                    out.extend_from_slice(&(len as u32).to_be_bytes());
+                }
+                for v in values {
+                    v.encode(out);
+                }
+            }
+        }
+    }
+
+    /// Decodes a single value from the start of @bytes, advancing @pos past it.
+    pub fn decode(bytes: &[u8], pos: &mut usize) -> io::Result<Value> {
+        Self::decode_nested(bytes, pos, 0)
+    }
+
+    fn decode_nested(bytes: &[u8], pos: &mut usize, depth: usize) -> io::Result<Value> {
+        let marker = take::<1>(bytes, pos)?[0];
+
+        let value = match marker {
+            0x00..=0x7f => Value::UInt(marker as u64),
+            0xe0..=0xff => Value::Int(marker as i8 as i64),
+            0xc0 => Value::Nil,
+            0xc2 => Value::Bool(false),
+            0xc3 => Value::Bool(true),
+            0xcc => Value::UInt(take::<1>(bytes, pos)?[0] as u64),
+            0xcd => Value::UInt(u16::from_be_bytes(take(bytes, pos)?) as u64),
+            0xce => Value::UInt(u32::from_be_bytes(take(bytes, pos)?) as u64),
+            0xcf => Value::UInt(u64::from_be_bytes(take(bytes, pos)?)),
+            0xd0 => Value::Int(take::<1>(bytes, pos)?[0] as i8 as i64),
+            0xd1 => Value::Int(i16::from_be_bytes(take(bytes, pos)?) as i64),
+            0xd2 => Value::Int(i32::from_be_bytes(take(bytes, pos)?) as i64),
+            0xd3 => Value::Int(i64::from_be_bytes(take(bytes, pos)?)),
+            0xca => Value::Float(f32::from_be_bytes(take(bytes, pos)?) as f64),
+            0xcb => Value::Float(f64::from_be_bytes(take(bytes, pos)?)),
+            0xa0..=0xbf => decode_str((marker & 0x1f) as usize, bytes, pos)?,
+            0xd9 => {
+                let len = take::<1>(bytes, pos)?[0] as usize;
+                decode_str(len, bytes, pos)?
+            }
+            0xda => {
+                let len = u16::from_be_bytes(take(bytes, pos)?) as usize;
+                decode_str(len, bytes, pos)?
+            }
+            0xdb => {
+                let len = u32::from_be_bytes(take(bytes, pos)?) as usize;
+                decode_str(len, bytes, pos)?
+            }
+            0x90..=0x9f => decode_array((marker & 0x0f) as usize, bytes, pos, depth)?,
+            0xdc => {
+                let len = u16::from_be_bytes(take(bytes, pos)?) as usize;
+                decode_array(len, bytes, pos, depth)?
+            }
+            0xdd => {
+                let len = u32::from_be_bytes(take(bytes, pos)?) as usize;
+                decode_array(len, bytes, pos, depth)?
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unsupported MessagePack marker: {marker:#x}"),
+                ))
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+fn encode_uint(x: u64, out: &mut Vec<u8>) {
+    if x < 128 {
+        out.push(x as u8);
+    } else if x <= u8::MAX as u64 {
+        out.push(0xcc);
+        out.push(x as u8);
+    } else if x <= u16::MAX as u64 {
+        out.push(0xcd);
+        out.extend_from_slice(&(x as u16).to_be_bytes());
+    } else if x <= u32::MAX as u64 {
+        out.push(0xce);
+        out.extend_from_slice(&(x as u32).to_be_bytes());
+    } else {
+        out.push(0xcf);
+        out.extend_from_slice(&x.to_be_bytes());
+    }
+}
+
+fn decode_str(len: usize, bytes: &[u8], pos: &mut usize) -> io::Result<Value> {
+    let raw = bytes.get(*pos..*pos + len).ok_or_else(truncated)?.to_vec();
+    *pos += len;
+    String::from_utf8(raw)
+        .map(Value::Str)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Non-UTF8 MessagePack str"))
+}
+
+fn decode_array(len: usize, bytes: &[u8], pos: &mut usize, depth: usize) -> io::Result<Value> {
+    if depth >= MAX_DEPTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "MessagePack arrays nested too deeply",
+        ));
+    }
+    // Each value is at least one byte, which bounds the allocation.
+    if len > bytes.len() - *pos {
+        return Err(truncated());
+    }
+    (0..len)
+        .map(|_| Value::decode_nested(bytes, pos, depth + 1))
+        .collect::<io::Result<_>>()
+        .map(Value::Array)
+}
+
+fn take<const K: usize>(bytes: &[u8], pos: &mut usize) -> io::Result<[u8; K]> {
+    let value = bytes
+        .get(*pos..*pos + K)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(truncated)?;
+    *pos += K;
+    Ok(value)
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "Truncated MessagePack value")
+}
+
+#[cfg(test)]
+mod msgpack_values {
+    use super::*;
+
+    #[test]
+    fn values_survive_round_trip() {
+        let value = Value::Array(vec![
+            Value::Nil,
+            Value::Bool(true),
+            Value::UInt(5),
+            Value::UInt(300),
+            Value::UInt(u64::MAX),
+            Value::Int(-3),
+            Value::Int(-1000),
+            Value::Float(0.125),
+            Value::Str("boundary".to_string()),
+            Value::Str("x".repeat(40)),
+            Value::Array((0..20).map(Value::UInt).collect()),
+        ]);
+
+        let mut bytes = vec![];
+        value.encode(&mut bytes);
+        let mut pos = 0;
+        assert_eq!(Value::decode(&bytes, &mut pos).unwrap(), value);
+        assert_eq!(pos, bytes.len());
+    }
+
+    #[test]
+    fn decodes_known_encoding() {
+        // [1, 0.5, "a"] as encoded by reference implementations.
+        let bytes = [0x93, 0x01, 0xcb, 0x3f, 0xe0, 0, 0, 0, 0, 0, 0, 0xa1, b'a'];
+        let mut pos = 0;
+        assert_eq!(
+            Value::decode(&bytes, &mut pos).unwrap(),
+            Value::Array(vec![
+                Value::UInt(1),
+                Value::Float(0.5),
+                Value::Str("a".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_deeply_nested_arrays() {
+        let bytes = vec![0x91; 1 << 20];
+        let mut pos = 0;
+        let err = Value::decode(&bytes, &mut pos).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
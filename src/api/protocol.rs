@@ -0,0 +1,451 @@
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "msgpack")]
+use super::msgpack::Value;
+
+/// Sent in place of the legacy config to request the framed protocol.
+pub const PROTOCOL_MAGIC: [u8; 4] = *b"SMBS";
+/// The latest version of the framed protocol supported by SEMBAS.
+pub const PROTOCOL_VERSION: u16 = 1;
+/// The largest frame SEMBAS will accept, guarding against corrupt length prefixes.
+pub const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// How frames are serialized, selected by the FUT during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// The binary layout described by `Frame`.
+    #[default]
+    Binary,
+    /// Each frame is a MessagePack array of its type followed by its fields, e.g.
+    /// `[5, [[0.1, 0.2], [0.3, 0.4]]]` for a request. Requires the `msgpack`
+    /// feature.
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl Encoding {
+    /// The encoding for the id sent in the handshake, if supported.
+    pub fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(Encoding::Binary),
+            #[cfg(feature = "msgpack")]
+            1 => Some(Encoding::MessagePack),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        match self {
+            Encoding::Binary => 0,
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => 1,
+        }
+    }
+}
+
+const TYPE_CONFIG: u8 = 0x01;
+const TYPE_OK: u8 = 0x02;
+const TYPE_ERROR: u8 = 0x03;
+const TYPE_TEXT: u8 = 0x04;
+const TYPE_REQUEST: u8 = 0x05;
+const TYPE_RESPONSE: u8 = 0x06;
+const TYPE_RICH_RESPONSE: u8 = 0x07;
+
+/// A message of the framed remote protocol.
+///
+/// ## Handshake
+/// 1. FUT sends PROTOCOL_MAGIC, its max supported version (u16 BE), and the id of
+///    the `Encoding` to use for all following frames (u16 BE).
+/// 2. FUT sends a Config frame.
+/// 3. SEMBAS responds with Ok, containing the version both sides will use, or
+///    Error if the config is invalid.
+///
+/// ## Framing
+/// Every frame is a u32 BE length, followed by that many bytes: a type byte and
+/// the payload. All numbers are big endian, except for point coordinates, which
+/// are little endian f64s.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// FUT -> SEMBAS. The number of params and max batch size of the FUT.
+    Config { num_params: u64, max_batch: u64 },
+    /// SEMBAS -> FUT. Accepts the config with the selected protocol version.
+    Ok { version: u16 },
+    /// Either direction. A fatal error, with a description.
+    Error(String),
+    /// Either direction. A text message, e.g. phases or custom signals.
+    Text(String),
+    /// SEMBAS -> FUT. The points to classify.
+    Request(Vec<Vec<f64>>),
+    /// FUT -> SEMBAS. The classes of the requested points, in order.
+    Response(Vec<bool>),
+    /// FUT -> SEMBAS. May be sent in place of Response to include auxiliary scalars
+    /// for each point, e.g. lap time or collision severity. Every point must have
+    /// the same number of scalars.
+    RichResponse {
+        classes: Vec<bool>,
+        metadata: Vec<Vec<f64>>,
+    },
+}
+
+impl Frame {
+    /// Encodes the frame's type and payload, without the length prefix.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        match self {
+            Frame::Config {
+                num_params,
+                max_batch,
+            } => {
+                bytes.push(TYPE_CONFIG);
+                bytes.extend_from_slice(&num_params.to_be_bytes());
+                bytes.extend_from_slice(&max_batch.to_be_bytes());
+            }
+            Frame::Ok { version } => {
+                bytes.push(TYPE_OK);
+                bytes.extend_from_slice(&version.to_be_bytes());
+            }
+            Frame::Error(msg) => {
+                bytes.push(TYPE_ERROR);
+                bytes.extend_from_slice(msg.as_bytes());
+            }
+            Frame::Text(msg) => {
+                bytes.push(TYPE_TEXT);
+                bytes.extend_from_slice(msg.as_bytes());
+            }
+            Frame::Request(points) => {
+                let dims = points.first().map_or(0, |p| p.len());
+                bytes.push(TYPE_REQUEST);
+                bytes.extend_from_slice(&(points.len() as u32).to_be_bytes());
+                bytes.extend_from_slice(&(dims as u32).to_be_bytes());
+                for x in points.iter().flatten() {
+                    bytes.extend_from_slice(&x.to_le_bytes());
+                }
+            }
+            Frame::Response(classes) => {
+                bytes.push(TYPE_RESPONSE);
+                bytes.extend_from_slice(&(classes.len() as u32).to_be_bytes());
+                bytes.extend(classes.iter().map(|&c| c as u8));
+            }
+            Frame::RichResponse { classes, metadata } => {
+                let m = metadata.first().map_or(0, |x| x.len());
+                bytes.push(TYPE_RICH_RESPONSE);
+                bytes.extend_from_slice(&(classes.len() as u32).to_be_bytes());
+                bytes.extend_from_slice(&(m as u32).to_be_bytes());
+                bytes.extend(classes.iter().map(|&c| c as u8));
+                for x in metadata.iter().flatten() {
+                    bytes.extend_from_slice(&x.to_le_bytes());
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes a frame's type and payload, without the length prefix.
+    pub fn decode(bytes: &[u8]) -> io::Result<Frame> {
+        let (&kind, payload) = bytes.split_first().ok_or_else(|| invalid("Empty frame"))?;
+
+        match kind {
+            TYPE_CONFIG => Ok(Frame::Config {
+                num_params: u64::from_be_bytes(take(payload, 0)?),
+                max_batch: u64::from_be_bytes(take(payload, 8)?),
+            }),
+            TYPE_OK => Ok(Frame::Ok {
+                version: u16::from_be_bytes(take(payload, 0)?),
+            }),
+            TYPE_ERROR => Ok(Frame::Error(text(payload)?)),
+            TYPE_TEXT => Ok(Frame::Text(text(payload)?)),
+            TYPE_REQUEST => {
+                let k = u32::from_be_bytes(take(payload, 0)?) as usize;
+                let dims = u32::from_be_bytes(take(payload, 4)?) as usize;
+                let points = (0..k)
+                    .map(|i| {
+                        (0..dims)
+                            .map(|j| Ok(f64::from_le_bytes(take(payload, 8 + 8 * (i * dims + j))?)))
+                            .collect::<io::Result<Vec<f64>>>()
+                    })
+                    .collect::<io::Result<_>>()?;
+                Ok(Frame::Request(points))
+            }
+            TYPE_RESPONSE => {
+                let k = u32::from_be_bytes(take(payload, 0)?) as usize;
+                let classes = payload
+                    .get(4..4 + k)
+                    .ok_or_else(|| invalid("Truncated response frame"))?;
+                if classes.iter().any(|&c| c > 1) {
+                    return Err(invalid("Response contained a non-bool class"));
+                }
+                Ok(Frame::Response(classes.iter().map(|&c| c == 1).collect()))
+            }
+            TYPE_RICH_RESPONSE => {
+                let k = u32::from_be_bytes(take(payload, 0)?) as usize;
+                let m = u32::from_be_bytes(take(payload, 4)?) as usize;
+                let classes = payload
+                    .get(8..8 + k)
+                    .ok_or_else(|| invalid("Truncated rich response frame"))?;
+                if classes.iter().any(|&c| c > 1) {
+                    return Err(invalid("Response contained a non-bool class"));
+                }
+                let metadata = (0..k)
+                    .map(|i| {
+                        (0..m)
+                            .map(|j| {
+                                Ok(f64::from_le_bytes(take(payload, 8 + k + 8 * (i * m + j))?))
+                            })
+                            .collect::<io::Result<Vec<f64>>>()
+                    })
+                    .collect::<io::Result<_>>()?;
+                Ok(Frame::RichResponse {
+                    classes: classes.iter().map(|&c| c == 1).collect(),
+                    metadata,
+                })
+            }
+            _ => Err(invalid(&format!("Unknown frame type: {kind}"))),
+        }
+    }
+
+    /// Writes the length-prefixed frame to @writer.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_encoded(writer, Encoding::Binary)
+    }
+
+    /// Reads a length-prefixed frame from @reader.
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Frame> {
+        Frame::read_encoded(reader, Encoding::Binary)
+    }
+
+    /// Writes the length-prefixed frame to @writer, serialized with @encoding.
+    pub fn write_encoded<W: Write>(&self, writer: &mut W, encoding: Encoding) -> io::Result<()> {
+        let bytes = match encoding {
+            Encoding::Binary => self.encode(),
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => self.encode_msgpack(),
+        };
+        writer.write_all(&(bytes.len() as u32).to_be_bytes())?;
+        writer.write_all(&bytes)?;
+        writer.flush()
+    }
+
+    /// Reads a length-prefixed frame from @reader, serialized with @encoding.
+    pub fn read_encoded<R: Read>(reader: &mut R, encoding: Encoding) -> io::Result<Frame> {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME_SIZE {
+            return Err(invalid(&format!("Frame of {len} bytes exceeds max size")));
+        }
+
+        let mut bytes = vec![0u8; len];
+        reader.read_exact(&mut bytes)?;
+        match encoding {
+            Encoding::Binary => Frame::decode(&bytes),
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => Frame::decode_msgpack(&bytes),
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Frame {
+    /// Encodes the frame as a MessagePack array, without the length prefix.
+    pub fn encode_msgpack(&self) -> Vec<u8> {
+        let fields = match self {
+            Frame::Config {
+                num_params,
+                max_batch,
+            } => vec![
+                Value::UInt(TYPE_CONFIG as u64),
+                Value::UInt(*num_params),
+                Value::UInt(*max_batch),
+            ],
+            Frame::Ok { version } => {
+                vec![Value::UInt(TYPE_OK as u64), Value::UInt(*version as u64)]
+            }
+            Frame::Error(msg) => vec![Value::UInt(TYPE_ERROR as u64), Value::Str(msg.clone())],
+            Frame::Text(msg) => vec![Value::UInt(TYPE_TEXT as u64), Value::Str(msg.clone())],
+            Frame::Request(points) => vec![
+                Value::UInt(TYPE_REQUEST as u64),
+                Value::Array(
+                    points
+                        .iter()
+                        .map(|p| Value::Array(p.iter().map(|&x| Value::Float(x)).collect()))
+                        .collect(),
+                ),
+            ],
+            Frame::Response(classes) => vec![
+                Value::UInt(TYPE_RESPONSE as u64),
+                Value::Array(classes.iter().map(|&c| Value::Bool(c)).collect()),
+            ],
+            Frame::RichResponse { classes, metadata } => vec![
+                Value::UInt(TYPE_RICH_RESPONSE as u64),
+                Value::Array(classes.iter().map(|&c| Value::Bool(c)).collect()),
+                Value::Array(
+                    metadata
+                        .iter()
+                        .map(|x| Value::Array(x.iter().map(|&v| Value::Float(v)).collect()))
+                        .collect(),
+                ),
+            ],
+        };
+
+        let mut bytes = vec![];
+        Value::Array(fields).encode(&mut bytes);
+        bytes
+    }
+
+    /// Decodes a frame from a MessagePack array, without the length prefix.
+    pub fn decode_msgpack(bytes: &[u8]) -> io::Result<Frame> {
+        let value = Value::decode(bytes, &mut 0)?;
+        let fields = value
+            .as_array()
+            .ok_or_else(|| invalid("MessagePack frame must be an array"))?;
+        let field = |i: usize| {
+            fields
+                .get(i)
+                .ok_or_else(|| invalid("MessagePack frame is missing fields"))
+        };
+        let uint = |i: usize| {
+            field(i)?
+                .as_u64()
+                .ok_or_else(|| invalid("Expected an unsigned integer"))
+        };
+        let string = |i: usize| {
+            field(i)?
+                .as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| invalid("Expected a string"))
+        };
+        let array = |i: usize| {
+            field(i)?
+                .as_array()
+                .ok_or_else(|| invalid("Expected an array"))
+        };
+        let vectors = |i: usize| -> io::Result<Vec<Vec<f64>>> {
+            array(i)?
+                .iter()
+                .map(|p| {
+                    p.as_array()
+                        .ok_or_else(|| invalid("Expected an array of numbers"))?
+                        .iter()
+                        .map(|x| x.as_f64().ok_or_else(|| invalid("Expected a number")))
+                        .collect()
+                })
+                .collect()
+        };
+        let bools = |i: usize| -> io::Result<Vec<bool>> {
+            array(i)?
+                .iter()
+                .map(|c| c.as_bool().ok_or_else(|| invalid("Expected a bool class")))
+                .collect()
+        };
+
+        match uint(0)? as u8 {
+            TYPE_CONFIG => Ok(Frame::Config {
+                num_params: uint(1)?,
+                max_batch: uint(2)?,
+            }),
+            TYPE_OK => Ok(Frame::Ok {
+                version: uint(1)? as u16,
+            }),
+            TYPE_ERROR => Ok(Frame::Error(string(1)?)),
+            TYPE_TEXT => Ok(Frame::Text(string(1)?)),
+            TYPE_REQUEST => vectors(1).map(Frame::Request),
+            TYPE_RESPONSE => bools(1).map(Frame::Response),
+            TYPE_RICH_RESPONSE => Ok(Frame::RichResponse {
+                classes: bools(1)?,
+                metadata: vectors(2)?,
+            }),
+            kind => Err(invalid(&format!("Unknown frame type: {kind}"))),
+        }
+    }
+}
+
+fn take<const K: usize>(payload: &[u8], start: usize) -> io::Result<[u8; K]> {
+    payload
+        .get(start..start + K)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| invalid("Truncated frame"))
+}
+
+fn text(payload: &[u8]) -> io::Result<String> {
+    String::from_utf8(payload.to_vec()).map_err(|_| invalid("Non-UTF8 text frame"))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod framing {
+    use super::*;
+
+    #[test]
+    fn frames_survive_round_trip() {
+        let frames = [
+            Frame::Config {
+                num_params: 3,
+                max_batch: 16,
+            },
+            Frame::Ok { version: 1 },
+            Frame::Error("bad config".to_string()),
+            Frame::Text("GS".to_string()),
+            Frame::Request(vec![vec![0.1, 0.2], vec![0.3, 0.4]]),
+            Frame::Response(vec![true, false, true]),
+            Frame::RichResponse {
+                classes: vec![true, false],
+                metadata: vec![vec![81.2, 0.0], vec![95.7, 0.4]],
+            },
+        ];
+
+        let mut buffer = vec![];
+        for frame in frames.iter() {
+            frame.write_to(&mut buffer).unwrap();
+        }
+
+        let mut reader = buffer.as_slice();
+        for frame in frames.iter() {
+            assert_eq!(&Frame::read_from(&mut reader).unwrap(), frame);
+        }
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_frames_survive_round_trip() {
+        let frames = [
+            Frame::Config {
+                num_params: 3,
+                max_batch: 16,
+            },
+            Frame::Ok { version: 1 },
+            Frame::Text("GS".to_string()),
+            Frame::Request(vec![vec![0.1, 0.2], vec![0.3, 0.4]]),
+            Frame::Response(vec![true, false, true]),
+            Frame::RichResponse {
+                classes: vec![true, false],
+                metadata: vec![vec![81.2, 0.0], vec![95.7, 0.4]],
+            },
+        ];
+
+        let mut buffer = vec![];
+        for frame in frames.iter() {
+            frame
+                .write_encoded(&mut buffer, Encoding::MessagePack)
+                .unwrap();
+        }
+
+        let mut reader = buffer.as_slice();
+        for frame in frames.iter() {
+            assert_eq!(
+                &Frame::read_encoded(&mut reader, Encoding::MessagePack).unwrap(),
+                frame
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_frames() {
+        let mut bytes = Frame::Request(vec![vec![0.1, 0.2]]).encode();
+        bytes.pop();
+        assert!(Frame::decode(&bytes).is_err());
+    }
+}
@@ -0,0 +1,522 @@
+use std::{
+    error::Error,
+    fmt, io, net,
+    time::{Duration, Instant},
+};
+
+use nalgebra::SVector;
+
+use super::{RemoteClassifier, Transport};
+use crate::prelude::messages::{is_mode_id, Progress, SessionMessage};
+use crate::structs::{error, Classifier, Sample, SamplingError};
+
+/// The state of a `SembasSession`.
+/// * Messaging : The client may send messages, ending with CONT to request a sample.
+/// * Requesting : The client sent CONT and is waiting for a request.
+/// * Incomplete : A request failed as OutOfBounds before reaching the client, which
+///   is still waiting for a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionState {
+    Messaging,
+    Requesting,
+    Incomplete,
+}
+
+/// A violation of the session protocol, by either the client or the application.
+#[derive(Debug)]
+pub enum SessionError {
+    /// The operation is not valid in the session's current state.
+    InvalidState {
+        expected: SessionState,
+        actual: SessionState,
+    },
+    /// The client sent a message other than CONT when a request was due.
+    UnexpectedMessage(SessionMessage),
+    /// The mode id is empty or contains whitespace.
+    InvalidModeId(String),
+    /// A mode was begun while the mode with this id was still active.
+    ModeAlreadyActive(String),
+    /// The mode with this id was ended before `run_mode()` could end it.
+    ModeEnded(String),
+    /// The connection to the client failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::InvalidState { expected, actual } => {
+                write!(
+                    f,
+                    "Session must be in {expected:?} state, but is {actual:?}"
+                )
+            }
+            SessionError::UnexpectedMessage(msg) => {
+                write!(f, "Expected client to request CONTINUE, got '{msg}'")
+            }
+            SessionError::InvalidModeId(id) => {
+                write!(
+                    f,
+                    "Mode id must be non-empty and contain no whitespace! Got '{id}'"
+                )
+            }
+            SessionError::ModeAlreadyActive(id) => {
+                write!(f, "Mode '{id}' must be ended before beginning another")
+            }
+            SessionError::ModeEnded(id) => write!(f, "Mode '{id}' was ended during exploration"),
+            SessionError::Io(e) => write!(f, "Session connection failed: {e}"),
+        }
+    }
+}
+
+impl Error for SessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SessionError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SessionError {
+    fn from(value: io::Error) -> Self {
+        SessionError::Io(value)
+    }
+}
+
+impl From<SessionError> for SamplingError {
+    fn from(value: SessionError) -> Self {
+        match value {
+            SessionError::Io(e) => SamplingError::from(e),
+            e => SamplingError::InvalidClassifierResponse(e.to_string()),
+        }
+    }
+}
+
+/// The result of exploring a single performance mode. See
+/// `SembasSession::run_mode()`.
+#[derive(Debug, Clone)]
+pub struct ModeResult<T> {
+    /// The id of the performance mode.
+    pub id: String,
+    /// The number of samples classified while the mode was active.
+    pub n_samples: usize,
+    /// The value returned by the exploration of the mode.
+    pub result: T,
+}
+
+/// Provides a bi-direction communication solution that enables the client to send signals
+/// to SEMBAS, and for SEMBAS to inform the client (FUT) what phase it is in (e.g. surface
+/// search).
+pub struct SembasSession<const N: usize, S: Transport = net::TcpStream> {
+    classifier: RemoteClassifier<N, S>,
+    phase: SessionMessage,
+    state: SessionState,
+    b_count: usize,
+    n_samples: usize,
+    eta: Option<Duration>,
+    progress_interval: Option<Duration>,
+    last_progress: Instant,
+    mode: Option<(String, usize)>,
+}
+
+impl<const N: usize, S: Transport> SembasSession<N, S> {
+    /// Create a new session from an existing RemoteClassifier.
+    pub fn new(
+        classifier: RemoteClassifier<N, S>,
+        initial_phase: impl Into<SessionMessage>,
+    ) -> io::Result<Self> {
+        let mut s = Self {
+            classifier,
+            state: SessionState::Messaging,
+            phase: initial_phase.into(),
+            b_count: 0,
+            n_samples: 0,
+            eta: None,
+            progress_interval: None,
+            last_progress: Instant::now(),
+            mode: None,
+        };
+
+        s.send_phase()?;
+
+        Ok(s)
+    }
+
+    /// Update the phase ID, which will be sent to the client prior to next communication
+    /// cycle.
+    pub fn update_phase(&mut self, phase: impl Into<SessionMessage>) {
+        self.phase = phase.into();
+    }
+
+    /// The phase that is sent to the client prior to each communication cycle.
+    pub fn phase(&self) -> &SessionMessage {
+        &self.phase
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Enables periodic progress updates: once @interval has elapsed since the last
+    /// update, a SessionMessage::Progress is sent immediately before the next phase
+    /// message. The client must be prepared to receive progress messages when
+    /// enabled. None disables progress updates.
+    pub fn set_progress_interval(&mut self, interval: Option<Duration>) {
+        self.progress_interval = interval;
+    }
+
+    /// Updates the exploration progress reported to the client. The number of
+    /// samples is tracked by the session.
+    /// ## Arguments
+    /// * b_count : The number of boundary points found so far.
+    /// * eta : The estimated time remaining, if known.
+    pub fn update_progress(&mut self, b_count: usize, eta: Option<Duration>) {
+        self.b_count = b_count;
+        self.eta = eta;
+    }
+
+    /// The current progress of the session.
+    pub fn progress(&self) -> Progress {
+        Progress {
+            phase: self.phase.to_wire(),
+            b_count: self.b_count,
+            n_samples: self.n_samples,
+            eta: self.eta,
+        }
+    }
+
+    /// Immediately sends the current progress to the client.
+    pub fn send_progress(&mut self) -> io::Result<()> {
+        self.last_progress = Instant::now();
+        self.classifier.send_msg(&self.progress().to_string())
+    }
+
+    /// The id of the active performance mode, if any.
+    pub fn mode(&self) -> Option<&str> {
+        self.mode.as_ref().map(|(id, _)| id.as_str())
+    }
+
+    /// Announces MODE_BEGIN to the client, which should switch its pass/fail
+    /// criterion to the performance mode @id, allowing several modes to be explored
+    /// sequentially over one connection.
+    /// ## Arguments
+    /// * id : The performance mode's id, which must be non-empty and contain no
+    ///   whitespace.
+    /// ## Error (Err)
+    /// * InvalidModeId : If @id is empty or contains whitespace.
+    /// * ModeAlreadyActive : If another mode has not been ended.
+    /// * InvalidState : If a request is Incomplete, i.e. the client is waiting for
+    ///   a request rather than messages.
+    /// * Io : If the connection to the client failed.
+    pub fn begin_mode(&mut self, id: impl Into<String>) -> Result<(), SessionError> {
+        let id = id.into();
+        if !is_mode_id(&id) {
+            return Err(SessionError::InvalidModeId(id));
+        }
+        if let Some((active, _)) = &self.mode {
+            return Err(SessionError::ModeAlreadyActive(active.clone()));
+        }
+
+        self.send_message(SessionMessage::ModeBegin(id.clone()))?;
+        self.mode = Some((id, self.n_samples));
+
+        Ok(())
+    }
+
+    /// Announces MODE_END to the client for the active performance mode.
+    /// ## Return (Ok)
+    /// * summary : The ended mode's id and number of samples, with result (), or
+    ///   None if no mode was active.
+    /// ## Error (Err)
+    /// * InvalidState : If a request is Incomplete.
+    /// * Io : If the connection to the client failed.
+    pub fn end_mode(&mut self) -> Result<Option<ModeResult<()>>, SessionError> {
+        let Some((id, start)) = self.mode.take() else {
+            return Ok(None);
+        };
+
+        self.send_message(SessionMessage::ModeEnd(id.clone()))?;
+
+        Ok(Some(ModeResult {
+            id,
+            n_samples: self.n_samples - start,
+            result: (),
+        }))
+    }
+
+    /// Explores the performance mode @id, tagging the value returned by @explore
+    /// with the mode. MODE_BEGIN is sent before @explore is called, and MODE_END
+    /// after it returns.
+    /// ## Error (Err)
+    /// * ModeEnded : If @explore ended the mode itself. The value it returned is
+    ///   discarded.
+    /// * See `begin_mode()` and `end_mode()`.
+    /// ## Example
+    /// ```ignore
+    /// let lane_keeping = session.run_mode("lane_keeping", |session| explore(session))?;
+    /// let overtaking = session.run_mode("overtaking", |session| explore(session))?;
+    /// ```
+    pub fn run_mode<T>(
+        &mut self,
+        id: impl Into<String>,
+        explore: impl FnOnce(&mut Self) -> T,
+    ) -> Result<ModeResult<T>, SessionError> {
+        let id = id.into();
+        self.begin_mode(id.clone())?;
+        let result = explore(self);
+        if self.mode() != Some(id.as_str()) {
+            return Err(SessionError::ModeEnded(id));
+        }
+
+        let summary = self.end_mode()?.ok_or(SessionError::ModeEnded(id))?;
+        Ok(ModeResult {
+            id: summary.id,
+            n_samples: summary.n_samples,
+            result,
+        })
+    }
+
+    fn send_message(&mut self, msg: SessionMessage) -> Result<(), SessionError> {
+        if self.state == SessionState::Incomplete {
+            return Err(SessionError::InvalidState {
+                expected: SessionState::Messaging,
+                actual: self.state,
+            });
+        }
+
+        Ok(self.classifier.send_msg(&msg.to_wire())?)
+    }
+
+    fn send_phase(&mut self) -> io::Result<()> {
+        if self
+            .progress_interval
+            .is_some_and(|interval| self.last_progress.elapsed() >= interval)
+        {
+            self.send_progress()?;
+        }
+        self.classifier.send_msg(&self.phase.to_wire())
+    }
+
+    /// Listens for a message during Messaging state. SessionMessage::Continue
+    /// indicates that the client is waiting for a new request.
+    /// ## Error (Err)
+    /// * InvalidState : If the client already requested CONTINUE, and is waiting
+    ///   for a request rather than sending messages.
+    /// * Io : If the connection to the client failed.
+    pub fn expect_msg(&mut self) -> Result<SessionMessage, SessionError> {
+        match self.state {
+            SessionState::Messaging => Ok(self.receive_msg()?),
+            SessionState::Incomplete => Ok(SessionMessage::Continue),
+            SessionState::Requesting => Err(SessionError::InvalidState {
+                expected: SessionState::Messaging,
+                actual: self.state,
+            }),
+        }
+    }
+
+    fn receive_msg(&mut self) -> io::Result<SessionMessage> {
+        self.send_phase()?;
+
+        let msg = SessionMessage::from(self.classifier.receive_msg()?);
+
+        if msg == SessionMessage::Continue {
+            self.state = SessionState::Requesting;
+        }
+
+        Ok(msg)
+    }
+
+    /// Performs a request, transitioning between states:
+    /// * Messaging : Sends the phase and expects the client to request CONTINUE,
+    ///   then proceeds as Requesting. Any other message is returned as
+    ///   UnexpectedMessage, leaving the session in Messaging state.
+    /// * Requesting : Sends the phase followed by the request.
+    /// * Incomplete : Sends the request, the phase having already been sent.
+    ///
+    /// The session is Incomplete after an OutOfBounds request, and Messaging
+    /// otherwise.
+    fn request(&mut self, p: SVector<f64, N>) -> Result<error::Result<Sample<N>>, SessionError> {
+        if self.state == SessionState::Messaging {
+            self.send_phase()?;
+            let msg = SessionMessage::from(self.classifier.receive_msg()?);
+            if msg != SessionMessage::Continue {
+                return Err(SessionError::UnexpectedMessage(msg));
+            }
+            self.state = SessionState::Requesting;
+        }
+
+        if self.state == SessionState::Requesting {
+            self.send_phase()?;
+        }
+
+        let result = self.classifier.classify(p);
+        self.state = match result {
+            Err(SamplingError::OutOfBounds) => SessionState::Incomplete,
+            _ => SessionState::Messaging,
+        };
+
+        Ok(result)
+    }
+}
+
+impl<const N: usize> SembasSession<N> {
+    /// Create a new session for a given IP address. Creates a RemoteClassifier with the IP.
+    pub fn bind(addr: String, initial_phase: impl Into<SessionMessage>) -> io::Result<Self> {
+        SembasSession::new(RemoteClassifier::<N>::bind(addr)?, initial_phase)
+    }
+}
+
+impl<const N: usize, S: Transport> Classifier<N> for SembasSession<N, S> {
+    fn classify(&mut self, p: SVector<f64, N>) -> error::Result<Sample<N>> {
+        let result = self.request(p)?;
+        if result.is_ok() {
+            self.n_samples += 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod session_protocol {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        thread,
+    };
+
+    use nalgebra::vector;
+
+    use super::*;
+    use crate::prelude::messages::Phase;
+
+    #[test]
+    fn reports_protocol_violations_without_panicking() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let mut conn = BufReader::new(net::TcpStream::connect(addr).unwrap());
+            let mut line = String::new();
+            let mut expect_line = |conn: &mut BufReader<net::TcpStream>, expected: &str| {
+                line.clear();
+                conn.read_line(&mut line).unwrap();
+                assert_eq!(line.trim(), expected);
+            };
+
+            conn.get_mut().write_all(&2usize.to_be_bytes()).unwrap();
+            expect_line(&mut conn, "OK");
+            expect_line(&mut conn, "GS");
+
+            // Answers a request with a custom message rather than CONT.
+            expect_line(&mut conn, "GS");
+            conn.get_mut().write_all(b"RESET\n").unwrap();
+
+            // expect_msg()
+            expect_line(&mut conn, "GS");
+            conn.get_mut().write_all(b"CONT\n").unwrap();
+
+            // classify()
+            expect_line(&mut conn, "GS");
+            let mut point = [0u8; 16];
+            conn.read_exact(&mut point).unwrap();
+            conn.get_mut().write_all(&[1]).unwrap();
+
+            expect_line(&mut conn, "END");
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        let mut session = SembasSession::new(classifier, Phase::GlobalSearch).unwrap();
+
+        assert!(matches!(
+            session.classify(vector![0.5, 0.5]),
+            Err(SamplingError::InvalidClassifierResponse(_))
+        ));
+        assert_eq!(session.state(), SessionState::Messaging);
+
+        assert_eq!(session.expect_msg().unwrap(), SessionMessage::Continue);
+        assert!(matches!(
+            session.expect_msg(),
+            Err(SessionError::InvalidState {
+                expected: SessionState::Messaging,
+                actual: SessionState::Requesting
+            })
+        ));
+
+        assert!(session.classify(vector![0.5, 0.5]).unwrap().class());
+        assert_eq!(session.progress().n_samples, 1);
+        drop(session);
+
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn announces_modes_in_sequence() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let mut conn = BufReader::new(net::TcpStream::connect(addr).unwrap());
+            let mut line = String::new();
+            let mut expect_line = |conn: &mut BufReader<net::TcpStream>, expected: &str| {
+                line.clear();
+                conn.read_line(&mut line).unwrap();
+                assert_eq!(line.trim(), expected);
+            };
+
+            conn.get_mut().write_all(&2usize.to_be_bytes()).unwrap();
+            expect_line(&mut conn, "OK");
+            expect_line(&mut conn, "GS");
+
+            for (mode, cls) in [("a", 1u8), ("b", 0u8)] {
+                expect_line(&mut conn, &format!("MODE_BEGIN {mode}"));
+                expect_line(&mut conn, "GS");
+                conn.get_mut().write_all(b"CONT\n").unwrap();
+                expect_line(&mut conn, "GS");
+                let mut point = [0u8; 16];
+                conn.read_exact(&mut point).unwrap();
+                conn.get_mut().write_all(&[cls]).unwrap();
+                expect_line(&mut conn, &format!("MODE_END {mode}"));
+            }
+            for mode in ["c", "e"] {
+                expect_line(&mut conn, &format!("MODE_BEGIN {mode}"));
+                expect_line(&mut conn, &format!("MODE_END {mode}"));
+            }
+
+            expect_line(&mut conn, "END");
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let classifier = RemoteClassifier::<2>::from_stream(stream).unwrap();
+        let mut session = SembasSession::new(classifier, Phase::GlobalSearch).unwrap();
+
+        let classify =
+            |session: &mut SembasSession<2>| session.classify(vector![0.5, 0.5]).unwrap().class();
+        let a = session.run_mode("a", classify).unwrap();
+        assert_eq!((a.id.as_str(), a.n_samples, a.result), ("a", 1, true));
+
+        session.begin_mode("b").unwrap();
+        assert_eq!(session.mode(), Some("b"));
+        assert!(!classify(&mut session));
+        let b = session.end_mode().unwrap().unwrap();
+        assert_eq!((b.id.as_str(), b.n_samples), ("b", 1));
+        assert!(session.end_mode().unwrap().is_none());
+
+        // Misuse is reported rather than panicking, without messaging the client
+        assert!(matches!(
+            session.begin_mode("not an id"),
+            Err(SessionError::InvalidModeId(_))
+        ));
+        session.begin_mode("c").unwrap();
+        assert!(matches!(
+            session.begin_mode("d"),
+            Err(SessionError::ModeAlreadyActive(id)) if id == "c"
+        ));
+        session.end_mode().unwrap();
+        let ended = session.run_mode("e", |session| session.end_mode().unwrap());
+        assert!(matches!(ended, Err(SessionError::ModeEnded(id)) if id == "e"));
+        drop(session);
+
+        client.join().unwrap();
+    }
+}
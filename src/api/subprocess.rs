@@ -0,0 +1,224 @@
+use std::{
+    ffi::{OsStr, OsString},
+    io::{self, Read, Write},
+    ops::{Deref, DerefMut},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use nalgebra::SVector;
+
+use super::{RemoteClassifier, Transport};
+use crate::structs::{error, Classifier, Sample};
+
+/// How long the FUT is given to exit after END before it is killed.
+const EXIT_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// A connection to an FUT child process over its stdin and stdout. The child is
+/// killed when the stream is dropped, if it has not exited on its own.
+pub struct ChildStream {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: ChildStdout,
+}
+
+/// Runs the FUT as a child process, communicating over its stdin/stdout rather
+/// than a socket. The FUT follows the same connection sequence as with
+/// `RemoteClassifier::bind()`, and should write its diagnostics to stderr, which is
+/// inherited from SEMBAS.
+///
+/// SEMBAS owns the FUT's lifecycle: if the FUT crashes, a new process is spawned
+/// and the lost request is retried, and the FUT is killed when the classifier is
+/// dropped.
+pub struct SubprocessClassifier<const N: usize> {
+    classifier: RemoteClassifier<N, ChildStream>,
+}
+
+impl ChildStream {
+    /// Spawns @command with piped stdin and stdout.
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().expect("Child stdout was not piped?");
+
+        Ok(ChildStream {
+            child,
+            stdin,
+            stdout,
+        })
+    }
+
+    /// The OS-assigned process id of the FUT.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+}
+
+impl Read for ChildStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stdout.read(buf)
+    }
+}
+
+impl Write for ChildStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.stdin.as_mut() {
+            Some(stdin) => stdin.write(buf),
+            None => Err(io::ErrorKind::BrokenPipe.into()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.stdin.as_mut() {
+            Some(stdin) => stdin.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Transport for ChildStream {}
+
+impl Drop for ChildStream {
+    fn drop(&mut self) {
+        // Closing stdin signals EOF, allowing the FUT to shut down gracefully.
+        self.stdin = None;
+
+        let start = Instant::now();
+        while start.elapsed() < EXIT_GRACE_PERIOD {
+            match self.child.try_wait() {
+                Ok(Some(_)) | Err(_) => return,
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+            }
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl<const N: usize> SubprocessClassifier<N> {
+    /// Spawns the FUT and completes the connection sequence.
+    /// ## Arguments
+    /// * program : The FUT executable, e.g. "python3".
+    /// * args : The arguments to @program, e.g. the FUT script.
+    pub fn spawn<I, A>(program: impl AsRef<OsStr>, args: I) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<OsStr>,
+    {
+        let program: OsString = program.as_ref().to_owned();
+        let args: Vec<OsString> = args.into_iter().map(|a| a.as_ref().to_owned()).collect();
+        let launch = move || ChildStream::spawn(Command::new(&program).args(&args));
+
+        let mut classifier =
+            RemoteClassifier::from_stream(launch()?)?.with_acceptor(Box::new(launch));
+        classifier.set_auto_reconnect(true);
+
+        Ok(SubprocessClassifier { classifier })
+    }
+
+    /// Kills the current FUT process and spawns a new one.
+    pub fn restart(&mut self) -> io::Result<()> {
+        self.classifier.reconnect()
+    }
+
+    /// Returns the underlying RemoteClassifier.
+    pub fn into_inner(self) -> RemoteClassifier<N, ChildStream> {
+        self.classifier
+    }
+}
+
+impl<const N: usize> Deref for SubprocessClassifier<N> {
+    type Target = RemoteClassifier<N, ChildStream>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.classifier
+    }
+}
+
+impl<const N: usize> DerefMut for SubprocessClassifier<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.classifier
+    }
+}
+
+impl<const N: usize> Classifier<N> for SubprocessClassifier<N> {
+    fn classify(&mut self, p: SVector<f64, N>) -> error::Result<Sample<N>> {
+        self.classifier.classify(p)
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> error::Result<Vec<Sample<N>>> {
+        self.classifier.classify_batch(points)
+    }
+}
+
+#[cfg(test)]
+mod subprocess_fut {
+    use std::process::Command;
+
+    use nalgebra::vector;
+
+    use super::*;
+    use crate::structs::SamplingError;
+
+    /// A framed-protocol FUT that classifies x < 0.5 as in-mode, and crashes
+    /// when asked to classify x > 0.9.
+    const FUT: &str = r#"
+import struct, sys
+inp, out = sys.stdin.buffer, sys.stdout.buffer
+def send(kind, payload):
+    out.write(struct.pack(">IB", len(payload) + 1, kind) + payload)
+    out.flush()
+def recv():
+    head = inp.read(4)
+    if len(head) < 4:
+        sys.exit(0)
+    body = inp.read(struct.unpack(">I", head)[0])
+    return body[0], body[1:]
+out.write(b"SMBS" + struct.pack(">HH", 1, 0))
+send(1, struct.pack(">QQ", 2, 1))
+recv()
+while True:
+    kind, payload = recv()
+    if kind == 4:
+        sys.exit(0)
+    k, n = struct.unpack(">II", payload[:8])
+    x = struct.unpack("<" + "d" * (k * n), payload[8:])
+    if x[0] > 0.9:
+        sys.exit(1)
+    send(6, struct.pack(">I", k) + bytes(int(x[i * n] < 0.5) for i in range(k)))
+"#;
+
+    fn python_available() -> bool {
+        Command::new("python3")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    #[test]
+    fn restarts_crashed_fut() {
+        if !python_available() {
+            println!("Skipping, python3 is unavailable.");
+            return;
+        }
+
+        let mut classifier = SubprocessClassifier::<2>::spawn("python3", ["-c", FUT]).unwrap();
+        assert_eq!(classifier.protocol_version(), Some(1));
+
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+        assert!(!classifier.classify(vector![0.75, 0.5]).unwrap().class());
+
+        // The retried request crashes the restarted FUT as well.
+        assert!(matches!(
+            classifier.classify(vector![0.95, 0.5]),
+            Err(SamplingError::Disconnected)
+        ));
+        assert!(classifier.classify(vector![0.25, 0.5]).unwrap().class());
+    }
+}
@@ -0,0 +1,432 @@
+use std::io::{self, Read, Write};
+use std::net;
+
+use std::time::Duration;
+
+use super::protocol::MAX_FRAME_SIZE;
+use super::{RemoteClassifier, SembasSession, Transport};
+use crate::prelude::messages::SessionMessage;
+
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const MAX_HEADER_SIZE: usize = 8192;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// A server-side WebSocket connection that presents the payload of incoming
+/// frames as a byte stream, allowing browser-based FUTs to use the same protocol
+/// as the raw TCP socket.
+///
+/// Each flush sends the buffered writes as a single binary frame. Text and binary
+/// frames from the client are both read as raw bytes.
+pub struct WebSocketStream {
+    stream: net::TcpStream,
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    write_buf: Vec<u8>,
+    closed: bool,
+}
+
+impl WebSocketStream {
+    /// Performs the server side of the WebSocket opening handshake on an accepted
+    /// connection.
+    pub fn accept(mut stream: net::TcpStream) -> io::Result<Self> {
+        let request = read_http_request(&mut stream)?;
+
+        let key = request
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("Sec-WebSocket-Key"))
+            .map(|(_, value)| value.trim().to_string())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "WebSocket handshake is missing Sec-WebSocket-Key",
+                )
+            })?;
+
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(&key)
+        );
+        stream.write_all(response.as_bytes())?;
+        stream.flush()?;
+
+        Ok(WebSocketStream {
+            stream,
+            read_buf: vec![],
+            read_pos: 0,
+            write_buf: vec![],
+            closed: false,
+        })
+    }
+
+    fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> io::Result<()> {
+        let mut header = vec![0x80 | opcode];
+        let len = payload.len();
+        if len < 126 {
+            header.push(len as u8);
+        } else if len <= u16::MAX as usize {
+            header.push(126);
+            header.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            header.push(127);
+            header.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+
+        self.stream.write_all(&header)?;
+        self.stream.write_all(payload)?;
+        self.stream.flush()
+    }
+
+    /// Reads frames until a data payload is available. Returns false if the client
+    /// closed the connection. Frames larger than `MAX_FRAME_SIZE` are rejected as
+    /// InvalidData.
+    fn read_frame(&mut self) -> io::Result<bool> {
+        loop {
+            let mut head = [0u8; 2];
+            self.stream.read_exact(&mut head)?;
+            let opcode = head[0] & 0x0F;
+            let masked = head[1] & 0x80 != 0;
+            let len = match head[1] & 0x7F {
+                126 => {
+                    let mut ext = [0u8; 2];
+                    self.stream.read_exact(&mut ext)?;
+                    u16::from_be_bytes(ext) as u64
+                }
+                127 => {
+                    let mut ext = [0u8; 8];
+                    self.stream.read_exact(&mut ext)?;
+                    u64::from_be_bytes(ext)
+                }
+                len => len as u64,
+            };
+            if len > MAX_FRAME_SIZE as u64 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("WebSocket frame of {len} bytes exceeds the max of {MAX_FRAME_SIZE}"),
+                ));
+            }
+            let len = len as usize;
+
+            let mut mask = [0u8; 4];
+            if masked {
+                self.stream.read_exact(&mut mask)?;
+            }
+
+            let mut payload = vec![0u8; len];
+            self.stream.read_exact(&mut payload)?;
+            if masked {
+                for (i, byte) in payload.iter_mut().enumerate() {
+                    *byte ^= mask[i % 4];
+                }
+            }
+
+            match opcode {
+                OP_CONTINUATION | OP_TEXT | OP_BINARY => {
+                    if payload.is_empty() {
+                        continue;
+                    }
+                    self.read_buf = payload;
+                    self.read_pos = 0;
+                    return Ok(true);
+                }
+                OP_CLOSE => {
+                    if !self.closed {
+                        self.closed = true;
+                        self.write_frame(OP_CLOSE, &payload)?;
+                    }
+                    return Ok(false);
+                }
+                OP_PING => self.write_frame(OP_PONG, &payload)?,
+                OP_PONG => (),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Unsupported WebSocket opcode: {opcode}"),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl Read for WebSocketStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.read_pos >= self.read_buf.len() && (self.closed || !self.read_frame()?) {
+            return Ok(0);
+        }
+
+        let available = &self.read_buf[self.read_pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.read_pos += n;
+
+        Ok(n)
+    }
+}
+
+impl Write for WebSocketStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.write_buf.is_empty() {
+            return Ok(());
+        }
+        if self.closed {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "WebSocket connection was closed",
+            ));
+        }
+
+        let payload = std::mem::take(&mut self.write_buf);
+        self.write_frame(OP_BINARY, &payload)
+    }
+}
+
+impl Transport for WebSocketStream {
+    fn set_timeouts(&mut self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()> {
+        self.stream.set_timeouts(read, write)
+    }
+}
+
+impl<const N: usize> RemoteClassifier<N, WebSocketStream> {
+    /// Opens a socket to be connected to by a remote function under test (FUT)
+    /// over WebSocket. Follows the same connection sequence as `bind()`, after
+    /// completing the WebSocket handshake.
+    pub fn bind_websocket(addr: String) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        println!("Listening for WebSocket client connection...");
+        let (stream, _) = listener.accept()?;
+        println!("Connection established.");
+
+        RemoteClassifier::from_stream(WebSocketStream::accept(stream)?).map(|c| {
+            c.with_acceptor(Box::new(move || {
+                WebSocketStream::accept(listener.accept()?.0)
+            }))
+        })
+    }
+}
+
+impl<const N: usize> SembasSession<N, WebSocketStream> {
+    /// Create a new session for a given IP address, served over WebSocket.
+    pub fn bind_websocket(
+        addr: String,
+        initial_phase: impl Into<SessionMessage>,
+    ) -> io::Result<Self> {
+        SembasSession::new(RemoteClassifier::bind_websocket(addr)?, initial_phase)
+    }
+}
+
+/// Reads the HTTP upgrade request byte by byte, to avoid consuming any frames
+/// that follow it.
+fn read_http_request(stream: &mut net::TcpStream) -> io::Result<String> {
+    let mut request = vec![];
+    let mut byte = [0u8; 1];
+    while !request.ends_with(b"\r\n\r\n") {
+        if request.len() >= MAX_HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "WebSocket handshake exceeded maximum header size",
+            ));
+        }
+        stream.read_exact(&mut byte)?;
+        request.push(byte[0]);
+    }
+
+    String::from_utf8(request)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Non-UTF8 handshake"))
+}
+
+fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{WS_GUID}").as_bytes()))
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in msg.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (hi, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *hi = hi.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (i, v) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&v.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod websocket_transport {
+    use std::{io::BufRead, thread};
+
+    use nalgebra::vector;
+
+    use crate::structs::Classifier;
+
+    use super::*;
+
+    #[test]
+    fn computes_handshake_accept_key() {
+        // Example from RFC 6455, section 1.3
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    /// Sends a masked client frame.
+    fn send_frame(stream: &mut net::TcpStream, opcode: u8, payload: &[u8]) {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        stream.write_all(&frame).unwrap();
+    }
+
+    /// Reads an unmasked server frame.
+    fn recv_frame(stream: &mut net::TcpStream) -> Vec<u8> {
+        let mut head = [0u8; 2];
+        stream.read_exact(&mut head).unwrap();
+        let mut payload = vec![0u8; (head[1] & 0x7F) as usize];
+        stream.read_exact(&mut payload).unwrap();
+        payload
+    }
+
+    #[test]
+    fn rejects_oversized_frame() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let mut ws = WebSocketStream {
+            stream,
+            read_buf: vec![],
+            read_pos: 0,
+            write_buf: vec![],
+            closed: false,
+        };
+
+        // A binary frame claiming a 2^63 byte payload
+        let mut header = vec![0x80 | OP_BINARY, 127];
+        header.extend_from_slice(&(1u64 << 63).to_be_bytes());
+        client.write_all(&header).unwrap();
+
+        let mut buf = [0u8; 1];
+        let err = ws.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn classifies_over_websocket() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = net::TcpStream::connect(addr).unwrap();
+
+            stream
+                .write_all(
+                    b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                    Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                    Sec-WebSocket-Version: 13\r\n\r\n",
+                )
+                .unwrap();
+            let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+            }
+
+            send_frame(&mut stream, OP_BINARY, &2usize.to_be_bytes());
+            assert_eq!(recv_frame(&mut stream), b"OK\n");
+
+            let request = recv_frame(&mut stream);
+            let p: &[f64] = bytemuck::cast_slice(&request);
+            let cls = (p[0] - 0.5).powi(2) + (p[1] - 0.5).powi(2) < 0.1;
+            send_frame(&mut stream, OP_BINARY, &[cls as u8]);
+
+            assert_eq!(recv_frame(&mut stream), b"END\n");
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let stream =
+            WebSocketStream::accept(stream).expect("Failed to establish WebSocket connection");
+        let mut classifier = RemoteClassifier::<2, WebSocketStream>::from_stream(stream).unwrap();
+        let sample = classifier.classify(vector![0.5, 0.5]).unwrap();
+        assert!(sample.class());
+        drop(classifier);
+
+        client.join().unwrap();
+    }
+}
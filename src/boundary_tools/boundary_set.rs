@@ -0,0 +1,235 @@
+use nalgebra::SVector;
+
+use crate::prelude::{Boundary, BoundaryRTree, Halfspace, KnnNode, NodeID, Sample};
+
+use super::{
+    estimation::{approx_prediction, approx_weighted_prediction, NeighborWeighting, TieBreak},
+    falls_on_boundary, find_holes, get_rtree_from_boundary, ray_cast, signed_distance,
+};
+
+/// A boundary together with its RTree. Every change to the boundary goes through
+/// the set, so the RTree's indices always refer to the boundary, unlike a
+/// separately maintained Vec<Halfspace<N>> and BoundaryRTree<N>.
+///
+/// The set can be passed wherever a (boundary, btree) pair is expected via
+/// `as_pair()`, e.g. the groups of `estimation::approx_mc_volume()`.
+#[derive(Debug, Clone, Default)]
+pub struct BoundarySet<const N: usize> {
+    boundary: Vec<Halfspace<N>>,
+    btree: BoundaryRTree<N>,
+}
+
+impl<const N: usize> BoundarySet<N> {
+    /// Creates an empty BoundarySet.
+    pub fn new() -> Self {
+        BoundarySet {
+            boundary: vec![],
+            btree: BoundaryRTree::new(),
+        }
+    }
+
+    /// The halfspaces of the boundary, in the order they were added.
+    pub fn boundary(&self) -> &Boundary<N> {
+        &self.boundary
+    }
+
+    /// The RTree of the boundary.
+    pub fn btree(&self) -> &BoundaryRTree<N> {
+        &self.btree
+    }
+
+    /// The boundary and its RTree, in the form taken by the group estimators.
+    pub fn as_pair(&self) -> (&Boundary<N>, &BoundaryRTree<N>) {
+        (&self.boundary, &self.btree)
+    }
+
+    /// Takes the boundary and its RTree out of the set.
+    pub fn into_parts(self) -> (Vec<Halfspace<N>>, BoundaryRTree<N>) {
+        (self.boundary, self.btree)
+    }
+
+    pub fn len(&self) -> usize {
+        self.boundary.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boundary.is_empty()
+    }
+
+    /// Adds a halfspace to the boundary.
+    /// ## Returns
+    /// * id : The index of @hs in the boundary.
+    pub fn insert(&mut self, hs: Halfspace<N>) -> NodeID {
+        let id = self.boundary.len();
+        self.btree.insert(KnnNode::new(hs.b.into(), id));
+        self.boundary.push(hs);
+        id
+    }
+
+    /// Adds the halfspaces of @other that do not duplicate a halfspace already in
+    /// the set. See `boundary_tools::merge()`.
+    /// ## Arguments
+    /// * other : The boundary to merge into the set.
+    /// * tol : The distance within which a halfspace duplicates another.
+    /// ## Returns
+    /// * n_added : The number of halfspaces added.
+    pub fn merge(&mut self, other: &Boundary<N>, tol: f64) -> usize {
+        let n_before = self.len();
+        for hs in other {
+            let key: [f64; N] = hs.b.into();
+            let is_duplicate = self
+                .btree
+                .locate_within_distance(key, tol * tol)
+                .any(|node| self.boundary[node.data].n.dot(&hs.n) >= 0.0);
+            if !is_duplicate {
+                self.insert(*hs);
+            }
+        }
+
+        self.len() - n_before
+    }
+
+    /// Predicts the class of @p. See `estimation::approx_prediction()`.
+    pub fn predict(&self, p: SVector<f64, N>, k: u32) -> Sample<N> {
+        approx_prediction(p, &self.boundary, &self.btree, k)
+    }
+
+    /// Predicts the class of @p by a weighted vote. See
+    /// `estimation::approx_weighted_prediction()`.
+    pub fn predict_weighted(
+        &self,
+        p: SVector<f64, N>,
+        k: u32,
+        weighting: NeighborWeighting,
+        tie_break: TieBreak,
+    ) -> Sample<N> {
+        approx_weighted_prediction(p, &self.boundary, &self.btree, k, weighting, tie_break)
+    }
+
+    /// The signed distance from @p to the boundary. See
+    /// `boundary_tools::signed_distance()`.
+    pub fn signed_distance(&self, p: &SVector<f64, N>) -> Option<f64> {
+        signed_distance(p, &self.boundary, &self.btree)
+    }
+
+    /// The distance along a ray to its first crossing of the boundary. See
+    /// `boundary_tools::ray_cast()`.
+    pub fn ray_cast(&self, origin: &SVector<f64, N>, direction: &SVector<f64, N>) -> Option<f64> {
+        ray_cast(origin, direction, &self.boundary, &self.btree)
+    }
+
+    /// Whether @hs is likely to be on the boundary. See
+    /// `boundary_tools::falls_on_boundary()`.
+    /// ## Panic
+    /// When the set is empty.
+    pub fn falls_on_boundary(&self, d: f64, hs: &Halfspace<N>) -> bool {
+        falls_on_boundary(d, hs, &self.boundary, &self.btree)
+    }
+
+    /// The halfspaces bordering gaps in the boundary. See
+    /// `boundary_tools::find_holes()`.
+    pub fn find_holes(&self, d: f64) -> Vec<Halfspace<N>> {
+        find_holes(&self.boundary, &self.btree, d)
+    }
+}
+
+impl<const N: usize> From<Vec<Halfspace<N>>> for BoundarySet<N> {
+    fn from(boundary: Vec<Halfspace<N>>) -> Self {
+        let btree = get_rtree_from_boundary(&boundary);
+        BoundarySet { boundary, btree }
+    }
+}
+
+impl<const N: usize> Extend<Halfspace<N>> for BoundarySet<N> {
+    fn extend<T: IntoIterator<Item = Halfspace<N>>>(&mut self, iter: T) {
+        for hs in iter {
+            self.insert(hs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod boundary_set_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::estimation::{approx_mc_volume, PredictionMode},
+        prelude::{Halfspace, WithinMode},
+    };
+
+    use super::BoundarySet;
+
+    fn hs(b: SVector<f64, 2>, n: SVector<f64, 2>) -> Halfspace<2> {
+        Halfspace {
+            b: WithinMode(b),
+            n,
+        }
+    }
+
+    fn square() -> Vec<Halfspace<2>> {
+        (0..20)
+            .flat_map(|i| {
+                let t = 0.25 + 0.5 * (i as f64 + 0.5) / 20.0;
+                [
+                    hs(vector![t, 0.25], vector![0.0, -1.0]),
+                    hs(vector![t, 0.75], vector![0.0, 1.0]),
+                    hs(vector![0.25, t], vector![-1.0, 0.0]),
+                    hs(vector![0.75, t], vector![1.0, 0.0]),
+                ]
+            })
+            .collect()
+    }
+
+    fn assert_in_sync(set: &BoundarySet<2>) {
+        assert_eq!(set.btree().size(), set.len());
+        for (i, hs) in set.boundary().iter().enumerate() {
+            let node = set.btree().nearest_neighbor(&hs.b.into()).unwrap();
+            assert_eq!(set.boundary()[node.data].b, hs.b, "Desync at {i}");
+        }
+    }
+
+    #[test]
+    fn stays_in_sync_through_insert_extend_and_merge() {
+        let boundary = square();
+        let mut set = BoundarySet::from(boundary[..20].to_vec());
+        assert_in_sync(&set);
+
+        set.insert(boundary[20]);
+        set.extend(boundary[21..40].iter().copied());
+        assert_in_sync(&set);
+
+        // The overlapping half is dropped
+        let n_added = set.merge(&boundary[20..], 1e-6);
+        assert_eq!(n_added, boundary.len() - 40);
+        assert_eq!(set.len(), boundary.len());
+        assert_in_sync(&set);
+    }
+
+    #[test]
+    fn merge_keeps_opposite_facing_halfspaces() {
+        let mut set = BoundarySet::new();
+        set.insert(hs(vector![0.5, 0.5], vector![1.0, 0.0]));
+
+        assert_eq!(
+            set.merge(&[hs(vector![0.5, 0.5], vector![-1.0, 0.0])], 0.1),
+            1
+        );
+        assert_eq!(
+            set.merge(&[hs(vector![0.55, 0.5], vector![1.0, 0.0])], 0.1),
+            0
+        );
+    }
+
+    #[test]
+    fn queries_match_free_functions() {
+        let set = BoundarySet::from(square());
+
+        assert!(set.predict(vector![0.5, 0.5], 1).class());
+        assert!(!set.predict(vector![0.9, 0.5], 1).class());
+        assert!((set.signed_distance(&vector![0.5, 0.3]).unwrap() - 0.05).abs() < 1e-10);
+        assert!(set.falls_on_boundary(0.05, &hs(vector![0.5, 0.26], vector![0.0, -1.0])));
+
+        let volume = approx_mc_volume(PredictionMode::Union, &[set.as_pair()], 2000, 1, None, 1);
+        assert!((volume - 0.25).abs() < 0.01, "Bad volume: {volume}");
+    }
+}
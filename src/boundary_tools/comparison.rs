@@ -0,0 +1,170 @@
+use nalgebra::SVector;
+
+use crate::prelude::NodeID;
+
+use super::{estimation::approx_mc_volume_intersection, BoundarySet};
+
+/// The differences between an old and a new boundary of the same envelope, e.g.
+/// before and after the FUT was updated. See `diff()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryDiff<const N: usize> {
+    /// For each halfspace of the old boundary, the index of its match in the new
+    /// boundary, or None if it has no match.
+    pub matches: Vec<Option<NodeID>>,
+    /// For each halfspace of the old boundary, the displacement along its surface
+    /// vector to the new boundary, or None if it has no match.
+    pub displacements: Vec<Option<SVector<f64, N>>>,
+    /// The mean distance the matched boundary points moved.
+    pub mean_drift: f64,
+    /// The largest distance a matched boundary point moved.
+    pub max_drift: f64,
+    /// The mean displacement of the matched boundary points along their surface
+    /// vectors, positive when the envelope grew and negative when it shrank.
+    pub mean_signed_drift: f64,
+    /// The volume within the old envelope but not the new one.
+    pub lost_volume: f64,
+    /// The volume within the new envelope but not the old one.
+    pub gained_volume: f64,
+}
+
+impl<const N: usize> BoundaryDiff<N> {
+    /// The number of old halfspaces without a match in the new boundary.
+    pub fn n_unmatched(&self) -> usize {
+        self.matches.iter().filter(|m| m.is_none()).count()
+    }
+}
+
+/// Compares two boundaries of the same envelope. Each halfspace of @old is matched
+/// to the new boundary along its surface vector, by casting a ray from its boundary
+/// point towards the new boundary (outwards if the point is still within the new
+/// envelope, inwards otherwise). The match is the new halfspace nearest to where
+/// the ray crosses the new boundary. See `ray_cast()`.
+/// ## Arguments
+/// * old : The previous boundary.
+/// * new : The current boundary.
+/// * n_samples : The number of MC samples used to estimate the lost and gained
+///   volumes. More -> higher accuracy.
+/// * seed : The seed to use while generating random points for MC.
+/// ## Returns
+/// * diff : The matches and displacements of the old halfspaces, and their
+///   summary. Without any matches, the drift statistics are 0.
+pub fn diff<const N: usize>(
+    old: &BoundarySet<N>,
+    new: &BoundarySet<N>,
+    n_samples: u32,
+    seed: u64,
+) -> BoundaryDiff<N> {
+    let (matches, displacements): (Vec<Option<NodeID>>, Vec<Option<SVector<f64, N>>>) = old
+        .boundary()
+        .iter()
+        .map(|hs| {
+            let outward = new.signed_distance(&hs.b).is_some_and(|d| d > 0.0);
+            let v = if outward { hs.n } else { -hs.n };
+            let Some(t) = new.ray_cast(&hs.b, &v) else {
+                return (None, None);
+            };
+            let displacement = t * v;
+            let crossing: [f64; N] = (*hs.b + displacement).into();
+            let id = new
+                .btree()
+                .nearest_neighbor(&crossing)
+                .map(|node| node.data);
+            (id, id.map(|_| displacement))
+        })
+        .unzip();
+
+    let (sum, max, signed_sum, n_matched) = old
+        .boundary()
+        .iter()
+        .zip(displacements.iter())
+        .filter_map(|(hs, d)| d.map(|d| (d.norm(), hs.n.dot(&d))))
+        .fold(
+            (0.0, 0.0f64, 0.0, 0usize),
+            |(sum, max, signed_sum, n), (drift, signed)| {
+                (sum + drift, max.max(drift), signed_sum + signed, n + 1)
+            },
+        );
+    let n_matched = n_matched.max(1) as f64;
+
+    let (lost_volume, gained_volume) = if old.is_empty() || new.is_empty() {
+        (0.0, 0.0)
+    } else {
+        let (_, old_only, new_only) = approx_mc_volume_intersection(
+            &[old.as_pair()],
+            &[new.as_pair()],
+            n_samples,
+            1,
+            None,
+            seed,
+        );
+        (old_only, new_only)
+    };
+
+    BoundaryDiff {
+        matches,
+        displacements,
+        mean_drift: sum / n_matched,
+        max_drift: max,
+        mean_signed_drift: signed_sum / n_matched,
+        lost_volume,
+        gained_volume,
+    }
+}
+
+#[cfg(test)]
+mod diff_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::test_utils::circle_boundary;
+
+    use super::*;
+
+    fn circle(center: SVector<f64, 2>, radius: f64, n: usize) -> BoundarySet<2> {
+        circle_boundary(center, radius, n).into()
+    }
+
+    #[test]
+    fn identical_boundaries_have_no_drift() {
+        let old = circle(SVector::repeat(0.5), 0.3, 200);
+        let d = diff(&old, &old.clone(), 1000, 1);
+
+        assert_eq!(d.n_unmatched(), 0);
+        assert!(d.max_drift < 1e-6, "Unexpected drift: {}", d.max_drift);
+        assert!(d.matches.iter().enumerate().all(|(i, m)| *m == Some(i)));
+        assert_eq!(d.lost_volume, 0.0);
+        assert_eq!(d.gained_volume, 0.0);
+    }
+
+    #[test]
+    fn measures_shrinking_envelope() {
+        let old = circle(SVector::repeat(0.5), 0.3, 200);
+        let new = circle(SVector::repeat(0.5), 0.2, 200);
+        let d = diff(&old, &new, 10000, 1);
+
+        assert_eq!(d.n_unmatched(), 0);
+        for (hs, v) in old.boundary().iter().zip(d.displacements.iter()) {
+            let v = v.unwrap();
+            assert!((v + 0.1 * hs.n).norm() < 1e-3, "Bad displacement: {v:?}");
+        }
+        assert!((d.mean_drift - 0.1).abs() < 1e-3);
+        assert!((d.mean_signed_drift + 0.1).abs() < 1e-3);
+
+        // The annulus between the circles
+        let lost = PI * (0.3f64.powi(2) - 0.2f64.powi(2));
+        assert!((d.lost_volume - lost).abs() < 0.01, "Bad lost volume");
+        assert!(d.gained_volume < 0.005);
+    }
+
+    #[test]
+    fn unmatched_when_envelope_vanishes_locally() {
+        let old = circle(SVector::repeat(0.5), 0.2, 100);
+        // Moved away, leaving the old boundary behind
+        let new = circle(vector![0.85, 0.5], 0.1, 100);
+        let d = diff(&old, &new, 1000, 1);
+
+        assert!(d.n_unmatched() > 0);
+        assert!(d.n_unmatched() < old.len());
+    }
+}
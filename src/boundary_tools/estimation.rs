@@ -1,11 +1,20 @@
-use nalgebra::{Const, OMatrix, SVector};
+use std::cmp::Ordering;
 
+use nalgebra::{Const, DMatrix, DVector, OMatrix, SVector, SymmetricEigen};
+#[cfg(feature = "global_search")]
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "global_search")]
+use rand_chacha::ChaCha20Rng;
+
+#[cfg(feature = "global_search")]
+use crate::search::global_search::gen_normal;
 use crate::{
     prelude::{
-        Adherer, AdhererFactory, AdhererState, Boundary, BoundaryRTree, Classifier, Domain,
-        Halfspace, MeshExplorer, Result, Sample,
+        Adherer, AdhererFactory, AdhererState, Boundary, Classifier, Domain, Halfspace,
+        MeshExplorer, Result, Sample, SpatialIndex,
     },
     search::global_search::{MonteCarloSearch, SearchFactory},
+    utils::sorted_eigen,
 };
 
 #[derive(Clone, Copy)]
@@ -14,6 +23,31 @@ pub enum PredictionMode {
     Intersection,
 }
 
+/// How the votes of the k nearest halfspaces are combined by
+/// `approx_weighted_prediction()`. Each halfspace votes WithinMode if the point is
+/// behind it, and OutOfMode otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NeighborWeighting {
+    /// WithinMode only if every vote is WithinMode, as `approx_prediction()`.
+    /// Produces systematic false negatives in concave regions of an envelope.
+    Unanimous,
+    /// Each vote is weighted by 1 / d, where d is the distance to the halfspace's
+    /// boundary point.
+    InverseDistance,
+    /// Each vote is weighted by exp(-d^2 / (2 h^2)), with the bandwidth h.
+    Gaussian(f64),
+}
+
+/// How a point is classified when the weighted votes for either class are tied.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TieBreak {
+    /// The nearest halfspace's vote decides.
+    #[default]
+    Nearest,
+    WithinMode,
+    OutOfMode,
+}
+
 /// Given an initial halfspace, determines a more accurate surface direction and
 /// returns the updated halfspace,.
 /// ## Arguments
@@ -77,6 +111,64 @@ where
     Ok((Halfspace { b: hs.b, n: new_n }, neighbors, all_samples))
 }
 
+/// Re-estimates the surface vector of each halfspace from a plane fit (PCA) of its
+/// @k nearest boundary points, replacing the noisy surface vectors acquired by the
+/// adherers, which degrade the accuracy of `approx_prediction()`.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
+/// * k : The number of boundary points to fit each plane to, including the
+///   halfspace's own. Must be at least N, ideally a few times N. Larger values
+///   smooth more, but round off edges and corners.
+/// ## Return
+/// * smoothed : The halfspaces with re-estimated surface vectors, in boundary order.
+///   Each surface vector keeps the side of its original, and halfspaces whose
+///   neighbors do not span a plane (e.g. too few, or colinear) are unchanged.
+pub fn smooth_surface_vectors<const N: usize, I>(
+    boundary: &Boundary<N>,
+    btree: &I,
+    k: usize,
+) -> Vec<Halfspace<N>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    assert!(k >= N, "k must be at least N to fit a plane! Got: {k}");
+
+    boundary
+        .iter()
+        .map(|hs| {
+            let neighbors: Vec<SVector<f64, N>> = btree
+                .nearest_neighbors(&hs.b.into(), k)
+                .into_iter()
+                .map(|node| SVector::from(*node.geom()))
+                .collect();
+            if neighbors.len() < N {
+                return *hs;
+            }
+
+            let centroid = neighbors.iter().sum::<SVector<f64, N>>() / neighbors.len() as f64;
+            let mut moment = OMatrix::<f64, Const<N>, Const<N>>::zeros();
+            for p in neighbors.iter() {
+                let v = p - centroid;
+                moment += v * v.transpose();
+            }
+
+            let eigen = sorted_eigen(&moment);
+
+            // The neighbors must span N - 1 dimensions for the normal to be unique.
+            if N > 1 && eigen[N - 2].0 <= 1e-9 * eigen[0].0 {
+                return *hs;
+            }
+
+            let n = eigen[N - 1].1.normalize();
+            Halfspace {
+                b: hs.b,
+                n: if n.dot(&hs.n) < 0.0 { -n } else { n },
+            }
+        })
+        .collect()
+}
+
 pub fn is_behind_halfspace<const N: usize>(p: &SVector<f64, N>, hs: &Halfspace<N>) -> bool {
     let s = (p - *hs.b).normalize();
     s.dot(&hs.n) < 0.0
@@ -88,18 +180,21 @@ pub fn is_behind_halfspace<const N: usize>(p: &SVector<f64, N>, hs: &Halfspace<N
 /// ## Arguments
 /// * p : The point to be classified.
 /// * boundary : The explored boundary for the target performance mode.
-/// * btree : The RTree for @boundary.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
 /// * k : The number of halfspaces to consider while classifier @p. A good default is
 ///   1, but with higher resolution and dimensional boundaries, playing with this
 ///   number may improve results.
-pub fn approx_prediction<const N: usize>(
+pub fn approx_prediction<const N: usize, I>(
     p: SVector<f64, N>,
     boundary: &Boundary<N>,
-    btree: &BoundaryRTree<N>,
+    btree: &I,
     k: u32,
-) -> Sample<N> {
+) -> Sample<N>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
     let mut cls = true;
-    for (_, neighbor) in (0..k).zip(btree.nearest_neighbor_iter(&p.into())) {
+    for neighbor in btree.nearest_neighbors(&p.into(), k as usize) {
         let hs = boundary.get(neighbor.data).expect(
             "Invalid neighbor index used on @boundary. Often a result of @boundary being out of sync or entirely different from @btree."
         );
@@ -113,22 +208,99 @@ pub fn approx_prediction<const N: usize>(
     Sample::from_class(p, cls)
 }
 
+/// Predicts the class of @p as `approx_prediction()` does, but by a weighted vote of
+/// the @k nearest halfspaces rather than requiring all of them to agree. Improves
+/// accuracy in concave regions, where a point within the envelope is often in front
+/// of a distant halfspace on the far side of the concavity.
+/// ## Arguments
+/// * p : The point to be classified.
+/// * boundary : The explored boundary for the target performance mode.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
+/// * k : The number of halfspaces that vote.
+/// * weighting : How the votes are weighted.
+/// * tie_break : How a tied vote is resolved.
+/// ## Returns
+/// * sample : The predicted class of @p. OutOfMode if @boundary is empty.
+pub fn approx_weighted_prediction<const N: usize, I>(
+    p: SVector<f64, N>,
+    boundary: &Boundary<N>,
+    btree: &I,
+    k: u32,
+    weighting: NeighborWeighting,
+    tie_break: TieBreak,
+) -> Sample<N>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let votes: Vec<(f64, bool)> = btree
+        .nearest_neighbors(&p.into(), k as usize)
+        .into_iter()
+        .map(|neighbor| {
+            let hs = boundary.get(neighbor.data).expect(
+                "Invalid neighbor index used on @boundary. Often a result of @boundary being out of sync or entirely different from @btree."
+            );
+            ((p - *hs.b).norm(), hs.n.dot(&(p - *hs.b)) < 0.0)
+        })
+        .collect();
+
+    let Some(&(_, nearest_vote)) = votes.first() else {
+        return Sample::from_class(p, false);
+    };
+
+    let weight = |d: f64| match weighting {
+        NeighborWeighting::Unanimous => 1.0,
+        NeighborWeighting::InverseDistance => 1.0 / d,
+        NeighborWeighting::Gaussian(h) => (-d * d / (2.0 * h * h)).exp(),
+    };
+
+    let cls = match weighting {
+        NeighborWeighting::Unanimous => votes.iter().all(|&(_, vote)| vote),
+        // A point on a boundary point is decided by that halfspace alone.
+        NeighborWeighting::InverseDistance if votes[0].0 <= f64::EPSILON => nearest_vote,
+        _ => {
+            let (within, out): (f64, f64) =
+                votes.iter().fold((0.0, 0.0), |(within, out), &(d, vote)| {
+                    if vote {
+                        (within + weight(d), out)
+                    } else {
+                        (within, out + weight(d))
+                    }
+                });
+
+            if (within - out).abs() <= f64::EPSILON * (within + out) {
+                match tie_break {
+                    TieBreak::Nearest => nearest_vote,
+                    TieBreak::WithinMode => true,
+                    TieBreak::OutOfMode => false,
+                }
+            } else {
+                within > out
+            }
+        }
+    };
+
+    Sample::from_class(p, cls)
+}
+
 /// Predicts whether or not some point, @p, will be classified as WithinMode or
 /// OutOfMode according to the explored boundary. As a result, does not require the
 /// classifier for the fut.
 /// ## Arguments
 /// * p : The point to be classified.
 /// * boundary : The explored boundary for the target performance mode.
-/// * btree : The RTree for @boundary.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
 /// * k : The number of halfspaces to consider while classifier @p. A good default is
 ///   1, but with higher resolution and dimensional boundaries, playing with this
 ///   number may improve results.
-pub fn approx_group_prediction<const N: usize>(
+pub fn approx_group_prediction<const N: usize, I>(
     mode: PredictionMode,
     p: SVector<f64, N>,
-    group: &[(&Boundary<N>, &BoundaryRTree<N>)],
+    group: &[(&Boundary<N>, &I)],
     k: u32,
-) -> Sample<N> {
+) -> Sample<N>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
     let mut cls = match mode {
         PredictionMode::Union => false,
         PredictionMode::Intersection => true,
@@ -136,13 +308,13 @@ pub fn approx_group_prediction<const N: usize>(
     for (boundary, btree) in group.iter() {
         match mode {
             PredictionMode::Union => {
-                if approx_prediction(p, boundary, btree, k).class() {
+                if approx_prediction(p, boundary, *btree, k).class() {
                     cls = true;
                     break;
                 }
             }
             PredictionMode::Intersection => {
-                if !approx_prediction(p, boundary, btree, k).class() {
+                if !approx_prediction(p, boundary, *btree, k).class() {
                     cls = false;
                     break;
                 }
@@ -154,10 +326,12 @@ pub fn approx_group_prediction<const N: usize>(
 }
 
 /// Estimates the volume of an envelope using Monte Carlo sampling using approximate
-/// predictions.
+/// predictions. See `approx_volume_report()` for other sampling strategies and the
+/// standard error of the estimate, and `hull::convex_hull_volume()` for an exact
+/// alternative for convex envelopes.
 /// ## Arguments
 /// * boundary : The boundary of the envelope whose volume is being measured.
-/// * btree : The RTree for the boundary.
+/// * btree : The spatial index (e.g. RTree) for the boundary.
 /// * n_samples : How many samples to take for estimating volume. More -> higher
 ///   accuracy
 /// * n_neighbors : Varies how many halfspaces should be considered while determining
@@ -167,14 +341,17 @@ pub fn approx_group_prediction<const N: usize>(
 /// * seed : The seed to use while generating random points for MC.
 /// ## Return
 /// * volume : The volume that lies within the envelope.
-pub fn approx_mc_volume<const N: usize>(
+pub fn approx_mc_volume<const N: usize, I>(
     mode: PredictionMode,
-    group: &[(&Boundary<N>, &BoundaryRTree<N>)],
+    group: &[(&Boundary<N>, &I)],
     n_samples: u32,
     n_neighbors: u32,
     domain: Option<&Domain<N>>,
     seed: u64,
-) -> f64 {
+) -> f64
+where
+    I: SpatialIndex<N> + ?Sized,
+{
     let mut pc: Vec<SVector<f64, N>> = vec![]; //group1.iter().chain(group2).map(|(hs, _)| *hs.b).collect();
 
     for (boundary, _) in group.iter() {
@@ -183,17 +360,164 @@ pub fn approx_mc_volume<const N: usize>(
 
     let domain = domain.cloned().unwrap_or(Domain::new_from_point_cloud(&pc));
     let mut mc = MonteCarloSearch::new(domain, seed);
+
+    approx_volume_with(mode, group, n_samples, n_neighbors, &mut mc)
+}
+
+/// A volume estimate with its statistical uncertainty. See `approx_volume_report()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeReport {
+    /// The estimated volume.
+    pub volume: f64,
+    /// The standard error of @volume, assuming independent samples. Conservative
+    /// for stratified or quasi-random samples, whose true error is lower.
+    pub std_err: f64,
+    /// The number of samples the estimate is based on.
+    pub n_used: u32,
+}
+
+/// The z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+impl VolumeReport {
+    fn from_counts(wm_count: u32, n_samples: u32, domain_volume: f64) -> Self {
+        let ratio = wm_count as f64 / n_samples as f64;
+
+        VolumeReport {
+            volume: ratio * domain_volume,
+            std_err: (ratio * (1.0 - ratio) / n_samples as f64).sqrt() * domain_volume,
+            n_used: n_samples,
+        }
+    }
+
+    /// The interval of @z standard errors around the volume, e.g. z = 1.96 for a
+    /// 95% confidence interval.
+    /// ## Return
+    /// * (low, high) : The bounds of the interval.
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        (
+            self.volume - z * self.std_err,
+            self.volume + z * self.std_err,
+        )
+    }
+
+    /// The standard error relative to the volume, e.g. 0.05 for +-5%. Infinite if
+    /// no volume was found.
+    pub fn rel_err(&self) -> f64 {
+        if self.volume > 0.0 {
+            self.std_err / self.volume
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Estimates the volume of an envelope using approximate predictions at the points
+/// generated by @search, e.g. a quasi-random sequence such as SobolSearch, whose
+/// estimates converge faster than those of Monte Carlo sampling.
+/// ## Arguments
+/// * group : The boundaries of the envelopes whose volume is being measured.
+/// * n_samples : How many samples to take for estimating volume. More -> higher
+///   accuracy
+/// * n_neighbors : Varies how many halfspaces should be considered while determining
+///   if a point falls within an envelope. See `approx_mc_volume()`.
+/// * search : The source of the sampled points, whose domain is the measured space.
+/// ## Return
+/// * volume : The volume that lies within the envelope.
+pub fn approx_volume_with<const N: usize, I, S>(
+    mode: PredictionMode,
+    group: &[(&Boundary<N>, &I)],
+    n_samples: u32,
+    n_neighbors: u32,
+    search: &mut S,
+) -> f64
+where
+    I: SpatialIndex<N> + ?Sized,
+    S: SearchFactory<N> + ?Sized,
+{
+    approx_volume_report(mode, group, n_samples, n_neighbors, search).volume
+}
+
+/// Estimates the volume of an envelope as `approx_volume_with()` does, reporting the
+/// standard error of the estimate, e.g. to judge whether @n_samples was enough for
+/// the dimensionality of the envelope. Pass a StratifiedSearch or SobolSearch as
+/// @search for lower error than MonteCarloSearch.
+/// ## Return
+/// * report : The volume, its standard error and the number of samples used.
+pub fn approx_volume_report<const N: usize, I, S>(
+    mode: PredictionMode,
+    group: &[(&Boundary<N>, &I)],
+    n_samples: u32,
+    n_neighbors: u32,
+    search: &mut S,
+) -> VolumeReport
+where
+    I: SpatialIndex<N> + ?Sized,
+    S: SearchFactory<N> + ?Sized,
+{
     let mut wm_count = 0;
 
     for _ in 0..n_samples {
-        if approx_group_prediction(mode, mc.sample(), group, n_neighbors).class() {
+        if approx_group_prediction(mode, search.sample(), group, n_neighbors).class() {
             wm_count += 1;
         }
     }
 
-    let ratio = wm_count as f64 / n_samples as f64;
+    VolumeReport::from_counts(wm_count, n_samples, search.get_domain().volume())
+}
 
-    ratio * mc.get_domain().volume()
+/// Runs `approx_volume_report()` incrementally in batches of @batch_size samples,
+/// reporting the running estimate after each batch, so that the convergence of the
+/// estimate can be inspected instead of guessing @n_samples up front. Stops early
+/// once the 95% confidence interval of the estimate is within @target_rel_err of
+/// the volume.
+/// ## Arguments
+/// * group : The boundaries of the envelopes whose volume is being measured.
+/// * n_neighbors : Varies how many halfspaces should be considered while determining
+///   if a point falls within an envelope. See `approx_mc_volume()`.
+/// * search : The source of the sampled points, whose domain is the measured space.
+/// * batch_size : The number of samples between reports.
+/// * max_samples : The most samples to take, even if @target_rel_err is not met.
+/// * target_rel_err : The relative half-width of the 95% confidence interval at
+///   which to stop, e.g. 0.01 for +-1%. None to always take @max_samples.
+/// ## Return
+/// * series : The running estimate after each batch, the last being the final
+///   estimate. See `VolumeReport::confidence_interval()`.
+pub fn approx_volume_convergence<const N: usize, I, S>(
+    mode: PredictionMode,
+    group: &[(&Boundary<N>, &I)],
+    n_neighbors: u32,
+    search: &mut S,
+    batch_size: u32,
+    max_samples: u32,
+    target_rel_err: Option<f64>,
+) -> Vec<VolumeReport>
+where
+    I: SpatialIndex<N> + ?Sized,
+    S: SearchFactory<N> + ?Sized,
+{
+    assert!(batch_size > 0, "batch_size must be non-zero!");
+    let domain_volume = search.get_domain().volume();
+
+    let mut series = vec![];
+    let mut wm_count = 0;
+    let mut n_samples = 0;
+    while n_samples < max_samples {
+        for _ in 0..batch_size.min(max_samples - n_samples) {
+            if approx_group_prediction(mode, search.sample(), group, n_neighbors).class() {
+                wm_count += 1;
+            }
+            n_samples += 1;
+        }
+
+        let report = VolumeReport::from_counts(wm_count, n_samples, domain_volume);
+        series.push(report);
+        if target_rel_err.is_some_and(|target| Z_95 * report.rel_err() <= target) {
+            break;
+        }
+    }
+
+    series
 }
 
 /// Estimates the volume of an envelope using Monte Carlo sampling using approximate
@@ -201,8 +525,8 @@ pub fn approx_mc_volume<const N: usize>(
 /// ## Arguments
 /// * b1 : The first boundary.
 /// * b2 : The second boundary.
-/// * btree1 : The RTree for the first boundary.
-/// * btree : The RTree for the second boundary.
+/// * btree1 : The spatial index for the first boundary.
+/// * btree2 : The spatial index for the second boundary.
 /// * n_samples : How many samples to take for estimating volume. More -> higher
 ///   accuracy
 /// * n_neighbors : Varies how many halfspaces should be considered while determining
@@ -216,17 +540,22 @@ pub fn approx_mc_volume<const N: usize>(
 ///
 /// The total volume is the sum of these voumes. The total volume of an envelop is
 /// the sum of its volume and the intersection volume.
-pub fn approx_mc_volume_intersection<const N: usize>(
-    group1: &[(&Boundary<N>, &BoundaryRTree<N>)],
-    group2: &[(&Boundary<N>, &BoundaryRTree<N>)],
+pub fn approx_mc_volume_intersection<const N: usize, I1, I2>(
+    group1: &[(&Boundary<N>, &I1)],
+    group2: &[(&Boundary<N>, &I2)],
     n_samples: u32,
     n_neighbors: u32,
     domain: Option<&Domain<N>>,
     seed: u64,
-) -> (f64, f64, f64) {
+) -> (f64, f64, f64)
+where
+    I1: SpatialIndex<N> + ?Sized,
+    I2: SpatialIndex<N> + ?Sized,
+{
     let mut pc: Vec<SVector<f64, N>> = vec![]; //group1.iter().chain(group2).map(|(hs, _)| *hs.b).collect();
 
-    for (boundary, _) in group1.iter().chain(group2.iter()) {
+    let boundaries = group1.iter().map(|(b, _)| b);
+    for boundary in boundaries.chain(group2.iter().map(|(b, _)| b)) {
         pc.append(&mut boundary.iter().map(|hs| *hs.b).collect());
     }
 
@@ -260,6 +589,525 @@ pub fn approx_mc_volume_intersection<const N: usize>(
     (both_ratio * vol, b1_ratio * vol, b2_ratio * vol)
 }
 
+/// Estimates the (N-1)-dimensional surface area of an envelope using Monte Carlo
+/// sampling of approximate predictions, analogous to `approx_mc_volume()`. Short
+/// segments are cast from uniformly random points in uniformly random directions,
+/// and the fraction whose ends are predicted differently, i.e. that cross the
+/// surface, is proportional to its area (Cauchy-Crofton). Dividing by the volume
+/// gives the surface-to-volume ratio, a measure of how compact the envelope is.
+/// ## Arguments
+/// * group : The boundaries of the envelopes whose surface is being measured.
+/// * n_samples : How many segments to cast. More -> higher accuracy
+/// * n_neighbors : Varies how many halfspaces should be considered while determining
+///   if a point falls within an envelope. See `approx_mc_volume()`.
+/// * segment_length : The length of the segments. Shorter segments miss less of the
+///   surface's fine detail, but cross it less often, requiring more samples. A good
+///   default is the jump distance the boundary was explored with.
+/// * domain : The region to sample within, or None for the bounding box of the
+///   boundary points.
+/// * seed : The seed to use while generating random segments.
+/// ## Return
+/// * area : The surface area of the envelope within the domain.
+#[cfg(feature = "global_search")]
+pub fn approx_mc_surface_area<const N: usize, I>(
+    mode: PredictionMode,
+    group: &[(&Boundary<N>, &I)],
+    n_samples: u32,
+    n_neighbors: u32,
+    segment_length: f64,
+    domain: Option<&Domain<N>>,
+    seed: u64,
+) -> f64
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let pc: Vec<SVector<f64, N>> = group
+        .iter()
+        .flat_map(|(boundary, _)| boundary.iter().map(|hs| *hs.b))
+        .collect();
+    let domain = domain.cloned().unwrap_or(Domain::new_from_point_cloud(&pc));
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+
+    let mut n_crossings = 0;
+    for _ in 0..n_samples {
+        let p = SVector::<f64, N>::from_fn(|_, _| rng.gen()).component_mul(&domain.dimensions())
+            + domain.low();
+        let Some(v) = SVector::<f64, N>::from_fn(|_, _| gen_normal(&mut rng)).try_normalize(0.0)
+        else {
+            continue;
+        };
+
+        let cls1 = approx_group_prediction(mode, p, group, n_neighbors).class();
+        let cls2 =
+            approx_group_prediction(mode, p + segment_length * v, group, n_neighbors).class();
+        if cls1 != cls2 {
+            n_crossings += 1;
+        }
+    }
+
+    let ratio = n_crossings as f64 / n_samples as f64;
+    ratio * domain.volume() / (segment_length * mean_projection::<N>())
+}
+
+/// The mean of |u . n| over uniformly random unit vectors u, for any unit vector n,
+/// i.e. Gamma(N/2) / (sqrt(pi) Gamma((N + 1) / 2)).
+#[cfg(feature = "global_search")]
+fn mean_projection<const N: usize>() -> f64 {
+    // Gamma(k/2) / Gamma((k + 1)/2), starting from k = 1
+    let mut ratio = std::f64::consts::PI.sqrt();
+    for k in 1..N {
+        ratio = 1.0 / (k as f64 / 2.0 * ratio);
+    }
+    ratio / std::f64::consts::PI.sqrt()
+}
+
+#[cfg(all(test, feature = "global_search"))]
+mod surface_area_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::SVector;
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Domain, Halfspace},
+        test_utils::{circle_boundary, sphere_boundary},
+    };
+
+    use super::{approx_mc_surface_area, approx_mc_volume, mean_projection, PredictionMode};
+
+    /// Halfspaces on a sphere at the center of the unit cube, from the Fibonacci
+    /// lattice.
+    fn sphere(radius: f64, n: usize) -> Vec<Halfspace<3>> {
+        sphere_boundary(SVector::repeat(0.5), radius, n)
+    }
+
+    #[test]
+    fn mean_projection_of_known_dimensions() {
+        assert!((mean_projection::<1>() - 1.0).abs() < 1e-12);
+        assert!((mean_projection::<2>() - 2.0 / PI).abs() < 1e-12);
+        assert!((mean_projection::<3>() - 0.5).abs() < 1e-12);
+        assert!((mean_projection::<4>() - 4.0 / (3.0 * PI)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimates_circumference_of_circle() {
+        let radius = 0.3;
+        let boundary = circle_boundary(SVector::repeat(0.5), radius, 500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let group = [(boundary.as_slice(), &btree)];
+        let domain = Domain::normalized();
+
+        let area = approx_mc_surface_area(
+            PredictionMode::Union,
+            &group,
+            100_000,
+            1,
+            0.01,
+            Some(&domain),
+            1,
+        );
+        let circumference = 2.0 * PI * radius;
+        assert!(
+            (area - circumference).abs() < 0.1 * circumference,
+            "Bad circumference: {area} vs {circumference}"
+        );
+
+        // Surface to volume ratio of 2 / r
+        let volume = approx_mc_volume(PredictionMode::Union, &group, 10_000, 1, Some(&domain), 1);
+        let ratio = area / volume;
+        assert!((ratio - 2.0 / radius).abs() < 0.15 * 2.0 / radius);
+    }
+
+    #[test]
+    fn estimates_surface_area_of_sphere() {
+        let radius = 0.25;
+        let boundary = sphere(radius, 2000);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let area = approx_mc_surface_area(
+            PredictionMode::Union,
+            &[(boundary.as_slice(), &btree)],
+            100_000,
+            1,
+            0.02,
+            Some(&Domain::normalized()),
+            1,
+        );
+        let expected = 4.0 * PI * radius * radius;
+        assert!(
+            (area - expected).abs() < 0.1 * expected,
+            "Bad surface area: {area} vs {expected}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod volume_report_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::SVector;
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Domain, Halfspace},
+        search::global_search::{MonteCarloSearch, SobolSearch},
+        test_utils::circle_boundary,
+    };
+
+    use super::{approx_volume_convergence, approx_volume_report, PredictionMode};
+
+    const RADIUS: f64 = 0.3;
+
+    fn circle(n: usize) -> Vec<Halfspace<2>> {
+        circle_boundary(SVector::repeat(0.5), RADIUS, n)
+    }
+
+    #[test]
+    fn std_err_bounds_error_of_estimate() {
+        let boundary = circle(500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let area = PI * RADIUS * RADIUS;
+
+        let mut mc = MonteCarloSearch::new(Domain::normalized(), 0);
+        let mc_report = approx_volume_report(
+            PredictionMode::Union,
+            &[(&boundary, &btree)],
+            4000,
+            1,
+            &mut mc,
+        );
+        let mut sobol = SobolSearch::new(Domain::normalized());
+        let sobol_report = approx_volume_report(
+            PredictionMode::Union,
+            &[(&boundary, &btree)],
+            4096,
+            1,
+            &mut sobol,
+        );
+
+        assert_eq!(mc_report.n_used, 4000);
+        // p(1 - p) / n for p = pi * 0.09
+        let expected_err = (area * (1.0 - area) / 4000.0).sqrt();
+        assert!((mc_report.std_err - expected_err).abs() < 0.1 * expected_err);
+        for report in [mc_report, sobol_report] {
+            assert!(
+                (report.volume - area).abs() < 3.0 * report.std_err,
+                "Error outside of 3 std_err: {report:?} vs {area}"
+            );
+            assert!(report.rel_err() < 0.05);
+        }
+    }
+
+    #[test]
+    fn convergence_stops_at_target_error() {
+        let boundary = circle(500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let area = PI * RADIUS * RADIUS;
+
+        let mut mc = MonteCarloSearch::new(Domain::normalized(), 0);
+        let series = approx_volume_convergence(
+            PredictionMode::Union,
+            &[(&boundary, &btree)],
+            1,
+            &mut mc,
+            100,
+            100_000,
+            Some(0.05),
+        );
+
+        let last = series.last().unwrap();
+        assert!(last.n_used < 100_000, "Did not stop early");
+        assert!(1.96 * last.rel_err() <= 0.05);
+        assert!(series[..series.len() - 1]
+            .iter()
+            .all(|r| 1.96 * r.rel_err() > 0.05));
+        assert!(series
+            .iter()
+            .enumerate()
+            .all(|(i, r)| r.n_used == 100 * (i as u32 + 1)));
+
+        let (low, high) = last.confidence_interval(3.0);
+        assert!(
+            low < area && area < high,
+            "{area} outside of ({low}, {high})"
+        );
+    }
+
+    #[test]
+    fn convergence_takes_max_samples_without_target() {
+        let boundary = circle(500);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let mut mc = MonteCarloSearch::new(Domain::normalized(), 0);
+        let series = approx_volume_convergence(
+            PredictionMode::Union,
+            &[(&boundary, &btree)],
+            1,
+            &mut mc,
+            300,
+            1000,
+            None,
+        );
+
+        assert_eq!(series.len(), 4);
+        assert_eq!(series.last().unwrap().n_used, 1000);
+
+        // The series reproduces the one-shot estimate
+        let mut mc = MonteCarloSearch::new(Domain::normalized(), 0);
+        let report = approx_volume_report(
+            PredictionMode::Union,
+            &[(&boundary, &btree)],
+            1000,
+            1,
+            &mut mc,
+        );
+        assert_eq!(*series.last().unwrap(), report);
+    }
+}
+
+/// The principal curvatures and directions of a boundary at one of its points, i.e.
+/// the eigenvalues and eigenvectors of the shape operator. See
+/// `approx_principal_curvatures()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrincipalCurvatures<const N: usize> {
+    /// The N - 1 principal curvatures, in ascending order. Positive where the
+    /// envelope is convex, i.e. the surface bends away from its surface vector.
+    pub curvatures: Vec<f64>,
+    /// The unit direction along the surface of each principal curvature.
+    pub directions: Vec<SVector<f64, N>>,
+}
+
+impl<const N: usize> PrincipalCurvatures<N> {
+    /// The mean of the principal curvatures.
+    pub fn mean_curvature(&self) -> f64 {
+        self.curvatures.iter().sum::<f64>() / self.curvatures.len() as f64
+    }
+
+    /// The product of the principal curvatures.
+    pub fn gaussian_curvature(&self) -> f64 {
+        self.curvatures.iter().product()
+    }
+
+    /// Whether the surface bends in opposite directions, i.e. it is convex along
+    /// one principal direction and concave along another, by more than @tol.
+    pub fn is_saddle(&self, tol: f64) -> bool {
+        self.min() < -tol && self.max() > tol
+    }
+
+    /// Whether the surface is sharply bent along one direction, i.e. its largest
+    /// principal curvature exceeds @tol in magnitude while its smallest does not.
+    pub fn is_ridge(&self, tol: f64) -> bool {
+        let (min, max) = (self.min().abs(), self.max().abs());
+        min.max(max) > tol && min.min(max) <= tol
+    }
+
+    fn min(&self) -> f64 {
+        self.curvatures.first().copied().unwrap_or(0.0)
+    }
+
+    fn max(&self) -> f64 {
+        self.curvatures.last().copied().unwrap_or(0.0)
+    }
+}
+
+/// Estimates the principal curvatures and directions at each halfspace by fitting
+/// a quadratic height function over the tangent plane to its @k nearest boundary
+/// points. Enables detecting ridges and saddle regions of the envelope.
+/// ## Arguments
+/// * boundary : The explored boundary. Curvature estimates are only as accurate as
+///   its surface vectors, see `smooth_surface_vectors()`.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
+/// * k : The number of boundary points to fit each quadric to, including the
+///   halfspace's own. Must exceed the number of fitted terms, N(N - 1)/2 + N - 1.
+///   Larger values smooth more, but blur small features.
+/// ## Return
+/// * curvatures : The principal curvatures of each halfspace, in boundary order, or
+///   None where the neighbors do not span the tangent plane (e.g. too few, or
+///   colinear).
+pub fn approx_principal_curvatures<const N: usize, I>(
+    boundary: &Boundary<N>,
+    btree: &I,
+    k: usize,
+) -> Vec<Option<PrincipalCurvatures<N>>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let m = N - 1;
+    let n_terms = m * (m + 1) / 2 + m;
+    assert!(
+        k > n_terms,
+        "k must exceed {n_terms} to fit a quadric! Got: {k}"
+    );
+
+    boundary
+        .iter()
+        .map(|hs| {
+            let tangents = tangent_basis(&hs.n);
+            let neighbors = btree.nearest_neighbors(&hs.b.into(), k);
+            if neighbors.len() <= n_terms {
+                return None;
+            }
+
+            // h = 1/2 u^T S u + g^T u, with the height h below the tangent plane
+            let mut a = DMatrix::zeros(neighbors.len(), n_terms);
+            let mut h = DVector::zeros(neighbors.len());
+            for (row, node) in neighbors.iter().enumerate() {
+                let s = SVector::<f64, N>::from(*node.geom()) - *hs.b;
+                let u: Vec<f64> = tangents.iter().map(|t| t.dot(&s)).collect();
+                let mut col = 0;
+                for i in 0..m {
+                    for j in i..m {
+                        a[(row, col)] = if i == j {
+                            0.5 * u[i] * u[i]
+                        } else {
+                            u[i] * u[j]
+                        };
+                        col += 1;
+                    }
+                }
+                for ui in u.iter() {
+                    a[(row, col)] = *ui;
+                    col += 1;
+                }
+                h[row] = -hs.n.dot(&s);
+            }
+
+            let svd = a.svd(true, true);
+            if svd.rank(1e-12) < n_terms {
+                return None;
+            }
+            let coeffs = svd.solve(&h, 1e-12).ok()?;
+
+            let mut shape = DMatrix::zeros(m, m);
+            let mut col = 0;
+            for i in 0..m {
+                for j in i..m {
+                    shape[(i, j)] = coeffs[col];
+                    shape[(j, i)] = coeffs[col];
+                    col += 1;
+                }
+            }
+
+            let eigen = SymmetricEigen::new(shape);
+            let mut order: Vec<usize> = (0..m).collect();
+            order.sort_by(|&a, &b| {
+                eigen.eigenvalues[a]
+                    .partial_cmp(&eigen.eigenvalues[b])
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            Some(PrincipalCurvatures {
+                curvatures: order.iter().map(|&i| eigen.eigenvalues[i]).collect(),
+                directions: order
+                    .iter()
+                    .map(|&i| {
+                        eigen
+                            .eigenvectors
+                            .column(i)
+                            .iter()
+                            .zip(tangents.iter())
+                            .map(|(c, t)| *c * t)
+                            .sum::<SVector<f64, N>>()
+                            .normalize()
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// An orthonormal basis of the N - 1 dimensional plane orthogonal to @n.
+fn tangent_basis<const N: usize>(n: &SVector<f64, N>) -> Vec<SVector<f64, N>> {
+    let n = n.normalize();
+    let mut basis: Vec<SVector<f64, N>> = vec![];
+    for i in 0..N {
+        let mut v = SVector::<f64, N>::zeros();
+        v[i] = 1.0;
+        v -= v.dot(&n) * n;
+        for b in basis.iter() {
+            v -= v.dot(b) * b;
+        }
+        if let Some(v) = v.try_normalize(1e-6) {
+            basis.push(v);
+        }
+        if basis.len() == N - 1 {
+            break;
+        }
+    }
+
+    basis
+}
+
+#[cfg(test)]
+mod smooth_surface_vectors_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Halfspace, WithinMode},
+        test_utils::sphere_boundary,
+    };
+
+    use super::smooth_surface_vectors;
+
+    /// Halfspaces on a sphere of radius 0.3, from the Fibonacci lattice, whose
+    /// surface vectors are tilted by up to ~15 degrees.
+    fn noisy_sphere(n: usize) -> (Vec<Halfspace<3>>, Vec<SVector<f64, 3>>) {
+        sphere_boundary(SVector::repeat(0.5), 0.3, n)
+            .into_iter()
+            .enumerate()
+            .map(|(i, hs)| {
+                let noise = 0.25 * vector![(i as f64 * 1.7).sin(), (i as f64 * 2.3).cos(), 0.0];
+                let true_n = hs.n;
+                let hs = Halfspace {
+                    b: hs.b,
+                    n: (true_n + noise).normalize(),
+                };
+                (hs, true_n)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn reduces_angular_error_on_sphere() {
+        let (boundary, true_normals) = noisy_sphere(1000);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let smoothed = smooth_surface_vectors(&boundary, &btree, 8);
+
+        let mean_err = |hss: &[Halfspace<3>]| {
+            hss.iter()
+                .zip(true_normals.iter())
+                .map(|(hs, n)| hs.n.angle(n))
+                .sum::<f64>()
+                / hss.len() as f64
+        };
+        let before = mean_err(&boundary);
+        let after = mean_err(&smoothed);
+        assert!(
+            after < before / 3.0,
+            "Insufficient improvement: {before} -> {after}"
+        );
+        assert!(smoothed
+            .iter()
+            .zip(boundary.iter())
+            .all(|(s, hs)| s.b == hs.b && (s.n.norm() - 1.0).abs() < 1e-10));
+    }
+
+    #[test]
+    fn colinear_neighbors_are_unchanged() {
+        let boundary: Vec<Halfspace<3>> = (0..5)
+            .map(|i| Halfspace {
+                b: WithinMode(vector![0.1 * i as f64, 0.5, 0.5]),
+                n: vector![0.0, 0.6, 0.8],
+            })
+            .collect();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        assert_eq!(smooth_surface_vectors(&boundary, &btree, 3), boundary);
+    }
+}
+
 #[cfg(all(test, feature = "sps"))]
 mod approx_surface {
     use std::f64::consts::PI;
@@ -341,11 +1189,18 @@ mod approx_surface {
 
 #[cfg(test)]
 mod approx_mode_prediction {
-    use nalgebra::SVector;
+    use nalgebra::{vector, SVector};
 
     use crate::{
-        boundary_tools::estimation::is_behind_halfspace,
+        boundary_tools::{
+            estimation::{
+                approx_prediction, approx_weighted_prediction, is_behind_halfspace,
+                NeighborWeighting, TieBreak,
+            },
+            get_rtree_from_boundary,
+        },
         prelude::{Halfspace, WithinMode},
+        test_utils::circle_boundary,
     };
 
     #[test]
@@ -367,4 +1222,222 @@ mod approx_mode_prediction {
             "False negative prediction for a out-of-mode point."
         )
     }
+
+    /// An envelope with a circular bite taken out of it, i.e. WithinMode outside of
+    /// the circle, whose boundary is concave.
+    fn concave_boundary() -> Vec<Halfspace<2>> {
+        circle_boundary(vector![0.5, 0.5], 0.2, 100)
+            .into_iter()
+            .map(|hs| Halfspace { b: hs.b, n: -hs.n })
+            .collect()
+    }
+
+    #[test]
+    fn weighted_prediction_reduces_false_negatives_in_concave_region() {
+        let boundary = concave_boundary();
+        let btree = get_rtree_from_boundary(&boundary);
+        let k = 30;
+
+        let mut unanimous_errors = 0;
+        let mut weighted_errors = 0;
+        for i in 0..40 {
+            for j in 0..40 {
+                let p = vector![0.2 + 0.6 * i as f64 / 39.0, 0.2 + 0.6 * j as f64 / 39.0];
+                let r = (p - vector![0.5, 0.5]).norm();
+                if (r - 0.2).abs() < 0.01 {
+                    continue;
+                }
+                let expected = r > 0.2;
+
+                if approx_prediction(p, &boundary, &btree, k).class() != expected {
+                    unanimous_errors += 1;
+                }
+                let cls = approx_weighted_prediction(
+                    p,
+                    &boundary,
+                    &btree,
+                    k,
+                    NeighborWeighting::InverseDistance,
+                    TieBreak::Nearest,
+                )
+                .class();
+                if cls != expected {
+                    weighted_errors += 1;
+                }
+            }
+        }
+
+        assert!(
+            weighted_errors * 4 < unanimous_errors,
+            "Expected fewer errors: {weighted_errors} vs {unanimous_errors}"
+        );
+    }
+
+    #[test]
+    fn unanimous_weighting_matches_approx_prediction() {
+        let boundary = concave_boundary();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        for i in 0..100 {
+            let p = vector![0.25 + 0.005 * i as f64, 0.5];
+            assert_eq!(
+                approx_prediction(p, &boundary, &btree, 5).class(),
+                approx_weighted_prediction(
+                    p,
+                    &boundary,
+                    &btree,
+                    5,
+                    NeighborWeighting::Unanimous,
+                    TieBreak::Nearest
+                )
+                .class()
+            );
+        }
+    }
+
+    #[test]
+    fn tie_break_resolves_even_votes() {
+        // Two opposing halfspaces equidistant from p, as on either side of a
+        // thin envelope.
+        let boundary = [
+            Halfspace {
+                b: WithinMode(vector![0.4, 0.5]),
+                n: vector![1.0, 0.0],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.6, 0.5]),
+                n: vector![1.0, 0.0],
+            },
+        ];
+        let btree = get_rtree_from_boundary(&boundary);
+        let p = vector![0.5, 0.5];
+        let predict = |tie_break| {
+            approx_weighted_prediction(
+                p,
+                &boundary,
+                &btree,
+                2,
+                NeighborWeighting::Gaussian(0.1),
+                tie_break,
+            )
+            .class()
+        };
+
+        assert!(predict(TieBreak::WithinMode));
+        assert!(!predict(TieBreak::OutOfMode));
+    }
+}
+
+#[cfg(test)]
+mod principal_curvature_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Halfspace, WithinMode},
+        test_utils::sphere_boundary,
+    };
+
+    use super::approx_principal_curvatures;
+
+    const K: usize = 20;
+
+    /// Halfspaces on a grid of the surface z = f(x, y) around the origin, facing +z.
+    fn height_field(f: impl Fn(f64, f64) -> f64) -> Vec<Halfspace<3>> {
+        let mut boundary = vec![];
+        for i in -10..=10 {
+            for j in -10..=10 {
+                let (x, y) = (i as f64 * 0.02, j as f64 * 0.02);
+                let eps = 1e-6;
+                let dx = (f(x + eps, y) - f(x - eps, y)) / (2.0 * eps);
+                let dy = (f(x, y + eps) - f(x, y - eps)) / (2.0 * eps);
+                boundary.push(Halfspace {
+                    b: WithinMode(vector![x, y, f(x, y)]),
+                    n: vector![-dx, -dy, 1.0].normalize(),
+                });
+            }
+        }
+        boundary
+    }
+
+    fn center(boundary: &[Halfspace<3>]) -> usize {
+        boundary
+            .iter()
+            .position(|hs| hs.b[0].abs() < 1e-9 && hs.b[1].abs() < 1e-9)
+            .unwrap()
+    }
+
+    #[test]
+    fn sphere_has_equal_curvatures() {
+        let radius = 0.3;
+        let n = 2000;
+        let boundary = sphere_boundary(SVector::repeat(0.5), radius, n);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let curvatures = approx_principal_curvatures(&boundary, &btree, K);
+
+        for (hs, c) in boundary.iter().zip(curvatures.iter()) {
+            let c = c.as_ref().unwrap();
+            assert_eq!(c.curvatures.len(), 2);
+            assert!(
+                c.curvatures.iter().all(|k| (k - 1.0 / radius).abs() < 0.1),
+                "Bad curvatures: {:?}",
+                c.curvatures
+            );
+            assert!(c.directions.iter().all(|d| d.dot(&hs.n).abs() < 1e-9));
+            assert!(!c.is_saddle(0.5) && !c.is_ridge(0.5));
+        }
+    }
+
+    #[test]
+    fn detects_saddle() {
+        let boundary = height_field(|x, y| x * x - y * y);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let curvatures = approx_principal_curvatures(&boundary, &btree, K);
+        let c = curvatures[center(&boundary)].as_ref().unwrap();
+
+        // h = -(x^2 - y^2), so bending away from +z along y
+        assert!((c.curvatures[0] + 2.0).abs() < 1e-6, "{:?}", c.curvatures);
+        assert!((c.curvatures[1] - 2.0).abs() < 1e-6, "{:?}", c.curvatures);
+        assert!(c.directions[0].dot(&vector![1.0, 0.0, 0.0]).abs() > 1.0 - 1e-6);
+        assert!(c.is_saddle(1.0));
+        assert!(c.gaussian_curvature() < 0.0);
+        assert!(c.mean_curvature().abs() < 1e-6);
+    }
+
+    #[test]
+    fn detects_ridge() {
+        let radius = 0.1;
+        let boundary = height_field(|x, _| (radius * radius - x * x).max(0.0).sqrt() - radius);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let curvatures = approx_principal_curvatures(&boundary, &btree, K);
+        let c = curvatures[center(&boundary)].as_ref().unwrap();
+
+        assert!(c.curvatures[0].abs() < 0.1, "{:?}", c.curvatures);
+        assert!(
+            (c.curvatures[1] - 1.0 / radius).abs() < 0.5,
+            "{:?}",
+            c.curvatures
+        );
+        assert!(c.directions[1].dot(&vector![1.0, 0.0, 0.0]).abs() > 0.99);
+        assert!(c.is_ridge(1.0));
+        assert!(!c.is_saddle(1.0));
+    }
+
+    #[test]
+    fn colinear_neighbors_have_no_curvature() {
+        let boundary: Vec<Halfspace<3>> = (0..30)
+            .map(|i| Halfspace {
+                b: WithinMode(vector![0.01 * i as f64, 0.5, 0.5]),
+                n: vector![0.0, 0.0, 1.0],
+            })
+            .collect();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        assert!(approx_principal_curvatures(&boundary, &btree, K)
+            .iter()
+            .all(|c| c.is_none()));
+    }
 }
@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+
+use nalgebra::{Vector2, Vector3};
+
+use crate::prelude::Boundary;
+
+/// Points within this distance of a hull facet's plane are considered on it.
+const HULL_EPSILON: f64 = 1e-12;
+
+/// Computes the area of the convex hull of a 2D boundary's points.
+///
+/// For a convex envelope, the hull is the envelope up to the boundary error, so
+/// its area needs neither a classifier nor MC samples. For non-convex envelopes
+/// the hull overestimates the area; see `triangulation::polyline()` and
+/// `CurveMesh::area()`.
+/// ## Arguments
+/// * boundary: The boundary whose points span the hull.
+/// ## Returns
+/// * area: The hull's area, or 0.0 if the points are collinear.
+pub fn convex_hull_area(boundary: &Boundary<2>) -> f64 {
+    let mut points: Vec<Vector2<f64>> = boundary.iter().map(|hs| *hs.b).collect();
+    points.sort_unstable_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+
+    if points.len() < 3 {
+        return 0.0;
+    }
+
+    // Andrew's monotone chain
+    let cross = |o: &Vector2<f64>, a: &Vector2<f64>, b: &Vector2<f64>| (a - o).perp(&(b - o));
+    let mut hull: Vec<Vector2<f64>> = Vec::with_capacity(2 * points.len());
+    let chains: [Box<dyn Iterator<Item = &Vector2<f64>>>; 2] =
+        [Box::new(points.iter()), Box::new(points.iter().rev())];
+    for chain in chains {
+        let start = hull.len();
+        for p in chain {
+            while hull.len() >= start + 2
+                && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(*p);
+        }
+        // The last point of each chain starts the next
+        hull.pop();
+    }
+
+    hull.iter()
+        .zip(hull.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp(b) / 2.0)
+        .sum::<f64>()
+        .abs()
+}
+
+/// Computes the volume of the convex hull of a 3D boundary's points by
+/// incremental construction. See `convex_hull_area()`.
+/// ## Arguments
+/// * boundary: The boundary whose points span the hull.
+/// ## Returns
+/// * volume: The hull's volume, or 0.0 if the points are coplanar.
+pub fn convex_hull_volume(boundary: &Boundary<3>) -> f64 {
+    let points: Vec<Vector3<f64>> = boundary.iter().map(|hs| *hs.b).collect();
+    let Some(initial) = initial_simplex(&points) else {
+        return 0.0;
+    };
+
+    let interior = initial.iter().map(|&i| points[i]).sum::<Vector3<f64>>() / 4.0;
+    let outward = |[a, b, c]: [usize; 3]| {
+        let n = (points[b] - points[a]).cross(&(points[c] - points[a]));
+        if n.dot(&(points[a] - interior)) < 0.0 {
+            [a, c, b]
+        } else {
+            [a, b, c]
+        }
+    };
+    let [i0, i1, i2, i3] = initial;
+    let mut faces: Vec<[usize; 3]> = [[i0, i1, i2], [i0, i1, i3], [i0, i2, i3], [i1, i2, i3]]
+        .into_iter()
+        .map(outward)
+        .collect();
+
+    for (i, p) in points.iter().enumerate() {
+        if initial.contains(&i) {
+            continue;
+        }
+
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) =
+            faces.into_iter().partition(|&[a, b, c]| {
+                let n = (points[b] - points[a]).cross(&(points[c] - points[a]));
+                n.dot(&(p - points[a])) > HULL_EPSILON * n.norm()
+            });
+        faces = hidden;
+
+        // The horizon is made of the visible faces' edges that border hidden faces
+        let edges: HashSet<(usize, usize)> = visible
+            .iter()
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .collect();
+        faces.extend(
+            edges
+                .iter()
+                .filter(|&&(a, b)| !edges.contains(&(b, a)))
+                .map(|&(a, b)| [a, b, i]),
+        );
+    }
+
+    faces
+        .iter()
+        .map(|&[a, b, c]| {
+            let (a, b, c) = (
+                points[a] - interior,
+                points[b] - interior,
+                points[c] - interior,
+            );
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+/// Finds four points spanning a non-degenerate tetrahedron, or None if the
+/// points are coplanar.
+fn initial_simplex(points: &[Vector3<f64>]) -> Option<[usize; 4]> {
+    let farthest = |f: &dyn Fn(&Vector3<f64>) -> f64| {
+        (0..points.len()).max_by(|&i, &j| f(&points[i]).total_cmp(&f(&points[j])))
+    };
+
+    let i0 = farthest(&|p| -p[0])?;
+    let i1 = farthest(&|p| (p - points[i0]).norm())?;
+    let axis = (points[i1] - points[i0]).try_normalize(HULL_EPSILON)?;
+    let i2 = farthest(&|p| (p - points[i0]).cross(&axis).norm())?;
+    let normal = axis
+        .cross(&(points[i2] - points[i0]))
+        .try_normalize(HULL_EPSILON)?;
+    let i3 = farthest(&|p| normal.dot(&(p - points[i0])).abs())?;
+
+    if normal.dot(&(points[i3] - points[i0])).abs() <= HULL_EPSILON {
+        None
+    } else {
+        Some([i0, i1, i2, i3])
+    }
+}
+
+#[cfg(test)]
+mod hull_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        prelude::{Halfspace, WithinMode},
+        test_utils::{circle_boundary, sphere_boundary},
+    };
+
+    use super::{convex_hull_area, convex_hull_volume};
+
+    fn hs<const N: usize>(b: SVector<f64, N>) -> Halfspace<N> {
+        Halfspace {
+            b: WithinMode(b),
+            n: SVector::zeros(),
+        }
+    }
+
+    #[test]
+    fn square_hull_area_is_exact() {
+        // The square's corners, edges and some interior points
+        let boundary: Vec<Halfspace<2>> = (0..=10)
+            .flat_map(|i| (0..=10).map(move |j| vector![i as f64 * 0.05, j as f64 * 0.03]))
+            .map(|p| hs(p + vector![0.2, 0.3]))
+            .collect();
+
+        assert!((convex_hull_area(&boundary) - 0.5 * 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn circle_hull_area_converges() {
+        let boundary = circle_boundary(vector![0.5, 0.5], 0.3, 500);
+
+        let area = PI * 0.3 * 0.3;
+        assert!((convex_hull_area(&boundary) - area).abs() / area < 1e-3);
+    }
+
+    #[test]
+    fn cube_hull_volume_is_exact() {
+        let boundary: Vec<Halfspace<3>> = (0..125)
+            .map(|i| vector![(i % 5) as f64, ((i / 5) % 5) as f64, (i / 25) as f64])
+            .map(|p| hs(vector![0.1, 0.2, 0.3] + p.component_mul(&vector![0.1, 0.15, 0.05])))
+            .collect();
+
+        let volume = convex_hull_volume(&boundary);
+        assert!(
+            (volume - 0.4 * 0.6 * 0.2).abs() < 1e-12,
+            "Bad volume: {volume}"
+        );
+    }
+
+    #[test]
+    fn sphere_hull_volume_converges() {
+        let boundary = sphere_boundary(vector![0.5, 0.5, 0.5], 0.3, 1000);
+
+        let volume = 4.0 / 3.0 * PI * 0.3f64.powi(3);
+        let hull = convex_hull_volume(&boundary);
+        assert!(hull < volume);
+        assert!((hull - volume).abs() / volume < 0.01, "Bad volume: {hull}");
+    }
+
+    #[test]
+    fn degenerate_hulls_are_empty() {
+        let line: Vec<Halfspace<2>> = (0..10).map(|i| hs(vector![i as f64, i as f64])).collect();
+        let plane: Vec<Halfspace<3>> = (0..10)
+            .map(|i| hs(vector![i as f64, (i * i) as f64, 1.0]))
+            .collect();
+
+        assert_eq!(convex_hull_area(&line), 0.0);
+        assert_eq!(convex_hull_volume(&plane), 0.0);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use nalgebra::SVector;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Boundary, Halfspace, WithinMode};
+
+/// The current version of the boundary file schema. Files without a version
+/// predate versioning and are read as version 0, which shares the same layout.
+pub const BOUNDARY_SCHEMA_VERSION: u32 = 1;
+
+/// The JSON layout of a saved boundary. The boundary points and surface vectors
+/// are stored as separate lists of plain arrays, so the files can be read without
+/// SEMBAS, e.g. by numpy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundaryData {
+    #[serde(default)]
+    pub version: u32,
+    /// The dimensionality of the boundary, or None for unversioned files.
+    #[serde(default)]
+    pub ndim: Option<usize>,
+    pub boundary_points: Vec<Vec<f64>>,
+    pub boundary_surface: Vec<Vec<f64>>,
+}
+
+impl BoundaryData {
+    /// Creates the saved form of @boundary.
+    pub fn new<const N: usize>(boundary: &Boundary<N>) -> Self {
+        let (boundary_points, boundary_surface) = boundary
+            .iter()
+            .map(|hs| {
+                (
+                    hs.b.iter().copied().collect(),
+                    hs.n.iter().copied().collect(),
+                )
+            })
+            .unzip();
+
+        BoundaryData {
+            version: BOUNDARY_SCHEMA_VERSION,
+            ndim: Some(N),
+            boundary_points,
+            boundary_surface,
+        }
+    }
+
+    /// Converts the data back into a boundary.
+    /// ## Error (Err)
+    /// * Returns an InvalidData error if the schema version is newer than
+    ///   supported, or if the data is not of dimension N.
+    pub fn into_boundary<const N: usize>(self) -> io::Result<Vec<Halfspace<N>>> {
+        if self.version > BOUNDARY_SCHEMA_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported boundary schema version {}, expected at most {BOUNDARY_SCHEMA_VERSION}",
+                self.version
+            )));
+        }
+        if let Some(ndim) = self.ndim.filter(|&ndim| ndim != N) {
+            return Err(invalid_data(format!(
+                "Boundary has {ndim} dimensions, expected {N}"
+            )));
+        }
+        if self.boundary_points.len() != self.boundary_surface.len() {
+            return Err(invalid_data(format!(
+                "Boundary has {} points but {} surface vectors",
+                self.boundary_points.len(),
+                self.boundary_surface.len()
+            )));
+        }
+
+        self.boundary_points
+            .iter()
+            .zip(self.boundary_surface.iter())
+            .map(|(b, n)| {
+                Ok(Halfspace {
+                    b: WithinMode(to_vector(b)?),
+                    n: to_vector(n)?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn to_vector<const N: usize>(x: &[f64]) -> io::Result<SVector<f64, N>> {
+    if x.len() != N {
+        return Err(invalid_data(format!(
+            "Boundary vector has {} dimensions, expected {N}",
+            x.len()
+        )));
+    }
+
+    Ok(SVector::from_column_slice(x))
+}
+
+/// Saves @boundary to @path as JSON (see `BoundaryData`), creating any missing
+/// parent directories and replacing an existing file.
+pub fn save_boundary<const N: usize>(path: &str, boundary: &Boundary<N>) -> io::Result<()> {
+    let path = Path::new(path);
+    if let Some(prefix) = path.parent() {
+        fs::create_dir_all(prefix)?;
+    }
+
+    let f = File::create(path)?;
+    let mut writer = BufWriter::new(f);
+    serde_json::to_writer_pretty(&mut writer, &BoundaryData::new(boundary))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads a boundary saved by `save_boundary()`, or by earlier versions of it.
+/// ## Error (Err)
+/// * Returns an InvalidData error if the file is not a boundary of dimension N.
+pub fn load_boundary<const N: usize>(path: &str) -> io::Result<Vec<Halfspace<N>>> {
+    let f = File::open(path)?;
+    let data: BoundaryData = serde_json::from_reader(BufReader::new(f))?;
+    data.into_boundary()
+}
+
+#[cfg(test)]
+mod boundary_io_tests {
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("sembas_boundary_io_{}_{name}", std::process::id()))
+            .join("boundary.json")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn boundary() -> Vec<Halfspace<3>> {
+        vec![
+            Halfspace {
+                b: WithinMode(vector![0.1, 0.2, 0.3]),
+                n: vector![1.0, 0.0, 0.0],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.4, 0.5, 0.6]),
+                n: vector![0.0, 0.6, 0.8],
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trips_boundary() {
+        let path = temp_path("round_trip");
+        save_boundary(&path, &boundary()).expect("Failed to save");
+
+        let loaded: Vec<Halfspace<3>> = load_boundary(&path).expect("Failed to load");
+        assert_eq!(loaded, boundary());
+    }
+
+    #[test]
+    fn rejects_wrong_dimension() {
+        let path = temp_path("wrong_dim");
+        save_boundary(&path, &boundary()).expect("Failed to save");
+
+        let err = load_boundary::<2>(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn reads_unversioned_data() {
+        let json = r#"{"boundary_points": [[0.1, 0.2]], "boundary_surface": [[0.0, 1.0]]}"#;
+        let data: BoundaryData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.version, 0);
+
+        let boundary = data.into_boundary::<2>().expect("Failed to convert");
+        assert_eq!(*boundary[0].b, vector![0.1, 0.2]);
+
+        let data: BoundaryData = serde_json::from_str(json).unwrap();
+        let err = data.into_boundary::<3>().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let mut data = BoundaryData::new(&boundary());
+        data.version = BOUNDARY_SCHEMA_VERSION + 1;
+
+        assert!(data.into_boundary::<3>().is_err());
+    }
+}
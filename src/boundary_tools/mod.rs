@@ -1,8 +1,37 @@
-use crate::prelude::{Boundary, BoundaryRTree, Halfspace, KnnNode};
+use crate::prelude::{
+    bs_adherer::BinarySearchAdhererFactory, Boundary, BoundaryRTree, Halfspace, KnnNode,
+    MeshExplorer, SpatialIndex,
+};
+use nalgebra::{Const, OMatrix, SVector};
 use rstar::RTree;
 
+/// An expected neighbor farther than this (relative to d) from the nearest known
+/// boundary point is considered missing.
+const HOLE_DISTANCE: f64 = 0.5;
+
+/// The precision of `ray_cast()`, relative to the extent of the boundary.
+const RAY_TOLERANCE: f64 = 1e-9;
+
+/// The number of neighbors that must predict a halfspace for it to be removed by
+/// `decimate()`.
+const DECIMATION_K: usize = 4;
+
+pub mod boundary_set;
+pub mod comparison;
 pub mod estimation;
+pub mod hull;
+#[cfg(feature = "io")]
+pub mod io;
+pub mod optimization;
+pub mod perturbation;
+pub mod projection;
 pub mod reacquisition;
+pub mod triangulation;
+pub mod upsampling;
+
+pub use boundary_set::BoundarySet;
+pub use comparison::{diff, BoundaryDiff};
+pub use upsampling::upsample;
 
 /// Converts a boundary into an RTree. This is useful when many K-nearest neighbor
 /// searches are needed.
@@ -27,6 +56,157 @@ pub fn bulk_insert_rtree<const N: usize>(btree: &mut BoundaryRTree<N>, boundary:
     }
 }
 
+/// Merges several boundaries into one, dropping halfspaces that duplicate an
+/// already merged halfspace, and builds the RTree of the result. Guarantees that
+/// the RTree's indices refer to the merged boundary.
+/// ## Arguments
+/// * boundaries : The boundaries to merge, e.g. of overlapping explorations of the
+///   same envelope. Earlier boundaries take precedence over later duplicates.
+/// * tol : The distance within which a halfspace duplicates another. Halfspaces
+///   facing opposite directions are never duplicates, e.g. either side of a thin
+///   envelope.
+/// ## Return
+/// * (boundary, rtree) : The merged boundary, in the order given, and its RTree.
+pub fn merge<const N: usize>(
+    boundaries: &[&Boundary<N>],
+    tol: f64,
+) -> (Vec<Halfspace<N>>, BoundaryRTree<N>) {
+    let mut merged = BoundarySet::new();
+    for boundary in boundaries {
+        merged.merge(boundary, tol);
+    }
+
+    merged.into_parts()
+}
+
+/// Approximates the signed distance from @p to the boundary, as its distance from
+/// the tangent plane of the nearest halfspace. Unlike `approx_prediction()`, gives
+/// a graded margin rather than a binary class. Accurate near the boundary, but
+/// loses accuracy with distance from a curved boundary.
+/// ## Arguments
+/// * p : The point to measure.
+/// * boundary : The explored boundary.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
+/// ## Returns
+/// * Some(dist) : The signed distance, positive within the envelope (behind the
+///   halfspace) and negative outside of it.
+/// * None : If @boundary is empty.
+pub fn signed_distance<const N: usize, I>(
+    p: &SVector<f64, N>,
+    boundary: &Boundary<N>,
+    btree: &I,
+) -> Option<f64>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let node = btree.nearest_neighbor(&(*p).into())?;
+    let hs = boundary.get(node.data).expect(
+        "Invalid neighbor index used on @boundary. Often a result of @boundary being out of sync or entirely different from @btree."
+    );
+
+    Some(hs.n.dot(&(*hs.b - p)))
+}
+
+/// Approximates the signed distance from each of @points to the boundary. See
+/// `signed_distance()`.
+/// ## Returns
+/// * distances : The signed distance of each point, in the same order as @points.
+///   Empty if @boundary is empty.
+pub fn signed_distances<const N: usize, I>(
+    points: &[SVector<f64, N>],
+    boundary: &Boundary<N>,
+    btree: &I,
+) -> Vec<f64>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    points
+        .iter()
+        .map_while(|p| signed_distance(p, boundary, btree))
+        .collect()
+}
+
+/// Finds the distance along a ray at which it crosses the explored boundary, e.g.
+/// how far an input can be pushed in some direction before leaving the envelope,
+/// without running the classifier. Each point along the ray is classified by the
+/// halfspace nearest to it, so the crossing is that of the piecewise planar surface
+/// formed by the halfspaces.
+///
+/// The ray is marched in steps of half the distance to the nearest boundary point,
+/// jumping ahead to the nearest halfspace's plane when it is within reach, and the
+/// crossing is refined by bisection once the predicted class changes.
+/// ## Arguments
+/// * origin : The start of the ray. May be on either side of the boundary.
+/// * direction : The direction of the ray. Need not be normalized.
+/// * boundary : The explored boundary.
+/// * btree : The spatial index (e.g. RTree) for @boundary.
+/// ## Returns
+/// * Some(t) : The distance from @origin to the first crossing, in units of
+///   @direction's length.
+/// * None : If the ray leaves the bounding box of @boundary without crossing it,
+///   @boundary is empty, or @direction is zero.
+pub fn ray_cast<const N: usize, I>(
+    origin: &SVector<f64, N>,
+    direction: &SVector<f64, N>,
+    boundary: &Boundary<N>,
+    btree: &I,
+) -> Option<f64>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let dir = direction.try_normalize(0.0)?;
+    let first = boundary.first()?;
+    let (low, high) = boundary
+        .iter()
+        .fold((*first.b, *first.b), |(low, high), hs| {
+            (low.inf(&hs.b), high.sup(&hs.b))
+        });
+    let scale = (high - low).norm().max(f64::MIN_POSITIVE);
+    let tol = scale * RAY_TOLERANCE;
+    // The ray can't cross the boundary once it is past the bounding box
+    let t_max = (origin - low).norm().max((origin - high).norm()) + scale;
+
+    let is_within = |t: f64| signed_distance(&(origin + t * dir), boundary, btree).unwrap() > 0.0;
+    let cls = is_within(0.0);
+
+    let mut t = 0.0;
+    while t < t_max {
+        let p = origin + t * dir;
+        let node = btree.nearest_neighbor(&p.into())?;
+        let hs = &boundary[node.data];
+        let r = (*hs.b - p).norm();
+
+        let towards = hs.n.dot(&dir);
+        let to_plane = if towards != 0.0 {
+            hs.n.dot(&(*hs.b - p)) / towards
+        } else {
+            f64::INFINITY
+        };
+        let step = if (0.0..=r).contains(&to_plane) {
+            to_plane + tol
+        } else {
+            (r / 2.0).max(tol)
+        };
+
+        let next = t + step;
+        if is_within(next) != cls {
+            let (mut a, mut b) = (t, next);
+            while b - a > tol {
+                let mid = (a + b) / 2.0;
+                if is_within(mid) == cls {
+                    a = mid;
+                } else {
+                    b = mid;
+                }
+            }
+            return Some((a + b) / 2.0 / direction.norm());
+        }
+        t = next;
+    }
+
+    None
+}
+
 /// Returns true if the provided halfspace @hs is likely to be on the surface of
 /// @boundary. This is an early implementation, and is more of a proof-of-concept
 /// than a robust solution.
@@ -42,12 +222,15 @@ pub fn bulk_insert_rtree<const N: usize>(btree: &mut BoundaryRTree<N>, boundary:
 /// * is_on_boundary : true ifthe halfspace is likely to be on the boundary,
 ///   otherwise false. This is an approximation and may incur false positives and
 ///   negatives. Accuracy improves with density and completeness of @boundary.
-pub fn falls_on_boundary<const N: usize>(
+pub fn falls_on_boundary<const N: usize, I>(
     d: f64,
     hs: &Halfspace<N>,
     boundary: &Boundary<N>,
-    boundary_rtree: &BoundaryRTree<N>,
-) -> bool {
+    boundary_rtree: &I,
+) -> bool
+where
+    I: SpatialIndex<N> + ?Sized,
+{
     // The maximum distance between two points on the boundary.
     let max_dist = d * (N as f64).sqrt();
 
@@ -66,6 +249,420 @@ pub fn falls_on_boundary<const N: usize>(
     }
 }
 
+/// Finds gaps in an explored boundary, i.e. halfspaces whose expected neighbors
+/// (a jump distance @d along each cardinal direction of their surface) are farther
+/// than 0.5 * @d from any known boundary point. The returned halfspaces border these
+/// gaps and can be used as roots to restart exploration and fill them.
+/// ## Warning
+/// * Halfspaces on the edge of the input domain or of a partially explored
+///   envelope will also be reported, since their outward neighbors are missing.
+/// * Performs poorly when @d is not the jump distance the boundary was explored
+///   with.
+/// ## Arguments
+/// * boundary : The explored boundary to search for holes.
+/// * boundary_rtree : The spatial index (e.g. RTree) for @boundary.
+/// * d : The jump distance used to explore @boundary.
+/// ## Returns
+/// * seeds : The halfspaces bordering holes in @boundary, in boundary order.
+pub fn find_holes<const N: usize, I>(
+    boundary: &Boundary<N>,
+    boundary_rtree: &I,
+    d: f64,
+) -> Vec<Halfspace<N>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let basis_vectors = OMatrix::<f64, Const<N>, Const<N>>::identity();
+
+    boundary
+        .iter()
+        .filter(|hs| {
+            MeshExplorer::<N, BinarySearchAdhererFactory<N>>::create_cardinals(hs.n, basis_vectors)
+                .into_iter()
+                .any(|cardinal| {
+                    let expected: SVector<f64, N> = *hs.b + d * cardinal;
+                    boundary_rtree
+                        .nearest_neighbor(&expected.into())
+                        .is_none_or(|node| {
+                            (SVector::from(*node.geom()) - expected).norm() > HOLE_DISTANCE * d
+                        })
+                })
+        })
+        .copied()
+        .collect()
+}
+
+/// How far to decimate a boundary. See `decimate()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecimationTarget {
+    /// Remove halfspaces until this many remain.
+    Count(usize),
+    /// Remove halfspaces while they lie within this distance of the tangent planes
+    /// of their nearest remaining neighbors.
+    Tolerance(f64),
+}
+
+/// Removes redundant halfspaces from a boundary while preserving its geometry, e.g.
+/// to store or plot very large boundaries. A halfspace is redundant when it is
+/// well predicted by its nearest neighbor, i.e. it lies close to the neighbor's
+/// tangent plane. Flat regions are therefore thinned first, while edges and
+/// corners are retained.
+///
+/// Decimation proceeds in passes. Each pass removes the more redundant half of the
+/// halfspaces, most redundant first, while keeping the nearest neighbor of each removed halfspace, such
+/// that no region is emptied within a single pass.
+/// ## Arguments
+/// * boundary : The boundary to decimate.
+/// * target : The number of halfspaces to keep, or the maximum distance between a
+///   removed halfspace and the tangent planes of its nearest remaining neighbors.
+/// ## Returns
+/// * decimated : The retained halfspaces, in boundary order. Tolerance targets may
+///   stop short when no remaining halfspace is within tolerance.
+pub fn decimate<const N: usize>(
+    boundary: &Boundary<N>,
+    target: DecimationTarget,
+) -> Vec<Halfspace<N>> {
+    let (count, tolerance) = match target {
+        DecimationTarget::Count(count) => (count.max(1), f64::INFINITY),
+        DecimationTarget::Tolerance(tolerance) => (1, tolerance),
+    };
+
+    let mut kept: Vec<Halfspace<N>> = boundary.to_vec();
+    while kept.len() > count {
+        let btree = get_rtree_from_boundary(&kept);
+
+        // The error of removing each halfspace, i.e. its farthest distance from the
+        // tangent planes of its neighbors, and the nearest neighbor to keep.
+        let mut errors: Vec<(f64, usize, usize)> = kept
+            .iter()
+            .enumerate()
+            .filter_map(|(i, hs)| {
+                let neighbors: Vec<usize> =
+                    SpatialIndex::nearest_neighbors(&btree, &hs.b.into(), DECIMATION_K + 1)
+                        .into_iter()
+                        .map(|node| node.data)
+                        .filter(|&j| j != i)
+                        .collect();
+                let err = neighbors
+                    .iter()
+                    .map(|&j| kept[j].n.dot(&(*hs.b - *kept[j].b)).abs())
+                    .fold(0.0, f64::max);
+                Some((err, i, *neighbors.first()?))
+            })
+            .collect();
+        errors.sort_by(|a, b| a.0.total_cmp(&b.0));
+        // Only the more redundant half is considered, so that the least redundant
+        // halfspaces outlast the rest over the passes.
+        let cutoff = errors
+            .get(errors.len() / 2)
+            .map_or(tolerance, |e| e.0.min(tolerance));
+
+        let mut removed = vec![false; kept.len()];
+        let mut locked = vec![false; kept.len()];
+        let mut n_remaining = kept.len();
+        for (err, i, j) in errors {
+            if err > cutoff || n_remaining <= count {
+                break;
+            }
+            if locked[i] || removed[j] {
+                continue;
+            }
+            removed[i] = true;
+            locked[j] = true;
+            n_remaining -= 1;
+        }
+
+        if n_remaining == kept.len() {
+            break;
+        }
+        kept = kept
+            .into_iter()
+            .zip(removed)
+            .filter_map(|(hs, removed)| (!removed).then_some(hs))
+            .collect();
+    }
+
+    kept
+}
+
+/// Partitions a boundary into its connected components, e.g. to analyze each of
+/// the envelopes found by a multi-root exploration separately. Two halfspaces are
+/// connected if their boundary points are within @d * sqrt(N) of each other (the
+/// maximum distance between neighboring boundary points) and their surface vectors
+/// do not face opposite directions, so that nearby envelopes are kept apart.
+/// ## Arguments
+/// * boundary : The combined boundary to partition.
+/// * boundary_rtree : The spatial index (e.g. RTree) for @boundary.
+/// * d : The jump distance used to explore @boundary.
+/// ## Returns
+/// * envelopes : The boundary of each envelope, ordered by their first halfspace
+///   in @boundary. Each retains the order of @boundary.
+pub fn cluster_envelopes<const N: usize, I>(
+    boundary: &Boundary<N>,
+    boundary_rtree: &I,
+    d: f64,
+) -> Vec<BoundarySet<N>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let max_dist = d * (N as f64).sqrt();
+
+    // Union-find over boundary indices
+    let mut parent: Vec<usize> = (0..boundary.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for (i, hs) in boundary.iter().enumerate() {
+        for node in boundary_rtree.within_distance(&hs.b.into(), max_dist) {
+            let j = node.data;
+            if j > i && hs.n.dot(&boundary[j].n) >= 0.0 {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                // The earliest halfspace is the root, keeping envelopes in order
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+
+    let mut envelopes: Vec<BoundarySet<N>> = vec![];
+    let mut envelope_of_root: Vec<Option<usize>> = vec![None; boundary.len()];
+    for (i, hs) in boundary.iter().enumerate() {
+        let r = root(&mut parent, i);
+        let id = *envelope_of_root[r].get_or_insert_with(|| {
+            envelopes.push(BoundarySet::new());
+            envelopes.len() - 1
+        });
+        envelopes[id].insert(*hs);
+    }
+
+    envelopes
+}
+
+#[cfg(test)]
+mod cluster_envelopes_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::test_utils::circle_boundary;
+
+    use super::*;
+
+    const D: f64 = 0.02;
+
+    fn circle(center: SVector<f64, 2>, radius: f64) -> Vec<Halfspace<2>> {
+        circle_boundary(center, radius, (2.0 * PI * radius / D).ceil() as usize)
+    }
+
+    #[test]
+    fn separates_distant_envelopes() {
+        let a = circle(vector![0.25, 0.5], 0.15);
+        let b = circle(vector![0.75, 0.5], 0.1);
+        // Interleaved, as from overlapping explorations
+        let boundary: Vec<Halfspace<2>> = a
+            .iter()
+            .zip(b.iter())
+            .flat_map(|(ha, hb)| [*ha, *hb])
+            .chain(a[b.len()..].iter().copied())
+            .collect();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let envelopes = cluster_envelopes(&boundary, &btree, D);
+
+        assert_eq!(envelopes.len(), 2);
+        assert_eq!(envelopes[0].len(), a.len());
+        assert_eq!(envelopes[1].len(), b.len());
+        assert_eq!(envelopes[0].boundary()[0], a[0]);
+        assert_eq!(envelopes[1].boundary()[0], b[0]);
+    }
+
+    #[test]
+    fn separates_nearby_envelopes_facing_each_other() {
+        // A gap of less than d between the envelopes
+        let a = circle(vector![0.3, 0.5], 0.195);
+        let b = circle(vector![0.7, 0.5], 0.195);
+        let boundary: Vec<Halfspace<2>> = a.iter().chain(b.iter()).copied().collect();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let envelopes = cluster_envelopes(&boundary, &btree, D);
+
+        assert_eq!(envelopes.len(), 2);
+        assert_eq!(envelopes[0].boundary(), a.as_slice());
+        assert_eq!(envelopes[1].boundary(), b.as_slice());
+    }
+
+    #[test]
+    fn keeps_single_envelope_together() {
+        let boundary = circle(vector![0.5, 0.5], 0.3);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let envelopes = cluster_envelopes(&boundary, &btree, D);
+
+        assert_eq!(envelopes.len(), 1);
+        assert_eq!(envelopes[0].len(), boundary.len());
+        assert!(cluster_envelopes(&[], &BoundaryRTree::<2>::new(), D).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use nalgebra::vector;
+
+    use crate::prelude::WithinMode;
+
+    use super::*;
+
+    fn hs(x: f64, y: f64, nx: f64) -> Halfspace<2> {
+        Halfspace {
+            b: WithinMode(vector![x, y]),
+            n: vector![nx, 0.0],
+        }
+    }
+
+    #[test]
+    fn drops_duplicates_and_keeps_index_consistent() {
+        let b1 = vec![hs(0.5, 0.1, 1.0), hs(0.5, 0.2, 1.0)];
+        // The first is a near duplicate of b1's second, the last faces away
+        let b2 = vec![hs(0.501, 0.2, 1.0), hs(0.5, 0.3, 1.0), hs(0.5, 0.3, -1.0)];
+
+        let (merged, btree) = merge(&[&b1, &b2], 0.01);
+
+        assert_eq!(
+            merged,
+            vec![b1[0], b1[1], b2[1], b2[2]],
+            "Unexpected merged boundary"
+        );
+        assert_eq!(btree.size(), merged.len());
+        for node in btree.iter() {
+            assert_eq!(*node.geom(), <[f64; 2]>::from(merged[node.data].b));
+        }
+    }
+
+    #[test]
+    fn zero_tolerance_drops_only_exact_duplicates() {
+        let b1 = vec![hs(0.5, 0.1, 1.0), hs(0.5, 0.1, 1.0), hs(0.5, 0.1001, 1.0)];
+
+        let (merged, _) = merge(&[&b1], 0.0);
+
+        assert_eq!(merged, vec![b1[0], b1[2]]);
+    }
+}
+
+#[cfg(test)]
+mod signed_distance_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::test_utils::circle_boundary;
+
+    use super::*;
+
+    const RADIUS: f64 = 0.3;
+
+    fn circle(n: usize) -> Vec<Halfspace<2>> {
+        circle_boundary(SVector::repeat(0.5), RADIUS, n)
+    }
+
+    #[test]
+    fn approximates_distance_to_circle() {
+        let boundary = circle(500);
+        let btree = get_rtree_from_boundary(&boundary);
+
+        for (dist, angle) in [(0.1, 0.3f64), (0.02, 1.0), (-0.05, 2.0), (-0.1, 4.0)] {
+            let p = SVector::repeat(0.5) + (RADIUS - dist) * vector![angle.cos(), angle.sin()];
+            let approx = signed_distance(&p, &boundary, &btree).unwrap();
+            assert!(
+                (approx - dist).abs() < 1e-3,
+                "Expected ~{dist}, got {approx}"
+            );
+        }
+    }
+
+    #[test]
+    fn batched_matches_individual() {
+        let boundary = circle(100);
+        let btree = get_rtree_from_boundary(&boundary);
+        let points: Vec<SVector<f64, 2>> = (0..10).map(|i| vector![0.1 * i as f64, 0.5]).collect();
+
+        let distances = signed_distances(&points, &boundary, &btree);
+
+        assert_eq!(distances.len(), points.len());
+        for (p, d) in points.iter().zip(distances) {
+            assert_eq!(signed_distance(p, &boundary, &btree), Some(d));
+        }
+        assert!(signed_distances(&points, &[], &BoundaryRTree::new()).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod ray_cast_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::test_utils::sphere_boundary;
+
+    use super::*;
+
+    const RADIUS: f64 = 0.3;
+
+    fn sphere(n: usize) -> Vec<Halfspace<3>> {
+        sphere_boundary(SVector::repeat(0.5), RADIUS, n)
+    }
+
+    #[test]
+    fn finds_exit_from_inside() {
+        let boundary = sphere(2000);
+        let btree = get_rtree_from_boundary(&boundary);
+        let center = SVector::repeat(0.5);
+
+        for v in [
+            vector![1.0, 0.0, 0.0],
+            vector![0.3, -0.2, 0.9],
+            vector![-1.0, -1.0, -1.0],
+        ] {
+            let t = ray_cast(&center, &v, &boundary, &btree).unwrap() * v.norm();
+            assert!((t - RADIUS).abs() < 0.01, "Expected ~{RADIUS}, got {t}");
+        }
+    }
+
+    #[test]
+    fn finds_entry_from_outside() {
+        let boundary = sphere(2000);
+        let btree = get_rtree_from_boundary(&boundary);
+        let origin = vector![0.0, 0.5, 0.5];
+
+        // In units of the direction's length
+        let t = ray_cast(&origin, &vector![2.0, 0.0, 0.0], &boundary, &btree).unwrap();
+        assert!((t - 0.1).abs() < 0.005, "Expected ~0.1, got {t}");
+    }
+
+    #[test]
+    fn misses_return_none() {
+        let boundary = sphere(500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let origin = vector![0.0, 0.5, 0.5];
+
+        assert_eq!(
+            ray_cast(&origin, &vector![-1.0, 0.0, 0.0], &boundary, &btree),
+            None
+        );
+        assert_eq!(
+            ray_cast(&origin, &vector![0.0, 1.0, 0.0], &boundary, &btree),
+            None
+        );
+        assert_eq!(
+            ray_cast(&origin, &SVector::zeros(), &boundary, &btree),
+            None
+        );
+        assert_eq!(
+            ray_cast(&origin, &vector![1.0, 0.0, 0.0], &[], &BoundaryRTree::new()),
+            None
+        );
+    }
+}
+
 #[cfg(test)]
 mod falls_on_boundary_tests {
     use nalgebra::vector;
@@ -163,3 +760,147 @@ mod falls_on_boundary_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod find_holes_tests {
+    use nalgebra::vector;
+
+    use crate::prelude::WithinMode;
+
+    use super::*;
+
+    const JUMP_DIST: f64 = 0.1;
+
+    /// A square grid of halfspaces on the plane x = 0.5, with the cells in
+    /// @missing left out.
+    fn get_grid(size: usize, missing: &[(usize, usize)]) -> Vec<Halfspace<3>> {
+        let mut boundary = vec![];
+        for i in 0..size {
+            for j in 0..size {
+                if missing.contains(&(i, j)) {
+                    continue;
+                }
+                boundary.push(Halfspace {
+                    b: WithinMode(vector![0.5, i as f64 * JUMP_DIST, j as f64 * JUMP_DIST]),
+                    n: vector![1.0, 0.0, 0.0],
+                });
+            }
+        }
+        boundary
+    }
+
+    fn is_interior(hs: &Halfspace<3>, size: usize) -> bool {
+        let max = (size - 1) as f64 * JUMP_DIST;
+        (1..3).all(|i| hs.b[i] > 1e-10 && hs.b[i] < max - 1e-10)
+    }
+
+    #[test]
+    fn complete_grid_has_no_interior_holes() {
+        let boundary = get_grid(7, &[]);
+        let brtree = get_rtree_from_boundary(&boundary);
+
+        let seeds = find_holes(&boundary, &brtree, JUMP_DIST);
+
+        assert!(
+            seeds.iter().all(|hs| !is_interior(hs, 7)),
+            "find_holes reported a hole in the interior of a complete grid"
+        );
+    }
+
+    #[test]
+    fn missing_patch_is_surrounded_by_seeds() {
+        let missing = [(3, 3), (3, 4), (4, 3), (4, 4)];
+        let boundary = get_grid(8, &missing);
+        let brtree = get_rtree_from_boundary(&boundary);
+
+        let seeds = find_holes(&boundary, &brtree, JUMP_DIST);
+        let interior_seeds: Vec<_> = seeds.iter().filter(|hs| is_interior(hs, 8)).collect();
+
+        assert!(!interior_seeds.is_empty(), "find_holes missed the hole");
+        for hs in interior_seeds {
+            let i = (hs.b[1] / JUMP_DIST).round() as usize;
+            let j = (hs.b[2] / JUMP_DIST).round() as usize;
+            assert!(
+                missing
+                    .iter()
+                    .any(|&(mi, mj)| mi.abs_diff(i) + mj.abs_diff(j) == 1),
+                "Seed at ({i}, {j}) does not border the hole"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod decimate_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::estimation::approx_prediction, prelude::WithinMode,
+        test_utils::sphere_boundary,
+    };
+
+    use super::*;
+
+    #[test]
+    fn count_target_thins_flat_regions_first() {
+        // A flat grid on x = 0.5, bent into a ridge along y = 0.5
+        let mut boundary = vec![];
+        for i in 0..20 {
+            for j in 0..20 {
+                let y = i as f64 * 0.05;
+                let n = if y < 0.5 {
+                    vector![1.0, 0.0, 0.0]
+                } else {
+                    vector![1.0, 1.0, 0.0].normalize()
+                };
+                let x = if y < 0.5 { 0.5 } else { 0.5 - (y - 0.5) };
+                boundary.push(Halfspace {
+                    b: WithinMode(vector![x, y, j as f64 * 0.05]),
+                    n,
+                });
+            }
+        }
+
+        let decimated = decimate(&boundary, DecimationTarget::Count(100));
+
+        assert_eq!(decimated.len(), 100);
+        let on_ridge = |hs: &&Halfspace<3>| (hs.b[1] - 0.5).abs() < 0.06;
+        let ridge_kept = decimated.iter().filter(on_ridge).count() as f64
+            / boundary.iter().filter(on_ridge).count() as f64;
+        let flat_kept = decimated.iter().filter(|hs| !on_ridge(hs)).count() as f64
+            / boundary.iter().filter(|hs| !on_ridge(hs)).count() as f64;
+        assert!(
+            ridge_kept > 1.5 * flat_kept,
+            "Ridge was thinned as much as the flat regions: {ridge_kept} vs {flat_kept}"
+        );
+    }
+
+    #[test]
+    fn tolerance_target_preserves_predictions() {
+        let boundary = sphere_boundary(SVector::repeat(0.5), 0.3, 2000);
+        let decimated = decimate(&boundary, DecimationTarget::Tolerance(0.005));
+        assert!(
+            decimated.len() < boundary.len() / 2,
+            "Too few halfspaces removed: {}",
+            decimated.len()
+        );
+
+        let btree = get_rtree_from_boundary(&decimated);
+        let n_correct = sphere_boundary(SVector::repeat(0.5), 0.3, 500)
+            .iter()
+            .flat_map(|hs| [*hs.b - 0.05 * hs.n, *hs.b + 0.05 * hs.n])
+            .enumerate()
+            .filter(|(i, p)| approx_prediction(*p, &decimated, &btree, 1).class() == (i % 2 == 0))
+            .count();
+        assert!(n_correct >= 990, "Too many mispredictions: {n_correct}");
+    }
+
+    #[test]
+    fn zero_tolerance_keeps_curved_boundary() {
+        let boundary = sphere_boundary(SVector::repeat(0.5), 0.3, 200);
+        assert_eq!(
+            decimate(&boundary, DecimationTarget::Tolerance(0.0)).len(),
+            200
+        );
+    }
+}
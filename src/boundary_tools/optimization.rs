@@ -0,0 +1,181 @@
+use nalgebra::{Const, OMatrix, SVector};
+
+use crate::prelude::{
+    Adherer, AdhererFactory, AdhererState, Boundary, Classifier, Halfspace, MeshExplorer, Result,
+    SamplingError,
+};
+
+/// Finds the boundary point that minimizes a user-supplied objective, such as the
+/// distance from a nominal operating condition. Does not require the classifier.
+/// ## Arguments
+/// * boundary : The explored boundary to search.
+/// * objective : The function to minimize, evaluated at each boundary point.
+/// ## Returns
+/// * Some((index, value)) : The index of the best halfspace within @boundary and
+///   its objective value.
+/// * None : If @boundary is empty.
+pub fn minimize_on_boundary<const N: usize, O>(
+    boundary: &Boundary<N>,
+    objective: O,
+) -> Option<(usize, f64)>
+where
+    O: Fn(&SVector<f64, N>) -> f64,
+{
+    boundary
+        .iter()
+        .map(|hs| objective(&hs.b))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+/// Refines a boundary minimum by walking along the surface with the classifier,
+/// moving to neighboring boundary points that improve the objective and halving
+/// the step size when no neighbor improves upon the current point. Useful after
+/// `minimize_on_boundary` to locate the optimum with more precision than the
+/// explored resolution allows.
+/// ## Arguments
+/// * d : The initial distance to step along the surface.
+/// * min_d : The step size at which refinement terminates.
+/// * hs : The boundary halfspace to begin refinement from.
+/// * objective : The function to minimize.
+/// * adherer_f : The AdhererFactory to use for finding neighboring halfspaces.
+/// * classifier : The classifier for the FUT.
+/// * max_samples : The maximum number of classifier calls before terminating.
+/// ## Return (Ok((hs, value)))
+/// * hs : The best halfspace found.
+/// * value : The objective value of @hs.
+/// ## Error (Err)
+/// * SamplingError : Classifier errors other than BoundaryLost or OutOfBounds,
+///   which instead cause the offending direction to be skipped.
+pub fn refine_minimum<const N: usize, O, F, C>(
+    d: f64,
+    min_d: f64,
+    hs: Halfspace<N>,
+    objective: O,
+    adherer_f: &F,
+    classifier: &mut C,
+    max_samples: u32,
+) -> Result<(Halfspace<N>, f64)>
+where
+    O: Fn(&SVector<f64, N>) -> f64,
+    F: AdhererFactory<N>,
+    C: Classifier<N>,
+{
+    assert!(
+        min_d > 0.0 && d > min_d,
+        "Invalid step sizes, must satisfy 0 < min_d < d. Got d: {d}, min_d: {min_d}"
+    );
+
+    let basis_vectors = OMatrix::<f64, Const<N>, Const<N>>::identity();
+    let mut cur = hs;
+    let mut cur_value = objective(&cur.b);
+    let mut d = d;
+    let mut n_samples = 0;
+
+    while d >= min_d && n_samples < max_samples {
+        let mut best: Option<(Halfspace<N>, f64)> = None;
+
+        for v in MeshExplorer::<N, F>::create_cardinals(cur.n, basis_vectors) {
+            let mut adh = adherer_f.adhere_from(cur, d * v);
+            let result = loop {
+                match adh.get_state() {
+                    AdhererState::FoundBoundary(next) => break Ok(next),
+                    AdhererState::Searching if n_samples >= max_samples => {
+                        break Err(SamplingError::MaxSamplesExceeded)
+                    }
+                    AdhererState::Searching => {
+                        n_samples += 1;
+                        if let Err(e) = adh.sample_next(classifier) {
+                            break Err(e);
+                        }
+                    }
+                }
+            };
+
+            match result {
+                Ok(next) => {
+                    let value = objective(&next.b);
+                    if best.is_none_or(|(_, best_value)| value < best_value) {
+                        best = Some((next, value));
+                    }
+                }
+                Err(SamplingError::BoundaryLost)
+                | Err(SamplingError::OutOfBounds)
+                | Err(SamplingError::MaxSamplesExceeded) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        match best {
+            Some((next, value)) if value < cur_value => {
+                cur = next;
+                cur_value = value;
+            }
+            _ => d /= 2.0,
+        }
+    }
+
+    Ok((cur, cur_value))
+}
+
+#[cfg(all(test, feature = "sps"))]
+mod boundary_optimization {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        prelude::{ConstantAdhererFactory, Domain, Halfspace, WithinMode},
+        sps::Sphere,
+    };
+
+    use super::{minimize_on_boundary, refine_minimum};
+
+    #[test]
+    fn finds_minimum_of_boundary() {
+        let boundary: Vec<Halfspace<2>> = (0..8)
+            .map(|i| {
+                let v = vector![1.0, i as f64];
+                Halfspace {
+                    b: WithinMode(v),
+                    n: v.normalize(),
+                }
+            })
+            .collect();
+
+        let (i, value) = minimize_on_boundary(&boundary, |p| (p[1] - 3.2).abs()).unwrap();
+
+        assert_eq!(i, 3);
+        assert!((value - 0.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn refines_towards_nominal_point() {
+        let center = SVector::<f64, 3>::repeat(0.5);
+        let radius = 0.25;
+        let mut sphere = Sphere::new(center, radius, Some(Domain::normalized()));
+        let nominal = vector![1.0, 0.5, 0.5];
+
+        let hs = Halfspace {
+            b: WithinMode(vector![0.5, 0.5 + radius - 0.001, 0.5]),
+            n: vector![0.0, 1.0, 0.0],
+        };
+        let adherer_f = ConstantAdhererFactory::new(5.0f64.to_radians(), None);
+
+        let (best, value) = refine_minimum(
+            0.05,
+            0.005,
+            hs,
+            |p| (p - nominal).norm(),
+            &adherer_f,
+            &mut sphere,
+            2000,
+        )
+        .expect("Unexpected sampling error");
+
+        let optimum = vector![0.5 + radius, 0.5, 0.5];
+        assert!(
+            (*best.b - optimum).norm() < 0.05,
+            "Did not converge to the nearest boundary point. Got {:?} with value {value}",
+            best.b
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use nalgebra::SVector;
+
+#[cfg(feature = "surfacing")]
+use crate::{
+    prelude::{Boundary, BoundaryPair, BoundaryRTree, Classifier, Result, SamplingError},
+    search::surfacing::binary_surface_search,
+};
+
+use crate::prelude::Halfspace;
+
+/// The smallest known perturbation from a nominal point that crosses the boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crossing<const N: usize> {
+    /// The unit vector describing the direction of the perturbation.
+    pub direction: SVector<f64, N>,
+    /// The length of the perturbation, i.e. the distance to the boundary.
+    pub distance: f64,
+    /// The boundary halfspace where the crossing occurs.
+    pub hs: Halfspace<N>,
+}
+
+/// Finds the smallest perturbation from a nominal operating point that crosses the
+/// boundary, such as the nearest failure to a nominal scenario. The explored
+/// boundary is used to pick candidate directions, and the classifier is only used
+/// to confirm the crossing and surface it precisely.
+/// ## Arguments
+/// * p0 : The nominal point.
+/// * boundary : The explored boundary.
+/// * btree : The RTree for @boundary.
+/// * k : The number of nearest boundary points to try as candidate directions.
+///   Candidates that fail to classify or surface are skipped.
+/// * max_err : The desired maximum distance from the boundary.
+/// * max_samples : The maximum number of samples for surfacing each candidate.
+/// * classifier : The classifier for the FUT.
+/// ## Return (Ok(crossing))
+/// * crossing : The direction, distance, and boundary halfspace of the nearest
+///   crossing among the candidates.
+/// ## Error (Err)
+/// * BoundaryLost : None of the @k candidate directions crossed the boundary.
+/// * SamplingError : If @p0 could not be classified, or the classifier failed in
+///   a retryable way, e.g. Disconnected.
+#[cfg(feature = "surfacing")]
+pub fn find_nearest_crossing<const N: usize, C: Classifier<N>>(
+    p0: SVector<f64, N>,
+    boundary: &Boundary<N>,
+    btree: &BoundaryRTree<N>,
+    k: usize,
+    max_err: f64,
+    max_samples: u32,
+    classifier: &mut C,
+) -> Result<Crossing<N>> {
+    let s0 = classifier.classify(p0)?;
+
+    let mut nearest: Option<Crossing<N>> = None;
+    for node in btree.nearest_neighbor_iter(&p0.into()).take(k) {
+        let b = *boundary[node.data].b;
+        let dist = (b - p0).norm();
+        if dist <= f64::EPSILON {
+            continue;
+        }
+
+        let v = (b - p0) / dist;
+        let hs = classifier
+            .classify(p0 + v * (dist + 2.0 * max_err))
+            .and_then(|s1| match BoundaryPair::from_samples(s0, s1) {
+                Some(pair) => binary_surface_search(max_err, &pair, max_samples, classifier),
+                None => Err(SamplingError::BoundaryLost),
+            });
+
+        let hs = match hs {
+            Ok(hs) => hs,
+            Err(e) if e.is_retryable() => return Err(e),
+            // e.g. the candidate fell out of bounds or did not cross the boundary
+            Err(_) => continue,
+        };
+
+        let crossing = Crossing {
+            direction: v,
+            distance: (*hs.b - p0).dot(&v).abs(),
+            hs,
+        };
+        if nearest.is_none_or(|c| crossing.distance < c.distance) {
+            nearest = Some(crossing);
+        }
+    }
+
+    nearest.ok_or(SamplingError::BoundaryLost)
+}
+
+#[cfg(all(test, feature = "sps", feature = "surfacing"))]
+mod nearest_crossing {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Classifier, Domain, FunctionClassifier, SamplingError},
+        sps::Sphere,
+        test_utils::sphere_boundary,
+    };
+
+    use super::find_nearest_crossing;
+
+    const RADIUS: f64 = 0.25;
+
+    #[test]
+    fn finds_nearest_crossing_of_sphere() {
+        let max_err = 0.005;
+        let mut sphere = Sphere::new(SVector::repeat(0.5), RADIUS, Some(Domain::normalized()));
+        let boundary = sphere_boundary(SVector::repeat(0.5), RADIUS - 0.001, 500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let p0 = vector![0.6, 0.5, 0.5];
+
+        let crossing = find_nearest_crossing(p0, &boundary, &btree, 5, max_err, 100, &mut sphere)
+            .expect("Failed to find a crossing?");
+
+        assert!(
+            (crossing.distance - (RADIUS - 0.1)).abs() < 0.01,
+            "Incorrect distance to boundary: {}",
+            crossing.distance
+        );
+        assert!(
+            crossing.direction.angle(&vector![1.0, 0.0, 0.0]) < 10.0f64.to_radians(),
+            "Incorrect direction: {:?}",
+            crossing.direction
+        );
+    }
+
+    #[test]
+    fn skips_failed_candidates() {
+        let mut sphere = Sphere::new(SVector::repeat(0.5), RADIUS, Some(Domain::normalized()));
+        // The nearest candidate falls out of bounds
+        let mut n_calls = 0;
+        let mut classifier = FunctionClassifier::new(|p: SVector<f64, 3>| {
+            n_calls += 1;
+            if n_calls == 2 {
+                Err(SamplingError::OutOfBounds)
+            } else {
+                sphere.classify(p).map(|s| s.class())
+            }
+        });
+        let boundary = sphere_boundary(SVector::repeat(0.5), RADIUS - 0.001, 500);
+        let btree = get_rtree_from_boundary(&boundary);
+        let p0 = vector![0.6, 0.5, 0.5];
+
+        let crossing =
+            find_nearest_crossing(p0, &boundary, &btree, 20, 0.005, 100, &mut classifier)
+                .expect("Failed to find a crossing?");
+
+        assert!(
+            (crossing.distance - (RADIUS - 0.1)).abs() < 0.01,
+            "Incorrect distance to boundary: {}",
+            crossing.distance
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use nalgebra::{vector, Vector2};
+
+use crate::prelude::{Boundary, Span};
+
+use super::triangulation::circumcircle;
+
+/// The plane that a boundary is projected onto by `project()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectionPlane<const N: usize> {
+    /// The plane of two input dimensions, e.g. a pair of parameters. The projected
+    /// coordinates are the values of these dimensions.
+    Dims(usize, usize),
+    /// An arbitrary plane through the origin. The projected coordinates are the
+    /// components along the span's u and v.
+    Span(Span<N>),
+}
+
+/// Projects the boundary points onto a 2D plane, e.g. to plot a high-dimensional
+/// envelope over a chosen pair of parameters. See `concave_outline()` for the
+/// outline of the projected envelope.
+/// ## Arguments
+/// * boundary : The boundary to project.
+/// * plane : The plane to project onto.
+/// ## Returns
+/// * points : The projected boundary points, in boundary order.
+/// ## Panic
+/// When a dimension of a ProjectionPlane::Dims is not less than N.
+pub fn project<const N: usize>(
+    boundary: &Boundary<N>,
+    plane: &ProjectionPlane<N>,
+) -> Vec<[f64; 2]> {
+    boundary
+        .iter()
+        .map(|hs| match plane {
+            ProjectionPlane::Dims(i, j) => [hs.b[*i], hs.b[*j]],
+            ProjectionPlane::Span(span) => [span.u().dot(&hs.b), span.v().dot(&hs.b)],
+        })
+        .collect()
+}
+
+/// Finds the outline of a 2D point cloud, e.g. a projected boundary, as the
+/// boundary of its alpha shape: the Delaunay triangles whose circumradius is at
+/// most @alpha. Unlike the convex hull, the outline follows concavities and holes
+/// wider than ~2 * @alpha.
+///
+/// The Delaunay triangulation takes O(n^2) time, so very large point clouds may be
+/// decimated first. See `boundary_tools::decimate()`.
+/// ## Arguments
+/// * points : The point cloud to outline.
+/// * alpha : The maximum circumradius of a triangle within the shape. Somewhat
+///   larger than the spacing between points, e.g. ~1.5x the jump distance.
+/// ## Returns
+/// * loops : The closed outlines, without repeating their first point. Outer
+///   outlines are counter-clockwise and outlines of holes are clockwise.
+pub fn concave_outline(points: &[[f64; 2]], alpha: f64) -> Vec<Vec<[f64; 2]>> {
+    let mut points = points.to_vec();
+    points.sort_unstable_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+    let points: Vec<Vector2<f64>> = points.into_iter().map(Vector2::from).collect();
+
+    let triangles: Vec<[usize; 3]> = delaunay(&points)
+        .into_iter()
+        .filter(|&[a, b, c]| {
+            circumcircle(&points[a], &points[b], &points[c]).is_some_and(|(_, r)| r <= alpha)
+        })
+        .collect();
+
+    // Edges of exactly one triangle are on the outline, and are directed with the
+    // counter-clockwise triangles such that the shape is on their left.
+    let mut edge_count: HashMap<(usize, usize), usize> = HashMap::new();
+    for &[a, b, c] in triangles.iter() {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            *edge_count.entry((i.min(j), i.max(j))).or_insert(0) += 1;
+        }
+    }
+    let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &[a, b, c] in triangles.iter() {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            if edge_count[&(i.min(j), i.max(j))] == 1 {
+                next.entry(i).or_default().push(j);
+            }
+        }
+    }
+
+    let mut starts: Vec<usize> = next.keys().copied().collect();
+    starts.sort_unstable();
+
+    let mut loops = vec![];
+    for start in starts {
+        while let Some(mut j) = next.get_mut(&start).and_then(|out| out.pop()) {
+            let mut outline = vec![points[start].into()];
+            while j != start {
+                outline.push(points[j].into());
+                let Some(k) = next.get_mut(&j).and_then(|out| out.pop()) else {
+                    break;
+                };
+                j = k;
+            }
+            loops.push(outline);
+        }
+    }
+
+    loops
+}
+
+/// Triangulates the points by the Bowyer-Watson algorithm.
+/// ## Returns
+/// * triangles : The counter-clockwise Delaunay triangles.
+fn delaunay(points: &[Vector2<f64>]) -> Vec<[usize; 3]> {
+    let Some(first) = points.first() else {
+        return vec![];
+    };
+    let (low, high) = points
+        .iter()
+        .fold((*first, *first), |(low, high), p| (low.inf(p), high.sup(p)));
+    let center = (low + high) / 2.0;
+    let size = (high - low).max().max(f64::EPSILON) * 20.0;
+
+    // A triangle enclosing every point, whose vertices follow the points
+    let n = points.len();
+    let mut vertices = points.to_vec();
+    vertices.extend([
+        center + vector![-size, -size],
+        center + vector![size, -size],
+        center + vector![0.0, size],
+    ]);
+
+    let circle = |t: [usize; 3]| circumcircle(&vertices[t[0]], &vertices[t[1]], &vertices[t[2]]);
+    let mut triangles: Vec<([usize; 3], Vector2<f64>, f64)> = vec![];
+    let init = [n, n + 1, n + 2];
+    if let Some((c, r)) = circle(init) {
+        triangles.push((init, c, r));
+    }
+
+    for (i, p) in points.iter().enumerate() {
+        let (bad, good): (Vec<_>, Vec<_>) = triangles
+            .into_iter()
+            .partition(|(_, c, r)| (p - c).norm() < *r);
+        triangles = good;
+
+        // The edges of the cavity are those of exactly one bad triangle
+        let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut shared = vec![];
+        for (t, _, _) in bad.iter() {
+            for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                if edges.insert((a.min(b), a.max(b)), (a, b)).is_some() {
+                    shared.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+        for key in shared {
+            edges.remove(&key);
+        }
+
+        for (a, b) in edges.into_values() {
+            if let Some((c, r)) = circle([a, b, i]) {
+                triangles.push(([a, b, i], c, r));
+            }
+        }
+    }
+
+    triangles
+        .into_iter()
+        .map(|(t, _, _)| t)
+        .filter(|t| t.iter().all(|&v| v < n))
+        .map(|[a, b, c]| {
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            if (pb - pa).perp(&(pc - pa)) >= 0.0 {
+                [a, b, c]
+            } else {
+                [a, c, b]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod projection_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        prelude::{Halfspace, Span, WithinMode},
+        test_utils::sphere_boundary,
+    };
+
+    use super::*;
+
+    fn shoelace(outline: &[[f64; 2]]) -> f64 {
+        outline
+            .iter()
+            .zip(outline.iter().cycle().skip(1))
+            .map(|(a, b)| (a[0] * b[1] - b[0] * a[1]) / 2.0)
+            .sum()
+    }
+
+    fn grid(keep: impl Fn(f64, f64) -> bool) -> Vec<[f64; 2]> {
+        (0..=25)
+            .flat_map(|i| (0..=25).map(move |j| [i as f64 * 0.04, j as f64 * 0.04]))
+            .filter(|p| keep(p[0], p[1]))
+            .collect()
+    }
+
+    #[test]
+    fn projects_onto_dims_and_span() {
+        let boundary: Vec<Halfspace<3>> = (0..10)
+            .map(|i| Halfspace {
+                b: WithinMode(vector![0.1 * i as f64, 0.5, 1.0 - 0.1 * i as f64]),
+                n: SVector::zeros(),
+            })
+            .collect();
+
+        let dims = project(&boundary, &ProjectionPlane::Dims(2, 0));
+        let span = Span::new(vector![1.0, 0.0, 0.0], vector![0.0, 1.0, 1.0]);
+        let spanned = project(&boundary, &ProjectionPlane::Span(span.clone()));
+
+        for ((hs, d), s) in boundary.iter().zip(dims).zip(spanned) {
+            assert_eq!(d, [hs.b[2], hs.b[0]]);
+            assert!((s[0] - span.u().dot(&hs.b)).abs() < 1e-12);
+            assert!((s[1] - span.v().dot(&hs.b)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn outline_follows_concavity() {
+        // An L-shape, with area 1 - 0.48^2
+        let points = grid(|x, y| x <= 0.52 + 1e-9 || y <= 0.52 + 1e-9);
+        let outlines = concave_outline(&points, 0.06);
+
+        assert_eq!(outlines.len(), 1);
+        let area = shoelace(&outlines[0]);
+        // Up to a sliver in the inner corner
+        assert!((area - 0.7696).abs() < 0.002, "Bad area: {area}");
+    }
+
+    #[test]
+    fn outline_has_clockwise_holes() {
+        let points = grid(|x, y| ((x - 0.5).powi(2) + (y - 0.5).powi(2)).sqrt() > 0.2);
+        let outlines = concave_outline(&points, 0.06);
+
+        assert_eq!(outlines.len(), 2);
+        let areas: Vec<f64> = outlines.iter().map(|o| shoelace(o)).collect();
+        let (outer, hole) = if areas[0] > 0.0 {
+            (areas[0], areas[1])
+        } else {
+            (areas[1], areas[0])
+        };
+        assert!((outer - 1.0).abs() < 1e-9);
+        assert!(hole < 0.0 && (hole.abs() - PI * 0.2 * 0.2).abs() < 0.03);
+    }
+
+    #[test]
+    fn projected_sphere_outline_is_disk() {
+        let boundary = sphere_boundary(SVector::repeat(0.5), 0.3, 1000);
+
+        let points = project(&boundary, &ProjectionPlane::Dims(0, 1));
+        let outlines = concave_outline(&points, 0.1);
+
+        assert_eq!(outlines.len(), 1);
+        let area = shoelace(&outlines[0]);
+        assert!(
+            (area - PI * 0.09).abs() / (PI * 0.09) < 0.05,
+            "Bad area: {area}"
+        );
+    }
+}
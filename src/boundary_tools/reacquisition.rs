@@ -1,4 +1,15 @@
-use crate::prelude::{Boundary, Classifier, Domain, Halfspace, Result, Sample};
+use crate::prelude::{
+    Boundary, BoundaryRTree, Classifier, Domain, Halfspace, KnnNode, Result, Sample, SpatialIndex,
+    WithinMode,
+};
+
+/// The number of reacquired neighbors whose displacements are interpolated by
+/// `reacquire_all_propagated()`.
+const PROPAGATION_NEIGHBORS: usize = 4;
+
+/// The reacquired boundary and the displacement of each halfspace, where None
+/// indicates the halfspace could not be reacquired.
+pub type Reacquisition<const N: usize> = (Vec<Option<Halfspace<N>>>, Vec<Option<f64>>);
 
 /// Acquires the EXACT boundary for a given outdated halfspace.
 ///
@@ -9,7 +20,7 @@ use crate::prelude::{Boundary, Classifier, Domain, Halfspace, Result, Sample};
 /// - Ok(Some(hs)) : The halfspace that was successfully reacquired
 /// - Ok(None) : Failed to find the boundary.
 /// - Err(SamplingError) : Classifier induced error, generally unexpected unless @domain is
-///     incorret.
+///   incorret.
 fn reacquire_hs_incremental<const N: usize, C>(
     classifier: &mut C,
     hs: &Halfspace<N>,
@@ -40,7 +51,7 @@ where
     let boundary_exists = sample.class() != init_cls;
 
     match (boundary_exists, domain.contains(&sample), sample) {
-        (true, true, Sample::WithinMode(b)) => Ok(Some(Halfspace { b: b, n: hs.n })),
+        (true, true, Sample::WithinMode(b)) => Ok(Some(Halfspace { b, n: hs.n })),
         (true, _, _) => {
             if let Sample::WithinMode(b) = prev_sample {
                 Ok(Some(Halfspace { b, n: hs.n }))
@@ -52,6 +63,76 @@ where
     }
 }
 
+/// Acquires the boundary for a given outdated halfspace by bracketing the boundary
+/// with exponentially growing steps along the surface vector, then binary searching
+/// the bracket until it is within @max_err. Takes O(log(displacement / max_err))
+/// samples, rather than the O(displacement / max_err) of
+/// `reacquire_hs_incremental()`.
+///
+/// ### Return
+/// - Ok(Some(hs)) : The halfspace that was successfully reacquired
+/// - Ok(None) : Failed to find the boundary before the edge of @domain or
+///   @max_samples.
+/// - Err(SamplingError) : Classifier induced error.
+fn reacquire_hs_binary<const N: usize, C>(
+    classifier: &mut C,
+    hs: &Halfspace<N>,
+    domain: &Domain<N>,
+    max_err: f64,
+    max_samples: Option<u32>,
+) -> Result<Option<Halfspace<N>>>
+where
+    C: Classifier<N>,
+{
+    let init_sample = classifier.classify(*hs.b)?;
+    let init_cls = init_sample.class();
+
+    // Within mode moves outwards to the boundary, out of mode moves inwards
+    let v = if init_cls { hs.n } else { -hs.n };
+    let Ok(max_dist) = domain.distance_to_edge(&hs.b, &v) else {
+        return Ok(None);
+    };
+
+    let mut i = 0;
+    let (mut near, mut near_sample) = (0.0, init_sample);
+    let mut step = max_err;
+
+    let (mut far, mut far_sample) = loop {
+        if near >= max_dist || max_samples.is_some_and(|m| i >= m) {
+            return Ok(None);
+        }
+
+        let t = (near + step).min(max_dist);
+        let sample = classifier.classify(*hs.b + t * v)?;
+        i += 1;
+
+        if sample.class() != init_cls {
+            break (t, sample);
+        }
+        (near, near_sample) = (t, sample);
+        step *= 2.0;
+    };
+
+    while far - near > max_err && max_samples.is_none_or(|m| i < m) {
+        let t = (near + far) / 2.0;
+        let sample = classifier.classify(*hs.b + t * v)?;
+        i += 1;
+
+        if sample.class() == init_cls {
+            (near, near_sample) = (t, sample);
+        } else {
+            (far, far_sample) = (t, sample);
+        }
+    }
+
+    match (near_sample, far_sample) {
+        (Sample::WithinMode(b), _) | (_, Sample::WithinMode(b)) => {
+            Ok(Some(Halfspace { b, n: hs.n }))
+        }
+        _ => unreachable!("The bracket must contain a within-mode sample"),
+    }
+}
+
 /// Attempts to reacquire the EXACT boundary after the FUT has changed in some way.
 ///
 /// "Incremental" means that fixed-sized jump distances are used across all boundary
@@ -61,15 +142,16 @@ where
 /// Ok
 /// - new_boundary : The resultant boundary
 /// - displacements : corresponding displacements for each halfspace in the @boundary
+///
 /// ERR : Classifier induced error, generally unexpected unless @domain is
-///     incorret.
+/// incorret.
 pub fn reacquire_all_incremental<const N: usize, C>(
     classifier: &mut C,
     boundary: &Boundary<N>,
     domain: &Domain<N>,
     max_err: f64,
     samples_per_hs: Option<u32>,
-) -> Result<(Vec<Option<Halfspace<N>>>, Vec<Option<f64>>)>
+) -> Result<Reacquisition<N>>
 where
     C: Classifier<N>,
 {
@@ -85,3 +167,304 @@ where
 
     Ok((new_boundary, displacements))
 }
+
+/// Attempts to reacquire the boundary after the FUT has changed in some way, as
+/// `reacquire_all_incremental()` does, but binary searching for each halfspace's
+/// new boundary point. Far fewer samples are taken when the boundary has moved by
+/// many multiples of @max_err.
+///
+/// ### Return
+/// Ok
+/// - new_boundary : The resultant boundary
+/// - displacements : corresponding displacements for each halfspace in the @boundary
+///
+/// ERR : Classifier induced error.
+pub fn reacquire_all_binary<const N: usize, C>(
+    classifier: &mut C,
+    boundary: &Boundary<N>,
+    domain: &Domain<N>,
+    max_err: f64,
+    samples_per_hs: Option<u32>,
+) -> Result<Reacquisition<N>>
+where
+    C: Classifier<N>,
+{
+    let mut new_boundary = vec![];
+    let mut displacements = vec![];
+
+    for hs in boundary {
+        let result = reacquire_hs_binary(classifier, hs, domain, max_err, samples_per_hs)?;
+        new_boundary.push(result);
+
+        displacements.push(result.map(|new_hs| (new_hs.b - hs.b).norm()));
+    }
+
+    Ok((new_boundary, displacements))
+}
+
+/// Attempts to reacquire the boundary after the FUT has changed in some way, using
+/// the classifier for only a subsample of the halfspaces. Since reacquisition moves
+/// each boundary point along its surface vector, the rest are displaced along their
+/// surface vectors by interpolating the signed displacements of their nearest
+/// reacquired neighbors, weighted by inverse distance. A fraction of these are
+/// validated against the FUT.
+///
+/// The subsample (the anchors) is chosen greedily in boundary order, such that no
+/// two anchors are within @anchor_spacing of each other. Anchors are reacquired as
+/// `reacquire_all_binary()` does. An inferred halfspace is valid if its boundary
+/// point is within mode and the point @max_err along its surface vector is out of
+/// mode; invalid halfspaces are reacquired from their inferred location.
+///
+/// ## Warning
+/// Inferred halfspaces that were not validated have not been classified, so their
+/// boundary points are only predicted to be within mode.
+/// ## Arguments
+/// * anchor_spacing : The minimum distance between reacquired anchors. Larger
+///   spacing takes fewer samples, but interpolates over more of the boundary.
+/// * validation_rate : 0 <= validation_rate <= 1, the fraction of inferred
+///   halfspaces to validate, spread evenly through the boundary.
+/// ### Return
+/// Ok
+/// - new_boundary : The resultant boundary
+/// - displacements : corresponding displacements for each halfspace in the @boundary
+///
+/// ERR : Classifier induced error.
+pub fn reacquire_all_propagated<const N: usize, C>(
+    classifier: &mut C,
+    boundary: &Boundary<N>,
+    domain: &Domain<N>,
+    max_err: f64,
+    samples_per_hs: Option<u32>,
+    anchor_spacing: f64,
+    validation_rate: f64,
+) -> Result<Reacquisition<N>>
+where
+    C: Classifier<N>,
+{
+    let mut new_boundary: Vec<Option<Halfspace<N>>> = vec![None; boundary.len()];
+
+    let mut anchors = BoundaryRTree::new();
+    let mut is_anchor = vec![false; boundary.len()];
+    for (i, hs) in boundary.iter().enumerate() {
+        let key: [f64; N] = hs.b.into();
+        if anchors
+            .locate_within_distance(key, anchor_spacing * anchor_spacing)
+            .next()
+            .is_none()
+        {
+            anchors.insert(KnnNode::new(key, i));
+            is_anchor[i] = true;
+        }
+    }
+
+    // Only successfully reacquired anchors are interpolated.
+    let mut reacquired = BoundaryRTree::new();
+    for (i, hs) in boundary.iter().enumerate().filter(|&(i, _)| is_anchor[i]) {
+        new_boundary[i] = reacquire_hs_binary(classifier, hs, domain, max_err, samples_per_hs)?;
+        if new_boundary[i].is_some() {
+            reacquired.insert(KnnNode::new(hs.b.into(), i));
+        }
+    }
+
+    let validation_rate = validation_rate.clamp(0.0, 1.0);
+    let mut n_inferred = 0;
+    for (i, hs) in boundary.iter().enumerate().filter(|&(i, _)| !is_anchor[i]) {
+        let neighbors =
+            SpatialIndex::nearest_neighbors(&reacquired, &hs.b.into(), PROPAGATION_NEIGHBORS);
+        if neighbors.is_empty() {
+            continue;
+        }
+
+        let (displacement, total_weight) =
+            neighbors
+                .iter()
+                .fold((0.0, 0.0), |(displacement, total_weight), neighbor| {
+                    let old_hs = &boundary[neighbor.data];
+                    let new_hs = new_boundary[neighbor.data].expect("Anchor was not reacquired");
+                    let w = 1.0 / (*old_hs.b - *hs.b).norm().max(f64::EPSILON);
+                    let signed = old_hs.n.dot(&(*new_hs.b - *old_hs.b));
+                    (displacement + w * signed, total_weight + w)
+                });
+        let b = *hs.b + (displacement / total_weight) * hs.n;
+        let inferred = Halfspace {
+            b: WithinMode(domain.clip_vector(&b)),
+            n: hs.n,
+        };
+
+        // Validates whenever the validated fraction falls below validation_rate
+        let n_validated = (n_inferred as f64 * validation_rate).ceil();
+        n_inferred += 1;
+        new_boundary[i] = if (n_inferred as f64 * validation_rate).ceil() > n_validated
+            && !is_valid(classifier, &inferred, max_err)?
+        {
+            reacquire_hs_binary(classifier, &inferred, domain, max_err, samples_per_hs)?
+        } else {
+            Some(inferred)
+        };
+    }
+
+    let displacements = boundary
+        .iter()
+        .zip(new_boundary.iter())
+        .map(|(hs, new_hs)| new_hs.map(|new_hs| (new_hs.b - hs.b).norm()))
+        .collect();
+
+    Ok((new_boundary, displacements))
+}
+
+/// Checks that @hs lies on the boundary, to within @max_err along its surface vector.
+fn is_valid<const N: usize, C>(classifier: &mut C, hs: &Halfspace<N>, max_err: f64) -> Result<bool>
+where
+    C: Classifier<N>,
+{
+    Ok(
+        classifier.classify(*hs.b)?.class()
+            && !classifier.classify(*hs.b + max_err * hs.n)?.class(),
+    )
+}
+
+#[cfg(test)]
+mod reacquisition_tests {
+    use std::cell::Cell;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        prelude::{FunctionClassifier, Halfspace},
+        test_utils::circle_boundary,
+    };
+
+    use super::*;
+
+    const MAX_ERR: f64 = 0.005;
+
+    fn circle(radius: f64) -> Vec<Halfspace<2>> {
+        circle_boundary(SVector::repeat(0.5), radius, 50)
+    }
+
+    fn disk(radius: f64) -> impl FnMut(SVector<f64, 2>) -> Result<bool> {
+        move |p| Ok((p - SVector::repeat(0.5)).norm() <= radius)
+    }
+
+    #[test]
+    fn binary_reacquires_large_changes_with_fewer_samples() {
+        let domain = Domain::normalized();
+        // Grown and shrunk envelopes, i.e. initially within and out of mode
+        for (r0, r1) in [(0.2, 0.4), (0.4, 0.2)] {
+            let boundary = circle(r0);
+            let n = Cell::new(0);
+            let mut fut = disk(r1);
+            let mut classifier = FunctionClassifier::new(|p| {
+                n.set(n.get() + 1);
+                fut(p)
+            });
+
+            let binary =
+                reacquire_all_binary(&mut classifier, &boundary, &domain, MAX_ERR, None).unwrap();
+            let n_binary = n.replace(0);
+            let incremental =
+                reacquire_all_incremental(&mut classifier, &boundary, &domain, MAX_ERR, None)
+                    .unwrap();
+            let n_incremental = n.get();
+
+            for (hs, disp) in binary.0.iter().zip(binary.1.iter()) {
+                let hs = hs.expect("Failed to reacquire halfspace");
+                let r = (*hs.b - SVector::repeat(0.5)).norm();
+                assert!(
+                    r <= r1 && r1 - r <= MAX_ERR + 1e-10,
+                    "Bad boundary point: {r}"
+                );
+                assert!((disp.unwrap() - (r - r0).abs()).abs() < 1e-10);
+            }
+            assert!(incremental.0.iter().all(|hs| hs.is_some()));
+            assert!(
+                n_binary * 3 < n_incremental,
+                "Expected far fewer samples: {n_binary} vs {n_incremental}"
+            );
+        }
+    }
+
+    #[test]
+    fn binary_fails_when_boundary_is_out_of_reach() {
+        let domain = Domain::normalized();
+        let boundary = circle(0.2);
+
+        // The envelope grew to cover the whole domain
+        let mut classifier = FunctionClassifier::new(disk(1.0));
+        let (new_boundary, displacements) =
+            reacquire_all_binary(&mut classifier, &boundary, &domain, MAX_ERR, None).unwrap();
+        assert!(new_boundary.iter().all(|hs| hs.is_none()));
+        assert!(displacements.iter().all(|d| d.is_none()));
+
+        // Too few samples to bracket the boundary
+        let mut classifier = FunctionClassifier::new(disk(0.4));
+        let (new_boundary, _) =
+            reacquire_all_binary(&mut classifier, &boundary, &domain, MAX_ERR, Some(2)).unwrap();
+        assert!(new_boundary.iter().all(|hs| hs.is_none()));
+    }
+
+    fn count_samples_propagated(
+        r1: SVector<f64, 2>,
+        boundary: &Boundary<2>,
+        anchor_spacing: f64,
+        validation_rate: f64,
+    ) -> (Reacquisition<2>, usize) {
+        let n = Cell::new(0);
+        let mut classifier = FunctionClassifier::new(|p: SVector<f64, 2>| {
+            n.set(n.get() + 1);
+            Ok(((p - SVector::repeat(0.5)).component_div(&r1)).norm() <= 1.0)
+        });
+        let result = reacquire_all_propagated(
+            &mut classifier,
+            boundary,
+            &Domain::normalized(),
+            MAX_ERR,
+            None,
+            anchor_spacing,
+            validation_rate,
+        )
+        .unwrap();
+        (result, n.get())
+    }
+
+    #[test]
+    fn propagated_interpolates_uniform_change() {
+        let boundary = circle(0.2);
+        // Every halfspace is an anchor without spacing
+        let (_, n_full) = count_samples_propagated(SVector::repeat(0.3), &boundary, 0.0, 0.0);
+
+        let ((new_boundary, displacements), n_samples) =
+            count_samples_propagated(SVector::repeat(0.3), &boundary, 0.1, 0.0);
+
+        for (hs, disp) in new_boundary.iter().zip(displacements) {
+            let r = (*hs.unwrap().b - SVector::repeat(0.5)).norm();
+            assert!(
+                (r - 0.3).abs() <= MAX_ERR + 1e-10,
+                "Bad boundary point: {r}"
+            );
+            assert!((disp.unwrap() - 0.1).abs() <= MAX_ERR + 1e-10);
+        }
+        assert!(
+            n_samples * 3 < n_full,
+            "Expected far fewer samples: {n_samples} vs {n_full}"
+        );
+    }
+
+    #[test]
+    fn propagated_validation_corrects_inferred_halfspaces() {
+        let boundary = circle(0.2);
+        // Stretched unevenly, so interpolation alone is inaccurate
+        let r1 = vector![0.35, 0.25];
+        let is_on_boundary = |hs: &Halfspace<2>| {
+            let f = |p: SVector<f64, 2>| ((p - SVector::repeat(0.5)).component_div(&r1)).norm();
+            f(*hs.b) <= 1.0 && f(*hs.b + MAX_ERR * hs.n) > 1.0
+        };
+
+        let ((unvalidated, _), n_unvalidated) = count_samples_propagated(r1, &boundary, 0.1, 0.0);
+        let ((validated, _), n_validated) = count_samples_propagated(r1, &boundary, 0.1, 1.0);
+
+        assert!(unvalidated.iter().any(|hs| !is_on_boundary(&hs.unwrap())));
+        assert!(validated.iter().all(|hs| is_on_boundary(&hs.unwrap())));
+        assert!(n_validated > n_unvalidated);
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use nalgebra::{vector, SVector, Vector2, Vector3};
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+use crate::{boundary_tools::get_rtree_from_boundary, prelude::Boundary};
+
+/// A triangle mesh of a 3D boundary, e.g. for rendering or measuring its area.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceMesh {
+    /// The boundary points, in boundary order.
+    pub vertices: Vec<[f64; 3]>,
+    /// The indices of each triangle's vertices, counter-clockwise when viewed from
+    /// outside of the envelope.
+    pub faces: Vec<[usize; 3]>,
+}
+
+/// A polyline of a 2D boundary, e.g. for rendering or measuring its length.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveMesh {
+    /// The boundary points, in boundary order.
+    pub vertices: Vec<[f64; 2]>,
+    /// The indices of each segment's vertices, counter-clockwise around the
+    /// envelope, i.e. with the envelope on the left.
+    pub segments: Vec<[usize; 2]>,
+}
+
+impl SurfaceMesh {
+    /// The total area of the faces.
+    pub fn area(&self) -> f64 {
+        self.faces
+            .iter()
+            .map(|&[a, b, c]| {
+                let (a, b, c) = (
+                    Vector3::from(self.vertices[a]),
+                    Vector3::from(self.vertices[b]),
+                    Vector3::from(self.vertices[c]),
+                );
+                (b - a).cross(&(c - a)).norm() / 2.0
+            })
+            .sum()
+    }
+
+    /// The volume enclosed by the faces, by the divergence theorem. Only meaningful
+    /// for a closed mesh, and underestimates the volume by that of any holes'
+    /// missing caps. With @max_edge acting as alpha, this is an alpha-shape
+    /// estimate that also holds for non-convex envelopes.
+    pub fn volume(&self) -> f64 {
+        self.faces
+            .iter()
+            .map(|&[a, b, c]| {
+                let (a, b, c) = (
+                    Vector3::from(self.vertices[a]),
+                    Vector3::from(self.vertices[b]),
+                    Vector3::from(self.vertices[c]),
+                );
+                a.dot(&b.cross(&c)) / 6.0
+            })
+            .sum::<f64>()
+            .abs()
+    }
+}
+
+impl CurveMesh {
+    /// The total length of the segments.
+    pub fn length(&self) -> f64 {
+        self.segments
+            .iter()
+            .map(|&[a, b]| {
+                (Vector2::from(self.vertices[a]) - Vector2::from(self.vertices[b])).norm()
+            })
+            .sum()
+    }
+    /// The area enclosed by the segments, by the shoelace formula. Only meaningful
+    /// for closed curves. See `SurfaceMesh::volume()`.
+    pub fn area(&self) -> f64 {
+        self.segments
+            .iter()
+            .map(|&[a, b]| {
+                let (a, b) = (self.vertices[a], self.vertices[b]);
+                (a[0] * b[1] - b[0] * a[1]) / 2.0
+            })
+            .sum::<f64>()
+            .abs()
+    }
+}
+
+/// Triangulates a 3D boundary by local Delaunay triangulation. The @k nearest
+/// neighbors of each boundary point are projected onto its tangent plane and the
+/// Delaunay triangles incident to the point are proposed. Triangles proposed by at
+/// least two of their vertices are kept, which removes most of the inconsistencies
+/// between neighboring projections, although small holes may remain where the
+/// boundary is sparse or sharply curved.
+/// ## Arguments
+/// * boundary : The boundary to triangulate.
+/// * k : The number of neighbors to consider per point. 10 - 15 suits boundaries
+///   explored by MeshExplorer.
+/// * max_edge : The maximum length of a triangle's edges, preventing triangles from
+///   bridging gaps in the boundary. ~2x the jump distance is a good default.
+/// ## Return
+/// * mesh : The triangle mesh, whose vertices are the boundary points.
+pub fn triangulate(boundary: &Boundary<3>, k: usize, max_edge: f64) -> SurfaceMesh {
+    let btree = get_rtree_from_boundary(boundary);
+    let mut votes: HashMap<[usize; 3], usize> = HashMap::new();
+
+    for (i, hs) in boundary.iter().enumerate() {
+        let (u, v) = tangent_basis(&hs.n);
+        let local: Vec<(usize, Vector2<f64>)> = btree
+            .nearest_neighbor_iter(&hs.b.into())
+            .take(k + 1)
+            .map(|node| node.data)
+            .filter(|&j| j != i && (*boundary[j].b - *hs.b).norm() <= max_edge)
+            .map(|j| {
+                let s = *boundary[j].b - *hs.b;
+                (j, vector![s.dot(&u), s.dot(&v)])
+            })
+            .collect();
+
+        for (a, &(ja, pa)) in local.iter().enumerate() {
+            for &(jb, pb) in local.iter().skip(a + 1) {
+                if (*boundary[ja].b - *boundary[jb].b).norm() > max_edge {
+                    continue;
+                }
+                let Some((center, radius)) = circumcircle(&Vector2::zeros(), &pa, &pb) else {
+                    continue;
+                };
+                let is_delaunay = local
+                    .iter()
+                    .filter(|&&(j, _)| j != ja && j != jb)
+                    .all(|(_, p)| (p - center).norm() >= radius * (1.0 - 1e-9));
+                if is_delaunay {
+                    let mut key = [i, ja, jb];
+                    key.sort_unstable();
+                    *votes.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut faces: Vec<[usize; 3]> = votes
+        .into_iter()
+        .filter(|&(_, n)| n >= 2)
+        .map(|([a, b, c], _)| {
+            // Orient the face along the surface vectors of its vertices
+            let (pa, pb, pc) = (*boundary[a].b, *boundary[b].b, *boundary[c].b);
+            let n = boundary[a].n + boundary[b].n + boundary[c].n;
+            if (pb - pa).cross(&(pc - pa)).dot(&n) >= 0.0 {
+                [a, b, c]
+            } else {
+                [a, c, b]
+            }
+        })
+        .collect();
+    faces.sort_unstable();
+
+    SurfaceMesh {
+        vertices: boundary.iter().map(|hs| hs.b.into()).collect(),
+        faces,
+    }
+}
+
+/// Connects the points of a 2D boundary into a polyline. Each point is joined to its
+/// nearest neighbor on either side along its tangent, and segments chosen by both of
+/// their points are kept.
+/// ## Arguments
+/// * boundary : The boundary to connect.
+/// * k : The number of neighbors to consider per point.
+/// * max_edge : The maximum length of a segment, preventing segments from bridging
+///   gaps in the boundary. ~2x the jump distance is a good default.
+/// ## Return
+/// * mesh : The polyline, whose vertices are the boundary points.
+pub fn polyline(boundary: &Boundary<2>, k: usize, max_edge: f64) -> CurveMesh {
+    let btree = get_rtree_from_boundary(boundary);
+    let mut votes: HashMap<[usize; 2], usize> = HashMap::new();
+
+    for (i, hs) in boundary.iter().enumerate() {
+        let tangent = vector![-hs.n[1], hs.n[0]];
+        let neighbors: Vec<(usize, f64)> = btree
+            .nearest_neighbor_iter(&hs.b.into())
+            .take(k + 1)
+            .map(|node| node.data)
+            .filter(|&j| j != i && (*boundary[j].b - *hs.b).norm() <= max_edge)
+            .map(|j| (j, (*boundary[j].b - *hs.b).dot(&tangent)))
+            .collect();
+
+        // The neighbors are ordered nearest first
+        let ahead = neighbors.iter().find(|(_, t)| *t > 0.0);
+        let behind = neighbors.iter().find(|(_, t)| *t < 0.0);
+        for &(j, _) in ahead.into_iter().chain(behind) {
+            *votes.entry([i.min(j), i.max(j)]).or_insert(0) += 1;
+        }
+    }
+
+    let mut segments: Vec<[usize; 2]> = votes
+        .into_iter()
+        .filter(|&(_, n)| n >= 2)
+        .map(|([a, b], _)| {
+            // Counter-clockwise, such that the surface vectors point to the right
+            let s = *boundary[b].b - *boundary[a].b;
+            let n = boundary[a].n + boundary[b].n;
+            if s[1] * n[0] - s[0] * n[1] >= 0.0 {
+                [a, b]
+            } else {
+                [b, a]
+            }
+        })
+        .collect();
+    segments.sort_unstable();
+
+    CurveMesh {
+        vertices: boundary.iter().map(|hs| hs.b.into()).collect(),
+        segments,
+    }
+}
+
+/// An orthonormal basis of the plane orthogonal to @n.
+fn tangent_basis(n: &SVector<f64, 3>) -> (Vector3<f64>, Vector3<f64>) {
+    let axis = if n[0].abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = n.cross(&axis).normalize();
+    let v = n.cross(&u).normalize();
+    (u, v)
+}
+
+/// The center and radius of the circle through three points, or None if they are
+/// colinear.
+pub(super) fn circumcircle(
+    a: &Vector2<f64>,
+    b: &Vector2<f64>,
+    c: &Vector2<f64>,
+) -> Option<(Vector2<f64>, f64)> {
+    let (b, c) = (b - a, c - a);
+    let d = 2.0 * (b[0] * c[1] - b[1] * c[0]);
+    if d.abs() < 1e-12 {
+        return None;
+    }
+
+    let (b2, c2) = (b.norm_squared(), c.norm_squared());
+    let center = vector![(c[1] * b2 - b[1] * c2) / d, (b[0] * c2 - c[0] * b2) / d];
+    Some((a + center, center.norm()))
+}
+
+#[cfg(test)]
+mod triangulation_tests {
+    use std::{collections::HashMap, f64::consts::PI};
+
+    use nalgebra::SVector;
+
+    use crate::{
+        prelude::Halfspace,
+        test_utils::{circle_boundary, sphere_boundary},
+    };
+
+    use super::{polyline, triangulate};
+
+    const RADIUS: f64 = 0.3;
+
+    fn sphere(n: usize) -> Vec<Halfspace<3>> {
+        sphere_boundary(SVector::repeat(0.5), RADIUS, n)
+    }
+
+    #[test]
+    fn sphere_mesh_is_nearly_watertight() {
+        let boundary = sphere(1000);
+        let mesh = triangulate(&boundary, 12, 0.1);
+
+        let area = 4.0 * PI * RADIUS * RADIUS;
+        assert!(
+            (mesh.area() - area).abs() / area < 0.05,
+            "Bad area: {} vs {area}",
+            mesh.area()
+        );
+        let volume = 4.0 / 3.0 * PI * RADIUS.powi(3);
+        assert!(
+            (mesh.volume() - volume).abs() / volume < 0.05,
+            "Bad volume: {} vs {volume}",
+            mesh.volume()
+        );
+
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for &[a, b, c] in mesh.faces.iter() {
+            for (p, q) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((p.min(q), p.max(q))).or_insert(0) += 1;
+            }
+        }
+        let n_manifold = edges.values().filter(|&&n| n == 2).count();
+        assert!(
+            n_manifold as f64 / edges.len() as f64 > 0.95,
+            "Too many open or non-manifold edges: {n_manifold} / {}",
+            edges.len()
+        );
+
+        // Faces are wound outwards
+        for &[a, b, c] in mesh.faces.iter() {
+            let (pa, pb, pc) = (*boundary[a].b, *boundary[b].b, *boundary[c].b);
+            let outward = (pa + pb + pc) / 3.0 - SVector::repeat(0.5);
+            assert!((pb - pa).cross(&(pc - pa)).dot(&outward) > 0.0);
+        }
+    }
+
+    #[test]
+    fn max_edge_prevents_bridging_gaps() {
+        let boundary: Vec<_> = sphere(1000)
+            .into_iter()
+            .filter(|hs| hs.n[2].abs() > 0.2)
+            .collect();
+        let mesh = triangulate(&boundary, 12, 0.1);
+
+        // No face spans the band removed around the equator
+        assert!(mesh
+            .faces
+            .iter()
+            .all(|f| f.iter().all(|&i| boundary[i].n[2] > 0.0)
+                || f.iter().all(|&i| boundary[i].n[2] < 0.0)));
+    }
+
+    #[test]
+    fn circle_polyline_is_closed() {
+        let n = 200;
+        let boundary = circle_boundary(SVector::repeat(0.5), RADIUS, n);
+
+        let mesh = polyline(&boundary, 4, 0.1);
+
+        assert_eq!(mesh.segments.len(), n);
+        let mut degree = vec![0; n];
+        for &[a, b] in mesh.segments.iter() {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        assert!(degree.iter().all(|&d| d == 2));
+        assert!((mesh.length() - 2.0 * PI * RADIUS).abs() < 0.01);
+        assert!((mesh.area() - PI * RADIUS * RADIUS).abs() < 0.01);
+        // Counter-clockwise about the center
+        for &[a, b] in mesh.segments.iter() {
+            let (pa, pb) = (*boundary[a].b - SVector::repeat(0.5), *boundary[b].b);
+            assert!(pa[0] * pb[1] - pa[1] * pb[0] > pa[0] * 0.5 - pa[1] * 0.5);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use nalgebra::SVector;
+
+use crate::prelude::{Boundary, Halfspace, SpatialIndex, WithinMode};
+
+/// How far synthesized boundary points are pulled from the chord between their
+/// neighbors towards the neighbors' tangent planes. 1/2 follows a curved surface to
+/// second order.
+const SHAPE_FACTOR: f64 = 0.5;
+
+/// Densifies a coarse boundary without taking any further samples from the FUT, by
+/// synthesizing halfspaces between neighboring ones. Each synthesized boundary
+/// point is interpolated between its neighbors' boundary points, curved to follow
+/// their tangent planes (Phong tessellation) rather than cutting across a curved
+/// surface, and its surface vector is interpolated spherically (slerp) between
+/// theirs. Useful for
+/// smoothing `estimation::approx_prediction()` along a boundary explored with a
+/// large jump distance. Neighbors are found as in `cluster_envelopes()`, so that
+/// points are not synthesized across nearby envelopes.
+/// ## Arguments
+/// * boundary : The boundary to densify.
+/// * boundary_rtree : The spatial index (e.g. RTree) for @boundary.
+/// * d : The jump distance used to explore @boundary.
+/// * n_between : The number of halfspaces to synthesize between each pair of
+///   neighbors, evenly spaced.
+/// ## Returns
+/// * upsampled : @boundary, followed by the synthesized halfspaces.
+pub fn upsample<const N: usize, I>(
+    boundary: &Boundary<N>,
+    boundary_rtree: &I,
+    d: f64,
+    n_between: usize,
+) -> Vec<Halfspace<N>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let max_dist = d * (N as f64).sqrt();
+    let mut upsampled = boundary.to_vec();
+
+    for (i, hs) in boundary.iter().enumerate() {
+        for node in boundary_rtree.within_distance(&hs.b.into(), max_dist) {
+            let other = &boundary[node.data];
+            // Each pair is interpolated once, and never across opposing surfaces.
+            if node.data <= i || hs.n.dot(&other.n) < 0.0 {
+                continue;
+            }
+
+            for k in 1..=n_between {
+                let t = k as f64 / (n_between + 1) as f64;
+                upsampled.push(Halfspace {
+                    b: WithinMode(interpolate(hs, other, t)),
+                    n: slerp(&hs.n, &other.n, t),
+                });
+            }
+        }
+    }
+
+    upsampled
+}
+
+/// Interpolates between the boundary points of @h1 and @h2, blending the point on
+/// their chord with its projections onto their tangent planes.
+fn interpolate<const N: usize>(h1: &Halfspace<N>, h2: &Halfspace<N>, t: f64) -> SVector<f64, N> {
+    let l = *h1.b + t * (*h2.b - *h1.b);
+    let p1 = l - h1.n.dot(&(l - *h1.b)) * h1.n;
+    let p2 = l - h2.n.dot(&(l - *h2.b)) * h2.n;
+
+    (1.0 - SHAPE_FACTOR) * l + SHAPE_FACTOR * ((1.0 - t) * p1 + t * p2)
+}
+
+/// Spherically interpolates between unit vectors @n1 and @n2, which must not face
+/// opposite directions.
+fn slerp<const N: usize>(n1: &SVector<f64, N>, n2: &SVector<f64, N>, t: f64) -> SVector<f64, N> {
+    let angle = n1.angle(n2);
+    if angle < 1e-10 {
+        return *n1;
+    }
+
+    let (w1, w2) = (((1.0 - t) * angle).sin(), (t * angle).sin());
+    ((w1 * n1 + w2 * n2) / angle.sin()).normalize()
+}
+
+#[cfg(test)]
+mod upsample_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::vector;
+
+    use crate::{
+        boundary_tools::{estimation::approx_prediction, get_rtree_from_boundary},
+        test_utils::circle_boundary,
+    };
+
+    use super::*;
+
+    const RADIUS: f64 = 0.3;
+
+    /// Halfspaces evenly spaced around a circle at the center of the unit square.
+    fn circle(n: usize) -> Vec<Halfspace<2>> {
+        circle_boundary(SVector::repeat(0.5), RADIUS, n)
+    }
+
+    #[test]
+    fn synthesizes_between_neighbors() {
+        let boundary = circle(20);
+        let d = 2.0 * PI * RADIUS / 20.0;
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let upsampled = upsample(&boundary, &btree, d, 3);
+
+        // Each point has two neighbors on the circle, so each of the 20 pairs
+        // gains 3 halfspaces
+        assert_eq!(upsampled.len(), 20 + 20 * 3);
+        assert_eq!(&upsampled[..20], boundary.as_slice());
+        for hs in upsampled[20..].iter() {
+            let r = *hs.b - SVector::repeat(0.5);
+            assert!((hs.n.norm() - 1.0).abs() < 1e-10);
+            // Close to the circle, with an outward facing normal
+            assert!(hs.n.angle(&r) < 0.01, "Bad normal: {:?}", hs.n);
+            assert!((r.norm() - RADIUS).abs() < 1e-3, "Bad point: {:?}", hs.b);
+        }
+    }
+
+    #[test]
+    fn smooths_predictions() {
+        let boundary = circle(8);
+        let d = 2.0 * PI * RADIUS / 8.0;
+        let btree = get_rtree_from_boundary(&boundary);
+        let upsampled = upsample(&boundary, &btree, d, 4);
+        let upsampled_btree = get_rtree_from_boundary(&upsampled);
+
+        // Points just inside and outside of the circle, between the original
+        // boundary points
+        let probes: Vec<(SVector<f64, 2>, bool)> = (0..360)
+            .flat_map(|i| {
+                let theta = (i as f64).to_radians();
+                let n = vector![theta.cos(), theta.sin()];
+                [
+                    (SVector::repeat(0.5) + (RADIUS - 0.01) * n, true),
+                    (SVector::repeat(0.5) + (RADIUS + 0.01) * n, false),
+                ]
+            })
+            .collect();
+        let n_correct = |boundary: &Vec<Halfspace<2>>, btree| {
+            probes
+                .iter()
+                .filter(|(p, cls)| approx_prediction(*p, boundary, btree, 1).class() == *cls)
+                .count()
+        };
+
+        let (n_upsampled, n_coarse) = (
+            n_correct(&upsampled, &upsampled_btree),
+            n_correct(&boundary, &btree),
+        );
+        assert!(
+            n_upsampled > n_coarse,
+            "{n_upsampled} <= {n_coarse} correct"
+        );
+    }
+
+    #[test]
+    fn does_not_interpolate_opposing_surfaces() {
+        // Either side of a thin slab
+        let boundary = vec![
+            Halfspace {
+                b: WithinMode(vector![0.5, 0.49]),
+                n: vector![0.0, -1.0],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.5, 0.51]),
+                n: vector![0.0, 1.0],
+            },
+        ];
+        let btree = get_rtree_from_boundary(&boundary);
+
+        assert_eq!(upsample(&boundary, &btree, 0.05, 2).len(), 2);
+    }
+}
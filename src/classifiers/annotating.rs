@@ -0,0 +1,145 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use nalgebra::SVector;
+
+use crate::structs::{AnnotatedSample, Classifier, Result, Sample, SamplePhase};
+
+/// Records every sample classified by the wrapped classifier as an AnnotatedSample,
+/// labeled with its sequence number, the time it was classified, and the current
+/// phase and tags. Set the phase before each stage of an exploration, e.g.
+/// GlobalSearch before `find_initial_boundary_pair()`, to later attribute the FUT
+/// budget to each stage.
+pub struct AnnotatingClassifier<C, const N: usize> {
+    classifier: C,
+    phase: Option<SamplePhase>,
+    tags: Vec<String>,
+    samples: Vec<AnnotatedSample<N>>,
+}
+
+impl<C, const N: usize> AnnotatingClassifier<C, N> {
+    pub fn new(classifier: C) -> Self {
+        AnnotatingClassifier {
+            classifier,
+            phase: None,
+            tags: vec![],
+            samples: vec![],
+        }
+    }
+
+    /// Labels the samples classified from now on with @phase.
+    pub fn set_phase(&mut self, phase: Option<SamplePhase>) {
+        self.phase = phase;
+    }
+
+    pub fn phase(&self) -> Option<SamplePhase> {
+        self.phase
+    }
+
+    /// Labels the samples classified from now on with @tags.
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    /// The samples classified so far, in order.
+    pub fn samples(&self) -> &[AnnotatedSample<N>] {
+        &self.samples
+    }
+
+    /// The number of samples classified during each phase. Samples taken without a
+    /// phase are counted under None.
+    pub fn phase_counts(&self) -> HashMap<Option<SamplePhase>, usize> {
+        let mut counts = HashMap::new();
+        for s in self.samples.iter() {
+            *counts.entry(s.phase).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Returns the wrapped classifier and the recorded samples.
+    pub fn into_parts(self) -> (C, Vec<AnnotatedSample<N>>) {
+        (self.classifier, self.samples)
+    }
+
+    fn annotate(&mut self, sample: Sample<N>, timestamp: SystemTime) {
+        self.samples.push(AnnotatedSample {
+            sample,
+            sequence: self.samples.len() as u64,
+            timestamp: Some(timestamp),
+            phase: self.phase,
+            tags: self.tags.clone(),
+        });
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for AnnotatingClassifier<C, N>
+where
+    C: Classifier<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let sample = self.classifier.classify(p)?;
+        self.annotate(sample, SystemTime::now());
+        Ok(sample)
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        let samples = self.classifier.classify_batch(points)?;
+        let timestamp = SystemTime::now();
+        for &sample in samples.iter() {
+            self.annotate(sample, timestamp);
+        }
+        Ok(samples)
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        let (sample, confidence) = self.classifier.classify_with_confidence(p)?;
+        self.annotate(sample, SystemTime::now());
+        Ok((sample, confidence))
+    }
+}
+
+#[cfg(test)]
+mod annotating_classifier_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::structs::{FunctionClassifier, SamplingError};
+
+    use super::*;
+
+    #[test]
+    fn annotates_samples_with_phase_and_tags() {
+        let mut classifier =
+            AnnotatingClassifier::new(FunctionClassifier::new(|p: SVector<f64, 2>| {
+                if p[0] > 1.0 {
+                    Err(SamplingError::OutOfBounds)
+                } else {
+                    Ok(p[0] > 0.5)
+                }
+            }));
+
+        classifier.set_phase(Some(SamplePhase::GlobalSearch));
+        classifier.classify(vector![0.2, 0.5]).unwrap();
+        classifier.classify(vector![0.7, 0.5]).unwrap();
+
+        classifier.set_phase(Some(SamplePhase::Surfacing));
+        classifier.set_tags(vec!["run-1".to_string()]);
+        classifier
+            .classify_batch(&[vector![0.4, 0.5], vector![0.6, 0.5]])
+            .unwrap();
+        assert!(classifier.classify(vector![1.5, 0.5]).is_err());
+
+        let samples = classifier.samples();
+        assert_eq!(samples.len(), 4);
+        assert!(samples
+            .iter()
+            .enumerate()
+            .all(|(i, s)| s.sequence == i as u64));
+        assert!(samples.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        assert!(samples[1].class() && !samples[2].class());
+        assert!(!samples[1].has_tag("run-1") && samples[3].has_tag("run-1"));
+
+        let counts = classifier.phase_counts();
+        assert_eq!(counts[&Some(SamplePhase::GlobalSearch)], 2);
+        assert_eq!(counts[&Some(SamplePhase::Surfacing)], 2);
+        assert!(!counts.contains_key(&None));
+    }
+}
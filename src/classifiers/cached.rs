@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use nalgebra::SVector;
+
+use crate::structs::{Classifier, Result, Sample, SamplingError};
+
+/// Memoizes the classifications of the wrapped classifier, so that repeated
+/// requests for the same point are served without running the FUT again, e.g. the
+/// nearly identical probes made by surfacing, chord finding, and reacquisition.
+/// Failed classifications are not cached. Only use with a deterministic FUT.
+pub struct CachedClassifier<C, const N: usize> {
+    classifier: C,
+    resolution: Option<f64>,
+    cache: HashMap<[i64; N], Entry>,
+    n_hits: usize,
+    n_misses: usize,
+}
+
+/// A cached classification.
+#[derive(Clone, Copy)]
+struct Entry {
+    cls: bool,
+    confidence: Option<f64>,
+    /// Batches do not report confidence, so points first classified in a batch
+    /// are classified again when their confidence is requested.
+    has_confidence: bool,
+}
+
+impl<C, const N: usize> CachedClassifier<C, N> {
+    /// Creates a CachedClassifier that only matches bitwise-identical points.
+    pub fn new(classifier: C) -> Self {
+        CachedClassifier {
+            classifier,
+            resolution: None,
+            cache: HashMap::new(),
+            n_hits: 0,
+            n_misses: 0,
+        }
+    }
+
+    /// Quantizes points to a grid with spacing @resolution before caching, so that
+    /// points within the same cell share a classification. The resolution should be
+    /// well below the precision of the exploration, e.g. a small fraction of the
+    /// jump distance, or the cache will blur the boundary. Clears the cache.
+    pub fn with_resolution(mut self, resolution: f64) -> Self {
+        assert!(resolution > 0.0, "Resolution must be positive non-zero!");
+        self.resolution = Some(resolution);
+        self.cache.clear();
+        self
+    }
+
+    /// The number of classifications served from the cache.
+    pub fn hits(&self) -> usize {
+        self.n_hits
+    }
+
+    /// The number of classifications passed on to the wrapped classifier,
+    /// including those that failed.
+    pub fn misses(&self) -> usize {
+        self.n_misses
+    }
+
+    /// The number of cached classifications.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets all cached classifications, e.g. after the FUT has changed.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Returns the wrapped classifier.
+    pub fn into_inner(self) -> C {
+        self.classifier
+    }
+
+    fn key(&self, p: &SVector<f64, N>) -> [i64; N] {
+        match self.resolution {
+            Some(r) => std::array::from_fn(|i| (p[i] / r).round() as i64),
+            None => std::array::from_fn(|i| p[i].to_bits() as i64),
+        }
+    }
+
+    /// Finds the cached classification of @p, if any, that satisfies @is_usable.
+    fn lookup(&mut self, p: &SVector<f64, N>, is_usable: impl Fn(&Entry) -> bool) -> Option<Entry> {
+        let cached = self.cache.get(&self.key(p)).copied().filter(is_usable);
+        if cached.is_some() {
+            self.n_hits += 1;
+        }
+        cached
+    }
+
+    fn store(&mut self, p: &SVector<f64, N>, entry: Entry) {
+        self.cache.insert(self.key(p), entry);
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for CachedClassifier<C, N>
+where
+    C: Classifier<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        match self.lookup(&p, |_| true) {
+            Some(entry) => Ok(Sample::from_class(p, entry.cls)),
+            None => Ok(self.classify_miss(p)?.0),
+        }
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        let cached: Vec<Option<Entry>> = points.iter().map(|p| self.lookup(p, |_| true)).collect();
+        let misses: Vec<SVector<f64, N>> = points
+            .iter()
+            .zip(cached.iter())
+            .filter(|(_, c)| c.is_none())
+            .map(|(p, _)| *p)
+            .collect();
+
+        let classified = if misses.is_empty() {
+            vec![]
+        } else {
+            self.n_misses += misses.len();
+            self.classifier.classify_batch(&misses)?
+        };
+        if classified.len() != misses.len() {
+            return Err(SamplingError::InvalidClassifierResponse(format!(
+                "Expected {} classifications, got {}",
+                misses.len(),
+                classified.len()
+            )));
+        }
+        for s in classified.iter() {
+            let entry = Entry {
+                cls: s.class(),
+                confidence: None,
+                has_confidence: false,
+            };
+            self.store(s, entry);
+        }
+
+        let mut classified = classified.into_iter();
+        Ok(points
+            .iter()
+            .zip(cached)
+            .filter_map(|(&p, c)| match c {
+                Some(entry) => Some(Sample::from_class(p, entry.cls)),
+                None => classified.next(),
+            })
+            .collect())
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        match self.lookup(&p, |entry| entry.has_confidence) {
+            Some(entry) => Ok((Sample::from_class(p, entry.cls), entry.confidence)),
+            None => self.classify_miss(p),
+        }
+    }
+}
+
+impl<C, const N: usize> CachedClassifier<C, N>
+where
+    C: Classifier<N>,
+{
+    fn classify_miss(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        self.n_misses += 1;
+        let (sample, confidence) = self.classifier.classify_with_confidence(p)?;
+        let entry = Entry {
+            cls: sample.class(),
+            confidence,
+            has_confidence: true,
+        };
+        self.store(&p, entry);
+        Ok((sample, confidence))
+    }
+}
+
+#[cfg(test)]
+mod cached_classifier_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::structs::{FunctionClassifier, SamplingError};
+
+    use super::*;
+
+    fn counting_classifier(
+        count: &mut usize,
+    ) -> FunctionClassifier<impl FnMut(SVector<f64, 2>) -> Result<bool> + '_, 2> {
+        FunctionClassifier::new(move |p: SVector<f64, 2>| {
+            *count += 1;
+            if p[0] < 0.0 {
+                Err(SamplingError::OutOfBounds)
+            } else {
+                Ok(p[0] > 0.5)
+            }
+        })
+    }
+
+    #[test]
+    fn serves_repeats_from_cache() {
+        let mut count = 0;
+        let mut classifier = CachedClassifier::new(counting_classifier(&mut count));
+
+        let p = vector![0.7, 0.2];
+        assert!(classifier.classify(p).unwrap().class());
+        assert!(classifier.classify(p).unwrap().class());
+        assert!(classifier.classify(vector![0.7 - 1e-12, 0.2]).is_ok());
+        assert!(classifier.classify(vector![-0.1, 0.2]).is_err());
+        assert!(classifier.classify(vector![-0.1, 0.2]).is_err());
+
+        // Failed classifications are misses, but are not cached
+        assert_eq!((classifier.hits(), classifier.misses()), (1, 4));
+        assert_eq!(classifier.len(), 2);
+        drop(classifier);
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn quantizes_nearby_points() {
+        let mut count = 0;
+        let mut classifier =
+            CachedClassifier::new(counting_classifier(&mut count)).with_resolution(1e-3);
+
+        let s = classifier.classify(vector![0.6, 0.2]).unwrap();
+        let near = vector![0.6 + 1e-4, 0.2 - 1e-4];
+        let s_near = classifier.classify(near).unwrap();
+
+        assert_eq!(s.class(), s_near.class());
+        assert_eq!(s_near.into_inner(), near);
+        assert_eq!(classifier.hits(), 1);
+        drop(classifier);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn batches_only_misses() {
+        let mut count = 0;
+        let mut classifier = CachedClassifier::new(counting_classifier(&mut count));
+        classifier.classify(vector![0.7, 0.5]).unwrap();
+
+        let points = [vector![0.2, 0.5], vector![0.7, 0.5], vector![0.9, 0.5]];
+        let samples = classifier.classify_batch(&points).unwrap();
+
+        assert_eq!(
+            samples.iter().map(|s| s.class()).collect::<Vec<_>>(),
+            vec![false, true, true]
+        );
+        assert!(samples.iter().zip(points.iter()).all(|(s, p)| **s == *p));
+        assert_eq!((classifier.hits(), classifier.misses()), (1, 3));
+        drop(classifier);
+        assert_eq!(count, 3);
+    }
+
+    /// Reports a confidence, but batches may come back one classification short.
+    struct Confident {
+        short_batches: bool,
+    }
+
+    impl Classifier<2> for Confident {
+        fn classify(&mut self, p: SVector<f64, 2>) -> Result<Sample<2>> {
+            Ok(Sample::from_class(p, p[0] > 0.5))
+        }
+
+        fn classify_batch(&mut self, points: &[SVector<f64, 2>]) -> Result<Vec<Sample<2>>> {
+            let skip = usize::from(self.short_batches);
+            points[skip..].iter().map(|&p| self.classify(p)).collect()
+        }
+
+        fn classify_with_confidence(
+            &mut self,
+            p: SVector<f64, 2>,
+        ) -> Result<(Sample<2>, Option<f64>)> {
+            Ok((self.classify(p)?, Some(0.9)))
+        }
+    }
+
+    #[test]
+    fn keeps_confidence() {
+        let mut classifier = CachedClassifier::new(Confident {
+            short_batches: false,
+        });
+        let p = vector![0.7, 0.2];
+
+        // Batches do not report confidence, so the first request re-classifies
+        classifier.classify_batch(&[p]).unwrap();
+        assert_eq!(classifier.classify_with_confidence(p).unwrap().1, Some(0.9));
+        assert_eq!(classifier.classify_with_confidence(p).unwrap().1, Some(0.9));
+        assert!(classifier.classify(p).unwrap().class());
+        assert_eq!((classifier.hits(), classifier.misses()), (2, 2));
+    }
+
+    #[test]
+    fn rejects_short_batches() {
+        let mut classifier = CachedClassifier::new(Confident {
+            short_batches: true,
+        });
+        let points = [vector![0.2, 0.5], vector![0.9, 0.5]];
+
+        assert!(matches!(
+            classifier.classify_batch(&points),
+            Err(SamplingError::InvalidClassifierResponse(_))
+        ));
+    }
+}
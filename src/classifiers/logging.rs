@@ -0,0 +1,292 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use nalgebra::SVector;
+
+use crate::structs::{Classifier, Result, Sample, SamplingError};
+
+/// The file format written by a LoggingClassifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per line, {"point": [..], "class": true, "duration": 0.1},
+    /// where the class is null for a failed classification.
+    Jsonl,
+    /// A header row, x0,..,xN-1,class,duration, followed by one row per
+    /// classification, where the class is 1, 0, or empty for a failed
+    /// classification.
+    Csv,
+}
+
+/// Appends every classification made by the wrapped classifier to a log file as it
+/// happens, including its point, class and duration (in seconds), so that a crashed
+/// experiment still leaves a complete audit trail of the FUT's evaluations.
+/// Failed classifications are logged without a class. Failing to write the log
+/// fails the classification with InvalidClassifierResponse.
+pub struct LoggingClassifier<C> {
+    classifier: C,
+    writer: BufWriter<File>,
+    format: LogFormat,
+    flush_interval: usize,
+    n_unflushed: usize,
+    n_logged: usize,
+}
+
+impl<C> LoggingClassifier<C> {
+    /// Creates a new log at @path, overwriting any existing file.
+    pub fn create<P: AsRef<Path>, const N: usize>(
+        classifier: C,
+        path: P,
+        format: LogFormat,
+    ) -> io::Result<Self> {
+        let f = File::create(path)?;
+        Self::from_file::<N>(classifier, f, format, true)
+    }
+
+    /// Continues the log at @path, e.g. when resuming a crashed experiment, or
+    /// creates it if it does not exist.
+    pub fn append<P: AsRef<Path>, const N: usize>(
+        classifier: C,
+        path: P,
+        format: LogFormat,
+    ) -> io::Result<Self> {
+        let f = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_empty = f.metadata()?.len() == 0;
+        Self::from_file::<N>(classifier, f, format, is_empty)
+    }
+
+    fn from_file<const N: usize>(
+        classifier: C,
+        f: File,
+        format: LogFormat,
+        write_header: bool,
+    ) -> io::Result<Self> {
+        let mut writer = BufWriter::new(f);
+        if write_header && format == LogFormat::Csv {
+            for i in 0..N {
+                write!(writer, "x{i},")?;
+            }
+            writeln!(writer, "class,duration")?;
+            writer.flush()?;
+        }
+
+        Ok(LoggingClassifier {
+            classifier,
+            writer,
+            format,
+            flush_interval: 1,
+            n_unflushed: 0,
+            n_logged: 0,
+        })
+    }
+
+    /// Flushes the log every @n_records classifications rather than after each
+    /// one, reducing overhead for fast FUTs at the risk of losing the last
+    /// @n_records - 1 records in a crash.
+    pub fn with_flush_interval(mut self, n_records: usize) -> Self {
+        assert!(n_records > 0, "Flush interval must be positive non-zero!");
+        self.flush_interval = n_records;
+        self
+    }
+
+    /// The number of classifications logged.
+    pub fn n_logged(&self) -> usize {
+        self.n_logged
+    }
+
+    /// Writes any buffered records to the log.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.n_unflushed = 0;
+        self.writer.flush()
+    }
+
+    /// Flushes the log and returns the wrapped classifier.
+    pub fn into_inner(mut self) -> io::Result<C> {
+        self.flush()?;
+        Ok(self.classifier)
+    }
+
+    fn write_record<const N: usize>(
+        &mut self,
+        p: &SVector<f64, N>,
+        class: Option<bool>,
+        duration: Duration,
+    ) -> io::Result<()> {
+        let duration = duration.as_secs_f64();
+        match self.format {
+            LogFormat::Jsonl => {
+                let point: Vec<String> = p.iter().map(|&x| json_number(x)).collect();
+                let class = class.map_or("null".to_string(), |c| c.to_string());
+                writeln!(
+                    self.writer,
+                    "{{\"point\":[{}],\"class\":{class},\"duration\":{duration}}}",
+                    point.join(",")
+                )?;
+            }
+            LogFormat::Csv => {
+                for x in p.iter() {
+                    write!(self.writer, "{x},")?;
+                }
+                let class = class.map_or(String::new(), |c| (c as u8).to_string());
+                writeln!(self.writer, "{class},{duration}")?;
+            }
+        }
+
+        self.n_logged += 1;
+        self.n_unflushed += 1;
+        if self.n_unflushed >= self.flush_interval {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn log<const N: usize>(
+        &mut self,
+        p: &SVector<f64, N>,
+        class: Option<bool>,
+        duration: Duration,
+    ) -> Result<()> {
+        self.write_record(p, class, duration).map_err(|e| {
+            SamplingError::InvalidClassifierResponse(format!("Failed to log sample: {e}"))
+        })
+    }
+}
+
+/// Formats @x as a JSON number, or null if it is not finite.
+fn json_number(x: f64) -> String {
+    if x.is_finite() {
+        x.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for LoggingClassifier<C>
+where
+    C: Classifier<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let start = Instant::now();
+        let result = self.classifier.classify(p);
+        let class = result.as_ref().ok().map(|s| s.class());
+        self.log(&p, class, start.elapsed())?;
+        result
+    }
+
+    /// The duration of the batch is divided evenly between its points.
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        let start = Instant::now();
+        let result = self.classifier.classify_batch(points);
+        let duration = start.elapsed() / points.len().max(1) as u32;
+        match &result {
+            Ok(samples) => {
+                for s in samples.iter() {
+                    self.log(&s.into_inner(), Some(s.class()), duration)?;
+                }
+            }
+            Err(_) => {
+                for p in points.iter() {
+                    self.log(p, None, duration)?;
+                }
+            }
+        }
+        result
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        let start = Instant::now();
+        let result = self.classifier.classify_with_confidence(p);
+        let class = result.as_ref().ok().map(|(s, _)| s.class());
+        self.log(&p, class, start.elapsed())?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod logging_classifier_tests {
+    use std::fs;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::structs::FunctionClassifier;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "sembas_logging_classifier_{}_{name}",
+            std::process::id()
+        ))
+    }
+
+    fn fut() -> FunctionClassifier<impl FnMut(SVector<f64, 2>) -> Result<bool>, 2> {
+        FunctionClassifier::new(|p: SVector<f64, 2>| {
+            if p[0] < 0.0 {
+                Err(SamplingError::OutOfBounds)
+            } else {
+                Ok(p[0] > 0.5)
+            }
+        })
+    }
+
+    #[test]
+    fn logs_csv_as_classified() {
+        let path = temp_path("csv");
+        let mut classifier = LoggingClassifier::create::<_, 2>(fut(), &path, LogFormat::Csv)
+            .expect("Failed to create log");
+
+        classifier.classify(vector![0.75, 0.25]).unwrap();
+        assert!(classifier.classify(vector![-0.5, 0.25]).is_err());
+
+        // Flushed without dropping the classifier
+        let log = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines[0], "x0,x1,class,duration");
+        assert!(lines[1].starts_with("0.75,0.25,1,"));
+        assert!(lines[2].starts_with("-0.5,0.25,,"));
+        assert_eq!(classifier.n_logged(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "io")]
+    fn logs_jsonl_batches() {
+        let path = temp_path("jsonl");
+        let mut classifier = LoggingClassifier::create::<_, 2>(fut(), &path, LogFormat::Jsonl)
+            .expect("Failed to create log");
+
+        classifier
+            .classify_batch(&[vector![0.25, 0.5], vector![1.0, 0.5]])
+            .unwrap();
+
+        let log = fs::read_to_string(&path).unwrap();
+        let records: Vec<serde_json::Value> = log
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("Invalid JSON"))
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["point"], serde_json::json!([0.25, 0.5]));
+        assert_eq!(records[0]["class"], false);
+        assert_eq!(records[1]["class"], true);
+        assert!(records[1]["duration"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[test]
+    fn append_continues_log() {
+        let path = temp_path("append");
+        let _ = fs::remove_file(&path);
+        for _ in 0..2 {
+            let mut classifier = LoggingClassifier::append::<_, 2>(fut(), &path, LogFormat::Csv)
+                .expect("Failed to open log")
+                .with_flush_interval(10);
+            classifier.classify(vector![0.75, 0.25]).unwrap();
+            classifier.into_inner().unwrap();
+        }
+
+        let log = fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), 3);
+        assert_eq!(log.matches("class").count(), 1);
+    }
+}
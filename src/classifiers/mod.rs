@@ -0,0 +1,15 @@
+pub mod annotating;
+pub mod cached;
+pub mod logging;
+pub mod probabilistic;
+pub mod region;
+pub mod replay;
+pub mod subspace;
+
+pub use annotating::*;
+pub use cached::*;
+pub use logging::*;
+pub use probabilistic::*;
+pub use region::*;
+pub use replay::*;
+pub use subspace::*;
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use nalgebra::SVector;
+
+use crate::{
+    structs::{Boundary, Classifier, ProbabilisticClassifier, Result, Sample},
+    utils::point_key,
+};
+
+/// An estimate of the probability that a point is classified as within the target
+/// performance mode, acquired through repeated sampling of a stochastic FUT.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModeProbability {
+    /// The fraction of samples that were classified as WithinMode.
+    pub p: f64,
+    /// The number of samples that the estimate is based on.
+    pub n: u32,
+}
+
+/// Wraps a stochastic classifier, i.e. a FUT where the same point may be classified
+/// differently between executions, and classifies a point as WithinMode when its
+/// estimated probability of being in-mode is at least alpha. Explorers using this
+/// classifier will therefore explore the p = alpha iso-probability surface, rather
+/// than a single noisy realization of the boundary.
+pub struct IsoProbabilityClassifier<C, const N: usize>
+where
+    C: Classifier<N>,
+{
+    classifier: C,
+    alpha: f64,
+    n_samples: u32,
+    estimates: HashMap<[u64; N], ModeProbability>,
+}
+
+impl ModeProbability {
+    /// The standard error of the probability estimate, sqrt(p * (1 - p) / n).
+    pub fn std_err(&self) -> f64 {
+        (self.p * (1.0 - self.p) / self.n as f64).sqrt()
+    }
+
+    /// Combines two estimates of the same point into a single estimate.
+    pub fn merge(&self, other: &ModeProbability) -> ModeProbability {
+        let n = self.n + other.n;
+        let p = (self.p * self.n as f64 + other.p * other.n as f64) / n as f64;
+        ModeProbability { p, n }
+    }
+}
+
+impl<C, const N: usize> IsoProbabilityClassifier<C, N>
+where
+    C: Classifier<N>,
+{
+    /// Creates an IsoProbabilityClassifier.
+    /// ## Arguments
+    /// * classifier : The stochastic classifier for the FUT.
+    /// * alpha : 0 < alpha < 1, the probability of being in-mode that defines the
+    ///   surface to be explored.
+    /// * n_samples : The number of times each point is classified to estimate its
+    ///   probability of being in-mode. More samples -> lower uncertainty.
+    pub fn new(classifier: C, alpha: f64, n_samples: u32) -> Self {
+        assert!(
+            alpha > 0.0 && alpha < 1.0,
+            "Invalid alpha, must be between 0 and 1! Got: {alpha}"
+        );
+        assert!(n_samples > 0, "n_samples must be positive non-zero!");
+
+        IsoProbabilityClassifier {
+            classifier,
+            alpha,
+            n_samples,
+            estimates: HashMap::new(),
+        }
+    }
+
+    /// The probability threshold of the iso-probability surface.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The number of samples taken per classification.
+    pub fn n_samples(&self) -> u32 {
+        self.n_samples
+    }
+
+    /// The probability estimate for a previously classified point, if one exists.
+    pub fn estimate(&self, p: &SVector<f64, N>) -> Option<ModeProbability> {
+        self.estimates.get(&point_key(p)).copied()
+    }
+
+    /// Retrieves the probability estimate for each halfspace in @boundary, which
+    /// describes the uncertainty of the halfspace's classification.
+    /// ## Arguments
+    /// * boundary : A boundary explored using this classifier.
+    /// ## Returns
+    /// * estimates : Parallel to @boundary, the probability estimate of each
+    ///   boundary point. None if the boundary point was never classified by this
+    ///   classifier.
+    pub fn boundary_uncertainty(&self, boundary: &Boundary<N>) -> Vec<Option<ModeProbability>> {
+        boundary.iter().map(|hs| self.estimate(&hs.b)).collect()
+    }
+
+    /// Returns the wrapped classifier.
+    pub fn into_inner(self) -> C {
+        self.classifier
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for IsoProbabilityClassifier<C, N>
+where
+    C: Classifier<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let mut n_within = 0;
+        for _ in 0..self.n_samples {
+            if self.classifier.classify(p)?.class() {
+                n_within += 1;
+            }
+        }
+
+        let estimate = ModeProbability {
+            p: n_within as f64 / self.n_samples as f64,
+            n: self.n_samples,
+        };
+
+        let estimate = self
+            .estimates
+            .entry(point_key(&p))
+            .and_modify(|prev| *prev = prev.merge(&estimate))
+            .or_insert(estimate);
+
+        Ok(Sample::from_class(p, estimate.p >= self.alpha))
+    }
+}
+
+/// Adapts a ProbabilisticClassifier to a Classifier, classifying each point by its
+/// reported class. The confidence remains available through
+/// `Classifier::classify_with_confidence()`.
+pub struct ProbabilisticAdapter<C> {
+    classifier: C,
+}
+
+impl<C> ProbabilisticAdapter<C> {
+    pub fn new(classifier: C) -> Self {
+        ProbabilisticAdapter { classifier }
+    }
+
+    /// Returns the wrapped classifier.
+    pub fn into_inner(self) -> C {
+        self.classifier
+    }
+}
+
+impl<C, const N: usize> Classifier<N> for ProbabilisticAdapter<C>
+where
+    C: ProbabilisticClassifier<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        Ok(self.classifier.classify_probabilistic(p)?.0)
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        let (sample, confidence) = self.classifier.classify_probabilistic(p)?;
+        Ok((sample, Some(confidence.clamp(0.0, 1.0))))
+    }
+}
+
+#[cfg(test)]
+mod iso_probability_classifier {
+    use nalgebra::SVector;
+
+    use crate::{
+        explorer_core::Explorer,
+        prelude::{ConstantAdhererFactory, Halfspace, MeshExplorer, WithinMode},
+        structs::{Classifier, ProbabilisticClassifier, Result, Sample},
+    };
+
+    use super::{IsoProbabilityClassifier, ProbabilisticAdapter};
+
+    const N_SAMPLES: u32 = 10;
+
+    /// In-mode with a probability that decreases linearly from 1 at a radius of 0.2
+    /// to 0 at a radius of 0.3, making the p = 0.5 surface a sphere of radius 0.25.
+    /// Deterministically cycles through N_SAMPLES outcomes.
+    struct NoisySphere<const N: usize> {
+        i: u32,
+    }
+
+    impl<const N: usize> Classifier<N> for NoisySphere<N> {
+        fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+            let r = (p - SVector::<f64, N>::repeat(0.5)).norm();
+            let prob = ((0.3 - r) / 0.1).clamp(0.0, 1.0);
+            let cls = (self.i % N_SAMPLES) < (prob * N_SAMPLES as f64).round() as u32;
+            self.i += 1;
+            Ok(Sample::from_class(p, cls))
+        }
+    }
+
+    #[test]
+    fn classifies_by_probability_threshold() {
+        let mut classifier = IsoProbabilityClassifier::new(NoisySphere::<3> { i: 0 }, 0.5, 10);
+        let inside = SVector::from([0.5 + 0.22, 0.5, 0.5]);
+        let outside = SVector::from([0.5 + 0.28, 0.5, 0.5]);
+
+        assert!(classifier.classify(inside).unwrap().class());
+        assert!(!classifier.classify(outside).unwrap().class());
+
+        let est = classifier.estimate(&inside).expect("Missing estimate?");
+        assert!((est.p - 0.8).abs() < 1e-10, "Incorrect estimate: {est:?}");
+    }
+
+    #[test]
+    fn adapter_reports_confidence() {
+        struct Calibrated;
+        impl ProbabilisticClassifier<2> for Calibrated {
+            fn classify_probabilistic(&mut self, p: SVector<f64, 2>) -> Result<(Sample<2>, f64)> {
+                let cls = p[0] > 0.5;
+                Ok((Sample::from_class(p, cls), 0.5 + (p[0] - 0.5).abs()))
+            }
+        }
+
+        let mut classifier = ProbabilisticAdapter::new(Calibrated);
+        let p = SVector::from([0.9, 0.5]);
+
+        assert!(classifier.classify(p).unwrap().class());
+        let (sample, confidence) = classifier.classify_with_confidence(p).unwrap();
+        assert!(sample.class());
+        assert!((confidence.unwrap() - 0.9).abs() < 1e-10);
+
+        // Plain classifiers do not report a confidence
+        let mut plain = NoisySphere::<2> { i: 0 };
+        assert_eq!(plain.classify_with_confidence(p).unwrap().1, None);
+    }
+
+    #[test]
+    fn explores_iso_probability_surface() {
+        let d = 0.05;
+        let mut classifier = IsoProbabilityClassifier::new(NoisySphere::<3> { i: 0 }, 0.5, 10);
+        let root = Halfspace {
+            b: WithinMode(SVector::from([0.5 + 0.24, 0.5, 0.5])),
+            n: SVector::from([1.0, 0.0, 0.0]),
+        };
+        let adherer_f = ConstantAdhererFactory::new(10.0f64.to_radians(), None);
+        let mut expl = MeshExplorer::new(d, root, d * 0.9, adherer_f);
+
+        for _ in 0..2000 {
+            if let Ok(None) = expl.step(&mut classifier) {
+                break;
+            }
+        }
+
+        let uncertainty = classifier.boundary_uncertainty(expl.boundary());
+        assert!(
+            uncertainty.iter().skip(1).all(|est| est.is_some()),
+            "Boundary points were missing probability estimates?"
+        );
+
+        assert!(
+            expl.boundary()
+                .iter()
+                .all(|hs| ((hs.b - SVector::repeat(0.5)).norm() - 0.25).abs() <= d),
+            "Boundary points strayed from the iso-probability surface."
+        );
+    }
+}
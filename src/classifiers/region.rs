@@ -0,0 +1,84 @@
+use nalgebra::SVector;
+
+use crate::structs::{Classifier, Region, Result, Sample, SamplingError};
+
+/// Restricts a classifier to a region, e.g. an ObliqueDomain, so that points
+/// outside of the region are reported as OutOfBounds instead of being classified.
+/// Explorers then prune their paths at the region's edge, as they do at the edge
+/// of the classifier's own domain.
+pub struct RegionClassifier<C, R> {
+    classifier: C,
+    region: R,
+}
+
+impl<C, R> RegionClassifier<C, R> {
+    pub fn new(classifier: C, region: R) -> Self {
+        RegionClassifier { classifier, region }
+    }
+
+    pub fn region(&self) -> &R {
+        &self.region
+    }
+
+    pub fn into_inner(self) -> C {
+        self.classifier
+    }
+}
+
+impl<C, R, const N: usize> Classifier<N> for RegionClassifier<C, R>
+where
+    C: Classifier<N>,
+    R: Region<N>,
+{
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        if !self.region.contains(&p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+        self.classifier.classify(p)
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        if points.iter().any(|p| !self.region.contains(p)) {
+            return Err(SamplingError::OutOfBounds);
+        }
+        self.classifier.classify_batch(points)
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        if !self.region.contains(&p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+        self.classifier.classify_with_confidence(p)
+    }
+}
+
+#[cfg(test)]
+mod region_classifier_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::structs::{Domain, FunctionClassifier, ObliqueDomain};
+
+    use super::*;
+
+    #[test]
+    fn rejects_points_outside_of_region() {
+        // The lower triangle of the unit square
+        let region =
+            ObliqueDomain::from_constraints(Domain::normalized(), &[(vector![-1.0, 1.0], 0.0)]);
+        let mut classifier = RegionClassifier::new(
+            FunctionClassifier::new(|p: SVector<f64, 2>| Ok(p[0] > 0.5)),
+            region,
+        );
+
+        assert!(classifier.classify(vector![0.7, 0.2]).unwrap().class());
+        assert!(!classifier.classify(vector![0.3, 0.2]).unwrap().class());
+        assert_eq!(
+            classifier.classify(vector![0.3, 0.6]),
+            Err(SamplingError::OutOfBounds)
+        );
+        assert_eq!(
+            classifier.classify_batch(&[vector![0.7, 0.2], vector![0.3, 0.6]]),
+            Err(SamplingError::OutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use nalgebra::SVector;
+
+use crate::{
+    structs::{Classifier, Result, Sample, SamplingError},
+    utils::point_key,
+};
+
+/// Writes classified samples to a transcript file, one sample per line as the
+/// comma separated coordinates followed by the class (1 or 0). Lines starting
+/// with '#' are comments.
+pub struct TranscriptWriter {
+    writer: BufWriter<File>,
+}
+
+/// Serves classifications recorded in a transcript, allowing an exploration to be
+/// re-run deterministically without the FUT.
+pub struct ReplayClassifier<const N: usize> {
+    samples: HashMap<[u64; N], Sample<N>>,
+    tolerance: Option<f64>,
+}
+
+impl TranscriptWriter {
+    /// Creates a new transcript at @path, overwriting any existing file.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "# sembas transcript")?;
+        Ok(TranscriptWriter { writer })
+    }
+
+    /// Appends a classified sample to the transcript.
+    pub fn record<const N: usize>(&mut self, sample: &Sample<N>) -> io::Result<()> {
+        for x in sample.iter() {
+            write!(self.writer, "{x},")?;
+        }
+        writeln!(self.writer, "{}", sample.class() as u8)?;
+        self.writer.flush()
+    }
+}
+
+impl<const N: usize> ReplayClassifier<N> {
+    /// Creates a ReplayClassifier from previously classified samples.
+    pub fn from_samples(samples: Vec<Sample<N>>) -> Self {
+        ReplayClassifier {
+            samples: samples.into_iter().map(|s| (point_key(&s), s)).collect(),
+            tolerance: None,
+        }
+    }
+
+    /// Loads a transcript written by a TranscriptWriter.
+    /// ## Error (Err)
+    /// * InvalidData : If a line does not contain N coordinates and a class.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut samples = vec![];
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid transcript entry on line {}: {line}", i + 1),
+                )
+            };
+
+            let values: Vec<&str> = line.split(',').collect();
+            if values.len() != N + 1 {
+                return Err(invalid());
+            }
+
+            let mut p = SVector::<f64, N>::zeros();
+            for (x, v) in p.iter_mut().zip(&values[..N]) {
+                *x = v.parse().map_err(|_| invalid())?;
+            }
+            let cls = match values[N] {
+                "1" => true,
+                "0" => false,
+                _ => return Err(invalid()),
+            };
+
+            samples.push(Sample::from_class(p, cls));
+        }
+
+        Ok(ReplayClassifier::from_samples(samples))
+    }
+
+    /// Allows points that were not recorded exactly to be answered by the nearest
+    /// recorded sample within @tolerance distance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(tolerance >= 0.0, "Tolerance must be non-negative!");
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// The number of recorded samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+impl<const N: usize> Classifier<N> for ReplayClassifier<N> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        if let Some(sample) = self.samples.get(&point_key(&p)) {
+            return Ok(*sample);
+        }
+
+        self.tolerance
+            .and_then(|tol| {
+                self.samples
+                    .values()
+                    .map(|s| ((**s - p).norm(), s))
+                    .filter(|(dist, _)| *dist <= tol)
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            })
+            .map(|(_, s)| Sample::from_class(p, s.class()))
+            .ok_or_else(|| {
+                SamplingError::InvalidClassifierResponse(format!(
+                    "Point was not recorded in the transcript: {p:?}"
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod transcript_replay {
+    use nalgebra::vector;
+
+    use super::*;
+
+    #[test]
+    fn replays_recorded_transcript() {
+        let path = std::env::temp_dir().join("sembas_transcript_replay.csv");
+        let samples = [
+            Sample::from_class(vector![0.1, 0.2], true),
+            Sample::from_class(vector![1.0 / 3.0, 0.7], false),
+        ];
+
+        let mut writer = TranscriptWriter::create(&path).unwrap();
+        for s in samples.iter() {
+            writer.record(s).unwrap();
+        }
+        drop(writer);
+
+        let mut replay = ReplayClassifier::<2>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replay.len(), 2);
+        for s in samples {
+            assert_eq!(replay.classify(*s).unwrap(), s);
+        }
+        assert!(replay.classify(vector![0.1, 0.2001]).is_err());
+
+        let mut replay = replay.with_tolerance(1e-3);
+        assert!(replay.classify(vector![0.1, 0.2001]).unwrap().class());
+    }
+}
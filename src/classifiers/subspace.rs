@@ -0,0 +1,119 @@
+use nalgebra::SVector;
+
+use crate::structs::{subspace::Subspace, Classifier, Result, Sample};
+
+/// Adapts an N-dimensional classifier to an M-dimensional subspace, allowing any
+/// explorer to explore the boundary's intersection with the subspace. Explored
+/// boundaries can be mapped back with `Subspace::lift_boundary`.
+pub struct SubspaceClassifier<'a, C, const N: usize, const M: usize>
+where
+    C: Classifier<N>,
+{
+    classifier: &'a mut C,
+    subspace: &'a Subspace<N, M>,
+}
+
+impl<'a, C, const N: usize, const M: usize> SubspaceClassifier<'a, C, N, M>
+where
+    C: Classifier<N>,
+{
+    pub fn new(classifier: &'a mut C, subspace: &'a Subspace<N, M>) -> Self {
+        SubspaceClassifier {
+            classifier,
+            subspace,
+        }
+    }
+}
+
+impl<C, const N: usize, const M: usize> Classifier<M> for SubspaceClassifier<'_, C, N, M>
+where
+    C: Classifier<N>,
+{
+    fn classify(&mut self, q: SVector<f64, M>) -> Result<Sample<M>> {
+        let cls = self.classifier.classify(self.subspace.lift(&q))?.class();
+        Ok(Sample::from_class(q, cls))
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, M>]) -> Result<Vec<Sample<M>>> {
+        let lifted: Vec<SVector<f64, N>> = points.iter().map(|q| self.subspace.lift(q)).collect();
+        let samples = self.classifier.classify_batch(&lifted)?;
+        Ok(points
+            .iter()
+            .zip(samples)
+            .map(|(&q, s)| Sample::from_class(q, s.class()))
+            .collect())
+    }
+}
+
+#[cfg(all(test, feature = "global_search"))]
+mod subspace_exploration {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        explorer_core::Explorer,
+        prelude::{
+            ConstantAdhererFactory, Domain, FunctionClassifier, Halfspace, MeshExplorer,
+            SamplingError, WithinMode,
+        },
+        search::global_search::{MonteCarloSearch, SearchFactory},
+        structs::{subspace::Subspace, Classifier},
+    };
+
+    use super::SubspaceClassifier;
+
+    const RADIUS: f64 = 0.25;
+
+    /// A cylinder along the z axis, whose boundary only depends on x and y.
+    fn cylinder(p: SVector<f64, 3>) -> Result<bool, SamplingError> {
+        if !Domain::<3>::normalized().contains(&p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+        Ok(((p[0] - 0.5).powi(2) + (p[1] - 0.5).powi(2)).sqrt() <= RADIUS)
+    }
+
+    #[test]
+    fn identifies_active_subspace() {
+        let mut classifier = FunctionClassifier::new(cylinder);
+        let mut mc = MonteCarloSearch::new(Domain::<3>::normalized(), 1);
+        let samples: Vec<_> = (0..500)
+            .map(|_| classifier.classify(mc.sample()).unwrap())
+            .collect();
+
+        let subspace: Subspace<3, 2> = Subspace::from_samples(SVector::repeat(0.5), &samples)
+            .expect("Failed to identify subspace?");
+
+        for i in 0..2 {
+            assert!(
+                subspace.basis()[(2, i)].abs() < 0.2,
+                "Basis included the inactive z dimension: {:?}",
+                subspace.basis()
+            );
+        }
+    }
+
+    #[test]
+    fn explores_and_lifts_boundary() {
+        let d = 0.05;
+        let mut classifier = FunctionClassifier::new(cylinder);
+        let subspace = Subspace::from_directions(
+            SVector::repeat(0.5),
+            [vector![1.0, 0.0, 0.0], vector![0.0, 1.0, 0.0]],
+        );
+        let mut sub_classifier = SubspaceClassifier::new(&mut classifier, &subspace);
+
+        let root = Halfspace {
+            b: WithinMode(vector![RADIUS - 0.01, 0.0]),
+            n: vector![1.0, 0.0],
+        };
+        let adherer_f = ConstantAdhererFactory::new(10.0f64.to_radians(), None);
+        let mut expl = MeshExplorer::new(d, root, d * 0.9, adherer_f);
+        while let Ok(Some(_)) = expl.step(&mut sub_classifier) {}
+
+        let boundary = subspace.lift_boundary(expl.boundary());
+        assert!(boundary.len() > 10, "Failed to explore the subspace?");
+        assert!(boundary.iter().all(|hs| {
+            let r = ((hs.b[0] - 0.5).powi(2) + (hs.b[1] - 0.5).powi(2)).sqrt();
+            (r - RADIUS).abs() <= d && hs.b[2] == 0.5
+        }));
+    }
+}
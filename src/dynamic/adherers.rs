@@ -0,0 +1,267 @@
+use std::f64::consts::PI;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::structs::{Result, SamplingError};
+
+use super::{DClassifier, DHalfspace, DSample, DSpan};
+
+/// A valid state of a DAdherer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DAdhererState {
+    Searching,
+    FoundBoundary(DHalfspace),
+}
+
+/// Acquires a boundary halfspace relative to a known adjacent halfspace. The
+/// dynamic counterpart of Adherer.
+pub trait DAdherer {
+    /// Takes a step in the adherence process. See `Adherer::sample_next()`.
+    fn sample_next<C: DClassifier>(&mut self, classifier: &mut C) -> Result<&DSample>;
+
+    /// Returns the current state of the adherer, either Searching or
+    /// FoundBoundary(hs) where hs is the resulting halfspace.
+    fn get_state(&self) -> &DAdhererState;
+}
+
+/// Builds a DAdherer and returns it. The dynamic counterpart of AdhererFactory.
+pub trait DAdhererFactory: Copy + Clone {
+    type TargetAdherer: DAdherer;
+    /// Constructs a DAdherer that will find a boundary halfspace neighboring the
+    /// given @hs halfspace in the given direction @v. See
+    /// `AdhererFactory::adhere_from()`.
+    fn adhere_from(&self, hs: &DHalfspace, v: DVector<f64>) -> Self::TargetAdherer;
+}
+
+/// Pivots around a known boundary halfspace by taking fixed-angle rotations until
+/// the boundary is crossed. The dynamic counterpart of ConstantAdherer.
+#[derive(Debug)]
+pub struct DConstantAdherer {
+    span: DSpan,
+    pivot: DHalfspace,
+    v: DVector<f64>,
+    samples: Vec<DSample>,
+    rot: Option<DMatrix<f64>>,
+    angle: f64,
+    delta_angle: f64,
+    max_rotation: f64,
+    pub state: DAdhererState,
+}
+
+/// Builds a DConstantAdherer instance.
+#[derive(Debug, Copy, Clone)]
+pub struct DConstantAdhererFactory {
+    delta_angle: f64,
+    max_rotation: Option<f64>,
+}
+
+/// Pivots around a known boundary halfspace by rotating with a binary search until
+/// the boundary is found. The dynamic counterpart of BinarySearchAdherer.
+pub struct DBinarySearchAdherer {
+    pivot: DHalfspace,
+    v: DVector<f64>,
+    samples: Vec<DSample>,
+    n_iter: u32,
+    angle: f64,
+    prev_cls: Option<bool>,
+    t: Option<DVector<f64>>,
+    x: Option<DVector<f64>>,
+    rot_factory: Box<dyn Fn(f64) -> DMatrix<f64>>,
+    pub state: DAdhererState,
+}
+
+/// Builds a DBinarySearchAdherer instance.
+#[derive(Debug, Copy, Clone)]
+pub struct DBinarySearchAdhererFactory {
+    init_angle: f64,
+    n_iter: u32,
+}
+
+impl DConstantAdherer {
+    /// Creates a DConstantAdherer. See `ConstantAdherer::new()`.
+    pub fn new(
+        pivot: DHalfspace,
+        v: DVector<f64>,
+        delta_angle: f64,
+        max_rotation: Option<f64>,
+    ) -> Self {
+        let span = DSpan::new(&pivot.n, &v);
+
+        DConstantAdherer {
+            span,
+            pivot,
+            v,
+            delta_angle,
+            max_rotation: max_rotation.unwrap_or(PI),
+            rot: None,
+            samples: vec![],
+            angle: 0.0,
+            state: DAdhererState::Searching,
+        }
+    }
+
+    fn take_initial_sample<C: DClassifier>(&mut self, classifier: &mut C) -> Result<DSample> {
+        let sample = classifier.classify(&(&self.pivot.b + &self.v))?;
+        let delta_angle = if sample.class() {
+            self.delta_angle
+        } else {
+            -self.delta_angle
+        };
+        self.rot = Some(self.span.get_rotater()(delta_angle));
+        Ok(sample)
+    }
+
+    fn take_sample<C: DClassifier>(&mut self, classifier: &mut C) -> Result<DSample> {
+        let rot = self.rot.as_ref().expect("Rotation must be set");
+        let v = rot * &self.v;
+
+        // The rotation is only applied once classified, so that a failed request
+        // can be retried.
+        let sample = classifier.classify(&(&self.pivot.b + &v))?;
+        self.v = v;
+        self.angle += self.delta_angle;
+
+        Ok(sample)
+    }
+}
+
+impl DAdherer for DConstantAdherer {
+    fn get_state(&self) -> &DAdhererState {
+        &self.state
+    }
+
+    fn sample_next<C: DClassifier>(&mut self, classifier: &mut C) -> Result<&DSample> {
+        let cur = if self.rot.is_some() {
+            self.take_sample(classifier)?
+        } else {
+            self.take_initial_sample(classifier)?
+        };
+
+        if let Some(prev) = self.samples.last() {
+            match (&cur, prev) {
+                (DSample::WithinMode(t), DSample::OutOfMode(_))
+                | (DSample::OutOfMode(_), DSample::WithinMode(t)) => {
+                    let s = t - &self.pivot.b;
+                    let rot90 = self.span.get_rotater()(PI / 2.0);
+                    let n = (rot90 * s).normalize();
+                    self.state = DAdhererState::FoundBoundary(DHalfspace { b: t.clone(), n });
+                }
+                _ => (),
+            }
+        }
+
+        if matches!(self.state, DAdhererState::Searching) && self.angle > self.max_rotation {
+            return Err(SamplingError::BoundaryLost);
+        }
+
+        self.samples.push(cur);
+
+        Ok(self
+            .samples
+            .last()
+            .expect("Invalid state, cur was not added to samples?"))
+    }
+}
+
+impl DConstantAdhererFactory {
+    pub fn new(delta_angle: f64, max_rotation: Option<f64>) -> Self {
+        DConstantAdhererFactory {
+            delta_angle,
+            max_rotation,
+        }
+    }
+}
+
+impl DAdhererFactory for DConstantAdhererFactory {
+    type TargetAdherer = DConstantAdherer;
+    fn adhere_from(&self, hs: &DHalfspace, v: DVector<f64>) -> DConstantAdherer {
+        DConstantAdherer::new(hs.clone(), v, self.delta_angle, self.max_rotation)
+    }
+}
+
+impl DBinarySearchAdherer {
+    /// Creates a DBinarySearchAdherer. See `BinarySearchAdherer::new()`.
+    pub fn new(pivot: DHalfspace, v: DVector<f64>, init_angle: f64, n_iter: u32) -> Self {
+        let rot_factory = DSpan::new(&pivot.n, &v).get_rotater();
+
+        DBinarySearchAdherer {
+            pivot,
+            v,
+            samples: vec![],
+            n_iter,
+            angle: init_angle,
+            prev_cls: None,
+            t: None,
+            x: None,
+            rot_factory: Box::new(rot_factory),
+            state: DAdhererState::Searching,
+        }
+    }
+
+    fn take_sample<C: DClassifier>(&mut self, classifier: &mut C) -> Result<DSample> {
+        let v = match self.prev_cls {
+            Some(prev_cls) => {
+                let ccw = if prev_cls { 1.0 } else { -1.0 };
+                (self.rot_factory)(ccw * self.angle) * &self.v
+            }
+            None => self.v.clone(),
+        };
+
+        let sample = classifier.classify(&(&self.pivot.b + &v))?;
+        if self.prev_cls.is_some() {
+            self.angle /= 2.0;
+        }
+        self.v = v;
+        self.prev_cls = Some(sample.class());
+
+        match &sample {
+            DSample::WithinMode(t) => self.t = Some(t.clone()),
+            DSample::OutOfMode(x) => self.x = Some(x.clone()),
+        }
+
+        self.n_iter -= 1;
+
+        Ok(sample)
+    }
+}
+
+impl DAdherer for DBinarySearchAdherer {
+    fn get_state(&self) -> &DAdhererState {
+        &self.state
+    }
+
+    fn sample_next<C: DClassifier>(&mut self, classifier: &mut C) -> Result<&DSample> {
+        let cur = self.take_sample(classifier)?;
+
+        if self.n_iter == 0 {
+            if let (Some(t), Some(_)) = (&self.t, &self.x) {
+                let rot90 = (self.rot_factory)(PI / 2.0);
+                let s = t - &self.pivot.b;
+                let n = (rot90 * s).normalize();
+                self.state = DAdhererState::FoundBoundary(DHalfspace { b: t.clone(), n })
+            } else {
+                return Err(SamplingError::BoundaryLost);
+            }
+        }
+
+        self.samples.push(cur);
+
+        Ok(self
+            .samples
+            .last()
+            .expect("Invalid state, cur was not added to samples?"))
+    }
+}
+
+impl DBinarySearchAdhererFactory {
+    pub fn new(init_angle: f64, n_iter: u32) -> Self {
+        DBinarySearchAdhererFactory { init_angle, n_iter }
+    }
+}
+
+impl DAdhererFactory for DBinarySearchAdhererFactory {
+    type TargetAdherer = DBinarySearchAdherer;
+    fn adhere_from(&self, hs: &DHalfspace, v: DVector<f64>) -> DBinarySearchAdherer {
+        DBinarySearchAdherer::new(hs.clone(), v, self.init_angle, self.n_iter)
+    }
+}
@@ -0,0 +1,254 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::{extensions::Queue, prelude::NodeID, structs::Result};
+
+use super::{DAdherer, DAdhererFactory, DAdhererState, DClassifier, DHalfspace, DSample, DSpan};
+
+/// A path along the surface, from the boundary point with the given id in the
+/// given direction.
+pub type DPath = (NodeID, DVector<f64>);
+
+/// Explores a surface uniformly by using a grid-search approach, for input spaces
+/// whose dimensionality is only known at runtime. The dynamic counterpart of
+/// MeshExplorer.
+///
+/// Prefer MeshExplorer when the dimensionality is known at compile time: it avoids
+/// heap-allocated vectors and indexes the boundary for nearest-neighbor queries,
+/// whereas DMeshExplorer checks paths for overlap against every known boundary
+/// point.
+pub struct DMeshExplorer<F: DAdhererFactory> {
+    d: f64,
+    dim: usize,
+    boundary: Vec<DHalfspace>,
+    margin: f64,
+    path_queue: Vec<DPath>,
+    current_parent: NodeID,
+    adherer: Option<F::TargetAdherer>,
+    adherer_f: F,
+}
+
+impl<F: DAdhererFactory> DMeshExplorer<F> {
+    /// Creates a DMeshExplorer instance. See `MeshExplorer::new()`.
+    /// ## Arguments
+    /// * d: The jump distance between boundary points.
+    /// * root: The initial boundary halfspace to begin exploration from. Its
+    ///   dimensionality determines that of the explored space.
+    /// * margin: 0 < margin < d, The minimum distance between a sample and a known
+    ///   halfspace before a path along a cardinal direction is rejected.
+    pub fn new(d: f64, root: DHalfspace, margin: f64, adherer_f: F) -> Self {
+        let dim = root.dim();
+        assert!(dim >= 2, "Explored space must have at least 2 dimensions!");
+        assert_eq!(
+            root.n.len(),
+            dim,
+            "Root's surface vector and boundary point have different dimensionality!"
+        );
+
+        let mut exp = DMeshExplorer {
+            d,
+            dim,
+            boundary: vec![],
+            margin,
+            path_queue: vec![],
+            current_parent: 0,
+            adherer: None,
+            adherer_f,
+        };
+
+        exp.add_child(root);
+
+        exp
+    }
+
+    /// The dimensionality of the explored space.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Takes a step in the exploration process. See `Explorer::step()`.
+    pub fn step<C: DClassifier>(&mut self, classifier: &mut C) -> Result<Option<DSample>> {
+        if self.adherer.is_none() {
+            if let Some((id, v)) = self.select_parent() {
+                self.current_parent = id;
+                self.adherer = Some(self.adherer_f.adhere_from(&self.boundary[id], v * self.d));
+            }
+        }
+
+        let Some(adh) = self.adherer.as_mut() else {
+            // Ends exploration
+            return Ok(None);
+        };
+
+        match adh.sample_next(classifier) {
+            Ok(sample) => {
+                let sample = sample.clone();
+                if let DAdhererState::FoundBoundary(hs) = adh.get_state() {
+                    let hs = hs.clone();
+                    self.add_child(hs);
+                    self.adherer = None;
+                }
+
+                Ok(Some(sample))
+            }
+            Err(e) => {
+                // A lost connection is retryable, so the path is kept for the next
+                // step.
+                if !e.is_retryable() {
+                    self.adherer = None;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    pub fn boundary(&self) -> &Vec<DHalfspace> {
+        &self.boundary
+    }
+
+    pub fn boundary_owned(self) -> Vec<DHalfspace> {
+        self.boundary
+    }
+
+    pub fn boundary_count(&self) -> usize {
+        self.boundary.len()
+    }
+
+    /// Finds the cardinal directions of the surface described by @n. See
+    /// `MeshExplorer::create_cardinals()`.
+    pub fn create_cardinals(n: &DVector<f64>) -> Vec<DVector<f64>> {
+        let basis_vectors = DMatrix::<f64>::identity(n.len(), n.len());
+        let align_vector: DVector<f64> = basis_vectors.column(0).into();
+        let angle = align_vector.angle(n);
+
+        let axes = if angle <= 1e-10 {
+            basis_vectors
+        } else {
+            let rot = DSpan::new(n, &align_vector).get_rotater()(angle);
+            rot * basis_vectors
+        };
+
+        let mut cardinals = vec![];
+
+        for i in 1..axes.ncols() {
+            let column: DVector<f64> = axes.column(i).into();
+            cardinals.push(column.clone());
+            cardinals.push(-column);
+        }
+
+        cardinals
+    }
+
+    fn select_parent(&mut self) -> Option<DPath> {
+        while let Some((id, v)) = self.path_queue.dequeue() {
+            let p = &self.boundary[id].b + self.d * &v;
+
+            if !self.check_overlap(&p) {
+                return Some((id, v));
+            }
+        }
+
+        None
+    }
+
+    fn add_child(&mut self, hs: DHalfspace) {
+        assert_eq!(
+            hs.dim(),
+            self.dim,
+            "Halfspace has the wrong dimensionality! Expected {}, Got {}",
+            self.dim,
+            hs.dim()
+        );
+        let id = self.boundary.len();
+
+        self.path_queue
+            .extend(Self::create_cardinals(&hs.n).into_iter().map(|v| (id, v)));
+        self.boundary.push(hs);
+    }
+
+    fn check_overlap(&self, p: &DVector<f64>) -> bool {
+        self.boundary
+            .iter()
+            .any(|hs| (&hs.b - p).norm() < self.margin)
+    }
+}
+
+#[cfg(test)]
+mod dynamic_mesh_explorer {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        dynamic::{
+            DBinarySearchAdhererFactory, DConstantAdhererFactory, DFunctionClassifier, DHalfspace,
+        },
+        prelude::{ConstantAdhererFactory, Explorer, FunctionClassifier, Halfspace, MeshExplorer},
+        structs::{SamplingError, WithinMode},
+    };
+
+    use super::DMeshExplorer;
+
+    const D: f64 = 0.1;
+    const RADIUS: f64 = 0.25;
+
+    fn root() -> Halfspace<3> {
+        Halfspace {
+            b: WithinMode(vector![0.5 + RADIUS - 0.01, 0.5, 0.5]),
+            n: vector![1.0, 0.0, 0.0],
+        }
+    }
+
+    fn in_sphere(p: &[f64]) -> Result<bool, SamplingError> {
+        if p.iter().any(|x| !(0.0..=1.0).contains(x)) {
+            return Err(SamplingError::OutOfBounds);
+        }
+        Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum::<f64>().sqrt() <= RADIUS)
+    }
+
+    #[test]
+    fn matches_fixed_dimension_explorer() {
+        let mut fixed_classifier =
+            FunctionClassifier::new(|p: SVector<f64, 3>| in_sphere(p.as_slice()));
+        let mut fixed = MeshExplorer::new(
+            D,
+            root(),
+            0.9 * D,
+            ConstantAdhererFactory::new(5.0f64.to_radians(), None),
+        );
+        while !matches!(fixed.step(&mut fixed_classifier), Ok(None)) {}
+
+        let mut classifier = DFunctionClassifier::new(|p| in_sphere(p.as_slice()));
+        let mut expl = DMeshExplorer::new(
+            D,
+            DHalfspace::from(root()),
+            0.9 * D,
+            DConstantAdhererFactory::new(5.0f64.to_radians(), None),
+        );
+        while !matches!(expl.step(&mut classifier), Ok(None)) {}
+
+        assert_eq!(expl.dim(), 3);
+        assert_eq!(expl.boundary_count(), fixed.boundary_count());
+        for (dhs, hs) in expl.boundary().iter().zip(fixed.boundary()) {
+            let dhs = dhs.clone().into_fixed::<3>().expect("Wrong dimensionality");
+            assert!((*dhs.b - *hs.b).norm() < 1e-10);
+            assert!((dhs.n - hs.n).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn explores_with_binary_search_adherer() {
+        let mut classifier = DFunctionClassifier::new(|p| in_sphere(p.as_slice()));
+        let mut expl = DMeshExplorer::new(
+            D,
+            DHalfspace::from(root()),
+            0.9 * D,
+            DBinarySearchAdhererFactory::new(120.0f64.to_radians(), 4),
+        );
+        while !matches!(expl.step(&mut classifier), Ok(None)) {}
+
+        let center = vector![0.5, 0.5, 0.5];
+        assert!(expl.boundary_count() > 20);
+        assert!(expl.boundary().iter().all(|hs| {
+            let b = SVector::<f64, 3>::from_column_slice(hs.b.as_slice());
+            ((b - center).norm() - RADIUS).abs() < D
+        }));
+    }
+}
@@ -0,0 +1,202 @@
+use nalgebra::{DMatrix, DVector, SVector};
+
+use crate::structs::{Halfspace, OutOfMode, Result, Sample, WithinMode};
+
+pub mod adherers;
+pub mod mesh_explorer;
+
+pub use adherers::*;
+pub use mesh_explorer::*;
+
+/// A sample whose dimensionality is only known at runtime. The dynamic
+/// counterpart of Sample, for use with DClassifier and the D-prefixed explorers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DSample {
+    WithinMode(DVector<f64>),
+    OutOfMode(DVector<f64>),
+}
+
+/// A halfspace whose dimensionality is only known at runtime. The dynamic
+/// counterpart of Halfspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DHalfspace {
+    pub b: DVector<f64>,
+    pub n: DVector<f64>,
+}
+
+/// A system under test whose input dimensionality is only known at runtime, e.g.
+/// when the scenario schema is loaded from a config file. The dynamic counterpart
+/// of Classifier.
+pub trait DClassifier {
+    fn classify(&mut self, p: &DVector<f64>) -> Result<DSample>;
+}
+
+/// A DClassifier defined by a function (p: &DVector) -> Result<bool>
+pub struct DFunctionClassifier<F>
+where
+    F: FnMut(&DVector<f64>) -> Result<bool>,
+{
+    fut: F,
+}
+
+impl DSample {
+    pub fn from_class(p: DVector<f64>, cls: bool) -> Self {
+        if cls {
+            DSample::WithinMode(p)
+        } else {
+            DSample::OutOfMode(p)
+        }
+    }
+
+    /// Strips the point of the semantics, returning the raw DVector
+    pub fn into_inner(self) -> DVector<f64> {
+        match self {
+            DSample::WithinMode(p) | DSample::OutOfMode(p) => p,
+        }
+    }
+
+    pub fn point(&self) -> &DVector<f64> {
+        match self {
+            DSample::WithinMode(p) | DSample::OutOfMode(p) => p,
+        }
+    }
+
+    pub fn class(&self) -> bool {
+        matches!(self, DSample::WithinMode(_))
+    }
+
+    /// Converts into a Sample of fixed dimensionality, or None if N does not match
+    /// the sample's dimensionality.
+    pub fn into_fixed<const N: usize>(self) -> Option<Sample<N>> {
+        let cls = self.class();
+        let p = self.into_inner();
+        (p.len() == N).then(|| Sample::from_class(SVector::from_column_slice(p.as_slice()), cls))
+    }
+}
+
+impl<const N: usize> From<Sample<N>> for DSample {
+    fn from(value: Sample<N>) -> Self {
+        let cls = value.class();
+        DSample::from_class(
+            DVector::from_column_slice(value.into_inner().as_slice()),
+            cls,
+        )
+    }
+}
+
+impl DHalfspace {
+    /// The dimensionality of the halfspace.
+    pub fn dim(&self) -> usize {
+        self.b.len()
+    }
+
+    /// Converts into a Halfspace of fixed dimensionality, or None if N does not
+    /// match the halfspace's dimensionality.
+    pub fn into_fixed<const N: usize>(self) -> Option<Halfspace<N>> {
+        (self.dim() == N).then(|| Halfspace {
+            b: WithinMode(SVector::from_column_slice(self.b.as_slice())),
+            n: SVector::from_column_slice(self.n.as_slice()),
+        })
+    }
+}
+
+impl<const N: usize> From<Halfspace<N>> for DHalfspace {
+    fn from(value: Halfspace<N>) -> Self {
+        DHalfspace {
+            b: DVector::from_column_slice(value.b.as_slice()),
+            n: DVector::from_column_slice(value.n.as_slice()),
+        }
+    }
+}
+
+impl<const N: usize> From<OutOfMode<N>> for DSample {
+    fn from(value: OutOfMode<N>) -> Self {
+        DSample::OutOfMode(DVector::from_column_slice(value.as_slice()))
+    }
+}
+
+impl<const N: usize> From<WithinMode<N>> for DSample {
+    fn from(value: WithinMode<N>) -> Self {
+        DSample::WithinMode(DVector::from_column_slice(value.as_slice()))
+    }
+}
+
+impl<F> DFunctionClassifier<F>
+where
+    F: FnMut(&DVector<f64>) -> Result<bool>,
+{
+    pub fn new(fut: F) -> Self {
+        Self { fut }
+    }
+}
+
+impl<F> DClassifier for DFunctionClassifier<F>
+where
+    F: FnMut(&DVector<f64>) -> Result<bool>,
+{
+    fn classify(&mut self, p: &DVector<f64>) -> Result<DSample> {
+        Ok(DSample::from_class(p.clone(), (self.fut)(p)?))
+    }
+}
+
+/// A 2-dimensional subspace of a runtime-dimensional input space. The dynamic
+/// counterpart of Span.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DSpan {
+    u: DVector<f64>,
+    v: DVector<f64>,
+}
+
+impl DSpan {
+    /// Constructs a DSpan across @u and @v. See `Span::new()`.
+    pub fn new(u: &DVector<f64>, v: &DVector<f64>) -> Self {
+        let u = u.normalize();
+        let v = v.normalize();
+        let v = (&v - &u * u.dot(&v)).normalize();
+        DSpan { u, v }
+    }
+
+    /// Provides a rotater function rot(angle: f64) which returns a rotation matrix
+    /// that rotates by an angle in radians along &self's span.
+    pub fn get_rotater(&self) -> impl Fn(f64) -> DMatrix<f64> {
+        let identity = DMatrix::<f64>::identity(self.u.len(), self.u.len());
+
+        let a = &self.u * self.v.transpose() - &self.v * self.u.transpose();
+        let b = &self.v * self.v.transpose() + &self.u * self.u.transpose();
+
+        move |angle: f64| &identity + &a * angle.sin() + &b * (angle.cos() - 1.0)
+    }
+}
+
+#[cfg(test)]
+mod dynamic_conversions {
+    use nalgebra::{dvector, vector};
+
+    use super::*;
+
+    #[test]
+    fn halfspace_round_trips_through_fixed() {
+        let hs = Halfspace {
+            b: WithinMode(vector![0.1, 0.2, 0.3]),
+            n: vector![0.0, 1.0, 0.0],
+        };
+
+        let dhs = DHalfspace::from(hs);
+        assert_eq!(dhs.dim(), 3);
+        assert_eq!(dhs.clone().into_fixed::<2>(), None);
+        assert_eq!(dhs.into_fixed::<3>(), Some(hs));
+    }
+
+    #[test]
+    fn rotater_preserves_norm_and_angle() {
+        let u = dvector![0.5, 0.5, 0.1, 0.4, 1.0];
+        let v = dvector![1.1, -0.2, -0.5, 0.1, 0.8];
+        let span = DSpan::new(&u, &v);
+
+        let angle = 25.0f64.to_radians();
+        let x1 = span.get_rotater()(angle) * &v;
+
+        assert!((x1.norm() - v.norm()).abs() < 1e-10);
+        assert!((v.angle(&x1) - angle).abs() < 1e-10);
+    }
+}
@@ -1,8 +1,74 @@
+use std::{marker::PhantomData, time::Instant};
+
+use nalgebra::SVector;
+
 use crate::{
-    prelude::{report::ExplorationStatus, AdhererFactory},
-    structs::{Classifier, Halfspace, Result, Sample},
+    prelude::{
+        report::{EfficiencyReport, ExplorationStatus, PhaseReport},
+        AdherenceFailure, AdherenceStats, AdhererFactory,
+    },
+    structs::{
+        BudgetExhausted, BudgetTracker, Classifier, Halfspace, Result, Sample, SamplingError,
+        StepOutcome,
+    },
 };
 
+/// Receives notifications of exploration events, e.g. for live logging or UI
+/// updates. All methods default to doing nothing. See `Explorer::add_observer()`.
+pub trait ExplorationObserver<const N: usize> {
+    /// A sample was taken.
+    fn on_sample(&mut self, _sample: &Sample<N>) {}
+
+    /// A new boundary halfspace was acquired.
+    fn on_boundary_found(&mut self, _hs: &Halfspace<N>) {}
+
+    /// The path from @parent was abandoned due to @reason, e.g. a lost boundary or
+    /// the domain's edge.
+    fn on_branch_pruned(&mut self, _parent: &Halfspace<N>, _reason: &SamplingError) {}
+
+    /// An adherence failed, e.g. with BoundaryLost, leaving the partial results in
+    /// @failure. Always followed by `on_branch_pruned()`.
+    fn on_adherence_failed(&mut self, _failure: &AdherenceFailure<N>) {}
+
+    /// A step failed with @error.
+    fn on_error(&mut self, _error: &SamplingError) {}
+}
+
+/// The observers registered with an explorer, notifying each in the order they
+/// were added.
+#[derive(Default)]
+pub struct Observers<const N: usize>(Vec<Box<dyn ExplorationObserver<N>>>);
+
+impl<const N: usize> Observers<N> {
+    pub fn add(&mut self, observer: Box<dyn ExplorationObserver<N>>) {
+        self.0.push(observer);
+    }
+
+    pub fn sample(&mut self, sample: &Sample<N>) {
+        self.0.iter_mut().for_each(|o| o.on_sample(sample));
+    }
+
+    pub fn boundary_found(&mut self, hs: &Halfspace<N>) {
+        self.0.iter_mut().for_each(|o| o.on_boundary_found(hs));
+    }
+
+    pub fn branch_pruned(&mut self, parent: &Halfspace<N>, reason: &SamplingError) {
+        self.0
+            .iter_mut()
+            .for_each(|o| o.on_branch_pruned(parent, reason));
+    }
+
+    pub fn adherence_failed(&mut self, failure: &AdherenceFailure<N>) {
+        self.0
+            .iter_mut()
+            .for_each(|o| o.on_adherence_failed(failure));
+    }
+
+    pub fn error(&mut self, error: &SamplingError) {
+        self.0.iter_mut().for_each(|o| o.on_error(error));
+    }
+}
+
 /// The system responsible for the full boundary exploration process. Leverages
 /// Adherers to find neighboring boundary points.
 pub trait Explorer<const N: usize, F>
@@ -37,4 +103,225 @@ where
     fn boundary_count(&self) -> usize;
 
     fn describe(&self) -> ExplorationStatus<N, F>;
+
+    /// Gets every sample taken so far, in order, including those acquired as
+    /// boundary points, if the explorer retains its sample history (e.g.
+    /// `MeshExplorer::with_sample_history()`). The number of samples is the
+    /// number of times the classifier was run.
+    /// ## Returns
+    /// * samples: The retained samples, or None if history is not retained.
+    fn samples(&self) -> Option<&[Sample<N>]> {
+        None
+    }
+
+    /// Gets the statistics of the adherence that acquired each boundary halfspace,
+    /// parallel to `boundary()`, if the explorer records them. Halfspaces that were
+    /// not acquired by adherence (e.g. the root or a loaded boundary) have default
+    /// statistics with no samples.
+    /// ## Returns
+    /// * stats: The per-halfspace statistics, or None if not recorded.
+    fn adherence_stats(&self) -> Option<&[AdherenceStats]> {
+        None
+    }
+
+    /// Registers an observer to be notified of exploration events as they occur.
+    /// Explorers that do not report events drop the observer.
+    fn add_observer(&mut self, observer: Box<dyn ExplorationObserver<N>>) {
+        drop(observer);
+    }
+
+    /// Takes a step in the boundary exploration process, unless @budget has been
+    /// exhausted. Every classification made during the step is spent from
+    /// @budget, including those of failed adherences and batched requests, so a
+    /// step may overrun the sample limit by the samples it takes.
+    /// ## Arguments
+    /// * classifier: The system under test whose target performance boundaries are
+    ///   being explored.
+    /// * budget: The resources remaining for the exploration.
+    /// ## Returns
+    /// * outcome: The sample taken, or why exploration has ended.
+    fn step_within<C: Classifier<N>>(
+        &mut self,
+        classifier: &mut C,
+        budget: &mut BudgetTracker,
+    ) -> Result<StepOutcome<N>> {
+        if let Some(limit) = budget.exhausted(self.boundary_count()) {
+            return Ok(StepOutcome::Terminated(limit));
+        }
+
+        let mut counter = SampleCounter {
+            classifier,
+            n_samples: 0,
+        };
+        let result = self.step(&mut counter);
+        for _ in 0..counter.n_samples {
+            budget.spend_sample();
+        }
+        budget.record_boundary_count(self.boundary_count());
+
+        match result? {
+            Some(sample) => Ok(StepOutcome::Sampled(sample)),
+            None => Ok(StepOutcome::Complete),
+        }
+    }
+
+    /// Creates an iterator over the results of each exploration step, ending once
+    /// the explorer runs out of boundary to explore (i.e. `step()` returns
+    /// Ok(None)).
+    /// ## Example
+    /// ```ignore
+    /// let n_errors = expl.iter(&mut classifier).take(1000).filter(|r| r.is_err()).count();
+    /// ```
+    fn iter<'a, C: Classifier<N>>(
+        &'a mut self,
+        classifier: &'a mut C,
+    ) -> ExplorationIter<'a, N, F, Self, C>
+    where
+        Self: Sized,
+    {
+        ExplorationIter {
+            explorer: self,
+            classifier,
+            done: false,
+            _adherer_f: PhantomData,
+        }
+    }
+
+    /// Explores the boundary until it is fully explored or @budget is exhausted.
+    /// BoundaryLost and OutOfBounds errors prune the explored path and are
+    /// otherwise ignored.
+    /// ## Arguments
+    /// * classifier: The system under test whose target performance boundaries are
+    ///   being explored.
+    /// * budget: The resources remaining for the exploration.
+    /// ## Returns
+    /// * Ok(None): If the boundary was fully explored.
+    /// * Ok(Some(limit)): The limit that ended the exploration.
+    /// * Err(e): If the classifier failed.
+    fn explore<C: Classifier<N>>(
+        &mut self,
+        classifier: &mut C,
+        budget: &mut BudgetTracker,
+    ) -> Result<Option<BudgetExhausted>> {
+        loop {
+            match self.step_within(classifier, budget) {
+                Ok(StepOutcome::Sampled(_)) => (),
+                Ok(StepOutcome::Complete) => return Ok(None),
+                Ok(StepOutcome::Terminated(limit)) => return Ok(Some(limit)),
+                Err(SamplingError::BoundaryLost | SamplingError::OutOfBounds) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Explores the boundary as `explore()` does, reporting the samples, boundary
+    /// points, lost boundaries, out of bounds steps and time it took. The report
+    /// has a single "exploration" phase; prior phases, such as surfacing, can be
+    /// added with `EfficiencyReport::with_phase()`.
+    /// ## Arguments
+    /// * classifier: The system under test whose target performance boundaries are
+    ///   being explored.
+    /// * budget: The resources remaining for the exploration.
+    /// ## Returns
+    /// * Ok((limit, report)): The limit that ended the exploration, or None if the
+    ///   boundary was fully explored, and the efficiency of the exploration.
+    /// * Err(e): If the classifier failed.
+    fn explore_with_report<C: Classifier<N>>(
+        &mut self,
+        classifier: &mut C,
+        budget: &mut BudgetTracker,
+    ) -> Result<(Option<BudgetExhausted>, EfficiencyReport)> {
+        let start = Instant::now();
+        let b_count = self.boundary_count();
+        let n_samples = budget.n_samples();
+        let mut phase = PhaseReport::new("exploration");
+
+        let limit = loop {
+            match self.step_within(classifier, budget) {
+                Ok(StepOutcome::Sampled(_)) => (),
+                Ok(StepOutcome::Complete) => break None,
+                Ok(StepOutcome::Terminated(limit)) => break Some(limit),
+                Err(SamplingError::BoundaryLost) => phase.n_boundary_lost += 1,
+                Err(SamplingError::OutOfBounds) => phase.n_out_of_bounds += 1,
+                Err(e) => return Err(e),
+            }
+        };
+
+        phase.n_samples = budget.n_samples() - n_samples;
+        phase.n_boundary_points = self.boundary_count().saturating_sub(b_count);
+        phase.duration = start.elapsed();
+
+        Ok((limit, EfficiencyReport::new().with_phase(phase)))
+    }
+}
+
+/// Counts the samples successfully classified by the wrapped classifier.
+struct SampleCounter<'a, C> {
+    classifier: &'a mut C,
+    n_samples: usize,
+}
+
+impl<const N: usize, C: Classifier<N>> Classifier<N> for SampleCounter<'_, C> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let sample = self.classifier.classify(p)?;
+        self.n_samples += 1;
+        Ok(sample)
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        let samples = self.classifier.classify_batch(points)?;
+        self.n_samples += samples.len();
+        Ok(samples)
+    }
+
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        let result = self.classifier.classify_with_confidence(p)?;
+        self.n_samples += 1;
+        Ok(result)
+    }
+}
+
+/// An iterator over the results of an Explorer's steps. See `Explorer::iter()`.
+pub struct ExplorationIter<'a, const N: usize, F, E, C>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+    C: Classifier<N>,
+{
+    explorer: &'a mut E,
+    classifier: &'a mut C,
+    done: bool,
+    _adherer_f: PhantomData<F>,
+}
+
+impl<const N: usize, F, E, C> Iterator for ExplorationIter<'_, N, F, E, C>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+    C: Classifier<N>,
+{
+    type Item = Result<Sample<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.explorer.step(self.classifier) {
+            Ok(Some(sample)) => Some(Ok(sample)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<const N: usize, F, E, C> std::iter::FusedIterator for ExplorationIter<'_, N, F, E, C>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+    C: Classifier<N>,
+{
 }
@@ -0,0 +1,224 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use nalgebra::SVector;
+
+use crate::{
+    adherer_core::AdhererFactory,
+    explorer_core::Explorer,
+    structs::{Classifier, Result, Sample, SamplingError},
+    utils::point_key,
+};
+
+/// Drives an Explorer without a Classifier: the explorer yields the points it
+/// needs classified through `next_request()`, and accepts their classifications
+/// through `tell()`. This allows SEMBAS to be embedded in an existing event loop,
+/// with the application scheduling and batching classifications itself.
+///
+/// ## Example
+/// ```ignore
+/// let mut expl = AskTellExplorer::new(explorer);
+/// loop {
+///     let points = expl.next_request();
+///     if points.is_empty() {
+///         break; // Exploration complete
+///     }
+///     expl.tell(my_event_loop.classify_all(points));
+/// }
+/// ```
+pub struct AskTellExplorer<const N: usize, F, E>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+{
+    explorer: E,
+    answers: HashMap<[u64; N], Result<Sample<N>>>,
+    requests: Vec<SVector<f64, N>>,
+    done: bool,
+    _factory: PhantomData<F>,
+}
+
+/// Answers classifications from the told samples. Points that have not been told
+/// are recorded as requests and reported as Pending, which the explorers treat as
+/// retryable.
+struct Oracle<'a, const N: usize> {
+    answers: &'a mut HashMap<[u64; N], Result<Sample<N>>>,
+    requests: &'a mut Vec<SVector<f64, N>>,
+}
+
+impl<const N: usize, F, E> AskTellExplorer<N, F, E>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+{
+    pub fn new(explorer: E) -> Self {
+        AskTellExplorer {
+            explorer,
+            answers: HashMap::new(),
+            requests: vec![],
+            done: false,
+            _factory: PhantomData,
+        }
+    }
+
+    /// Advances the exploration until it needs points classified.
+    /// ## Return
+    /// * points : The points that must be told before exploration can continue. If
+    ///   empty, the exploration is complete.
+    pub fn next_request(&mut self) -> Vec<SVector<f64, N>> {
+        if !self.requests.is_empty() {
+            return self.requests.clone();
+        }
+
+        while self.requests.is_empty() && !self.done {
+            let mut oracle = Oracle {
+                answers: &mut self.answers,
+                requests: &mut self.requests,
+            };
+
+            // Other sampling errors are part of exploration (e.g. a lost
+            // boundary) and are handled by the explorer.
+            if let Ok(None) = self.explorer.step(&mut oracle) {
+                self.done = true;
+            }
+        }
+
+        // Answers that went unused are stale once new points are requested.
+        self.answers.clear();
+        self.requests.clone()
+    }
+
+    /// Provides the classifications of requested points.
+    pub fn tell(&mut self, samples: impl IntoIterator<Item = Sample<N>>) {
+        for sample in samples {
+            self.answers.insert(point_key(&sample), Ok(sample));
+        }
+        self.clear_answered();
+    }
+
+    /// Marks a requested point as unclassifiable, e.g. because it falls outside of
+    /// the input domain. The explorer receives OutOfBounds for @p.
+    pub fn reject(&mut self, p: SVector<f64, N>) {
+        self.answers
+            .insert(point_key(&p), Err(SamplingError::OutOfBounds));
+        self.clear_answered();
+    }
+
+    /// Whether the exploration is complete.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn explorer(&self) -> &E {
+        &self.explorer
+    }
+
+    pub fn into_inner(self) -> E {
+        self.explorer
+    }
+
+    fn clear_answered(&mut self) {
+        let answers = &self.answers;
+        self.requests
+            .retain(|p| !answers.contains_key(&point_key(p)));
+    }
+}
+
+impl<const N: usize> Oracle<'_, N> {
+    fn request(&mut self, p: SVector<f64, N>) {
+        if !self.requests.contains(&p) {
+            self.requests.push(p);
+        }
+    }
+}
+
+impl<const N: usize> Classifier<N> for Oracle<'_, N> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        match self.answers.get(&point_key(&p)) {
+            Some(result) => result.clone(),
+            None => {
+                self.request(p);
+                Err(SamplingError::Pending)
+            }
+        }
+    }
+
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        let missing: Vec<_> = points
+            .iter()
+            .filter(|p| !self.answers.contains_key(&point_key(p)))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            missing.into_iter().for_each(|p| self.request(p));
+            return Err(SamplingError::Pending);
+        }
+
+        points.iter().map(|&p| self.classify(p)).collect()
+    }
+}
+
+#[cfg(all(test, feature = "sps"))]
+mod ask_tell_exploration {
+    use std::{cell::Cell, rc::Rc};
+
+    use nalgebra::SVector;
+
+    use super::*;
+    use crate::{
+        explorer_core::ExplorationObserver,
+        prelude::{ConstantAdhererFactory, Domain, Halfspace, MeshExplorer, WithinMode},
+        sps::Sphere,
+    };
+
+    struct ErrorCounter(Rc<Cell<usize>>);
+
+    impl<const N: usize> ExplorationObserver<N> for ErrorCounter {
+        fn on_error(&mut self, _error: &SamplingError) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    fn setup_explorer(batch_size: usize) -> MeshExplorer<3, ConstantAdhererFactory<3>> {
+        let root = Halfspace {
+            b: WithinMode(SVector::from_fn(|i, _| if i == 0 { 0.74 } else { 0.5 })),
+            n: SVector::from_fn(|i, _| if i == 0 { 1.0 } else { 0.0 }),
+        };
+        let adherer_f = ConstantAdhererFactory::new(0.26, None).with_batch_size(batch_size);
+        MeshExplorer::new(0.1, root, 0.085, adherer_f)
+    }
+
+    #[test]
+    fn matches_direct_exploration() {
+        let mut sphere = Sphere::new(SVector::repeat(0.5), 0.25, Some(Domain::normalized()));
+
+        for batch_size in [1, 4] {
+            let direct_errors = Rc::new(Cell::new(0));
+            let mut direct = setup_explorer(batch_size);
+            direct.add_observer(Box::new(ErrorCounter(direct_errors.clone())));
+            while !matches!(direct.step(&mut sphere), Ok(None)) {}
+
+            // Points awaiting classification are not reported as errors.
+            let errors = Rc::new(Cell::new(0));
+            let mut explorer = setup_explorer(batch_size);
+            explorer.add_observer(Box::new(ErrorCounter(errors.clone())));
+            let mut expl = AskTellExplorer::new(explorer);
+            loop {
+                let points = expl.next_request();
+                if points.is_empty() {
+                    break;
+                }
+                assert!(batch_size > 1 || points.len() == 1);
+                for p in points {
+                    match sphere.classify(p) {
+                        Ok(sample) => expl.tell([sample]),
+                        Err(_) => expl.reject(p),
+                    }
+                }
+            }
+
+            assert!(expl.is_done());
+            assert_eq!(expl.explorer().boundary(), direct.boundary());
+            assert_eq!(errors.get(), direct_errors.get());
+        }
+    }
+}
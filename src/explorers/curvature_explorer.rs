@@ -0,0 +1,300 @@
+use std::{any::type_name, cmp::Ordering, collections::BinaryHeap, collections::HashMap};
+
+use nalgebra::{Const, OMatrix, SVector};
+
+use crate::{
+    adherer_core::{AdherenceFailure, AdherenceStats, Adherer, AdhererFactory, AdhererState},
+    explorer_core::{ExplorationObserver, Explorer, Observers},
+    explorers::MeshExplorer,
+    prelude::{report::ExplorationStatus, KnnNode, NodeID},
+    spatial_index::{SpatialIndex, SpatialIndexKind},
+    structs::{Classifier, Halfspace, Result, Sample, SamplingError},
+    utils::array_distance,
+};
+
+/// A frontier path, prioritized by the estimated curvature of the surface at its
+/// origin. Ties are broken in the order the paths were found.
+struct FrontierPath<const N: usize> {
+    score: f64,
+    order: usize,
+    id: NodeID,
+    v: SVector<f64, N>,
+}
+
+/// Explores a surface along the same grid-like paths as MeshExplorer, but
+/// prioritizes paths from regions of high estimated curvature, e.g. the corners
+/// of an envelope. Flat regions are explored once the curved regions are
+/// exhausted, so this is most effective when the sample budget is limited.
+///
+/// The curvature at a boundary point is estimated as the angle between its
+/// surface normal and that of the boundary point it was found from, divided by
+/// the distance between them.
+pub struct CurvatureExplorer<const N: usize, F: AdhererFactory<N>> {
+    d: f64,
+    boundary: Vec<Halfspace<N>>,
+    curvature: Vec<f64>,
+    margin: f64,
+    basis_vectors: OMatrix<f64, Const<N>, Const<N>>,
+    frontier: BinaryHeap<FrontierPath<N>>,
+    n_paths: usize,
+    current_parent: NodeID,
+    knn_kind: SpatialIndexKind,
+    knn_index: Box<dyn SpatialIndex<N>>,
+    adherer: Option<F::TargetAdherer>,
+    adherer_f: F,
+    observers: Observers<N>,
+    sample_history: Option<Vec<Sample<N>>>,
+    adherence_stats: Vec<AdherenceStats>,
+}
+
+impl<const N: usize, F: AdhererFactory<N>> CurvatureExplorer<N, F> {
+    /// Creates a CurvatureExplorer instance.
+    /// ## Arguments
+    /// * d: The jump distance between boundary points. Describes how far apart the
+    ///   samples are taken.
+    /// * root: The initial boundary halfspace to begin exploration from.
+    /// * margin: 0 < margin < d, The minimum distance between a sample and a known
+    ///   halfspace before a path along a cardinal direction is rejected. See
+    ///   `MeshExplorer::new()`.
+    pub fn new(d: f64, root: Halfspace<N>, margin: f64, adherer_f: F) -> Self {
+        let mut exp = CurvatureExplorer {
+            d,
+            boundary: vec![],
+            curvature: vec![],
+            margin,
+            basis_vectors: OMatrix::<f64, Const<N>, Const<N>>::identity(),
+            frontier: BinaryHeap::new(),
+            n_paths: 0,
+            current_parent: 0,
+            knn_kind: SpatialIndexKind::default(),
+            knn_index: SpatialIndexKind::default().build(),
+            adherer: None,
+            adherer_f,
+            observers: Observers::default(),
+            sample_history: None,
+            adherence_stats: vec![],
+        };
+
+        exp.add_child(root, None, AdherenceStats::default());
+
+        exp
+    }
+
+    /// Uses the given backend for the explorer's nearest-neighbor index. See
+    /// `MeshExplorer::with_spatial_index()`.
+    pub fn with_spatial_index(mut self, kind: SpatialIndexKind) -> Self {
+        self.knn_kind = kind;
+        self.knn_index = kind.build();
+        for (id, hs) in self.boundary.iter().enumerate() {
+            self.knn_index.insert(KnnNode::new(hs.b.into(), id));
+        }
+        self
+    }
+
+    /// Retains every sample taken. See `MeshExplorer::with_sample_history()`.
+    pub fn with_sample_history(mut self) -> Self {
+        self.sample_history.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// The estimated curvature at each boundary point, in the same order as the
+    /// boundary.
+    pub fn curvature(&self) -> &Vec<f64> {
+        &self.curvature
+    }
+
+    fn select_parent(&mut self) -> Option<(Halfspace<N>, NodeID, SVector<f64, N>)> {
+        while let Some(FrontierPath { id, v, .. }) = self.frontier.pop() {
+            let hs = &self.boundary[id];
+            let p = *hs.b + self.d * v;
+
+            if !self.check_overlap(&p) {
+                return Some((*hs, id, v));
+            }
+        }
+
+        None
+    }
+
+    fn add_child(&mut self, hs: Halfspace<N>, parent_id: Option<NodeID>, stats: AdherenceStats) {
+        let id = self.boundary.len();
+        let score = parent_id
+            .map(|pid| estimate_curvature(&self.boundary[pid], &hs))
+            .unwrap_or(0.0);
+
+        self.boundary.push(hs);
+        self.curvature.push(score);
+        self.adherence_stats.push(stats);
+
+        for v in MeshExplorer::<N, F>::create_cardinals(hs.n, self.basis_vectors) {
+            self.frontier.push(FrontierPath {
+                score,
+                order: self.n_paths,
+                id,
+                v,
+            });
+            self.n_paths += 1;
+        }
+
+        self.knn_index.insert(KnnNode::new(hs.b.into(), id));
+    }
+
+    fn check_overlap(&self, p: &SVector<f64, N>) -> bool {
+        let p: [f64; N] = (*p).into();
+
+        if let Some(nearest) = self.knn_index.nearest_neighbor(&p) {
+            array_distance(&p, nearest.geom()) < self.margin
+        } else {
+            false
+        }
+    }
+}
+
+fn estimate_curvature<const N: usize>(parent: &Halfspace<N>, child: &Halfspace<N>) -> f64 {
+    let dist = (*child.b - *parent.b).norm();
+    if dist <= 0.0 {
+        return 0.0;
+    }
+
+    parent.n.angle(&child.n) / dist
+}
+
+impl<const N: usize> PartialEq for FrontierPath<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<const N: usize> Eq for FrontierPath<N> {}
+
+impl<const N: usize> PartialOrd for FrontierPath<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for FrontierPath<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl<const N: usize, F: AdhererFactory<N>> Explorer<N, F> for CurvatureExplorer<N, F> {
+    fn step<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<Option<Sample<N>>> {
+        if self.adherer.is_none() {
+            if let Some((hs, id, v)) = self.select_parent() {
+                self.current_parent = id;
+                self.adherer = Some(self.adherer_f.adhere_from(hs, v * self.d))
+            }
+        }
+
+        let Some(ref mut adh) = self.adherer else {
+            // Ends exploration
+            return Ok(None);
+        };
+
+        let n_prev = adh.samples().len();
+        let result = adh.sample_next(classifier).copied();
+        // Includes the final sample of a failed adherence.
+        for sample in adh.samples().iter().skip(n_prev) {
+            self.observers.sample(sample);
+            if let Some(history) = &mut self.sample_history {
+                history.push(*sample);
+            }
+        }
+
+        match &result {
+            Ok(_) => {
+                if let AdhererState::FoundBoundary(hs) = adh.get_state() {
+                    let stats = AdherenceStats::from_samples(
+                        &self.boundary[self.current_parent],
+                        adh.samples(),
+                    );
+                    self.add_child(hs, Some(self.current_parent), stats);
+                    self.adherer = None;
+                    self.observers.boundary_found(&hs);
+                }
+            }
+            // A lost connection is retryable, so the path is kept for the next step.
+            Err(SamplingError::Pending) => (),
+            Err(e @ SamplingError::Disconnected) => self.observers.error(e),
+            Err(e) => {
+                let parent = self.boundary[self.current_parent];
+                let failure = AdherenceFailure::from_adherer(parent, adh, e.clone());
+                self.adherer = None;
+                self.observers.error(e);
+                self.observers.adherence_failed(&failure);
+                self.observers.branch_pruned(&parent, e);
+            }
+        }
+
+        result.map(Some)
+    }
+
+    fn boundary(&self) -> &Vec<Halfspace<N>> {
+        &self.boundary
+    }
+
+    fn boundary_owned(self) -> Vec<Halfspace<N>> {
+        self.boundary
+    }
+
+    fn boundary_count(&self) -> usize {
+        self.boundary.len()
+    }
+
+    fn adherence_stats(&self) -> Option<&[AdherenceStats]> {
+        Some(&self.adherence_stats)
+    }
+
+    fn describe(&self) -> ExplorationStatus<N, F> {
+        let mut expl_params = HashMap::new();
+        expl_params.insert("d".to_string(), self.d);
+        expl_params.insert("margin".to_string(), self.margin);
+
+        let status = ExplorationStatus::new(
+            "Curvature Explorer",
+            type_name::<F>(),
+            expl_params,
+            self.adherer_f,
+            &self.boundary,
+            None,
+        );
+
+        match &self.sample_history {
+            Some(samples) => status.with_samples(samples),
+            None => status,
+        }
+    }
+
+    fn samples(&self) -> Option<&[Sample<N>]> {
+        self.sample_history.as_deref()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn ExplorationObserver<N>>) {
+        self.observers.add(observer);
+    }
+
+    /// Loads a new boundary into the explorer, overwriting the existing boundary.
+    /// Each halfspace's curvature is estimated relative to its nearest preceding
+    /// neighbor.
+    fn load_boundary(&mut self, boundary: Vec<Halfspace<N>>) {
+        assert!(!boundary.is_empty(), "Boundary must be non-empty!");
+        self.boundary = vec![];
+        self.curvature = vec![];
+        self.adherence_stats = vec![];
+        self.knn_index = self.knn_kind.build();
+        self.frontier = BinaryHeap::new();
+        self.adherer = None;
+
+        for hs in boundary {
+            let parent = self
+                .knn_index
+                .nearest_neighbor(&hs.b.into())
+                .map(|neighbor| neighbor.data);
+            self.add_child(hs, parent, AdherenceStats::default());
+        }
+    }
+}
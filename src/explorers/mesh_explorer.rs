@@ -1,32 +1,114 @@
 use std::{any::type_name, collections::HashMap};
 
 use crate::{
-    adherer_core::{Adherer, AdhererFactory, AdhererState},
-    boundary_tools::get_rtree_from_boundary,
-    explorer_core::Explorer,
+    adherer_core::{AdherenceFailure, AdherenceStats, Adherer, AdhererFactory, AdhererState},
+    explorer_core::{ExplorationObserver, Explorer, Observers},
     extensions::Queue,
     prelude::{report::ExplorationStatus, KnnNode, NodeID},
-    structs::{backprop::Backpropagation, Classifier, Halfspace, Result, Sample, Span},
+    spatial_index::{SpatialIndex, SpatialIndexKind},
+    structs::{
+        backprop::Backpropagation, Classifier, Domain, Halfspace, Result, Sample, SamplingError,
+        Span,
+    },
     utils::array_distance,
 };
 use nalgebra::{self, Const, OMatrix, SVector};
 use petgraph::{graph::NodeIndex, visit::EdgeRef, Direction::Incoming, Graph};
-use rstar::{primitives::GeomWithData, RTree};
 
 pub type Path<const N: usize> = (NodeID, SVector<f64, N>);
 
+/// The outcome of one of the adherence searches dispatched by
+/// `MeshExplorer::step_parallel()`.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone)]
+pub struct ParallelAdherence<const N: usize> {
+    /// The boundary point the search started from.
+    pub parent: NodeID,
+    /// The samples taken during the search, in order. For an adherence resumed
+    /// from `step()`, excludes the samples `step()` already returned.
+    pub samples: Vec<Sample<N>>,
+    /// The acquired halfspace, or the error that ended the search.
+    pub result: Result<Halfspace<N>>,
+}
+
+/// The bounds of the automatically tuned margin, relative to d.
+const AUTO_MARGIN_BOUNDS: (f64, f64) = (0.5, 0.95);
+/// A new boundary point this close to an existing one (relative to d) is
+/// considered a duplicate, suggesting the margin is too small.
+const DUPLICATE_DISTANCE: f64 = 0.5;
+/// A path rejected despite its target being this far from the nearest boundary
+/// point (relative to d) is considered to leave a hole, suggesting the margin is
+/// too large.
+const HOLE_DISTANCE: f64 = 0.8;
+/// The factors the margin is grown and shrunk by for each observation.
+const AUTO_MARGIN_RATES: (f64, f64) = (1.05, 0.99);
+/// A path slid along the domain's walls is rejected if its jump is shortened to
+/// less than this fraction of the jump distance, as it mostly leaves the domain.
+const MIN_SLIDE_DISTANCE: f64 = 0.5;
+
+/// The bounds and target of MeshExplorer's curvature-adaptive jump distance. See
+/// `MeshExplorer::with_adaptive_jump()`.
+#[derive(Debug, Clone, Copy)]
+struct AdaptiveJump {
+    min_d: f64,
+    max_d: f64,
+    target_angle: f64,
+}
+
+/// The path currently being adhered along by `MeshExplorer::step()`, kept so that
+/// it can be retried at a shorter jump distance. See
+/// `MeshExplorer::with_lost_boundary_retries()`.
+#[derive(Debug, Clone, Copy)]
+struct ActivePath<const N: usize> {
+    pivot: Halfspace<N>,
+    v: SVector<f64, N>,
+    d: f64,
+    /// The statistics of the path's failed attempts so far.
+    stats: AdherenceStats,
+}
+
+/// A point or region of interest for MeshExplorer to explore the boundary around
+/// first. See `MeshExplorer::with_goal()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Goal<const N: usize> {
+    Point(SVector<f64, N>),
+    Region(Domain<N>),
+}
+
+impl<const N: usize> Goal<N> {
+    /// The distance from @p to the goal, which is 0 within a goal region.
+    pub fn distance(&self, p: &SVector<f64, N>) -> f64 {
+        match self {
+            Goal::Point(goal) => (p - goal).norm(),
+            Goal::Region(domain) => (p - domain.clip_vector(p)).norm(),
+        }
+    }
+}
+
 /// Explores a surface uniformly by using a grid-search approach.
 pub struct MeshExplorer<const N: usize, F: AdhererFactory<N>> {
     d: f64,
     boundary: Vec<Halfspace<N>>,
     margin: f64,
     basis_vectors: OMatrix<f64, Const<N>, Const<N>>,
+    active_dims: Option<Vec<usize>>,
+    dedup_tolerance: Option<f64>,
+    adaptive_jump: Option<AdaptiveJump>,
+    auto_margin: bool,
+    goal: Option<Goal<N>>,
+    walls: Option<Domain<N>>,
+    max_lost_retries: u32,
+    active_path: Option<ActivePath<N>>,
     path_queue: Vec<Path<N>>,
     current_parent: NodeID,
     tree: Graph<Halfspace<N>, ()>,
-    knn_index: RTree<KnnNode<N>>,
+    knn_kind: SpatialIndexKind,
+    knn_index: Box<dyn SpatialIndex<N>>,
     adherer: Option<F::TargetAdherer>,
     adherer_f: F,
+    observers: Observers<N>,
+    sample_history: Option<Vec<Sample<N>>>,
+    adherence_stats: Vec<AdherenceStats>,
 }
 
 impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
@@ -45,19 +127,31 @@ impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
         let path_queue = vec![];
         let current_parent = 0; // dunno
         let tree = Graph::new();
-        let knn_index = RTree::new();
+        let knn_index = SpatialIndexKind::default().build();
 
         let mut exp = MeshExplorer {
             d,
             boundary,
             margin,
             basis_vectors,
+            active_dims: None,
+            dedup_tolerance: None,
+            adaptive_jump: None,
+            auto_margin: false,
+            goal: None,
+            walls: None,
+            max_lost_retries: 0,
+            active_path: None,
             path_queue,
             current_parent,
             tree,
+            knn_kind: SpatialIndexKind::default(),
             knn_index,
             adherer: None,
             adherer_f,
+            observers: Observers::default(),
+            sample_history: None,
+            adherence_stats: vec![AdherenceStats::default()],
         };
 
         exp.add_child(root, None);
@@ -65,17 +159,320 @@ impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
         exp
     }
 
-    pub fn knn_index(&self) -> &RTree<GeomWithData<[f64; N], usize>> {
-        &self.knn_index
+    /// Restricts exploration to the boundary's intersection with the subspace of
+    /// the given dimensions that passes through the root, e.g. when only a few of
+    /// many scenario parameters are of interest. Paths only travel along
+    /// @dims, so the remaining parameters keep the root's values. The root's
+    /// surface normal must not be orthogonal to the subspace.
+    /// ## Arguments
+    /// * dims : The indices of at least two distinct dimensions to explore.
+    pub fn with_dimensions(mut self, dims: &[usize]) -> Self {
+        assert!(
+            dims.len() >= 2,
+            "At least two dimensions are required to explore a surface!"
+        );
+        assert!(
+            dims.iter().all(|&i| i < N),
+            "Dimensions must be less than N ({N})! Got {dims:?}"
+        );
+        assert!(
+            dims.iter()
+                .enumerate()
+                .all(|(i, a)| !dims[i + 1..].contains(a)),
+            "Dimensions must be distinct! Got {dims:?}"
+        );
+
+        // The explored dimensions come first, so that the cardinals along them
+        // can be distinguished from the rest.
+        let order = dims
+            .iter()
+            .copied()
+            .chain((0..N).filter(|i| !dims.contains(i)));
+        for (col, i) in order.enumerate() {
+            let mut e = SVector::<f64, N>::zeros();
+            e[i] = 1.0;
+            self.basis_vectors.set_column(col, &e);
+        }
+        self.active_dims = Some(dims.to_vec());
+
+        self.path_queue = (0..self.boundary.len())
+            .flat_map(|id| self.get_next_paths_from(id))
+            .collect();
+
+        self
+    }
+
+    /// Merges newly acquired halfspaces into any existing halfspace within
+    /// @tolerance distance rather than adding them to the boundary, e.g. where
+    /// exploration wraps around the envelope. The existing boundary point is kept
+    /// and the normals are averaged.
+    pub fn with_dedup_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(tolerance >= 0.0, "Tolerance must be non-negative!");
+        self.dedup_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Merges all halfspaces within @tolerance distance of one another, keeping
+    /// the earliest boundary point and averaging the normals. The explorer is
+    /// reloaded with the compacted boundary (see `load_boundary()`), and the
+    /// adherence statistics of merged halfspaces are combined into those kept.
+    /// ## Return
+    /// * n_merged : The number of halfspaces removed from the boundary.
+    pub fn compact(&mut self, tolerance: f64) -> usize {
+        assert!(tolerance >= 0.0, "Tolerance must be non-negative!");
+        let mut kept: Vec<Halfspace<N>> = vec![];
+        let mut kept_stats: Vec<AdherenceStats> = vec![];
+        let mut index = SpatialIndexKind::default().build();
+
+        for (hs, stats) in self.boundary.iter().zip(self.adherence_stats.iter()) {
+            match find_duplicate(index.as_ref(), hs, tolerance) {
+                Some(id) => {
+                    kept[id].n = merge_normals(&kept[id].n, &hs.n);
+                    kept_stats[id] = kept_stats[id].merge(stats);
+                }
+                None => {
+                    index.insert(KnnNode::new(hs.b.into(), kept.len()));
+                    kept.push(*hs);
+                    kept_stats.push(*stats);
+                }
+            }
+        }
+
+        let n_merged = self.boundary.len() - kept.len();
+        if n_merged > 0 {
+            self.load_boundary(kept);
+            self.adherence_stats = kept_stats;
+        }
+
+        n_merged
+    }
+
+    /// Tunes the margin online: the margin grows whenever a newly acquired boundary
+    /// point duplicates an existing one, and shrinks whenever a path is rejected
+    /// despite its target being loosely covered, which would leave a hole. The
+    /// margin is kept within [0.5 * d, 0.95 * d], starting from the value provided
+    /// to `new()`. The effective margin is reported by `margin()` and
+    /// `describe()`.
+    pub fn with_auto_margin(mut self) -> Self {
+        self.auto_margin = true;
+        self.margin = self
+            .margin
+            .clamp(AUTO_MARGIN_BOUNDS.0 * self.d, AUTO_MARGIN_BOUNDS.1 * self.d);
+        self
+    }
+
+    /// The effective margin, which changes over time if auto-margin is enabled.
+    pub fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    fn tune_margin(&mut self, rate: f64) {
+        self.margin = (self.margin * rate)
+            .clamp(AUTO_MARGIN_BOUNDS.0 * self.d, AUTO_MARGIN_BOUNDS.1 * self.d);
+    }
+
+    /// Adapts the jump distance of each path to the local curvature of the
+    /// surface, such that neighboring normals diverge by roughly @target_angle.
+    /// Flat regions are explored with larger steps, and sharply curved regions
+    /// (e.g. corners) are refined with smaller steps rather than losing the
+    /// boundary. The curvature at a boundary point is estimated from the angle
+    /// between its normal and that of the point it was found from, and the margin
+    /// is scaled along with the jump distance.
+    /// ## Arguments
+    /// * min_d, max_d : The bounds of the jump distance, 0 < min_d <= d <= max_d.
+    /// * target_angle : The desired angle in radians between neighboring normals.
+    pub fn with_adaptive_jump(mut self, min_d: f64, max_d: f64, target_angle: f64) -> Self {
+        assert!(
+            0.0 < min_d && min_d <= self.d && self.d <= max_d,
+            "Jump distance bounds must satisfy 0 < min_d <= d <= max_d!"
+        );
+        assert!(target_angle > 0.0, "Target angle must be positive!");
+        self.adaptive_jump = Some(AdaptiveJump {
+            min_d,
+            max_d,
+            target_angle,
+        });
+        self
+    }
+
+    /// Retains every sample taken, e.g. for training surrogate models or auditing
+    /// how many FUT runs an exploration consumed. The history is available from
+    /// `samples()` and included in `describe()`.
+    pub fn with_sample_history(mut self) -> Self {
+        self.sample_history.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Biases path selection toward @goal: rather than first-in-first-out, the
+    /// next path explored is the one whose target is nearest the goal, e.g. to map
+    /// the boundary near a nominal operating condition first. Ties are broken in
+    /// the order the paths were found. Exploration still continues past the goal
+    /// until the boundary is exhausted, unless limited by a Budget.
+    pub fn with_goal(mut self, goal: Goal<N>) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    /// Replaces the goal that path selection is biased toward (see `with_goal()`),
+    /// e.g. to steer an ongoing exploration. None restores first-in-first-out
+    /// path selection.
+    pub fn set_goal(&mut self, goal: Option<Goal<N>>) {
+        self.goal = goal;
+    }
+
+    /// The goal that path selection is biased toward, if any.
+    pub fn goal(&self) -> Option<&Goal<N>> {
+        self.goal.as_ref()
+    }
+
+    /// Slides paths along the walls of @domain rather than jumping out of it, e.g.
+    /// to follow an envelope that is truncated by the domain. A path whose target
+    /// falls outside of the domain is shortened to end on its walls (see
+    /// `Domain::project_onto_surface()`), and is rejected if that leaves less than
+    /// half of the jump. Without this, such paths usually end in OutOfBounds.
+    pub fn with_domain_walls(mut self, domain: Domain<N>) -> Self {
+        self.walls = Some(domain);
+        self
+    }
+
+    /// Slides the jump @d * @v from @hs along the domain's walls, if enabled.
+    /// ## Returns
+    /// * (v', d') : The direction and distance of the slid jump, or None if it is
+    ///   too short or parallel to @hs's surface vector.
+    fn slide_path(
+        &self,
+        hs: &Halfspace<N>,
+        v: SVector<f64, N>,
+        d: f64,
+    ) -> Option<(SVector<f64, N>, f64)> {
+        let Some(walls) = &self.walls else {
+            return Some((v, d));
+        };
+
+        let jump = walls.project_onto_surface(&hs.b, &(d * v));
+        let slid_d = jump.norm();
+        if slid_d < MIN_SLIDE_DISTANCE * d {
+            return None;
+        }
+
+        let v = jump / slid_d;
+        if (v - hs.n.dot(&v) * hs.n).norm() < 1e-10 {
+            return None;
+        }
+
+        Some((v, slid_d))
+    }
+
+    /// Retries a path that lost the boundary at half the jump distance, up to
+    /// @max_retries times, before pruning it. Sharp corners that a full-length
+    /// jump overshoots can often be adhered to from a shorter one. Each failed
+    /// attempt is still returned from `step()` as BoundaryLost. Only applies to
+    /// `step()`; paths searched by `step_parallel()` are not retried.
+    pub fn with_lost_boundary_retries(mut self, max_retries: u32) -> Self {
+        self.max_lost_retries = max_retries;
+        self
+    }
+
+    /// Restarts the active path at half its jump distance, if it has retries
+    /// remaining.
+    /// ## Arguments
+    /// * failed : The statistics of the attempt that lost the boundary.
+    /// ## Returns
+    /// * retried : Whether the path was restarted.
+    fn retry_active_path(&mut self, failed: AdherenceStats) -> bool {
+        let Some(path) = self.active_path.as_mut() else {
+            return false;
+        };
+        if path.stats.n_retries >= self.max_lost_retries {
+            return false;
+        }
+
+        path.stats = path.stats.merge(&failed);
+        path.stats.n_retries += 1;
+        path.d /= 2.0;
+        self.adherer = Some(self.adherer_f.adhere_from(path.pivot, path.v * path.d));
+        true
+    }
+
+    /// Removes the next path to explore from the queue, which is the nearest path
+    /// to the goal if one is set.
+    fn next_path(&mut self) -> Option<Path<N>> {
+        let Some(goal) = &self.goal else {
+            return self.path_queue.dequeue();
+        };
+
+        let distance = |(id, v): &Path<N>| {
+            goal.distance(&(*self.boundary[*id].b + self.jump_distance(*id) * v))
+        };
+        let (i, _) = self
+            .path_queue
+            .iter()
+            .map(distance)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        Some(self.path_queue.remove(i))
+    }
+
+    /// The jump distance for paths from the boundary point @id.
+    fn jump_distance(&self, id: NodeID) -> f64 {
+        let Some(adaptive) = self.adaptive_jump else {
+            return self.d;
+        };
+        let Some(parent) = self.get_parent(NodeIndex::new(id)) else {
+            return self.d;
+        };
+
+        let (hs, parent) = (&self.boundary[id], &self.boundary[parent.index()]);
+        let dist = (*hs.b - *parent.b).norm();
+        let angle = hs.n.angle(&parent.n);
+        if angle <= 1e-10 {
+            return adaptive.max_d;
+        }
+
+        (adaptive.target_angle * dist / angle).clamp(adaptive.min_d, adaptive.max_d)
+    }
+
+    /// Uses the given backend for the explorer's nearest-neighbor index, e.g.
+    /// KdTree or Hnsw for high-dimensional spaces where the default RTree is slow.
+    /// The known boundary is reindexed.
+    pub fn with_spatial_index(mut self, kind: SpatialIndexKind) -> Self {
+        self.knn_kind = kind;
+        self.knn_index = kind.build();
+        for (id, hs) in self.boundary.iter().enumerate() {
+            self.knn_index.insert(KnnNode::new(hs.b.into(), id));
+        }
+        self
     }
 
-    fn select_parent(&mut self) -> Option<(Halfspace<N>, NodeID, SVector<f64, N>)> {
-        while let Some((id, v)) = self.path_queue.dequeue() {
-            let hs = &self.boundary[id];
-            let p = *hs.b + self.d * v;
+    pub fn knn_index(&self) -> &dyn SpatialIndex<N> {
+        self.knn_index.as_ref()
+    }
 
-            if !self.check_overlap(&p) {
-                return Some((*hs, id, v));
+    /// Selects the next path that does not overlap the explored boundary.
+    /// ## Return
+    /// * (hs, id, v, d) : The halfspace and id of the path's origin, and the
+    ///   direction and distance to travel.
+    fn select_parent(&mut self) -> Option<(Halfspace<N>, NodeID, SVector<f64, N>, f64)> {
+        while let Some((id, v)) = self.next_path() {
+            let hs = self.boundary[id];
+            let d = self.jump_distance(id);
+            let p = *hs.b + d * v;
+
+            match self.nearest_distance(&p) {
+                Some(dist) if dist < self.margin * d / self.d => {
+                    if self.auto_margin && dist > HOLE_DISTANCE * d {
+                        self.tune_margin(AUTO_MARGIN_RATES.1);
+                    }
+                }
+                _ => {
+                    // Adheres within the explored subspace, if restricted.
+                    let n = self.project(&hs.n).unwrap_or(hs.n);
+                    let hs = Halfspace { b: hs.b, n };
+                    if let Some((v, d)) = self.slide_path(&hs, v, d) {
+                        return Some((hs, id, v, d));
+                    }
+                }
             }
         }
 
@@ -96,14 +493,60 @@ impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
         self.knn_index.insert(KnnNode::new(b, next_id.index()));
     }
 
+    /// Adds an acquired halfspace to the boundary, or merges it into an existing
+    /// duplicate if deduplication is enabled.
+    fn insert_halfspace(&mut self, hs: Halfspace<N>, parent_id: NodeID, stats: AdherenceStats) {
+        if self.auto_margin
+            && self
+                .nearest_distance(&hs.b)
+                .is_some_and(|dist| dist < DUPLICATE_DISTANCE * self.jump_distance(parent_id))
+        {
+            self.tune_margin(AUTO_MARGIN_RATES.0);
+        }
+
+        if let Some(tolerance) = self.dedup_tolerance {
+            if let Some(id) = find_duplicate(self.knn_index.as_ref(), &hs, tolerance) {
+                let n = merge_normals(&self.boundary[id].n, &hs.n);
+                self.boundary[id].n = n;
+                self.tree[NodeIndex::new(id)].n = n;
+                self.adherence_stats[id] = self.adherence_stats[id].merge(&stats);
+                return;
+            }
+        }
+
+        self.boundary.push(hs);
+        self.adherence_stats.push(stats);
+        self.add_child(hs, Some(NodeIndex::new(parent_id)));
+        self.observers.boundary_found(&hs);
+    }
+
     fn get_next_paths_from(&self, id: NodeID) -> Vec<Path<N>> {
         let hs = &self.boundary[id];
-        let next_paths = Self::create_cardinals(hs.n, self.basis_vectors)
-            .iter()
-            .map(|&v| (id, v))
-            .collect();
+        let (n, n_cardinals) = match &self.active_dims {
+            Some(dims) => match self.project(&hs.n) {
+                Some(n) => (n, 2 * (dims.len() - 1)),
+                // The surface is parallel to the subspace, so there is no
+                // intersection to follow.
+                None => return vec![],
+            },
+            None => (hs.n, 2 * (N - 1)),
+        };
 
-        next_paths
+        Self::create_cardinals(n, self.basis_vectors)
+            .into_iter()
+            .take(n_cardinals)
+            .map(|v| (id, v))
+            .collect()
+    }
+
+    /// Projects @n onto the explored subspace, returning None if unrestricted or
+    /// if @n is orthogonal to the subspace.
+    fn project(&self, n: &SVector<f64, N>) -> Option<SVector<f64, N>> {
+        let dims = self.active_dims.as_ref()?;
+        let projected =
+            SVector::<f64, N>::from_fn(|i, _| if dims.contains(&i) { n[i] } else { 0.0 });
+
+        projected.try_normalize(1e-10)
     }
 
     pub fn create_cardinals(
@@ -132,17 +575,16 @@ impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
         cardinals
     }
 
-    fn check_overlap(&self, p: &SVector<f64, N>) -> bool {
+    /// The distance from @p to the nearest known boundary point.
+    fn nearest_distance(&self, p: &SVector<f64, N>) -> Option<f64> {
         let p: &[f64; N] = p
             .as_slice()
             .try_into()
             .expect("Unable to convert SVector to array");
 
-        if let Some(nearest) = self.knn_index.nearest_neighbor(p) {
-            array_distance(p, nearest.geom()) < self.margin
-        } else {
-            false
-        }
+        self.knn_index
+            .nearest_neighbor(p)
+            .map(|nearest| array_distance(p, nearest.geom()))
     }
 
     fn get_parent(&self, id: NodeIndex) -> Option<NodeIndex> {
@@ -153,23 +595,218 @@ impl<const N: usize, F: AdhererFactory<N>> MeshExplorer<N, F> {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl<const N: usize, F> MeshExplorer<N, F>
+where
+    F: AdhererFactory<N>,
+    F::TargetAdherer: Send,
+{
+    /// Dispatches up to one adherence search per classifier, running them
+    /// concurrently to completion. Paths are selected from the queue in order,
+    /// skipping paths whose target is within margin of another dispatched path,
+    /// and the acquired halfspaces are merged in that same order, so the result is
+    /// deterministic for deterministic classifiers. An in-progress adherence from
+    /// `step()` is completed as the first search.
+    /// ## Arguments
+    /// * classifiers : The classifiers to sample with, e.g. one simulation
+    ///   instance per core. Each search uses a single classifier.
+    /// ## Return
+    /// * Some(adherences) : The outcome of each search, in the order they were
+    ///   merged.
+    /// * None : If exploration is complete.
+    pub fn step_parallel<C>(&mut self, classifiers: &mut [C]) -> Option<Vec<ParallelAdherence<N>>>
+    where
+        C: Classifier<N> + Send,
+    {
+        assert!(
+            !classifiers.is_empty(),
+            "At least one classifier is required!"
+        );
+
+        // Each job carries the number of samples step() already reported and the
+        // statistics of its path's earlier attempts.
+        let mut jobs = vec![];
+        let mut targets: Vec<SVector<f64, N>> = vec![];
+        if let Some(adh) = self.adherer.take() {
+            let stats = self.active_path.take().map(|path| path.stats);
+            let n_reported = adh.samples().len();
+            jobs.push((
+                self.current_parent,
+                None,
+                adh,
+                n_reported,
+                stats.unwrap_or_default(),
+            ));
+        }
+
+        let mut deferred = vec![];
+        while jobs.len() < classifiers.len() {
+            let Some((hs, id, v, d)) = self.select_parent() else {
+                break;
+            };
+            let p = *hs.b + d * v;
+            if targets
+                .iter()
+                .any(|t| (t - p).norm() < self.margin * d / self.d)
+            {
+                deferred.push((id, v));
+                continue;
+            }
+
+            targets.push(p);
+            let adh = self.adherer_f.adhere_from(hs, v * d);
+            jobs.push((id, Some(v), adh, 0, AdherenceStats::default()));
+        }
+
+        // Deferred paths are revisited once the dispatched searches are merged.
+        deferred.append(&mut self.path_queue);
+        self.path_queue = deferred;
+
+        if jobs.is_empty() {
+            return None;
+        }
+
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs
+                .into_iter()
+                .zip(classifiers.iter_mut())
+                .map(|((parent, v, adh, n_reported, stats), classifier)| {
+                    scope.spawn(move || (parent, v, n_reported, stats, adhere(adh, classifier)))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("Adherence search panicked?"))
+                .collect()
+        });
+
+        let mut retries = vec![];
+        let adherences = outcomes
+            .into_iter()
+            .map(
+                |(parent, v, n_reported, stats, (samples, result, displacement))| {
+                    let new_samples = samples[n_reported..].to_vec();
+                    new_samples.iter().for_each(|s| self.observers.sample(s));
+                    if let Some(history) = &mut self.sample_history {
+                        history.extend_from_slice(&new_samples);
+                    }
+                    match (&result, v) {
+                        (Ok(hs), _) => {
+                            let parent_hs = &self.boundary[parent];
+                            let stats =
+                                stats.merge(&AdherenceStats::from_samples(parent_hs, &samples));
+                            self.insert_halfspace(*hs, parent, stats)
+                        }
+                        // A lost connection is retryable, so the path is revisited.
+                        (Err(e), Some(v)) if e.is_retryable() => {
+                            if !matches!(e, SamplingError::Pending) {
+                                self.observers.error(e);
+                            }
+                            retries.push((parent, v));
+                        }
+                        (Err(e), _) => {
+                            self.observers.error(e);
+                            self.observers.adherence_failed(&AdherenceFailure {
+                                pivot: self.boundary[parent],
+                                samples: samples.clone(),
+                                displacement,
+                                error: e.clone(),
+                            });
+                            self.observers.branch_pruned(&self.boundary[parent], e);
+                        }
+                    }
+
+                    ParallelAdherence {
+                        parent,
+                        samples: new_samples,
+                        result,
+                    }
+                },
+            )
+            .collect();
+
+        retries.append(&mut self.path_queue);
+        self.path_queue = retries;
+
+        Some(adherences)
+    }
+}
+
+/// Finds a halfspace in @index within @tolerance distance of @hs.
+fn find_duplicate<const N: usize>(
+    index: &dyn SpatialIndex<N>,
+    hs: &Halfspace<N>,
+    tolerance: f64,
+) -> Option<NodeID> {
+    let b: [f64; N] = hs.b.into();
+    index
+        .nearest_neighbor(&b)
+        .filter(|nearest| array_distance(&b, nearest.geom()) <= tolerance)
+        .map(|nearest| nearest.data)
+}
+
+fn merge_normals<const N: usize>(n1: &SVector<f64, N>, n2: &SVector<f64, N>) -> SVector<f64, N> {
+    // Opposing normals cancel out, in which case the existing normal is kept.
+    (n1 + n2).try_normalize(1e-10).unwrap_or(*n1)
+}
+
+/// Runs @adherer to completion, returning the samples it took, its result and
+/// its final displacement.
+#[cfg(feature = "parallel")]
+fn adhere<const N: usize, A: Adherer<N>, C: Classifier<N>>(
+    mut adherer: A,
+    classifier: &mut C,
+) -> (Vec<Sample<N>>, Result<Halfspace<N>>, SVector<f64, N>) {
+    let result = loop {
+        if let Err(e) = adherer.sample_next(classifier) {
+            break Err(e);
+        }
+
+        if let AdhererState::FoundBoundary(hs) = adherer.get_state() {
+            break Ok(hs);
+        }
+    };
+
+    (adherer.samples().to_vec(), result, adherer.displacement())
+}
+
 impl<const N: usize, F: AdhererFactory<N>> Explorer<N, F> for MeshExplorer<N, F> {
     fn step<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<Option<Sample<N>>> {
         if self.adherer.is_none() {
-            if let Some((hs, id, v)) = self.select_parent() {
+            if let Some((hs, id, v, d)) = self.select_parent() {
                 self.current_parent = id;
-                self.adherer = Some(self.adherer_f.adhere_from(hs, v * self.d))
+                self.adherer = Some(self.adherer_f.adhere_from(hs, v * d));
+                self.active_path = Some(ActivePath {
+                    pivot: hs,
+                    v,
+                    d,
+                    stats: AdherenceStats::default(),
+                });
             }
         }
 
         let node = if let Some(ref mut adh) = self.adherer {
-            match adh.sample_next(classifier) {
-                Ok(result) => {
-                    let sample = *result;
+            let n_prev = adh.samples().len();
+            let result = adh.sample_next(classifier).copied();
+            // Includes the final sample of a failed adherence.
+            for sample in adh.samples().iter().skip(n_prev) {
+                self.observers.sample(sample);
+                if let Some(history) = &mut self.sample_history {
+                    history.push(*sample);
+                }
+            }
 
+            match result {
+                Ok(sample) => {
                     if let AdhererState::FoundBoundary(hs) = adh.get_state() {
-                        self.boundary.push(hs);
-                        self.add_child(hs, Some(NodeIndex::new(self.current_parent)));
+                        let parent = &self.boundary[self.current_parent];
+                        let stats = self
+                            .active_path
+                            .map(|path| path.stats)
+                            .unwrap_or_default()
+                            .merge(&AdherenceStats::from_samples(parent, adh.samples()));
+                        self.insert_halfspace(hs, self.current_parent, stats);
                         self.adherer = None
                     }
 
@@ -182,7 +819,29 @@ impl<const N: usize, F: AdhererFactory<N>> Explorer<N, F> for MeshExplorer<N, F>
             Ok(None)
         };
 
-        node.inspect_err(|_| self.adherer = None)
+        // A lost connection is retryable, so the path is kept for the next step. A
+        // pending classification is not an error, so observers are not notified.
+        node.inspect_err(|e| {
+            if !matches!(e, SamplingError::Pending) {
+                self.observers.error(e);
+            }
+            if !e.is_retryable() {
+                let parent = self.boundary[self.current_parent];
+                let mut failed = AdherenceStats::default();
+                if let Some(adh) = self.adherer.take() {
+                    let failure = AdherenceFailure::from_adherer(parent, &adh, e.clone());
+                    self.observers.adherence_failed(&failure);
+                    failed = AdherenceStats::from_samples(&parent, &failure.samples);
+                }
+                if !(matches!(e, SamplingError::BoundaryLost) && self.retry_active_path(failed)) {
+                    self.observers.branch_pruned(&parent, e);
+                }
+            }
+        })
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn ExplorationObserver<N>>) {
+        self.observers.add(observer);
     }
 
     fn boundary(&self) -> &Vec<Halfspace<N>> {
@@ -196,19 +855,49 @@ impl<const N: usize, F: AdhererFactory<N>> Explorer<N, F> for MeshExplorer<N, F>
         self.boundary.len()
     }
 
+    fn adherence_stats(&self) -> Option<&[AdherenceStats]> {
+        Some(&self.adherence_stats)
+    }
+
     fn describe(&self) -> ExplorationStatus<N, F> {
         let mut expl_params = HashMap::new();
         expl_params.insert("d".to_string(), self.d);
         expl_params.insert("margin".to_string(), self.margin);
+        if self.auto_margin {
+            expl_params.insert("auto_margin".to_string(), 1.0);
+        }
+        if self.goal.is_some() {
+            expl_params.insert("goal_biased".to_string(), 1.0);
+        }
+        if self.walls.is_some() {
+            expl_params.insert("domain_walls".to_string(), 1.0);
+        }
+        if self.max_lost_retries > 0 {
+            expl_params.insert("max_lost_retries".to_string(), self.max_lost_retries as f64);
+        }
+        if let Some(adaptive) = self.adaptive_jump {
+            expl_params.insert("min_d".to_string(), adaptive.min_d);
+            expl_params.insert("max_d".to_string(), adaptive.max_d);
+            expl_params.insert("target_angle".to_string(), adaptive.target_angle);
+        }
 
-        ExplorationStatus::new(
+        let status = ExplorationStatus::new(
             "Mesh Explorer",
             type_name::<F>(),
             expl_params,
             self.adherer_f,
             &self.boundary,
             None,
-        )
+        );
+
+        match &self.sample_history {
+            Some(samples) => status.with_samples(samples),
+            None => status,
+        }
+    }
+
+    fn samples(&self) -> Option<&[Sample<N>]> {
+        self.sample_history.as_deref()
     }
 
     /// Loads a new boundary into the explorer, overwriting the existing boundary.
@@ -219,24 +908,21 @@ impl<const N: usize, F: AdhererFactory<N>> Explorer<N, F> for MeshExplorer<N, F>
     ///          approach to developing the graph.
     fn load_boundary(&mut self, boundary: Vec<Halfspace<N>>) {
         assert!(!boundary.is_empty(), "Boundary must be non-empty!");
-        self.knn_index = get_rtree_from_boundary(&boundary);
+        self.boundary = boundary;
+        self.adherence_stats = vec![AdherenceStats::default(); self.boundary.len()];
+        self.tree = Graph::new();
+        self.knn_index = self.knn_kind.build();
         self.adherer = None;
         self.path_queue = vec![];
 
-        for hs in boundary.iter() {
-            if self.path_queue.is_empty() {
-                self.add_child(*hs, None);
-                continue;
-            }
-
-            if let Some(neighbor) = self.knn_index.nearest_neighbor(&hs.b.into()) {
-                self.add_child(*hs, Some(NodeIndex::new(neighbor.data)));
-            } else {
-                panic!("Unexpected error while loading")
-            }
+        for i in 0..self.boundary.len() {
+            let hs = self.boundary[i];
+            let parent = self
+                .knn_index
+                .nearest_neighbor(&hs.b.into())
+                .map(|neighbor| NodeIndex::new(neighbor.data));
+            self.add_child(hs, parent);
         }
-
-        self.boundary = boundary;
     }
 }
 
@@ -251,19 +937,14 @@ impl<const N: usize, F: AdhererFactory<N>> Backpropagation<N> for MeshExplorer<N
         let parent = self.boundary[parent_indx.index()];
 
         let b: [f64; N] = parent.b.into();
-        let rtree = &self.knn_index;
-        let neighbors = rtree.nearest_neighbor_iter(&b);
+        let neighbors = self.knn_index.within_distance(&b, margin);
 
         let mut n = SVector::zeros();
         let mut count = 0;
 
         for node in neighbors {
-            if array_distance(&b, node.geom()) <= margin {
-                n += self.boundary[node.data].n;
-                count += 1;
-            } else {
-                break;
-            }
+            n += self.boundary[node.data].n;
+            count += 1;
         }
 
         n /= count as f64;
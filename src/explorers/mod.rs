@@ -1,3 +1,9 @@
+pub mod ask_tell;
+pub mod curvature_explorer;
 pub mod mesh_explorer;
+pub mod surrogate_explorer;
 
+pub use ask_tell::*;
+pub use curvature_explorer::*;
 pub use mesh_explorer::*;
+pub use surrogate_explorer::*;
@@ -0,0 +1,237 @@
+use std::marker::PhantomData;
+
+use nalgebra::SVector;
+
+use crate::{
+    adherer_core::{AdherenceStats, AdhererFactory},
+    boundary_tools::{bulk_insert_rtree, get_rtree_from_boundary},
+    explorer_core::{ExplorationObserver, Explorer},
+    prelude::{report::ExplorationStatus, BoundaryRTree},
+    structs::{Classifier, Halfspace, Result, Sample},
+};
+
+/// Tracks how the classifications requested during surrogate-assisted exploration
+/// were answered.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SurrogateStats {
+    /// Classifications answered by the real classifier, including validations.
+    pub real_samples: u32,
+    /// Classifications answered by the surrogate.
+    pub surrogate_samples: u32,
+    /// Surrogate predictions that were checked against the real classifier.
+    pub validations: u32,
+    /// Validations where the surrogate prediction was incorrect.
+    pub validation_failures: u32,
+}
+
+/// Wraps an explorer, using the boundary it has explored so far as a surrogate
+/// model of the FUT. Adherence probes that fall confidently on one side of the
+/// known surface are answered by the surrogate, and only probes near the surface
+/// are sent to the real classifier. Every @validation_interval surrogate answer is
+/// checked against the real classifier, and the confidence margin is doubled
+/// whenever the surrogate is found to be wrong.
+///
+/// Most effective on smooth boundaries with adherers that probe far from the
+/// surface, such as the BinarySearchAdherer's initial rotations.
+pub struct SurrogateExplorer<const N: usize, F, E>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+{
+    explorer: E,
+    boundary: Vec<Halfspace<N>>,
+    btree: BoundaryRTree<N>,
+    margin: f64,
+    validation_interval: u32,
+    min_boundary: usize,
+    stats: SurrogateStats,
+    _adherer_f: PhantomData<F>,
+}
+
+/// The classifier handed to the inner explorer, which screens probes using the
+/// surrogate before forwarding them to the real classifier.
+struct ScreenedClassifier<'a, const N: usize, C: Classifier<N>> {
+    classifier: &'a mut C,
+    boundary: &'a [Halfspace<N>],
+    btree: &'a BoundaryRTree<N>,
+    margin: &'a mut f64,
+    validation_interval: u32,
+    min_boundary: usize,
+    stats: &'a mut SurrogateStats,
+}
+
+impl<const N: usize, F, E> SurrogateExplorer<N, F, E>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+{
+    /// Creates a SurrogateExplorer.
+    /// ## Arguments
+    /// * explorer : The explorer to assist.
+    /// * margin : The minimum distance from the nearest known halfspace's plane for
+    ///   the surrogate's prediction to be trusted. A good default is the jump
+    ///   distance, d, of the explorer.
+    /// * validation_interval : How many surrogate predictions to make per
+    ///   validation against the real classifier. Must be non-zero.
+    /// * min_boundary : The minimum number of halfspaces before the surrogate is
+    ///   used at all.
+    pub fn new(explorer: E, margin: f64, validation_interval: u32, min_boundary: usize) -> Self {
+        assert!(
+            validation_interval > 0,
+            "validation_interval must be positive non-zero!"
+        );
+
+        let boundary = explorer.boundary().clone();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        SurrogateExplorer {
+            explorer,
+            boundary,
+            btree,
+            margin,
+            validation_interval,
+            min_boundary,
+            stats: SurrogateStats::default(),
+            _adherer_f: PhantomData,
+        }
+    }
+
+    /// Statistics on how classifications were answered so far.
+    pub fn stats(&self) -> &SurrogateStats {
+        &self.stats
+    }
+
+    /// The current confidence margin, which grows when validation fails.
+    pub fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    /// The assisted explorer.
+    pub fn inner(&self) -> &E {
+        &self.explorer
+    }
+
+    /// Returns the assisted explorer.
+    pub fn into_inner(self) -> E {
+        self.explorer
+    }
+
+    /// Updates the surrogate to match the inner explorer's boundary. Appended
+    /// halfspaces are inserted, while any other change (e.g. merged or compacted
+    /// halfspaces) rebuilds the surrogate.
+    fn sync_surrogate(&mut self) {
+        let boundary = self.explorer.boundary();
+        let n = self.boundary.len();
+        if boundary.len() >= n && boundary[..n] == self.boundary[..] {
+            let new_hs = &boundary[n..];
+            bulk_insert_rtree(&mut self.btree, new_hs);
+            self.boundary.extend_from_slice(new_hs);
+        } else {
+            self.boundary = boundary.clone();
+            self.btree = get_rtree_from_boundary(&self.boundary);
+        }
+    }
+}
+
+impl<const N: usize, C: Classifier<N>> ScreenedClassifier<'_, N, C> {
+    /// The signed distance of @p from the plane of its nearest halfspace, where
+    /// negative values fall within the envelope.
+    fn surrogate_distance(&self, p: &SVector<f64, N>) -> Option<f64> {
+        if self.boundary.len() < self.min_boundary {
+            return None;
+        }
+
+        let node = self.btree.nearest_neighbor(&(*p).into())?;
+        let hs = &self.boundary[node.data];
+        Some((p - *hs.b).dot(&hs.n))
+    }
+}
+
+impl<const N: usize, C: Classifier<N>> Classifier<N> for ScreenedClassifier<'_, N, C> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let dist = match self.surrogate_distance(&p) {
+            Some(dist) if dist.abs() > *self.margin => dist,
+            _ => {
+                self.stats.real_samples += 1;
+                return self.classifier.classify(p);
+            }
+        };
+
+        let prediction = Sample::from_class(p, dist < 0.0);
+        self.stats.surrogate_samples += 1;
+
+        if self
+            .stats
+            .surrogate_samples
+            .is_multiple_of(self.validation_interval)
+        {
+            self.stats.real_samples += 1;
+            self.stats.validations += 1;
+            let truth = self.classifier.classify(p)?;
+            if truth.class() != prediction.class() {
+                self.stats.validation_failures += 1;
+                *self.margin *= 2.0;
+            }
+            return Ok(truth);
+        }
+
+        Ok(prediction)
+    }
+}
+
+impl<const N: usize, F, E> Explorer<N, F> for SurrogateExplorer<N, F, E>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+{
+    fn step<C: Classifier<N>>(&mut self, classifier: &mut C) -> Result<Option<Sample<N>>> {
+        let mut screened = ScreenedClassifier {
+            classifier,
+            boundary: &self.boundary,
+            btree: &self.btree,
+            margin: &mut self.margin,
+            validation_interval: self.validation_interval,
+            min_boundary: self.min_boundary,
+            stats: &mut self.stats,
+        };
+
+        let result = self.explorer.step(&mut screened);
+        self.sync_surrogate();
+
+        result
+    }
+
+    fn boundary(&self) -> &Vec<Halfspace<N>> {
+        self.explorer.boundary()
+    }
+
+    fn load_boundary(&mut self, boundary: Vec<Halfspace<N>>) {
+        self.explorer.load_boundary(boundary);
+        self.boundary = self.explorer.boundary().clone();
+        self.btree = get_rtree_from_boundary(&self.boundary);
+    }
+
+    fn boundary_owned(self) -> Vec<Halfspace<N>> {
+        self.explorer.boundary_owned()
+    }
+
+    fn boundary_count(&self) -> usize {
+        self.explorer.boundary_count()
+    }
+
+    fn describe(&self) -> ExplorationStatus<N, F> {
+        self.explorer.describe()
+    }
+
+    fn samples(&self) -> Option<&[Sample<N>]> {
+        self.explorer.samples()
+    }
+
+    fn adherence_stats(&self) -> Option<&[AdherenceStats]> {
+        self.explorer.adherence_stats()
+    }
+
+    fn add_observer(&mut self, observer: Box<dyn ExplorationObserver<N>>) {
+        self.explorer.add_observer(observer);
+    }
+}
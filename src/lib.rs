@@ -1,14 +1,22 @@
 pub mod adherer_core;
 pub mod adherers;
 pub mod boundary_tools;
+pub mod classifiers;
+pub mod dynamic;
 pub mod explorer_core;
 pub mod explorers;
 pub mod extensions;
 pub mod prelude;
 pub mod search;
+pub mod spatial_index;
 pub mod structs;
+#[cfg(feature = "io")]
+pub mod supervisor;
 mod utils;
 
+#[cfg(test)]
+mod test_utils;
+
 #[cfg(feature = "api")]
 pub mod api;
 
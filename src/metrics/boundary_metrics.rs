@@ -1,6 +1,6 @@
 use nalgebra::{Const, OMatrix, SVector};
 
-use crate::prelude::Boundary;
+use crate::{prelude::Boundary, utils::sorted_eigen};
 
 /// Calculates K, a metric that describes how the surface is curved relative to the
 /// CoM. Where -1 <= K <= 1.
@@ -9,7 +9,7 @@ use crate::prelude::Boundary;
 ///   to near and far boundaries canceling out and indicates a closed concave
 ///   topology for those dimensions.
 /// * If CoM falls outside of the envelope, values far from zero indicate an open
-///  concave topology for those dimensions.
+///   concave topology for those dimensions.
 /// ## Values
 /// A value of 1 means a concave sphere. A value -1 means a convex sphere. A value of
 /// 0 means a flat plane.
@@ -86,19 +86,119 @@ pub fn boundary_std_dev<const N: usize>(
     cov / count
 }
 
+/// How elongated the boundary is, from the principal components of its covariance.
+/// See `anisotropy()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anisotropy<const N: usize> {
+    /// The ratio of the largest to the smallest variance, 1 <= ratio. A value of 1
+    /// means an isotropic (e.g. spherical) boundary, larger values a more elongated
+    /// one. Infinite when the boundary is flat along some direction.
+    pub ratio: f64,
+    /// The variance of the boundary along each principal direction, in descending
+    /// order.
+    pub variances: Vec<f64>,
+    /// The unit principal directions, ordered as @variances.
+    pub directions: Vec<SVector<f64, N>>,
+}
+
+impl<const N: usize> Anisotropy<N> {
+    /// The ratio of the largest to the smallest standard deviation, i.e. roughly
+    /// how many times longer the envelope is than it is wide.
+    pub fn elongation(&self) -> f64 {
+        self.ratio.sqrt()
+    }
+}
+
+/// Calculates how elongated the boundary is, summarizing `boundary_std_dev()` as a
+/// single ratio together with the principal directions of the boundary.
+/// ## Arguments
+/// * boundary : The set of halfspaces describing the boundary.
+/// ## Returns
+/// * anisotropy : The ratio of the extreme variances and the principal directions.
+pub fn anisotropy<const N: usize>(boundary: &Boundary<N>) -> Anisotropy<N> {
+    let cov = boundary_std_dev(boundary);
+
+    let eigen = sorted_eigen(&cov);
+
+    // Round-off can leave the variance of flat directions slightly negative.
+    let variances: Vec<f64> = eigen.iter().map(|(value, _)| value.max(0.0)).collect();
+    let (max, min) = (variances[0], variances[N - 1]);
+    let ratio = if min > 1e-12 * max {
+        max / min
+    } else {
+        f64::INFINITY
+    };
+
+    Anisotropy {
+        ratio,
+        variances,
+        directions: eigen.into_iter().map(|(_, v)| v).collect(),
+    }
+}
+
+/// The distribution of the boundary's distances from its CoM. See `radius_stats()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadiusStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// The distance of each boundary point from the CoM, in ascending order.
+    pub distances: Vec<f64>,
+}
+
+impl RadiusStats {
+    /// The distance from the CoM at quantile @q, 0 <= q <= 1, interpolating linearly
+    /// between boundary points.
+    pub fn percentile(&self, q: f64) -> f64 {
+        let pos = q.clamp(0.0, 1.0) * (self.distances.len() - 1) as f64;
+        let (i, frac) = (pos.floor() as usize, pos.fract());
+        match self.distances.get(i + 1) {
+            Some(next) => self.distances[i] + frac * (next - self.distances[i]),
+            None => self.distances[i],
+        }
+    }
+}
+
+/// Calculates the distribution of distances between the boundary and its CoM.
+/// ## Arguments
+/// * boundary : The set of halfspaces describing the boundary.
+/// ## Returns
+/// * stats : The min, max, mean and percentiles of the distances from the CoM.
+/// ## Panic
+/// * When @boundary is empty.
+pub fn radius_stats<const N: usize>(boundary: &Boundary<N>) -> RadiusStats {
+    assert!(!boundary.is_empty(), "Must provide a non-empty boundary!");
+    let com = center_of_mass(boundary);
+
+    let mut distances: Vec<f64> = boundary.iter().map(|hs| (hs.b - com).norm()).collect();
+    distances.sort_by(|a, b| {
+        a.partial_cmp(b)
+            .expect("Unexpected NaN while sorting dist from com.")
+    });
+
+    RadiusStats {
+        min: distances[0],
+        max: distances[distances.len() - 1],
+        mean: distances.iter().sum::<f64>() / distances.len() as f64,
+        distances,
+    }
+}
+
 /// Calculates the radius of the boundary.
 /// ## Arguments
 /// * boundary : The set of halfspaces describing the boundary.
 /// ## Returns
 /// * radius : The maximum distance from the CoM
+/// ## Panic
+/// * When @boundary is empty.
 pub fn boundary_radius<const N: usize>(boundary: &Boundary<N>) -> f64 {
     let com = center_of_mass(boundary);
     boundary
         .iter()
         .map(|hs| (hs.b - com).norm())
-        .min_by(|a, b| {
+        .max_by(|a, b| {
             a.partial_cmp(b)
-                .expect("Unexpected NaN while finding min(dist from com).")
+                .expect("Unexpected NaN while finding max(dist from com).")
         })
         .expect("Must provide a non-empty boundary!")
 }
@@ -112,7 +212,7 @@ mod test_metrics {
         prelude::{Halfspace, WithinMode},
     };
 
-    use super::{boundary_radius, center_of_mass, mean_direction};
+    use super::{anisotropy, boundary_radius, center_of_mass, mean_direction, radius_stats};
 
     fn get_simple_line<const N: usize>(n: u32, max_err: f64) -> Vec<Halfspace<N>> {
         let mut boundary = vec![];
@@ -150,11 +250,26 @@ mod test_metrics {
     fn correct_radius() {
         let n_points = 10;
         let d = 0.1;
-        let correct_radius = d * n_points as f64 / 2.0;
+        let correct_radius = d * (n_points - 1) as f64 / 2.0;
         let boundary = get_simple_line::<10>(n_points, d);
         let r = boundary_radius(&boundary);
 
-        assert!(r - correct_radius <= 1e-10, "Incorrect radius?");
+        assert!((r - correct_radius).abs() <= 1e-10, "Incorrect radius?");
+    }
+
+    #[test]
+    fn radius_stats_of_line() {
+        let boundary = get_simple_line::<3>(11, 0.1);
+        let stats = radius_stats(&boundary);
+
+        assert!(stats.min.abs() <= 1e-10);
+        assert!((stats.max - 0.5).abs() <= 1e-10);
+        assert!((stats.max - boundary_radius(&boundary)).abs() <= 1e-10);
+        // Two points at each of 0.1, ..., 0.5 and one at 0
+        assert!((stats.mean - 3.0 / 11.0).abs() <= 1e-10);
+        assert!((stats.percentile(0.5) - 0.3).abs() <= 1e-10);
+        assert!((stats.percentile(0.95) - 0.5).abs() <= 1e-10);
+        assert_eq!(stats.percentile(0.0), stats.min);
     }
 
     #[test]
@@ -163,4 +278,44 @@ mod test_metrics {
         let k = curvature(&boundary);
         assert!(k <= 1e-10, "Curvature was not 0 for a plane.")
     }
+
+    fn get_ellipse(a: f64, b: f64, n: u32) -> Vec<Halfspace<2>> {
+        (0..n)
+            .map(|i| {
+                let theta = std::f64::consts::TAU * i as f64 / n as f64;
+                let p = SVector::from([a * theta.cos(), b * theta.sin()]);
+                Halfspace {
+                    b: WithinMode(p),
+                    n: SVector::from([p[0] / (a * a), p[1] / (b * b)]).normalize(),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn anisotropy_of_ellipse() {
+        let boundary = get_ellipse(0.4, 0.1, 100);
+        let a = anisotropy(&boundary);
+
+        assert!(
+            (a.ratio - 16.0).abs() < 1e-6,
+            "Incorrect ratio: {}",
+            a.ratio
+        );
+        assert!((a.elongation() - 4.0).abs() < 1e-6);
+        assert!((a.variances[0] - 0.08).abs() < 1e-10);
+        assert!(a.directions[0][0].abs() > 1.0 - 1e-10);
+    }
+
+    #[test]
+    fn anisotropy_of_circle_is_one() {
+        let boundary = get_ellipse(0.3, 0.3, 100);
+        assert!((anisotropy(&boundary).ratio - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn anisotropy_of_plane_is_infinite() {
+        let boundary = get_simple_line::<3>(10, 0.1);
+        assert_eq!(anisotropy(&boundary).ratio, f64::INFINITY);
+    }
 }
@@ -0,0 +1,178 @@
+use std::f64::consts::PI;
+
+use nalgebra::SVector;
+
+use crate::prelude::Boundary;
+
+/// An estimate of how much of an envelope's surface an exploration has covered.
+/// See `estimate_coverage()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceCoverage {
+    /// The surface area covered by the explored boundary points.
+    pub explored_area: f64,
+    /// The extrapolated surface area of the whole envelope.
+    pub total_area: f64,
+}
+
+impl SurfaceCoverage {
+    /// The fraction of the surface covered, 0 <= fraction <= 1.
+    pub fn fraction(&self) -> f64 {
+        if self.total_area <= 0.0 {
+            return 0.0;
+        }
+        (self.explored_area / self.total_area).min(1.0)
+    }
+
+    /// The percentage of the surface covered, 0 <= percent <= 100.
+    pub fn percent(&self) -> f64 {
+        100.0 * self.fraction()
+    }
+}
+
+/// The surface area covered by an explored boundary, assuming each boundary point
+/// covers a d^(N-1) cell of the surface, as with a mesh exploration.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * d : The jump distance used to explore @boundary.
+/// ## Returns
+/// * area : The (N-1)-dimensional surface area covered.
+pub fn explored_area<const N: usize>(boundary: &Boundary<N>, d: f64) -> f64 {
+    boundary.len() as f64 * d.powi(N as i32 - 1)
+}
+
+/// Estimates what fraction of the envelope's surface has been explored, so that
+/// long-running explorations can report their progress. The total surface area is
+/// extrapolated from a hypersphere fit to the explored halfspaces, which only needs
+/// part of the surface since each halfspace's surface vector points away from the
+/// center (see `fit_sphere()`).
+/// ## Warning
+/// * The extrapolation assumes a roughly spherical envelope. Elongated or concave
+///   envelopes are over- or under-estimated.
+/// * Use `SurfaceCoverage { explored_area, total_area }` directly when a better
+///   estimate of the total surface area is known.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * d : The jump distance used to explore @boundary.
+/// ## Returns
+/// * coverage : The explored and extrapolated total surface areas. Without a
+///   curved boundary to extrapolate from, the total area is infinite.
+pub fn estimate_coverage<const N: usize>(boundary: &Boundary<N>, d: f64) -> SurfaceCoverage {
+    let total_area = match fit_sphere(boundary) {
+        Some((_, radius)) => sphere_surface_area::<N>(radius),
+        None => f64::INFINITY,
+    };
+
+    SurfaceCoverage {
+        explored_area: explored_area(boundary, d),
+        total_area,
+    }
+}
+
+/// Fits a hypersphere to a boundary by least squares over b = center + radius * n.
+/// ## Returns
+/// * sphere : The (center, radius) of the fitted hypersphere, or None if the
+///   boundary is empty, flat, or faces inwards (radius <= 0).
+pub fn fit_sphere<const N: usize>(boundary: &Boundary<N>) -> Option<(SVector<f64, N>, f64)> {
+    if boundary.is_empty() {
+        return None;
+    }
+
+    let count = boundary.len() as f64;
+    let mean_b = boundary.iter().map(|hs| *hs.b).sum::<SVector<f64, N>>() / count;
+    let mean_n = boundary.iter().map(|hs| hs.n).sum::<SVector<f64, N>>() / count;
+
+    let (num, den) = boundary.iter().fold((0.0, 0.0), |(num, den), hs| {
+        let dn = hs.n - mean_n;
+        (num + (*hs.b - mean_b).dot(&dn), den + dn.norm_squared())
+    });
+    if den <= f64::EPSILON {
+        return None;
+    }
+
+    let radius = num / den;
+    (radius > 0.0).then(|| (mean_b - radius * mean_n, radius))
+}
+
+/// The surface area of a hypersphere in N dimensions.
+fn sphere_surface_area<const N: usize>(radius: f64) -> f64 {
+    // S_1 = 2, S_2 = 2 pi, S_n+2 = 2 pi / n * S_n for the unit hypersphere
+    let mut unit = if N % 2 == 1 { 2.0 } else { 2.0 * PI };
+    let mut n = 2 - N % 2;
+    while n < N {
+        unit *= 2.0 * PI / n as f64;
+        n += 2;
+    }
+
+    unit * radius.powi(N as i32 - 1)
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use nalgebra::vector;
+
+    use crate::{
+        prelude::{Halfspace, WithinMode},
+        test_utils::sphere_boundary,
+    };
+
+    use super::*;
+
+    const RADIUS: f64 = 0.3;
+
+    /// @n halfspaces evenly spread over a sphere, by the golden spiral.
+    fn sphere(n: usize) -> Vec<Halfspace<3>> {
+        sphere_boundary(vector![0.5, 0.5, 0.5], RADIUS, n)
+    }
+
+    #[test]
+    fn surface_area_of_known_spheres() {
+        assert!((sphere_surface_area::<2>(1.0) - 2.0 * PI).abs() < 1e-12);
+        assert!((sphere_surface_area::<3>(2.0) - 16.0 * PI).abs() < 1e-12);
+        assert!((sphere_surface_area::<4>(1.0) - 2.0 * PI * PI).abs() < 1e-12);
+        assert!((sphere_surface_area::<5>(1.0) - 8.0 / 3.0 * PI * PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fits_sphere_from_partial_boundary() {
+        let boundary: Vec<_> = sphere(1000)
+            .into_iter()
+            .filter(|hs| hs.n[2] > 0.5)
+            .collect();
+        let (center, radius) = fit_sphere(&boundary).unwrap();
+
+        assert!((center - SVector::repeat(0.5)).norm() < 1e-9);
+        assert!((radius - RADIUS).abs() < 1e-9);
+    }
+
+    #[test]
+    fn half_explored_sphere_is_half_covered() {
+        let n = 2000;
+        // The jump distance that spreads n points over the sphere
+        let d = (4.0 * PI * RADIUS * RADIUS / n as f64).sqrt();
+        let full = sphere(n);
+        let half: Vec<_> = full.iter().copied().filter(|hs| hs.n[2] > 0.0).collect();
+
+        let coverage = estimate_coverage(&full, d);
+        assert!((coverage.fraction() - 1.0).abs() < 1e-9);
+
+        let coverage = estimate_coverage(&half, d);
+        assert!(
+            (coverage.percent() - 50.0).abs() < 1.0,
+            "Bad coverage: {}%",
+            coverage.percent()
+        );
+    }
+
+    #[test]
+    fn flat_boundary_cannot_be_extrapolated() {
+        let boundary: Vec<_> = (0..10)
+            .map(|i| Halfspace {
+                b: WithinMode(vector![i as f64 * 0.1, 0.5]),
+                n: vector![0.0, 1.0],
+            })
+            .collect();
+
+        assert!(fit_sphere(&boundary).is_none());
+        assert_eq!(estimate_coverage(&boundary, 0.1).fraction(), 0.0);
+    }
+}
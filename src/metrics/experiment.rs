@@ -0,0 +1,370 @@
+use std::f64::consts::PI;
+#[cfg(feature = "io")]
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use nalgebra::SVector;
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    adherers::{bs_adherer::BinarySearchAdhererFactory, const_adherer::ConstantAdhererFactory},
+    boundary_tools::estimation::{approx_mc_volume, PredictionMode},
+    explorer_core::Explorer,
+    explorers::MeshExplorer,
+    prelude::{AdhererFactory, Halfspace},
+    structs::{Classifier, Domain, Result, Sample},
+};
+
+/// The adherer, and its parameters, to use for an experiment.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdhererConfig {
+    Constant {
+        delta_angle: f64,
+        max_rotation: Option<f64>,
+    },
+    BinarySearch {
+        init_angle: f64,
+        n_iter: u32,
+    },
+}
+
+/// The explorer settings for a single experiment.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExperimentConfig {
+    pub d: f64,
+    pub margin: f64,
+    pub adherer: AdhererConfig,
+    pub max_samples: usize,
+}
+
+impl ExperimentConfig {
+    /// Produces every combination of jump distance, margin and adherer.
+    /// ## Arguments
+    /// * ds : The jump distances to try.
+    /// * margin_ratios : The margins to try, as a fraction of the jump distance.
+    /// * adherers : The adherers to try.
+    /// * max_samples : The sample budget for each experiment.
+    pub fn sweep(
+        ds: &[f64],
+        margin_ratios: &[f64],
+        adherers: &[AdhererConfig],
+        max_samples: usize,
+    ) -> Vec<Self> {
+        let mut configs = vec![];
+        for &d in ds {
+            for &ratio in margin_ratios {
+                for &adherer in adherers {
+                    configs.push(ExperimentConfig {
+                        d,
+                        margin: d * ratio,
+                        adherer,
+                        max_samples,
+                    });
+                }
+            }
+        }
+
+        configs
+    }
+}
+
+/// A shape whose true boundary is known, allowing the accuracy of an exploration
+/// to be measured.
+pub trait GroundTruth<const N: usize> {
+    /// The true surface direction at (or nearest to) @p.
+    fn true_normal(&self, p: &SVector<f64, N>) -> SVector<f64, N>;
+    /// The true volume of the envelope.
+    fn true_volume(&self) -> f64;
+}
+
+/// The outcome of a single experiment.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentResult {
+    pub target: String,
+    pub config: ExperimentConfig,
+    pub b_count: usize,
+    pub n_samples: usize,
+    /// Boundary Sampling Efficiency, the ratio of boundary points to samples.
+    pub bse: f64,
+    /// The mean angle between the approximate and true OSVs, normalized to [0, 1].
+    pub osv_err: Option<f64>,
+    /// The relative error of the estimated volume.
+    pub volume_err: Option<f64>,
+}
+
+/// A collection of experiment results for comparing configurations.
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExperimentReport {
+    results: Vec<ExperimentResult>,
+}
+
+impl ExperimentReport {
+    pub fn new() -> Self {
+        ExperimentReport { results: vec![] }
+    }
+
+    pub fn push(&mut self, result: ExperimentResult) {
+        self.results.push(result);
+    }
+
+    pub fn extend(&mut self, other: ExperimentReport) {
+        self.results.extend(other.results);
+    }
+
+    pub fn results(&self) -> &[ExperimentResult] {
+        &self.results
+    }
+
+    /// The result with the highest BSE.
+    pub fn best_by_bse(&self) -> Option<&ExperimentResult> {
+        self.results
+            .iter()
+            .max_by(|a, b| a.bse.partial_cmp(&b.bse).unwrap())
+    }
+
+    /// Renders the report as a CSV table, with one row per experiment.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "target,d,margin,adherer,adherer_param1,adherer_param2,b_count,n_samples,bse,osv_err,volume_err\n",
+        );
+        let opt = |x: Option<f64>| x.map(|x| x.to_string()).unwrap_or_default();
+
+        for r in self.results.iter() {
+            let (name, p1, p2) = match r.config.adherer {
+                AdhererConfig::Constant {
+                    delta_angle,
+                    max_rotation,
+                } => ("constant", delta_angle.to_string(), opt(max_rotation)),
+                AdhererConfig::BinarySearch { init_angle, n_iter } => {
+                    ("binary_search", init_angle.to_string(), n_iter.to_string())
+                }
+            };
+            csv.push_str(&format!(
+                "{},{},{},{name},{p1},{p2},{},{},{},{},{}\n",
+                r.target,
+                r.config.d,
+                r.config.margin,
+                r.b_count,
+                r.n_samples,
+                r.bse,
+                opt(r.osv_err),
+                opt(r.volume_err),
+            ));
+        }
+
+        csv
+    }
+}
+
+#[cfg(feature = "io")]
+impl ExperimentReport {
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let f = File::create(path)?;
+        let mut writer = BufWriter::new(f);
+        serde_json::to_writer(&mut writer, &self)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Sets up ground truth evaluation for an experiment.
+pub struct Evaluation<'a, const N: usize> {
+    pub truth: &'a dyn GroundTruth<N>,
+    /// The domain to estimate volume within.
+    pub domain: Domain<N>,
+    /// The number of MC samples for volume estimation.
+    pub n_volume_samples: u32,
+}
+
+/// Explores a target with each configuration, collecting the results in a report.
+/// ## Arguments
+/// * target : The name of the target, e.g. the sps shape or FUT.
+/// * configs : The configurations to run.
+/// * root : The initial halfspace to explore from.
+/// * classifier : The classifier for the target.
+/// * evaluation : Ground truth to measure OSV and volume error against, if known.
+pub fn run_sweep<const N: usize, C: Classifier<N>>(
+    target: &str,
+    configs: &[ExperimentConfig],
+    root: Halfspace<N>,
+    classifier: &mut C,
+    evaluation: Option<&Evaluation<N>>,
+) -> ExperimentReport {
+    let mut report = ExperimentReport::new();
+    for config in configs {
+        report.push(run_experiment(target, config, root, classifier, evaluation));
+    }
+    report
+}
+
+/// Explores a target with a single configuration until the boundary is exhausted
+/// or the sample budget is spent.
+/// ## Arguments
+/// * target : The name of the target, e.g. the sps shape or FUT.
+/// * config : The explorer and adherer settings.
+/// * root : The initial halfspace to explore from.
+/// * classifier : The classifier for the target.
+/// * evaluation : Ground truth to measure OSV and volume error against, if known.
+pub fn run_experiment<const N: usize, C: Classifier<N>>(
+    target: &str,
+    config: &ExperimentConfig,
+    root: Halfspace<N>,
+    classifier: &mut C,
+    evaluation: Option<&Evaluation<N>>,
+) -> ExperimentResult {
+    let (boundary, n_samples) = match config.adherer {
+        AdhererConfig::Constant {
+            delta_angle,
+            max_rotation,
+        } => explore(
+            config,
+            root,
+            ConstantAdhererFactory::new(delta_angle, max_rotation),
+            classifier,
+        ),
+        AdhererConfig::BinarySearch { init_angle, n_iter } => explore(
+            config,
+            root,
+            BinarySearchAdhererFactory::new(init_angle, n_iter),
+            classifier,
+        ),
+    };
+
+    let b_count = boundary.len();
+    let bse = if n_samples > 0 {
+        b_count as f64 / n_samples as f64
+    } else {
+        0.0
+    };
+
+    let (osv_err, volume_err) = match evaluation {
+        Some(eval) if b_count > 0 => {
+            let osv_err = boundary
+                .iter()
+                .map(|hs| eval.truth.true_normal(&hs.b).angle(&hs.n) / PI)
+                .sum::<f64>()
+                / b_count as f64;
+
+            let btree = crate::boundary_tools::get_rtree_from_boundary(&boundary);
+            let volume = approx_mc_volume(
+                PredictionMode::Union,
+                &[(&boundary, &btree)],
+                eval.n_volume_samples,
+                1,
+                Some(&eval.domain),
+                1,
+            );
+            let true_volume = eval.truth.true_volume();
+            let volume_err = (volume - true_volume).abs() / true_volume;
+
+            (Some(osv_err), Some(volume_err))
+        }
+        _ => (None, None),
+    };
+
+    ExperimentResult {
+        target: target.to_string(),
+        config: *config,
+        b_count,
+        n_samples,
+        bse,
+        osv_err,
+        volume_err,
+    }
+}
+
+fn explore<const N: usize, F: AdhererFactory<N>, C: Classifier<N>>(
+    config: &ExperimentConfig,
+    root: Halfspace<N>,
+    adherer_f: F,
+    classifier: &mut C,
+) -> (Vec<Halfspace<N>>, usize) {
+    let mut classifier = CountingClassifier {
+        classifier,
+        count: 0,
+    };
+    let mut expl = MeshExplorer::new(config.d, root, config.margin, adherer_f);
+
+    while classifier.count < config.max_samples {
+        if let Ok(None) = expl.step(&mut classifier) {
+            break;
+        }
+    }
+
+    (expl.boundary_owned(), classifier.count)
+}
+
+struct CountingClassifier<'a, C> {
+    classifier: &'a mut C,
+    count: usize,
+}
+
+impl<const N: usize, C: Classifier<N>> Classifier<N> for CountingClassifier<'_, C> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        self.count += 1;
+        self.classifier.classify(p)
+    }
+}
+
+#[cfg(all(test, feature = "sps"))]
+mod experiment_runner {
+    use nalgebra::SVector;
+
+    use crate::{
+        prelude::{Domain, Halfspace, WithinMode},
+        sps::Sphere,
+    };
+
+    use super::*;
+
+    #[test]
+    fn sweeps_all_configurations() {
+        let mut sphere = Sphere::<3>::new(SVector::repeat(0.5), 0.25, Some(Domain::normalized()));
+        let truth = sphere.clone();
+        let mut n = SVector::zeros();
+        n[0] = 1.0;
+        let root = Halfspace {
+            b: WithinMode(SVector::from_fn(|i, _| if i == 0 { 0.74 } else { 0.5 })),
+            n,
+        };
+
+        let configs = ExperimentConfig::sweep(
+            &[0.05, 0.1],
+            &[0.85],
+            &[
+                AdhererConfig::Constant {
+                    delta_angle: 0.26,
+                    max_rotation: None,
+                },
+                AdhererConfig::BinarySearch {
+                    init_angle: PI / 2.0,
+                    n_iter: 4,
+                },
+            ],
+            5000,
+        );
+        assert_eq!(configs.len(), 4);
+
+        let eval = Evaluation {
+            truth: &truth,
+            domain: Domain::normalized(),
+            n_volume_samples: 1000,
+        };
+        let report = run_sweep("sphere", &configs, root, &mut sphere, Some(&eval));
+
+        assert_eq!(report.results().len(), 4);
+        for r in report.results() {
+            assert!(r.b_count > 0 && r.n_samples <= r.config.max_samples + 100);
+            assert!(r.osv_err.unwrap() < 0.1, "Excessive OSV error: {r:?}");
+            assert!(r.volume_err.unwrap() < 0.5, "Excessive volume error: {r:?}");
+        }
+        assert_eq!(report.to_csv().lines().count(), 5);
+    }
+}
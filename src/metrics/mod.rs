@@ -1,5 +1,13 @@
+#[cfg(feature = "global_search")]
+use nalgebra::SVector;
 use nalgebra::{Const, OMatrix};
+#[cfg(feature = "global_search")]
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "global_search")]
+use rand_chacha::ChaCha20Rng;
 
+#[cfg(feature = "global_search")]
+use crate::search::global_search::gen_normal;
 use crate::{
     prelude::{Halfspace, WithinMode},
     search::find_opposing_boundary,
@@ -9,6 +17,15 @@ use crate::{
 pub mod boundary_metrics;
 pub mod bs_adherer_metrics;
 pub mod const_adherer_metrics;
+pub mod coverage;
+pub mod experiment;
+pub mod overlap;
+pub mod sensitivity;
+pub mod surface_distance;
+pub mod topology;
+
+pub use overlap::{dice, iou, Overlap};
+pub use surface_distance::{hausdorff, surface_distance, SurfaceDistance};
 
 pub type Chord<const N: usize> = (Halfspace<N>, Halfspace<N>);
 
@@ -75,6 +92,128 @@ pub fn get_diameters_from_chords<const N: usize>(chords: &[Chord<N>]) -> Vec<f64
     chords.iter().map(|(h1, h2)| (h2.b - h1.b).norm()).collect()
 }
 
+/// The lengths of randomly sampled chords through an envelope, a descriptor of its
+/// shape. See `sample_chords()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChordDistribution<const N: usize> {
+    /// The sampled chords, in the order they were sampled.
+    pub chords: Vec<Chord<N>>,
+    /// The length of each chord.
+    pub lengths: Vec<f64>,
+    /// The number of chords ending at the edge of the domain rather than the
+    /// boundary, whose lengths underestimate those of the envelope.
+    pub n_truncated: usize,
+}
+
+impl<const N: usize> ChordDistribution<N> {
+    /// The mean chord length, or NaN without chords.
+    pub fn mean(&self) -> f64 {
+        self.lengths.iter().sum::<f64>() / self.lengths.len() as f64
+    }
+
+    /// The chord length at quantile @q, 0 <= q <= 1, interpolating linearly between
+    /// samples, or NaN without chords.
+    pub fn percentile(&self, q: f64) -> f64 {
+        let mut sorted = self.lengths.clone();
+        sorted.sort_by(f64::total_cmp);
+        if sorted.is_empty() {
+            return f64::NAN;
+        }
+
+        let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+        let (i, frac) = (pos.floor() as usize, pos.fract());
+        match sorted.get(i + 1) {
+            Some(next) => sorted[i] + frac * (next - sorted[i]),
+            None => sorted[i],
+        }
+    }
+
+    /// Counts the chord lengths in @n_bins equal-width bins from 0 to the longest
+    /// chord.
+    /// ## Returns
+    /// * bins : The (upper edge, count) of each bin, in increasing length.
+    pub fn histogram(&self, n_bins: usize) -> Vec<(f64, usize)> {
+        let max = self.lengths.iter().copied().fold(0.0, f64::max);
+        let width = max / n_bins as f64;
+        let mut counts = vec![0; n_bins];
+        for &l in self.lengths.iter() {
+            let bin = if width > 0.0 {
+                ((l / width) as usize).min(n_bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| ((i + 1) as f64 * width, count))
+            .collect()
+    }
+}
+
+/// Samples random chords through an envelope by hit-and-run: each chord passes
+/// through a point chosen uniformly along the previous chord, in a uniformly random
+/// direction, and its ends are found as `find_chords()` does. The points converge
+/// to a uniform distribution over the envelope, so longer chords are sampled
+/// proportionally more often, unlike the chords through the center of
+/// `find_chords()`.
+/// ## Arguments
+/// * max_err : The maximum error (distance) allowed for boundary points to be from
+///   the boundary.
+/// * t0 : A point within the envelope to start from.
+/// * n_chords : How many chords to sample.
+/// * domain : The region of the search space to limit the exploration to.
+/// * classifier : The classifier for the FUT.
+/// * seed : The seed of the random directions and points.
+/// ## Return (Ok)
+/// * distribution : The sampled chords and their lengths.
+/// ## Error (Err)
+/// * Returns a OutOfBounds exception if @t0 is outside of the domain.
+#[cfg(feature = "global_search")]
+pub fn sample_chords<const N: usize, C: Classifier<N>>(
+    max_err: f64,
+    t0: WithinMode<N>,
+    n_chords: usize,
+    domain: &Domain<N>,
+    classifier: &mut C,
+    seed: u64,
+) -> Result<ChordDistribution<N>> {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut p = t0;
+    let mut distribution = ChordDistribution {
+        chords: vec![],
+        lengths: vec![],
+        n_truncated: 0,
+    };
+
+    while distribution.chords.len() < n_chords {
+        let Some(v) = SVector::<f64, N>::from_fn(|_, _| gen_normal(&mut rng)).try_normalize(0.0)
+        else {
+            continue;
+        };
+
+        let b1 = find_opposing_boundary(max_err, p, v, domain, classifier, 10, 10)?;
+        let b2 = find_opposing_boundary(max_err, p, -v, domain, classifier, 10, 10)?;
+
+        let is_truncated =
+            |b: &WithinMode<N>, v: SVector<f64, N>| !domain.contains(&(**b + max_err * v));
+        if is_truncated(&b1, v) || is_truncated(&b2, -v) {
+            distribution.n_truncated += 1;
+        }
+
+        distribution.lengths.push((b2 - b1).norm());
+        distribution
+            .chords
+            .push((Halfspace { b: b1, n: v }, Halfspace { b: b2, n: -v }));
+
+        p = WithinMode(*b1 + rng.gen::<f64>() * (*b2 - *b1));
+    }
+
+    Ok(distribution)
+}
+
 #[cfg(test)]
 mod find_diameter {
     use nalgebra::SVector;
@@ -121,3 +260,73 @@ mod find_diameter {
         )
     }
 }
+
+#[cfg(all(test, feature = "sps", feature = "global_search"))]
+mod chord_distribution_tests {
+    use nalgebra::SVector;
+
+    use crate::{sps::Sphere, structs::WithinMode};
+
+    use super::*;
+
+    const RADIUS: f64 = 0.25;
+
+    #[test]
+    fn sphere_chords_match_analytic_distribution() {
+        let max_err = 0.001;
+        let domain = Domain::normalized();
+        let center: SVector<f64, 3> = SVector::repeat(0.5);
+        let mut classifier = Sphere::new(center, RADIUS, Some(Domain::normalized()));
+
+        let dist = sample_chords(
+            max_err,
+            WithinMode(center),
+            300,
+            &domain,
+            &mut classifier,
+            1,
+        )
+        .expect("Unexpected sampling error");
+
+        assert_eq!(dist.lengths.len(), 300);
+        assert_eq!(dist.n_truncated, 0);
+        // Chords through uniform points in a ball average 1.5 R
+        assert!(
+            (dist.mean() - 1.5 * RADIUS).abs() < 0.02,
+            "Bad mean: {}",
+            dist.mean()
+        );
+        assert!(dist.percentile(1.0) <= 2.0 * RADIUS + 2.0 * max_err);
+        assert!(dist.percentile(0.25) < dist.percentile(0.5));
+        assert!(dist.percentile(0.5) < dist.percentile(0.75));
+
+        let hist = dist.histogram(10);
+        assert_eq!(hist.iter().map(|(_, c)| c).sum::<usize>(), 300);
+        assert!((hist[9].0 - dist.percentile(1.0)).abs() < 1e-12);
+        // Longer chords are more likely
+        assert!(hist[8].1 > hist[1].1);
+    }
+
+    #[test]
+    fn truncated_chords_are_counted() {
+        let domain = Domain::normalized();
+        // The envelope covers the whole domain
+        let mut classifier = Sphere::new(
+            SVector::<f64, 2>::repeat(0.5),
+            1.0,
+            Some(Domain::normalized()),
+        );
+
+        let dist = sample_chords(
+            0.001,
+            WithinMode(SVector::repeat(0.5)),
+            10,
+            &domain,
+            &mut classifier,
+            1,
+        )
+        .expect("Unexpected sampling error");
+
+        assert_eq!(dist.n_truncated, 10);
+    }
+}
@@ -0,0 +1,170 @@
+use nalgebra::SVector;
+
+use crate::{
+    boundary_tools::estimation::approx_mc_volume_intersection,
+    prelude::{Boundary, Domain},
+    spatial_index::SpatialIndex,
+};
+
+/// A normalized overlap score between two groups of envelopes with its statistical
+/// uncertainty. See `iou()` and `dice()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overlap {
+    /// The overlap score, 0 <= score <= 1, where 1 means the envelopes coincide and
+    /// 0 that they are disjoint.
+    pub score: f64,
+    /// The standard error of @score, assuming independent samples.
+    pub std_err: f64,
+    /// The number of samples that fell within either group, which the score is
+    /// based on.
+    pub n_used: u32,
+}
+
+/// Estimates the intersection over union (Jaccard index) of two groups of envelopes
+/// by Monte Carlo sampling, i.e. the volume within both groups relative to the
+/// volume within either. See `estimation::approx_mc_volume_intersection()`.
+/// ## Arguments
+/// * group1 : The boundaries of the first group of envelopes.
+/// * group2 : The boundaries of the second group of envelopes.
+/// * n_samples : How many samples to take for estimating the volumes. More ->
+///   higher accuracy.
+/// * n_neighbors : Varies how many halfspaces should be considered while
+///   determining if a point falls within an envelope. A good default is 1.
+/// * domain : The region to sample, defaulting to the bounding box of the
+///   boundaries.
+/// * seed : The seed to use while generating random points for MC.
+/// ## Return
+/// * iou : The IoU and its standard error. 0 if no sample fell within either group.
+pub fn iou<const N: usize, I1, I2>(
+    group1: &[(&Boundary<N>, &I1)],
+    group2: &[(&Boundary<N>, &I2)],
+    n_samples: u32,
+    n_neighbors: u32,
+    domain: Option<&Domain<N>>,
+    seed: u64,
+) -> Overlap
+where
+    I1: SpatialIndex<N> + ?Sized,
+    I2: SpatialIndex<N> + ?Sized,
+{
+    let domain = domain.cloned().unwrap_or_else(|| {
+        let pc: Vec<SVector<f64, N>> = group1
+            .iter()
+            .map(|(b, _)| b)
+            .chain(group2.iter().map(|(b, _)| b))
+            .flat_map(|b| b.iter().map(|hs| *hs.b))
+            .collect();
+        Domain::new_from_point_cloud(&pc)
+    });
+
+    let (both, only1, only2) =
+        approx_mc_volume_intersection(group1, group2, n_samples, n_neighbors, Some(&domain), seed);
+
+    let union = both + only1 + only2;
+    let n_used = (union / domain.volume() * n_samples as f64).round() as u32;
+    if n_used == 0 {
+        return Overlap {
+            score: 0.0,
+            std_err: 0.0,
+            n_used,
+        };
+    }
+
+    // Given the samples within the union, those within both are binomial.
+    let score = both / union;
+    Overlap {
+        score,
+        std_err: (score * (1.0 - score) / n_used as f64).sqrt(),
+        n_used,
+    }
+}
+
+/// Estimates the Dice coefficient of two groups of envelopes by Monte Carlo
+/// sampling, i.e. twice the volume within both groups relative to the sum of their
+/// volumes. Equivalent to 2 IoU / (1 + IoU), see `iou()` for the arguments.
+/// ## Return
+/// * dice : The Dice coefficient and its standard error. 0 if no sample fell within
+///   either group.
+pub fn dice<const N: usize, I1, I2>(
+    group1: &[(&Boundary<N>, &I1)],
+    group2: &[(&Boundary<N>, &I2)],
+    n_samples: u32,
+    n_neighbors: u32,
+    domain: Option<&Domain<N>>,
+    seed: u64,
+) -> Overlap
+where
+    I1: SpatialIndex<N> + ?Sized,
+    I2: SpatialIndex<N> + ?Sized,
+{
+    let iou = iou(group1, group2, n_samples, n_neighbors, domain, seed);
+
+    // Propagated through d dice / d iou = 2 / (1 + iou)^2
+    Overlap {
+        score: 2.0 * iou.score / (1.0 + iou.score),
+        std_err: 2.0 * iou.std_err / (1.0 + iou.score).powi(2),
+        n_used: iou.n_used,
+    }
+}
+
+#[cfg(test)]
+mod overlap_tests {
+    use nalgebra::vector;
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{Halfspace, WithinMode},
+    };
+
+    use super::*;
+
+    /// The boundary of an axis-aligned square from @low to @low + @size.
+    fn square(low: SVector<f64, 2>, size: f64) -> Vec<Halfspace<2>> {
+        (0..50)
+            .flat_map(|i| {
+                let t = size * (i as f64 + 0.5) / 50.0;
+                [
+                    (vector![t, 0.0], vector![0.0, -1.0]),
+                    (vector![t, size], vector![0.0, 1.0]),
+                    (vector![0.0, t], vector![-1.0, 0.0]),
+                    (vector![size, t], vector![1.0, 0.0]),
+                ]
+            })
+            .map(|(b, n)| Halfspace {
+                b: WithinMode(low + b),
+                n,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn half_overlapping_squares() {
+        let b1 = square(vector![0.2, 0.2], 0.4);
+        let b2 = square(vector![0.4, 0.2], 0.4);
+        let (t1, t2) = (get_rtree_from_boundary(&b1), get_rtree_from_boundary(&b2));
+        let domain = Domain::normalized();
+
+        let iou = iou(&[(&b1, &t1)], &[(&b2, &t2)], 10000, 1, Some(&domain), 1);
+        let dice = dice(&[(&b1, &t1)], &[(&b2, &t2)], 10000, 1, Some(&domain), 1);
+
+        // The intersection is half of each square, a third of the union
+        assert!((iou.score - 1.0 / 3.0).abs() < 4.0 * iou.std_err, "{iou:?}");
+        assert!((dice.score - 0.5).abs() < 4.0 * dice.std_err, "{dice:?}");
+        assert!(iou.std_err > 0.0 && iou.std_err < 0.02);
+        assert!((iou.n_used as f64 - 2400.0).abs() < 200.0);
+    }
+
+    #[test]
+    fn identical_and_disjoint_envelopes() {
+        let b1 = square(vector![0.1, 0.1], 0.3);
+        let b2 = square(vector![0.6, 0.6], 0.3);
+        let (t1, t2) = (get_rtree_from_boundary(&b1), get_rtree_from_boundary(&b2));
+
+        let same = iou(&[(&b1, &t1)], &[(&b1, &t1)], 1000, 1, None, 1);
+        assert_eq!(same.score, 1.0);
+        assert_eq!(same.std_err, 0.0);
+
+        let disjoint = dice(&[(&b1, &t1)], &[(&b2, &t2)], 1000, 1, None, 1);
+        assert_eq!(disjoint.score, 0.0);
+    }
+}
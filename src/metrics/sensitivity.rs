@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+
+use crate::prelude::{Boundary, Domain};
+
+/// Describes how strongly a single input dimension shapes the boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DimensionSensitivity {
+    /// The index of the input dimension.
+    pub dim: usize,
+    /// The lowest value of the boundary points along this dimension.
+    pub low: f64,
+    /// The highest value of the boundary points along this dimension.
+    pub high: f64,
+    /// The mean magnitude of the OSVs' component along this dimension,
+    /// 0 <= osv_weight <= 1. Larger values mean the surface faces this dimension.
+    pub osv_weight: f64,
+    /// The fraction of OSVs whose largest component is along this dimension,
+    /// 0 <= aligned_fraction <= 1, i.e. how much of the surface faces this dimension
+    /// more than any other.
+    pub aligned_fraction: f64,
+    /// The boundary's extent along this dimension relative to the domain's,
+    /// 0 <= extent_ratio <= 1. A value of 1 means the envelope spans the entire
+    /// domain along this dimension, i.e. it is unconstrained by it.
+    pub extent_ratio: f64,
+    /// The combined sensitivity, (osv_weight + (1 - extent_ratio)) / 2.
+    pub score: f64,
+}
+
+/// Ranks the input dimensions by how strongly they shape the boundary, reporting the
+/// envelope's extent and the alignment of its OSVs along each. Dimensions with low
+/// scores have little influence on the performance mode, and are candidates for
+/// being frozen prior to a deeper exploration.
+/// ## Arguments
+/// * boundary : The set of halfspaces describing the boundary.
+/// * domain : The domain that the boundary was explored within.
+/// ## Returns
+/// * sensitivities : One per dimension, ordered from most to least sensitive.
+pub fn rank_dimensions<const N: usize>(
+    boundary: &Boundary<N>,
+    domain: &Domain<N>,
+) -> Vec<DimensionSensitivity> {
+    assert!(!boundary.is_empty(), "Must provide a non-empty boundary!");
+
+    let count = boundary.len() as f64;
+    let dimensions = domain.dimensions();
+    let mut sensitivities: Vec<DimensionSensitivity> = (0..N)
+        .map(|dim| {
+            let osv_weight = boundary.iter().map(|hs| hs.n[dim].abs()).sum::<f64>() / count;
+
+            let (low, high) = boundary
+                .iter()
+                .map(|hs| hs.b[dim])
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(l, h), x| {
+                    (l.min(x), h.max(x))
+                });
+            let extent_ratio = ((high - low) / dimensions[dim]).clamp(0.0, 1.0);
+
+            let n_aligned = boundary
+                .iter()
+                .filter(|hs| hs.n.iamax() == dim && hs.n[dim] != 0.0)
+                .count();
+
+            DimensionSensitivity {
+                dim,
+                low,
+                high,
+                osv_weight,
+                aligned_fraction: n_aligned as f64 / count,
+                extent_ratio,
+                score: (osv_weight + (1.0 - extent_ratio)) / 2.0,
+            }
+        })
+        .collect();
+
+    sensitivities.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+
+    sensitivities
+}
+
+#[cfg(test)]
+mod dimension_sensitivity {
+    use nalgebra::vector;
+
+    use crate::prelude::{Domain, Halfspace, WithinMode};
+
+    use super::rank_dimensions;
+
+    /// Two faces of a slab, 0.3 <= x <= 0.7, that spans the domain in y and z.
+    fn get_slab() -> Vec<Halfspace<3>> {
+        let mut boundary = vec![];
+        for (x, nx) in [(0.3, -1.0), (0.7, 1.0)] {
+            for y in [0.0, 0.5, 1.0] {
+                for z in [0.0, 0.5, 1.0] {
+                    boundary.push(Halfspace {
+                        b: WithinMode(vector![x, y, z]),
+                        n: vector![nx, 0.0, 0.0],
+                    });
+                }
+            }
+        }
+        boundary
+    }
+
+    #[test]
+    fn ranks_constraining_dimension_first() {
+        let ranks = rank_dimensions(&get_slab(), &Domain::normalized());
+
+        assert_eq!(ranks[0].dim, 0);
+        assert!((ranks[0].score - 0.8).abs() < 1e-10);
+        assert_eq!((ranks[0].low, ranks[0].high), (0.3, 0.7));
+        assert_eq!(ranks[0].aligned_fraction, 1.0);
+        assert!(ranks[1..]
+            .iter()
+            .all(|s| (s.low, s.high) == (0.0, 1.0) && s.aligned_fraction == 0.0));
+        assert!(
+            ranks[1..].iter().all(|s| s.score.abs() < 1e-10),
+            "Unconstrained dimensions had non-zero sensitivity: {ranks:?}"
+        );
+    }
+
+    #[test]
+    fn aligned_fraction_counts_dominant_axis() {
+        let boundary = vec![
+            Halfspace {
+                b: WithinMode(vector![0.5, 0.5]),
+                n: vector![0.8, 0.6],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.6, 0.5]),
+                n: vector![-0.6, 0.8],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.7, 0.5]),
+                n: vector![0.0, -1.0],
+            },
+        ];
+
+        let mut ranks = rank_dimensions(&boundary, &Domain::normalized());
+        ranks.sort_by_key(|s| s.dim);
+
+        assert!((ranks[0].aligned_fraction - 1.0 / 3.0).abs() < 1e-10);
+        assert!((ranks[1].aligned_fraction - 2.0 / 3.0).abs() < 1e-10);
+        assert_eq!((ranks[0].low, ranks[0].high), (0.5, 0.7));
+    }
+}
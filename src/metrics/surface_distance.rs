@@ -0,0 +1,150 @@
+use nalgebra::SVector;
+
+use crate::{prelude::Boundary, spatial_index::SpatialIndex};
+
+/// Summarizes how far apart two boundaries of the same envelope are, e.g. before
+/// and after the FUT was updated. See `surface_distance()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceDistance {
+    /// The mean distance from a boundary point to the nearest point of the other
+    /// boundary.
+    pub mean: f64,
+    /// The 95th percentile of the distances.
+    pub p95: f64,
+    /// The largest of the distances, i.e. the Hausdorff distance.
+    pub hausdorff: f64,
+}
+
+/// The distance from each point of @from to the nearest point of @to.
+fn directed_distances<const N: usize, I>(from: &Boundary<N>, to: &I) -> Vec<f64>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    from.iter()
+        .map(|hs| {
+            let node = to
+                .nearest_neighbor(&hs.b.into())
+                .expect("Must provide a non-empty boundary!");
+            (hs.b - SVector::from(*node.geom())).norm()
+        })
+        .collect()
+}
+
+/// Calculates the Hausdorff distance between two boundaries, i.e. the furthest any
+/// point of one boundary is from the other boundary.
+/// ## Arguments
+/// * b1 : The first boundary.
+/// * btree1 : The spatial index (e.g. RTree) for @b1.
+/// * b2 : The second boundary.
+/// * btree2 : The spatial index (e.g. RTree) for @b2.
+/// ## Returns
+/// * distance : The Hausdorff distance between @b1 and @b2.
+/// ## Panic
+/// * When either boundary is empty.
+pub fn hausdorff<const N: usize, I1, I2>(
+    b1: &Boundary<N>,
+    btree1: &I1,
+    b2: &Boundary<N>,
+    btree2: &I2,
+) -> f64
+where
+    I1: SpatialIndex<N> + ?Sized,
+    I2: SpatialIndex<N> + ?Sized,
+{
+    surface_distance(b1, btree1, b2, btree2).hausdorff
+}
+
+/// Calculates the symmetric surface distance statistics between two boundaries,
+/// pooling the distances from each point of @b1 to @b2 and from each point of @b2
+/// to @b1. Distances are measured to the nearest boundary point, so they are
+/// overestimated by up to half the spacing of the boundary points.
+/// ## Arguments
+/// * b1 : The first boundary.
+/// * btree1 : The spatial index (e.g. RTree) for @b1.
+/// * b2 : The second boundary.
+/// * btree2 : The spatial index (e.g. RTree) for @b2.
+/// ## Returns
+/// * distance : The mean, 95th percentile, and maximum (Hausdorff) distance.
+/// ## Panic
+/// * When either boundary is empty.
+pub fn surface_distance<const N: usize, I1, I2>(
+    b1: &Boundary<N>,
+    btree1: &I1,
+    b2: &Boundary<N>,
+    btree2: &I2,
+) -> SurfaceDistance
+where
+    I1: SpatialIndex<N> + ?Sized,
+    I2: SpatialIndex<N> + ?Sized,
+{
+    let mut distances = directed_distances(b1, btree2);
+    distances.extend(directed_distances(b2, btree1));
+    distances.sort_by(|a, b| {
+        a.partial_cmp(b)
+            .expect("Unexpected NaN while sorting surface distances.")
+    });
+
+    let pos = 0.95 * (distances.len() - 1) as f64;
+    let (i, frac) = (pos.floor() as usize, pos.fract());
+    let p95 = match distances.get(i + 1) {
+        Some(next) => distances[i] + frac * (next - distances[i]),
+        None => distances[i],
+    };
+
+    SurfaceDistance {
+        mean: distances.iter().sum::<f64>() / distances.len() as f64,
+        p95,
+        hausdorff: distances[distances.len() - 1],
+    }
+}
+
+#[cfg(test)]
+mod surface_distance_tests {
+    use nalgebra::SVector;
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary, prelude::WithinMode, test_utils::circle_boundary,
+    };
+
+    use super::*;
+
+    #[test]
+    fn identical_boundaries_have_no_distance() {
+        let b = circle_boundary(SVector::repeat(0.5), 0.3, 100);
+        let btree = get_rtree_from_boundary(&b);
+
+        let dist = surface_distance(&b, &btree, &b, &btree);
+
+        assert_eq!(dist.hausdorff, 0.0);
+        assert_eq!(dist.mean, 0.0);
+        assert_eq!(dist.p95, 0.0);
+    }
+
+    #[test]
+    fn concentric_circles_are_radius_difference_apart() {
+        let b1 = circle_boundary(SVector::repeat(0.5), 0.3, 100);
+        let b2 = circle_boundary(SVector::repeat(0.5), 0.2, 100);
+        let (t1, t2) = (get_rtree_from_boundary(&b1), get_rtree_from_boundary(&b2));
+
+        let dist = surface_distance(&b1, &t1, &b2, &t2);
+
+        assert!((dist.hausdorff - 0.1).abs() < 1e-10);
+        assert!((dist.mean - 0.1).abs() < 1e-10);
+        assert_eq!(hausdorff(&b2, &t2, &b1, &t1), dist.hausdorff);
+    }
+
+    #[test]
+    fn hausdorff_catches_local_changes() {
+        let b1 = circle_boundary(SVector::repeat(0.5), 0.3, 100);
+        let mut b2 = b1.clone();
+        // A bump on the boundary
+        b2[0].b = WithinMode(*b2[0].b + 0.2 * b2[0].n);
+        let (t1, t2) = (get_rtree_from_boundary(&b1), get_rtree_from_boundary(&b2));
+
+        let dist = surface_distance(&b1, &t1, &b2, &t2);
+
+        assert!((dist.hausdorff - 0.2).abs() < 1e-10);
+        assert!(dist.mean < 0.01);
+        assert!(dist.p95 < 1e-10);
+    }
+}
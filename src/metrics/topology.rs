@@ -0,0 +1,169 @@
+use crate::{
+    boundary_tools::{cluster_envelopes, BoundarySet},
+    prelude::{Boundary, Domain, Halfspace},
+    spatial_index::SpatialIndex,
+};
+
+/// The topology of one connected component of an explored boundary. See
+/// `find_topology()`.
+#[derive(Debug, Clone)]
+pub struct ComponentTopology<const N: usize> {
+    /// The boundary of the component.
+    pub envelope: BoundarySet<N>,
+    /// The number of halfspaces within the jump distance of the domain's edge, where
+    /// the envelope continues outside of the domain.
+    pub n_on_edge: usize,
+    /// The number of halfspaces away from the domain's edge that border gaps in the
+    /// boundary. See `boundary_tools::find_holes()`.
+    pub n_gaps: usize,
+    /// Whether the component encloses its envelope, i.e. it is not truncated by the
+    /// domain and no more than the tolerated fraction of its halfspaces border gaps.
+    pub is_closed: bool,
+}
+
+impl<const N: usize> ComponentTopology<N> {
+    /// Whether the domain cuts the envelope off, leaving the component open.
+    pub fn is_truncated(&self) -> bool {
+        self.n_on_edge > 0
+    }
+}
+
+/// Reports the connected components of an explored boundary and whether each one is
+/// closed or truncated by the domain. Volume estimates are only meaningful for the
+/// envelopes of closed components, since an open boundary does not separate the
+/// inside of its envelope from the outside.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * boundary_rtree : The spatial index (e.g. RTree) for @boundary.
+/// * d : The jump distance used to explore @boundary.
+/// * domain : The domain @boundary was explored in.
+/// * gap_tol : The fraction of a component's halfspaces that may border gaps while
+///   still considering it closed, 0 <= gap_tol <= 1. Absorbs spurious gaps, e.g.
+///   along sharp edges of the envelope.
+/// ## Returns
+/// * components : The topology of each connected component, in the order of
+///   `boundary_tools::cluster_envelopes()`.
+pub fn find_topology<const N: usize, I>(
+    boundary: &Boundary<N>,
+    boundary_rtree: &I,
+    d: f64,
+    domain: &Domain<N>,
+    gap_tol: f64,
+) -> Vec<ComponentTopology<N>>
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    let is_on_edge = |hs: &Halfspace<N>| {
+        (0..N).any(|i| hs.b[i] - domain.low()[i] <= d || domain.high()[i] - hs.b[i] <= d)
+    };
+
+    cluster_envelopes(boundary, boundary_rtree, d)
+        .into_iter()
+        .map(|envelope| {
+            let n_on_edge = envelope
+                .boundary()
+                .iter()
+                .filter(|hs| is_on_edge(hs))
+                .count();
+            let n_gaps = envelope
+                .find_holes(d)
+                .iter()
+                .filter(|hs| !is_on_edge(hs))
+                .count();
+            let is_closed = n_on_edge == 0 && n_gaps as f64 <= gap_tol * envelope.len() as f64;
+
+            ComponentTopology {
+                envelope,
+                n_on_edge,
+                n_gaps,
+                is_closed,
+            }
+        })
+        .collect()
+}
+
+/// The number of connected components of an explored boundary. See
+/// `boundary_tools::cluster_envelopes()`.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * boundary_rtree : The spatial index (e.g. RTree) for @boundary.
+/// * d : The jump distance used to explore @boundary.
+/// ## Returns
+/// * n : The number of components.
+pub fn count_components<const N: usize, I>(
+    boundary: &Boundary<N>,
+    boundary_rtree: &I,
+    d: f64,
+) -> usize
+where
+    I: SpatialIndex<N> + ?Sized,
+{
+    cluster_envelopes(boundary, boundary_rtree, d).len()
+}
+
+#[cfg(test)]
+mod topology_tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::{vector, SVector};
+
+    use crate::{boundary_tools::get_rtree_from_boundary, test_utils::arc_boundary};
+
+    use super::*;
+
+    const JUMP_DIST: f64 = 0.01;
+
+    /// Halfspaces @JUMP_DIST apart along the arc of a circle from angle @from to @to,
+    /// limited to the normalized domain.
+    fn arc(center: SVector<f64, 2>, radius: f64, from: f64, to: f64) -> Vec<Halfspace<2>> {
+        let n = ((to - from) * radius / JUMP_DIST).round() as usize;
+        let domain = Domain::normalized();
+        arc_boundary(center, radius, from, to, n)
+            .into_iter()
+            .filter(|hs| domain.contains(&hs.b))
+            .collect()
+    }
+
+    #[test]
+    fn separate_closed_envelopes() {
+        let mut boundary = arc(vector![0.25, 0.5], 0.15, 0.0, 2.0 * PI);
+        boundary.extend(arc(vector![0.75, 0.5], 0.15, 0.0, 2.0 * PI));
+        let rtree = get_rtree_from_boundary(&boundary);
+
+        let topology = find_topology(&boundary, &rtree, JUMP_DIST, &Domain::normalized(), 0.0);
+
+        assert_eq!(count_components(&boundary, &rtree, JUMP_DIST), 2);
+        assert_eq!(topology.len(), 2);
+        assert!(topology.iter().all(|c| c.is_closed && !c.is_truncated()));
+    }
+
+    #[test]
+    fn envelope_cut_off_by_domain_is_truncated() {
+        let boundary = arc(vector![0.9, 0.5], 0.2, 0.0, 2.0 * PI);
+        let rtree = get_rtree_from_boundary(&boundary);
+
+        let topology = find_topology(&boundary, &rtree, JUMP_DIST, &Domain::normalized(), 0.0);
+
+        assert_eq!(topology.len(), 1);
+        assert!(topology[0].is_truncated());
+        assert!(!topology[0].is_closed);
+        // The ends of the arc lie on the domain's edge, not a gap
+        assert_eq!(topology[0].n_gaps, 0);
+    }
+
+    #[test]
+    fn gaps_open_an_envelope_within_tolerance() {
+        let boundary = arc(vector![0.5, 0.5], 0.2, 0.0, 1.9 * PI);
+        let rtree = get_rtree_from_boundary(&boundary);
+        let domain = Domain::normalized();
+
+        let strict = find_topology(&boundary, &rtree, JUMP_DIST, &domain, 0.0);
+        assert_eq!(strict.len(), 1);
+        assert!(!strict[0].is_truncated());
+        assert_eq!(strict[0].n_gaps, 2);
+        assert!(!strict[0].is_closed);
+
+        let tolerant = find_topology(&boundary, &rtree, JUMP_DIST, &domain, 0.05);
+        assert!(tolerant[0].is_closed);
+    }
+}
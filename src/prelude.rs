@@ -2,4 +2,5 @@ pub use crate::adherer_core::*;
 pub use crate::adherers::*;
 pub use crate::explorer_core::*;
 pub use crate::explorers::*;
+pub use crate::spatial_index::*;
 pub use crate::structs::*;
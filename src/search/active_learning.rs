@@ -0,0 +1,324 @@
+use std::cmp::Ordering;
+
+use nalgebra::{Const, OMatrix, SVector};
+
+use crate::{
+    boundary_tools::{
+        estimation::{approx_prediction, is_behind_halfspace},
+        get_rtree_from_boundary,
+    },
+    prelude::{
+        AdhererFactory, Boundary, BoundaryRTree, ConstantAdhererFactory, Explorer, Goal, Halfspace,
+        MeshExplorer, Sample,
+    },
+    utils::array_distance,
+};
+
+/// Why a point was suggested for classification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SuggestionKind<const N: usize> {
+    /// The point extends the surface from the given halfspace into a region that
+    /// has not yet been explored. The halfspace can be used to seed an explorer.
+    FrontierGap(Halfspace<N>),
+    /// The neighboring halfspaces disagree about the class of the point.
+    Uncertain,
+    /// The point lies between the boundary and a sample from the history that the
+    /// boundary predicts incorrectly.
+    SurrogateDisagreement(Sample<N>),
+}
+
+/// A point that is expected to be informative to classify.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Suggestion<const N: usize> {
+    pub p: SVector<f64, N>,
+    /// How informative the point is expected to be, in [0, 1] for every kind of
+    /// suggestion, where larger is better.
+    pub score: f64,
+    pub kind: SuggestionKind<N>,
+}
+
+impl<const N: usize> Suggestion<N> {
+    /// The goal for biasing an explorer towards the suggested point (see
+    /// `MeshExplorer::with_goal()`).
+    pub fn goal(&self) -> Goal<N> {
+        Goal::Point(self.p)
+    }
+}
+
+/// Finds points along the surface, one jump distance away from known halfspaces,
+/// that have no known halfspace nearby.
+/// ## Arguments
+/// * d : The jump distance used to explore @boundary.
+/// * boundary : The explored boundary.
+/// * btree : The RTree for @boundary.
+/// ## Returns
+/// * suggestions : One suggestion per gap at least @d from the nearest known
+///   boundary point, scored by 1 - d / (2 * gap), i.e. from 0.5 for the smallest
+///   gaps towards 1 for unexplored regions.
+pub fn find_frontier_gaps<const N: usize>(
+    d: f64,
+    boundary: &Boundary<N>,
+    btree: &BoundaryRTree<N>,
+) -> Vec<Suggestion<N>> {
+    let basis_vectors = OMatrix::<f64, Const<N>, Const<N>>::identity();
+    let mut suggestions = vec![];
+
+    for hs in boundary.iter() {
+        for v in MeshExplorer::<N, ConstantAdhererFactory<N>>::create_cardinals(hs.n, basis_vectors)
+        {
+            let p = *hs.b + d * v;
+            let key: [f64; N] = p.into();
+            let gap = btree
+                .nearest_neighbor(&key)
+                .map(|node| array_distance(&key, node.geom()))
+                .unwrap_or(f64::INFINITY);
+
+            // The neighbor that p was derived from is exactly d away, anything
+            // nearer means the region has been explored.
+            if gap / d >= 1.0 - 1e-10 {
+                suggestions.push(Suggestion {
+                    p,
+                    score: 1.0 - 0.5 * d / gap,
+                    kind: SuggestionKind::FrontierGap(*hs),
+                });
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Finds points near the surface where the @k nearest halfspaces disagree about
+/// which side of the boundary the point falls on.
+/// ## Arguments
+/// * d : The jump distance used to explore @boundary.
+/// * boundary : The explored boundary.
+/// * btree : The RTree for @boundary.
+/// * k : The number of neighboring halfspaces to consult, k > 1.
+/// ## Returns
+/// * suggestions : Scored by 1 - |2f - 1|, where f is the fraction of neighbors
+///   that place the point within the envelope. Only scores > 0 are returned.
+pub fn find_uncertain_points<const N: usize>(
+    d: f64,
+    boundary: &Boundary<N>,
+    btree: &BoundaryRTree<N>,
+    k: usize,
+) -> Vec<Suggestion<N>> {
+    assert!(k > 1, "k must be greater than 1 for neighbors to disagree.");
+    let mut suggestions = vec![];
+
+    for hs in boundary.iter() {
+        for offset in [0.5 * d, -0.5 * d] {
+            let p = *hs.b + offset * hs.n;
+            let mut n_within = 0;
+            let mut n_total = 0;
+            for node in btree.nearest_neighbor_iter(&p.into()).take(k) {
+                n_total += 1;
+                if is_behind_halfspace(&p, &boundary[node.data]) {
+                    n_within += 1;
+                }
+            }
+
+            let f = n_within as f64 / n_total as f64;
+            let score = 1.0 - (2.0 * f - 1.0).abs();
+            if score > 0.0 {
+                suggestions.push(Suggestion {
+                    p,
+                    score,
+                    kind: SuggestionKind::Uncertain,
+                });
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Finds samples in @history whose class disagrees with the boundary's prediction,
+/// and suggests the midpoint between each sample and its nearest boundary point,
+/// where the surface is likely to be misrepresented.
+/// ## Arguments
+/// * boundary : The explored boundary.
+/// * btree : The RTree for @boundary.
+/// * history : Previously classified samples.
+/// ## Returns
+/// * suggestions : One per disagreeing sample, each with the maximum score of 1,
+///   since the boundary is known to be wrong there.
+pub fn find_surrogate_disagreements<const N: usize>(
+    boundary: &Boundary<N>,
+    btree: &BoundaryRTree<N>,
+    history: &[Sample<N>],
+) -> Vec<Suggestion<N>> {
+    history
+        .iter()
+        .filter(|s| approx_prediction(s.into_inner(), boundary, btree, 1).class() != s.class())
+        .filter_map(|s| {
+            let p = s.into_inner();
+            let nearest = btree.nearest_neighbor(&p.into())?;
+            let b = boundary[nearest.data].b;
+            Some(Suggestion {
+                p: (p + *b) / 2.0,
+                score: 1.0,
+                kind: SuggestionKind::SurrogateDisagreement(*s),
+            })
+        })
+        .collect()
+}
+
+/// Proposes the @n most informative points to classify next, drawing from
+/// frontier gaps, uncertain regions, and disagreement between the boundary and
+/// the sample history. Suggestions are ordered from most to least informative,
+/// their scores being comparable across kinds.
+/// ## Arguments
+/// * d : The jump distance used to explore @boundary.
+/// * boundary : The explored boundary.
+/// * btree : The RTree for @boundary.
+/// * history : Previously classified samples.
+/// * k : The number of neighboring halfspaces to consult for uncertainty, k > 1.
+/// * n : The maximum number of suggestions to return.
+pub fn suggest_next<const N: usize>(
+    d: f64,
+    boundary: &Boundary<N>,
+    btree: &BoundaryRTree<N>,
+    history: &[Sample<N>],
+    k: usize,
+    n: usize,
+) -> Vec<Suggestion<N>> {
+    let mut suggestions = find_surrogate_disagreements(boundary, btree, history);
+    suggestions.append(&mut find_frontier_gaps(d, boundary, btree));
+    suggestions.append(&mut find_uncertain_points(d, boundary, btree, k));
+
+    suggestions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    suggestions.truncate(n);
+
+    suggestions
+}
+
+/// Steers @explorer towards the most informative point to classify next, as
+/// proposed by `suggest_next()` from its boundary and sample history (see
+/// `MeshExplorer::with_sample_history()`), by making it the explorer's goal.
+/// Call this periodically during exploration, e.g. every few boundary points, to
+/// use active learning as the explorer's path selection strategy.
+/// ## Arguments
+/// * explorer : The explorer to steer.
+/// * d : The jump distance @explorer explores with.
+/// * k : The number of neighboring halfspaces to consult for uncertainty, k > 1.
+/// ## Returns
+/// * suggestion : The suggestion steered towards, or None if there is none, in
+///   which case the explorer's goal is cleared.
+pub fn steer_explorer<const N: usize, F: AdhererFactory<N>>(
+    explorer: &mut MeshExplorer<N, F>,
+    d: f64,
+    k: usize,
+) -> Option<Suggestion<N>> {
+    let boundary = explorer.boundary();
+    let btree = get_rtree_from_boundary(boundary);
+    let history = explorer.samples().unwrap_or_default();
+
+    let suggestion = suggest_next(d, boundary, &btree, history, k, 1).pop();
+    explorer.set_goal(suggestion.map(|s| s.goal()));
+    suggestion
+}
+
+#[cfg(test)]
+mod suggestion_tests {
+    use nalgebra::{vector, SVector};
+
+    use crate::{
+        boundary_tools::get_rtree_from_boundary,
+        prelude::{FunctionClassifier, Halfspace, OutOfMode, Sample, WithinMode},
+    };
+
+    use super::*;
+
+    const D: f64 = 0.1;
+
+    /// A 3x3 patch of a plane facing +z.
+    fn get_patch() -> Vec<Halfspace<3>> {
+        let mut boundary = vec![];
+        for i in -1..=1 {
+            for j in -1..=1 {
+                boundary.push(Halfspace {
+                    b: WithinMode(vector![0.5 + D * i as f64, 0.5 + D * j as f64, 0.5]),
+                    n: vector![0.0, 0.0, 1.0],
+                });
+            }
+        }
+        boundary
+    }
+
+    #[test]
+    fn frontier_gaps_lie_on_patch_edge() {
+        let boundary = get_patch();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        let gaps = find_frontier_gaps(D, &boundary, &btree);
+
+        assert_eq!(gaps.len(), 12, "Expected 3 gaps per side of the patch.");
+        assert!(gaps
+            .iter()
+            .all(|s| (s.p[0] - 0.5).abs() > 1.5 * D || (s.p[1] - 0.5).abs() > 1.5 * D));
+    }
+
+    #[test]
+    fn flat_surface_has_no_uncertainty() {
+        let boundary = get_patch();
+        let btree = get_rtree_from_boundary(&boundary);
+
+        assert!(find_uncertain_points(D, &boundary, &btree, 4).is_empty());
+    }
+
+    #[test]
+    fn suggests_misclassified_history_first() {
+        let boundary = get_patch();
+        let btree = get_rtree_from_boundary(&boundary);
+        let history = [
+            // Correctly predicted
+            Sample::OutOfMode(OutOfMode(vector![0.5, 0.5, 0.7])),
+            // Incorrectly predicted
+            Sample::WithinMode(WithinMode(vector![0.5, 0.5, 0.6])),
+        ];
+
+        let suggestions = suggest_next(D, &boundary, &btree, &history, 4, 5);
+
+        assert_eq!(suggestions.len(), 5);
+        assert_eq!(
+            suggestions[0].kind,
+            SuggestionKind::SurrogateDisagreement(history[1])
+        );
+        assert!((suggestions[0].p - vector![0.5, 0.5, 0.55]).norm() < 1e-10);
+    }
+
+    #[test]
+    fn scores_are_comparable_across_kinds() {
+        let boundary = get_patch();
+        let btree = get_rtree_from_boundary(&boundary);
+        let history = [Sample::WithinMode(WithinMode(vector![0.5, 0.5, 0.6]))];
+
+        let suggestions = suggest_next(D, &boundary, &btree, &history, 4, usize::MAX);
+
+        assert!(suggestions.iter().all(|s| (0.0..=1.0).contains(&s.score)));
+    }
+
+    #[test]
+    fn steers_explorer_towards_suggestion() {
+        let root = Halfspace {
+            b: WithinMode(vector![0.5, 0.5, 0.5]),
+            n: vector![0.0, 0.0, 1.0],
+        };
+        let mut plane = FunctionClassifier::new(|p: SVector<f64, 3>| Ok(p[2] < 0.5));
+        let mut expl = MeshExplorer::new(D, root, 0.9 * D, ConstantAdhererFactory::new(0.1, None))
+            .with_sample_history();
+        for _ in 0..50 {
+            expl.step(&mut plane).expect("Unexpected sampling error");
+        }
+
+        let suggestion = steer_explorer(&mut expl, D, 4).expect("Nothing to suggest?");
+
+        let btree = get_rtree_from_boundary(expl.boundary());
+        let history = expl.samples().unwrap();
+        let best = suggest_next(D, expl.boundary(), &btree, history, 4, 1);
+        assert_eq!(suggestion, best[0]);
+        assert_eq!(expl.goal(), Some(&Goal::Point(suggestion.p)));
+    }
+}
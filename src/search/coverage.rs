@@ -0,0 +1,143 @@
+use nalgebra::SVector;
+use rstar::RTree;
+
+use crate::{
+    prelude::KnnNode, search::global_search::SearchFactory, spatial_index::SpatialIndex,
+    utils::array_distance,
+};
+
+/// How well a set of samples covers a domain, measured by the gap between random
+/// probe points and their nearest sample. See `measure_coverage()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coverage {
+    /// The resolution the coverage was measured at.
+    pub resolution: f64,
+    /// 0 <= fraction <= 1, the estimated fraction of the domain's volume within
+    /// @resolution of a sample.
+    pub fraction: f64,
+    /// The largest gap between a probe and its nearest sample, a lower bound on
+    /// the radius of the largest unsampled ball in the domain.
+    pub max_gap: f64,
+    /// The mean gap between a probe and its nearest sample.
+    pub mean_gap: f64,
+}
+
+/// Measures how well @samples cover the domain of @probes at the given
+/// resolution, e.g. to report that X% of the space was covered at resolution r
+/// before declaring a performance mode absent. Any envelope that contains a ball
+/// of radius @resolution lying in the covered fraction would have been sampled.
+/// ## Arguments
+/// * samples : The points sampled so far, e.g. by global search.
+/// * resolution : The distance from a sample within which a point is covered.
+/// * probes : The source of the probe points, whose domain is the measured space.
+///   A low-discrepancy sequence (e.g. SobolSearch) gives the most accurate
+///   estimate, but must not be the same sequence that produced @samples.
+/// * n_probes : The number of probe points. More -> higher accuracy.
+/// ## Return
+/// * coverage : The covered fraction and gap statistics. With no samples, nothing
+///   is covered and the gaps are infinite.
+pub fn measure_coverage<const N: usize, S>(
+    samples: &[SVector<f64, N>],
+    resolution: f64,
+    probes: &mut S,
+    n_probes: u32,
+) -> Coverage
+where
+    S: SearchFactory<N> + ?Sized,
+{
+    assert!(n_probes > 0, "n_probes must be positive non-zero!");
+    let index: RTree<KnnNode<N>> = RTree::bulk_load(
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, p)| KnnNode::new((*p).into(), i))
+            .collect(),
+    );
+
+    let mut n_covered = 0;
+    let mut max_gap: f64 = 0.0;
+    let mut total_gap = 0.0;
+    for _ in 0..n_probes {
+        let p: [f64; N] = probes.sample().into();
+        let gap = SpatialIndex::nearest_neighbor(&index, &p)
+            .map(|node| array_distance(&p, node.geom()))
+            .unwrap_or(f64::INFINITY);
+
+        if gap <= resolution {
+            n_covered += 1;
+        }
+        max_gap = max_gap.max(gap);
+        total_gap += gap;
+    }
+
+    Coverage {
+        resolution,
+        fraction: n_covered as f64 / n_probes as f64,
+        max_gap,
+        mean_gap: total_gap / n_probes as f64,
+    }
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use nalgebra::SVector;
+
+    use crate::{
+        search::global_search::{MonteCarloSearch, SobolSearch},
+        structs::Domain,
+    };
+
+    use super::measure_coverage;
+
+    /// A grid of points at the centers of @n^2 cells of the unit square.
+    fn grid(n: usize) -> Vec<SVector<f64, 2>> {
+        (0..n * n)
+            .map(|i| {
+                SVector::from([
+                    ((i % n) as f64 + 0.5) / n as f64,
+                    ((i / n) as f64 + 0.5) / n as f64,
+                ])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grid_is_covered_at_half_cell_diagonal() {
+        let n = 10;
+        // Padded, since the first Sobol point lies exactly on a cell's corner
+        let half_diagonal = 2.0f64.sqrt() / (2.0 * n as f64) + 1e-12;
+        let mut probes = SobolSearch::new(Domain::normalized());
+
+        let coverage = measure_coverage(&grid(n), half_diagonal, &mut probes, 1024);
+
+        assert_eq!(coverage.fraction, 1.0);
+        assert!(coverage.max_gap <= half_diagonal);
+        assert!(coverage.mean_gap < coverage.max_gap);
+    }
+
+    #[test]
+    fn partial_coverage_matches_covered_area() {
+        // Within 1/(2n) of a grid center, a disc per cell: pi / 4 of the area
+        let n = 10;
+        let mut probes = SobolSearch::new(Domain::normalized());
+
+        let coverage = measure_coverage(&grid(n), 0.5 / n as f64, &mut probes, 4096);
+
+        let expected = std::f64::consts::PI / 4.0;
+        assert!(
+            (coverage.fraction - expected).abs() < 0.02,
+            "Expected ~{expected}, got {}",
+            coverage.fraction
+        );
+    }
+
+    #[test]
+    fn no_samples_cover_nothing() {
+        let mut probes = MonteCarloSearch::new(Domain::<3>::normalized(), 0);
+
+        let coverage = measure_coverage(&[], 1.0, &mut probes, 10);
+
+        assert_eq!(coverage.fraction, 0.0);
+        assert_eq!(coverage.max_gap, f64::INFINITY);
+    }
+}
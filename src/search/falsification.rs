@@ -0,0 +1,220 @@
+use nalgebra::SVector;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{
+    search::global_search::gen_normal,
+    structs::{Domain, Result, Sample, ScoredClassifier, WithinMode},
+};
+
+/// The bounds of the step size, relative to the size of the domain.
+const MIN_STEP_SIZE: f64 = 1e-4;
+const MAX_STEP_SIZE: f64 = 0.5;
+
+/// Falsification-style search for the target performance mode, minimizing the
+/// score reported by a ScoredClassifier by simulated annealing. Each candidate is a
+/// Gaussian perturbation of the current point, accepted if it scores lower, or
+/// otherwise with probability exp(-increase / temperature). The step size grows
+/// after accepted moves and shrinks after rejected ones, so the search can both
+/// cross the domain and close in on a narrow target.
+pub struct SimulatedAnnealing<const N: usize> {
+    rng: ChaCha20Rng,
+    domain: Domain<N>,
+    t0: f64,
+    cooling: f64,
+    step_size: f64,
+}
+
+/// The outcome of a falsification search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FalsificationResult<const N: usize> {
+    /// The first WithinMode sample found, if any.
+    pub found: Option<WithinMode<N>>,
+    /// The lowest score observed.
+    pub best_score: f64,
+    /// Every sample taken, in order.
+    pub samples: Vec<Sample<N>>,
+}
+
+impl<const N: usize> SimulatedAnnealing<N> {
+    /// Creates a SimulatedAnnealing search with an initial temperature of 1, a
+    /// cooling rate of 0.99 and an initial step size of 0.1.
+    /// ## Arguments
+    /// * domain : The domain to search.
+    /// * seed : The seed for the starting point, perturbations and acceptance.
+    pub fn new(domain: Domain<N>, seed: u64) -> Self {
+        SimulatedAnnealing {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+            domain,
+            t0: 1.0,
+            cooling: 0.99,
+            step_size: 0.1,
+        }
+    }
+
+    /// Sets the annealing schedule.
+    /// ## Arguments
+    /// * t0 : The initial temperature, in units of the classifier's score. Larger
+    ///   temperatures accept larger score increases, escaping local minima.
+    /// * cooling : 0 < cooling < 1, the factor the temperature is multiplied by
+    ///   after each sample.
+    pub fn with_temperature(mut self, t0: f64, cooling: f64) -> Self {
+        assert!(t0 > 0.0, "t0 must be positive non-zero! Got: {t0}");
+        assert!(
+            cooling > 0.0 && cooling < 1.0,
+            "Invalid cooling, must be between 0 and 1! Got: {cooling}"
+        );
+        self.t0 = t0;
+        self.cooling = cooling;
+        self
+    }
+
+    /// Sets the initial standard deviation of the perturbations, relative to the
+    /// size of the domain along each axis.
+    pub fn with_step_size(mut self, step_size: f64) -> Self {
+        assert!(
+            (MIN_STEP_SIZE..=MAX_STEP_SIZE).contains(&step_size),
+            "Invalid step_size, must be between {MIN_STEP_SIZE} and {MAX_STEP_SIZE}! Got: {step_size}"
+        );
+        self.step_size = step_size;
+        self
+    }
+
+    /// Searches for a WithinMode sample, starting from a random point in the domain.
+    /// ## Arguments
+    /// * classifier : The FUT, scoring its proximity to the target mode.
+    /// * max_samples : The maximum number of samples to take.
+    /// ## Return (Ok)
+    /// * result : The first WithinMode sample, if found within @max_samples, and
+    ///   the samples taken.
+    /// ## Error (Err)
+    /// * SamplingError : If the classifier fails.
+    pub fn find_within_mode<C: ScoredClassifier<N>>(
+        &mut self,
+        classifier: &mut C,
+        max_samples: u32,
+    ) -> Result<FalsificationResult<N>> {
+        let mut result = FalsificationResult {
+            found: None,
+            best_score: f64::INFINITY,
+            samples: vec![],
+        };
+        if max_samples == 0 {
+            return Ok(result);
+        }
+
+        let dims = self.domain.dimensions();
+        let v: SVector<f64, N> = SVector::from_fn(|_, _| self.rng.gen());
+        let mut cur = v.component_mul(&dims) + self.domain.low();
+        let mut cur_score = f64::INFINITY;
+        let mut temperature = self.t0;
+        let mut step_size = self.step_size;
+
+        for i in 0..max_samples {
+            let p = if i == 0 {
+                cur
+            } else {
+                let noise: SVector<f64, N> = SVector::from_fn(|_, _| gen_normal(&mut self.rng));
+                self.domain
+                    .clip_vector(&(cur + noise.component_mul(&dims) * step_size))
+            };
+
+            let (sample, score) = classifier.classify_scored(p)?;
+            result.samples.push(sample);
+            result.best_score = result.best_score.min(score);
+            if let Sample::WithinMode(t) = sample {
+                result.found = Some(t);
+                break;
+            }
+
+            let accept = score <= cur_score
+                || self.rng.gen::<f64>() < (-(score - cur_score) / temperature).exp();
+            if accept {
+                cur = p;
+                cur_score = score;
+                step_size = (step_size * 1.5).min(MAX_STEP_SIZE);
+            } else {
+                step_size = (step_size * 0.9).max(MIN_STEP_SIZE);
+            }
+            temperature *= self.cooling;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod simulated_annealing {
+    use nalgebra::SVector;
+
+    use crate::{
+        search::global_search::{MonteCarloSearch, SearchFactory},
+        structs::{Domain, Result, Sample, ScoredClassifier},
+    };
+
+    use super::SimulatedAnnealing;
+
+    const RADIUS: f64 = 0.02;
+
+    /// A rare target mode, a tiny sphere scored by the distance to its surface.
+    struct TinySphere {
+        center: SVector<f64, 3>,
+    }
+
+    impl ScoredClassifier<3> for TinySphere {
+        fn classify_scored(&mut self, p: SVector<f64, 3>) -> Result<(Sample<3>, f64)> {
+            let score = (p - self.center).norm() - RADIUS;
+            Ok((Sample::from_class(p, score <= 0.0), score))
+        }
+    }
+
+    #[test]
+    fn finds_rare_mode_that_random_search_misses() {
+        let center = SVector::from([0.8, 0.3, 0.7]);
+        let max_samples = 500;
+
+        for seed in 0..5 {
+            let mut sa =
+                SimulatedAnnealing::new(Domain::normalized(), seed).with_temperature(0.05, 0.98);
+            let result = sa
+                .find_within_mode(&mut TinySphere { center }, max_samples)
+                .expect("Unexpected sampling error");
+
+            let t = result.found.expect("Failed to find the target mode");
+            assert!((*t - center).norm() <= RADIUS);
+            assert_eq!(result.samples.last().map(|s| s.class()), Some(true));
+            assert!(result.samples.len() <= max_samples as usize);
+
+            let mut mc = MonteCarloSearch::new(Domain::normalized(), seed);
+            assert!(
+                (0..max_samples).all(|_| (mc.sample() - center).norm() > RADIUS),
+                "Random search was expected to miss the target mode"
+            );
+        }
+    }
+
+    #[test]
+    fn stops_at_max_samples_when_mode_is_absent() {
+        let mut sa = SimulatedAnnealing::new(Domain::normalized(), 0);
+        let mut classifier = TinySphere {
+            center: SVector::repeat(2.0),
+        };
+        let result = sa
+            .find_within_mode(&mut classifier, 100)
+            .expect("Unexpected sampling error");
+
+        assert_eq!(result.found, None);
+        assert_eq!(result.samples.len(), 100);
+        assert!(result
+            .samples
+            .iter()
+            .all(|s| Domain::normalized().contains(s)));
+        // Closes in on the corner of the domain nearest the sphere
+        let min_score = 3.0f64.sqrt() - RADIUS;
+        assert!(
+            result.best_score < min_score + 0.1,
+            "Failed to descend: {}",
+            result.best_score
+        );
+    }
+}
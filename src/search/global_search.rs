@@ -1,8 +1,8 @@
 use nalgebra::SVector;
-use rand::{Rng, SeedableRng};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
-use crate::structs::Domain;
+use crate::structs::{Boundary, Domain, Region, Sample, WithinMode};
 
 /// Random exploration of the search domain.
 pub struct MonteCarloSearch<const N: usize> {
@@ -10,6 +10,72 @@ pub struct MonteCarloSearch<const N: usize> {
     domain: Domain<N>,
 }
 
+/// Stratified random exploration of the search domain. Each axis is divided into
+/// @n_strata equal intervals, and each batch of @n_strata samples places exactly
+/// one sample in every interval of every axis. Covers the domain far more evenly
+/// than MonteCarloSearch for the same number of samples, especially in high
+/// dimensions.
+pub struct LatinHypercubeSearch<const N: usize> {
+    rng: ChaCha20Rng,
+    domain: Domain<N>,
+    n_strata: usize,
+    batch: Vec<SVector<f64, N>>,
+}
+
+/// Random exploration of the search domain that exploits the WithinMode samples
+/// found so far. Each sample is either drawn uniformly from the domain, or, with
+/// probability @exploit_rate, from a Gaussian perturbation of a previous hit. Once
+/// an envelope is found, this quickly finds neighboring envelopes of the same mode
+/// that uniform sampling would be unlikely to hit. Hits must be reported via
+/// `observe()`.
+pub struct AdaptiveSearch<const N: usize> {
+    rng: ChaCha20Rng,
+    domain: Domain<N>,
+    sigma: f64,
+    exploit_rate: f64,
+    hits: Vec<WithinMode<N>>,
+}
+
+/// Stratified random exploration of the search domain. The domain is partitioned
+/// into a grid of hyper-rectangular cells, and each pass over the cells samples
+/// every cell once, in random order. Since no region of the domain goes unsampled
+/// for long, small envelopes are hit sooner than with MonteCarloSearch. With
+/// refinement enabled, cells
+/// where WithinMode samples are found (see `observe()`) are split in half, so that
+/// the regions around hits receive more samples each pass.
+pub struct StratifiedSearch<const N: usize> {
+    rng: ChaCha20Rng,
+    domain: Domain<N>,
+    cells: Vec<Domain<N>>,
+    pass: Vec<usize>,
+    max_cells: Option<usize>,
+}
+
+/// Random exploration of the search domain concentrated near a prior boundary, e.g.
+/// one explored in a previous experiment against an earlier version of the FUT.
+/// Each sample is either drawn uniformly from the domain, or, with probability
+/// @prior_rate, from a Gaussian perturbation of a random prior boundary point. When
+/// the FUT has changed only slightly, the new boundary is found in far fewer
+/// samples. Note that the samples are not weighted, so they are not suited for
+/// unbiased estimates such as `approx_volume_with()`.
+pub struct BoundaryGuidedSearch<const N: usize> {
+    rng: ChaCha20Rng,
+    domain: Domain<N>,
+    prior: Vec<WithinMode<N>>,
+    sigma: f64,
+    prior_rate: f64,
+}
+
+/// Restricts a search to a region, e.g. an ObliqueDomain, by rejecting the points
+/// of @search that fall outside of it. @search should cover the region's bounding
+/// box, which is the domain reported by `get_domain()`. Since rejected points are
+/// not counted, the samples are not suited for volume estimates such as
+/// `approx_volume_with()`.
+pub struct RegionSearch<const N: usize, S, R> {
+    search: S,
+    region: R,
+}
+
 /// A system that produces points to be sampled for the purpose of exploring a
 /// domain, also referred to as global search.
 pub trait SearchFactory<const N: usize> {
@@ -35,6 +101,502 @@ impl<const N: usize> SearchFactory<N> for MonteCarloSearch<N> {
     }
 }
 
+impl<const N: usize, S, R> RegionSearch<N, S, R>
+where
+    S: SearchFactory<N>,
+    R: Region<N>,
+{
+    pub fn new(search: S, region: R) -> Self {
+        RegionSearch { search, region }
+    }
+
+    pub fn region(&self) -> &R {
+        &self.region
+    }
+}
+
+impl<const N: usize, S, R> SearchFactory<N> for RegionSearch<N, S, R>
+where
+    S: SearchFactory<N>,
+    R: Region<N>,
+{
+    /// ## Panic
+    /// * When 10000 consecutive points fall outside of the region, e.g. since it
+    ///   does not overlap @search's domain.
+    fn sample(&mut self) -> SVector<f64, N> {
+        (0..MAX_REJECTIONS)
+            .map(|_| self.search.sample())
+            .find(|p| self.region.contains(p))
+            .expect("The search's domain does not overlap the region!")
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        self.search.get_domain()
+    }
+}
+
+/// The number of consecutive points RegionSearch may reject before giving up.
+const MAX_REJECTIONS: usize = 10000;
+
+impl<const N: usize> LatinHypercubeSearch<N> {
+    /// Creates a LatinHypercubeSearch.
+    /// ## Arguments
+    /// * domain : The domain to search.
+    /// * n_strata : The number of intervals each axis is divided into, which is also
+    ///   the number of samples per batch. Ideally, the expected sample budget (or
+    ///   a divisor of it), so that the samples are fully stratified.
+    /// * seed : The seed for the random stratification and jitter.
+    pub fn new(domain: Domain<N>, n_strata: usize, seed: u64) -> Self {
+        assert!(n_strata > 0, "n_strata must be positive non-zero!");
+        let rng = ChaCha20Rng::seed_from_u64(seed);
+        LatinHypercubeSearch {
+            rng,
+            domain,
+            n_strata,
+            batch: vec![],
+        }
+    }
+
+    /// The number of intervals each axis is divided into.
+    pub fn n_strata(&self) -> usize {
+        self.n_strata
+    }
+
+    /// Generates the next batch of samples, in reverse order of use.
+    fn fill_batch(&mut self) {
+        let n = self.n_strata;
+        let strata: Vec<Vec<usize>> = (0..N)
+            .map(|_| {
+                let mut axis: Vec<usize> = (0..n).collect();
+                axis.shuffle(&mut self.rng);
+                axis
+            })
+            .collect();
+
+        self.batch = (0..n)
+            .map(|i| {
+                let v: SVector<f64, N> = SVector::from_fn(|j, _| {
+                    (strata[j][i] as f64 + self.rng.gen::<f64>()) / n as f64
+                });
+                v.component_mul(&self.domain.dimensions()) + self.domain.low()
+            })
+            .collect();
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for LatinHypercubeSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        if self.batch.is_empty() {
+            self.fill_batch();
+        }
+
+        self.batch.pop().expect("Batch must be non-empty")
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+impl<const N: usize> AdaptiveSearch<N> {
+    /// Creates an AdaptiveSearch.
+    /// ## Arguments
+    /// * domain : The domain to search.
+    /// * sigma : The standard deviation of the perturbation around hits, relative to
+    ///   the size of the domain along each axis.
+    /// * exploit_rate : 0 <= exploit_rate <= 1, the probability that a sample is
+    ///   drawn near a previous hit, once any hits have been observed.
+    /// * seed : The seed for the random sampling.
+    pub fn new(domain: Domain<N>, sigma: f64, exploit_rate: f64, seed: u64) -> Self {
+        assert!(sigma > 0.0, "sigma must be positive non-zero! Got: {sigma}");
+        assert!(
+            (0.0..=1.0).contains(&exploit_rate),
+            "Invalid exploit_rate, must be between 0 and 1! Got: {exploit_rate}"
+        );
+        let rng = ChaCha20Rng::seed_from_u64(seed);
+        AdaptiveSearch {
+            rng,
+            domain,
+            sigma,
+            exploit_rate,
+            hits: vec![],
+        }
+    }
+
+    /// Reports the classification of a sample, e.g. one generated by `sample()`.
+    /// WithinMode samples are added to the hits that are exploited.
+    pub fn observe(&mut self, sample: &Sample<N>) {
+        if let Sample::WithinMode(t) = sample {
+            self.hits.push(*t);
+        }
+    }
+
+    /// The WithinMode samples observed so far.
+    pub fn hits(&self) -> &[WithinMode<N>] {
+        &self.hits
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for AdaptiveSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        if !self.hits.is_empty() && self.rng.gen::<f64>() < self.exploit_rate {
+            let center = *self.hits[self.rng.gen_range(0..self.hits.len())];
+            perturb(&mut self.rng, &self.domain, center, self.sigma)
+        } else {
+            let v: SVector<f64, N> = SVector::from_fn(|_, _| self.rng.gen());
+            v.component_mul(&self.domain.dimensions()) + self.domain.low()
+        }
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+impl<const N: usize> BoundaryGuidedSearch<N> {
+    /// Creates a BoundaryGuidedSearch.
+    /// ## Arguments
+    /// * domain : The domain to search.
+    /// * prior : The previously explored boundary to concentrate samples near.
+    /// * sigma : The standard deviation of the perturbation around prior boundary
+    ///   points, relative to the size of the domain along each axis. Should cover
+    ///   the expected shift of the boundary.
+    /// * prior_rate : 0 <= prior_rate <= 1, the probability that a sample is drawn
+    ///   near the prior boundary rather than uniformly.
+    /// * seed : The seed for the random sampling.
+    pub fn new(
+        domain: Domain<N>,
+        prior: &Boundary<N>,
+        sigma: f64,
+        prior_rate: f64,
+        seed: u64,
+    ) -> Self {
+        assert!(sigma > 0.0, "sigma must be positive non-zero! Got: {sigma}");
+        assert!(
+            (0.0..=1.0).contains(&prior_rate),
+            "Invalid prior_rate, must be between 0 and 1! Got: {prior_rate}"
+        );
+        BoundaryGuidedSearch {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+            domain,
+            prior: prior.iter().map(|hs| hs.b).collect(),
+            sigma,
+            prior_rate,
+        }
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for BoundaryGuidedSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        if !self.prior.is_empty() && self.rng.gen::<f64>() < self.prior_rate {
+            let center = *self.prior[self.rng.gen_range(0..self.prior.len())];
+            perturb(&mut self.rng, &self.domain, center, self.sigma)
+        } else {
+            let v: SVector<f64, N> = SVector::from_fn(|_, _| self.rng.gen());
+            v.component_mul(&self.domain.dimensions()) + self.domain.low()
+        }
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+impl<const N: usize> StratifiedSearch<N> {
+    /// Creates a StratifiedSearch.
+    /// ## Arguments
+    /// * domain : The domain to search.
+    /// * n_divisions : The number of intervals each axis is divided into, resulting
+    ///   in n_divisions^N cells.
+    /// * seed : The seed for the order of the cells and the samples within them.
+    pub fn new(domain: Domain<N>, n_divisions: usize, seed: u64) -> Self {
+        assert!(n_divisions > 0, "n_divisions must be positive non-zero!");
+        let n_cells = u32::try_from(N)
+            .ok()
+            .and_then(|n| n_divisions.checked_pow(n))
+            .expect("Too many cells, reduce n_divisions!");
+
+        let size = domain.dimensions() / n_divisions as f64;
+        let cells = (0..n_cells)
+            .map(|mut i| {
+                let mut index = SVector::<f64, N>::zeros();
+                for j in 0..N {
+                    index[j] = (i % n_divisions) as f64;
+                    i /= n_divisions;
+                }
+                let low = domain.low() + index.component_mul(&size);
+                Domain::new(low, low + size)
+            })
+            .collect();
+
+        StratifiedSearch {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+            domain,
+            cells,
+            pass: vec![],
+            max_cells: None,
+        }
+    }
+
+    /// Splits cells in half along their longest axis when a WithinMode sample is
+    /// observed within them, until there are @max_cells cells.
+    pub fn with_refinement(mut self, max_cells: usize) -> Self {
+        self.max_cells = Some(max_cells);
+        self
+    }
+
+    /// Reports the classification of a sample, e.g. one generated by `sample()`.
+    /// Refines the cell containing a WithinMode sample, if refinement is enabled.
+    /// Refined cells are sampled from the next pass onwards.
+    pub fn observe(&mut self, sample: &Sample<N>) {
+        let (Sample::WithinMode(t), Some(max_cells)) = (sample, self.max_cells) else {
+            return;
+        };
+        if self.cells.len() >= max_cells {
+            return;
+        }
+        let Some(i) = self.cells.iter().position(|cell| cell.contains(t)) else {
+            return;
+        };
+
+        let cell = &self.cells[i];
+        let axis = cell.dimensions().imax();
+        let mid = (cell.low()[axis] + cell.high()[axis]) / 2.0;
+        let mut lower_high = *cell.high();
+        lower_high[axis] = mid;
+        let mut upper_low = *cell.low();
+        upper_low[axis] = mid;
+
+        let upper = Domain::new(upper_low, *cell.high());
+        self.cells[i] = Domain::new(*cell.low(), lower_high);
+        self.cells.push(upper);
+    }
+
+    /// The cells partitioning the domain.
+    pub fn cells(&self) -> &[Domain<N>] {
+        &self.cells
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for StratifiedSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        if self.pass.is_empty() {
+            self.pass = (0..self.cells.len()).collect();
+            self.pass.shuffle(&mut self.rng);
+        }
+
+        let cell = &self.cells[self.pass.pop().expect("Pass must be non-empty")];
+        let v: SVector<f64, N> = SVector::from_fn(|_, _| self.rng.gen());
+        v.component_mul(&cell.dimensions()) + cell.low()
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+/// The primitive polynomial degree s, coefficients a and initial direction numbers
+/// m of each dimension after the first, from Joe & Kuo (2008).
+const SOBOL_PARAMS: [(u32, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// The number of bits of precision of SobolSearch's points.
+const SOBOL_BITS: usize = 32;
+
+/// Quasi-random exploration of the search domain using the Sobol low-discrepancy
+/// sequence. Deterministic, and fills the domain more evenly than random points,
+/// such that estimates (e.g. of volume) converge faster than with
+/// MonteCarloSearch. Supports up to 21 dimensions, see HaltonSearch for more.
+pub struct SobolSearch<const N: usize> {
+    domain: Domain<N>,
+    index: u32,
+    x: [u32; N],
+    directions: Vec<[u32; SOBOL_BITS]>,
+}
+
+/// Quasi-random exploration of the search domain using the Halton low-discrepancy
+/// sequence, with the i-th prime as the base of the i-th axis. Supports any number
+/// of dimensions, although correlation between axes with large bases makes it less
+/// uniform than SobolSearch beyond ~10 dimensions.
+pub struct HaltonSearch<const N: usize> {
+    domain: Domain<N>,
+    index: u64,
+    bases: [u64; N],
+}
+
+impl<const N: usize> SobolSearch<N> {
+    /// Creates a SobolSearch, starting from the first point of the sequence.
+    pub fn new(domain: Domain<N>) -> Self {
+        assert!(
+            N <= SOBOL_PARAMS.len() + 1,
+            "SobolSearch supports at most {} dimensions! Got: {N}",
+            SOBOL_PARAMS.len() + 1
+        );
+
+        let mut directions = vec![std::array::from_fn(|k| 1 << (SOBOL_BITS - 1 - k))];
+        for &(s, a, m) in SOBOL_PARAMS.iter().take(N.saturating_sub(1)) {
+            let s = s as usize;
+            let mut v = [0u32; SOBOL_BITS];
+            for k in 0..SOBOL_BITS {
+                v[k] = if k < s {
+                    m[k] << (SOBOL_BITS - 1 - k)
+                } else {
+                    let mut vk = v[k - s] ^ (v[k - s] >> s);
+                    for i in 1..s {
+                        if (a >> (s - 1 - i)) & 1 == 1 {
+                            vk ^= v[k - i];
+                        }
+                    }
+                    vk
+                };
+            }
+            directions.push(v);
+        }
+
+        SobolSearch {
+            domain,
+            index: 0,
+            x: [0; N],
+            directions,
+        }
+    }
+
+    /// Skips ahead by @n points, e.g. to continue a previous search.
+    pub fn skip(mut self, n: u32) -> Self {
+        for _ in 0..n {
+            self.advance();
+        }
+        self
+    }
+
+    fn advance(&mut self) {
+        // Gray code ordering, flipping the direction of the lowest zero bit
+        let c = self.index.trailing_ones() as usize;
+        for (x, v) in self.x.iter_mut().zip(self.directions.iter()) {
+            *x ^= v[c];
+        }
+        self.index += 1;
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for SobolSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        let v: SVector<f64, N> =
+            SVector::from_fn(|i, _| self.x[i] as f64 / (1u64 << SOBOL_BITS) as f64);
+        self.advance();
+        v.component_mul(&self.domain.dimensions()) + self.domain.low()
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+impl<const N: usize> HaltonSearch<N> {
+    /// Creates a HaltonSearch, starting from the first point of the sequence.
+    pub fn new(domain: Domain<N>) -> Self {
+        let mut bases = [0; N];
+        let mut candidate = 2;
+        for base in bases.iter_mut() {
+            while (2..candidate)
+                .take_while(|d| d * d <= candidate)
+                .any(|d| candidate % d == 0)
+            {
+                candidate += 1;
+            }
+            *base = candidate;
+            candidate += 1;
+        }
+
+        HaltonSearch {
+            domain,
+            index: 1,
+            bases,
+        }
+    }
+
+    /// Skips ahead by @n points, e.g. to continue a previous search.
+    pub fn skip(mut self, n: u64) -> Self {
+        self.index += n;
+        self
+    }
+}
+
+impl<const N: usize> SearchFactory<N> for HaltonSearch<N> {
+    fn sample(&mut self) -> SVector<f64, N> {
+        let v: SVector<f64, N> =
+            SVector::from_fn(|i, _| radical_inverse(self.index, self.bases[i]));
+        self.index += 1;
+        v.component_mul(&self.domain.dimensions()) + self.domain.low()
+    }
+
+    fn get_domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+}
+
+/// A sample from the standard normal distribution, by the Box-Muller transform.
+pub(crate) fn gen_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// A Gaussian perturbation of @center with a standard deviation of @sigma relative
+/// to the size of @domain along each axis, redrawn if it falls outside of @domain.
+fn perturb<const N: usize, R: Rng>(
+    rng: &mut R,
+    domain: &Domain<N>,
+    center: SVector<f64, N>,
+    sigma: f64,
+) -> SVector<f64, N> {
+    const MAX_ATTEMPTS: usize = 10;
+
+    let scale = domain.dimensions() * sigma;
+    let mut p = center;
+    for _ in 0..MAX_ATTEMPTS {
+        let noise: SVector<f64, N> = SVector::from_fn(|_, _| gen_normal(rng));
+        p = center + noise.component_mul(&scale);
+        if domain.contains(&p) {
+            return p;
+        }
+    }
+
+    domain.clip_vector(&p)
+}
+
+/// Mirrors the digits of @i in @base about the radix point, e.g. 6 = 110b -> 0.011b.
+fn radical_inverse(mut i: u64, base: u64) -> f64 {
+    let mut result = 0.0;
+    let mut f = 1.0 / base as f64;
+    while i > 0 {
+        result += f * (i % base) as f64;
+        i /= base;
+        f /= base as f64;
+    }
+    result
+}
+
 #[cfg(test)]
 mod test_monte_carlo {
     use crate::structs::Domain;
@@ -53,3 +615,302 @@ mod test_monte_carlo {
         )
     }
 }
+
+#[cfg(test)]
+mod test_region_search {
+    use std::f64::consts::FRAC_PI_4;
+
+    use nalgebra::{vector, Rotation2};
+
+    use crate::structs::ObliqueDomain;
+
+    use super::*;
+
+    #[test]
+    fn samples_within_rotated_slab() {
+        let slab = ObliqueDomain::from_affine(
+            &Domain::new(vector![-0.5, -0.1], vector![0.5, 0.1]),
+            *Rotation2::new(FRAC_PI_4).matrix(),
+            vector![0.5, 0.5],
+        );
+        let mut search = RegionSearch::new(MonteCarloSearch::new(slab.bounding_box(), 1), slab);
+
+        let points: Vec<_> = (0..1000).map(|_| search.sample()).collect();
+        assert!(points.iter().all(|p| search.region().contains(p)));
+        // Covers both ends of the slab
+        assert!(points.iter().any(|p| p[0] + p[1] < 0.4));
+        assert!(points.iter().any(|p| p[0] + p[1] > 1.6));
+    }
+}
+
+#[cfg(test)]
+mod test_latin_hypercube {
+    use nalgebra::vector;
+
+    use crate::structs::Domain;
+
+    use super::{LatinHypercubeSearch, SearchFactory};
+
+    #[test]
+    fn each_batch_covers_every_stratum() {
+        let domain = Domain::new(vector![-1.0, 0.0, 2.0], vector![1.0, 0.5, 3.0]);
+        let n_strata = 20;
+        let mut lhs = LatinHypercubeSearch::new(domain.clone(), n_strata, 7);
+
+        for _ in 0..3 {
+            let batch: Vec<_> = (0..n_strata).map(|_| lhs.sample()).collect();
+            assert!(batch.iter().all(|p| domain.contains(p)));
+
+            for axis in 0..3 {
+                let mut strata: Vec<usize> = batch
+                    .iter()
+                    .map(|p| {
+                        let t = (p[axis] - domain.low()[axis]) / domain.dimensions()[axis];
+                        (t * n_strata as f64).floor() as usize
+                    })
+                    .collect();
+                strata.sort();
+                assert_eq!(strata, (0..n_strata).collect::<Vec<_>>(), "Axis {axis}");
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_produces_same_samples() {
+        let domain = Domain::<10>::normalized();
+        let mut a = LatinHypercubeSearch::new(domain.clone(), 16, 3);
+        let mut b = LatinHypercubeSearch::new(domain.clone(), 16, 3);
+        let mut c = LatinHypercubeSearch::new(domain, 16, 4);
+
+        let a: Vec<_> = (0..40).map(|_| a.sample()).collect();
+        let b: Vec<_> = (0..40).map(|_| b.sample()).collect();
+        let c: Vec<_> = (0..40).map(|_| c.sample()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
+
+#[cfg(test)]
+mod test_quasi_random {
+    use nalgebra::SVector;
+
+    use crate::structs::Domain;
+
+    use super::{HaltonSearch, SearchFactory, SobolSearch};
+
+    /// Whether every one of @n_strata intervals of @axis holds the same number of
+    /// @points.
+    fn is_stratified<const N: usize>(
+        points: &[SVector<f64, N>],
+        axis: usize,
+        n_strata: usize,
+    ) -> bool {
+        let mut counts = vec![0; n_strata];
+        for p in points {
+            counts[((p[axis] * n_strata as f64) as usize).min(n_strata - 1)] += 1;
+        }
+        counts.iter().all(|&c| c == points.len() / n_strata)
+    }
+
+    #[test]
+    fn sobol_points_are_stratified() {
+        let mut sobol = SobolSearch::<21>::new(Domain::normalized());
+        let points: Vec<_> = (0..256).map(|_| sobol.sample()).collect();
+
+        assert!((0..21).all(|axis| is_stratified(&points, axis, 256)));
+        assert_eq!(
+            SobolSearch::<21>::new(Domain::normalized())
+                .skip(255)
+                .sample(),
+            points[255]
+        );
+    }
+
+    #[test]
+    fn halton_points_are_stratified() {
+        let mut halton = HaltonSearch::<3>::new(Domain::normalized());
+        assert_eq!(halton.bases, [2, 3, 5]);
+
+        // Each base's axis is stratified over a power of the base.
+        let points: Vec<_> = (0..2 * 3 * 5).map(|_| halton.sample()).collect();
+        assert!(is_stratified(&points, 0, 2));
+        assert!(is_stratified(&points[..27], 1, 3));
+        assert!(is_stratified(&points[..25], 2, 5));
+        assert!(points.iter().all(|p| Domain::normalized().contains(p)));
+    }
+
+    #[test]
+    fn quasi_random_estimates_converge_faster() {
+        use crate::search::global_search::MonteCarloSearch;
+
+        // The mean of prod(2 * p) over the unit cube is exactly 1
+        let estimate = |search: &mut dyn SearchFactory<3>, n: usize| {
+            (0..n)
+                .map(|_| (2.0 * search.sample()).product())
+                .sum::<f64>()
+                / n as f64
+        };
+
+        let n = 1024;
+        let sobol_err = (estimate(&mut SobolSearch::new(Domain::normalized()), n) - 1.0).abs();
+        let halton_err = (estimate(&mut HaltonSearch::new(Domain::normalized()), n) - 1.0).abs();
+        let mc_err: f64 = (0..10)
+            .map(|seed| {
+                (estimate(&mut MonteCarloSearch::new(Domain::normalized(), seed), n) - 1.0).abs()
+            })
+            .sum::<f64>()
+            / 10.0;
+
+        assert!(sobol_err < mc_err, "Sobol: {sobol_err}, MC: {mc_err}");
+        assert!(halton_err < mc_err, "Halton: {halton_err}, MC: {mc_err}");
+    }
+}
+
+#[cfg(test)]
+mod test_adaptive {
+    use std::collections::HashSet;
+
+    use nalgebra::SVector;
+
+    use crate::structs::{Domain, Sample};
+
+    use super::{AdaptiveSearch, MonteCarloSearch, SearchFactory};
+
+    const RADIUS: f64 = 0.08;
+
+    /// A chain of small, disjoint spheres across the unit cube.
+    fn centers() -> Vec<SVector<f64, 3>> {
+        (0..5)
+            .map(|i| SVector::from([0.1 + 0.2 * i as f64, 0.5, 0.5]))
+            .collect()
+    }
+
+    /// The index of the sphere containing @p, if any.
+    fn sphere_at(p: &SVector<f64, 3>) -> Option<usize> {
+        centers().iter().position(|c| (p - c).norm() <= RADIUS)
+    }
+
+    #[test]
+    fn finds_more_neighboring_envelopes_than_monte_carlo() {
+        let n_samples = 300;
+        let mut n_adaptive = 0;
+        let mut n_mc = 0;
+        for seed in 0..10 {
+            let mut adaptive = AdaptiveSearch::new(Domain::normalized(), 0.1, 0.5, seed);
+            let mut hit = HashSet::new();
+            for _ in 0..n_samples {
+                let p = adaptive.sample();
+                let sphere = sphere_at(&p);
+                hit.extend(sphere);
+                adaptive.observe(&Sample::from_class(p, sphere.is_some()));
+            }
+            n_adaptive += hit.len();
+
+            let mut mc = MonteCarloSearch::new(Domain::normalized(), seed);
+            let hit: HashSet<usize> = (0..n_samples)
+                .filter_map(|_| sphere_at(&mc.sample()))
+                .collect();
+            n_mc += hit.len();
+        }
+
+        assert!(n_adaptive > n_mc, "Adaptive: {n_adaptive}, MC: {n_mc}");
+    }
+
+    #[test]
+    fn samples_stay_within_domain() {
+        let domain = Domain::<3>::new(SVector::repeat(-1.0), SVector::repeat(1.0));
+        let mut adaptive = AdaptiveSearch::new(domain.clone(), 0.5, 1.0, 0);
+        adaptive.observe(&Sample::from_class(SVector::repeat(0.99), true));
+        adaptive.observe(&Sample::from_class(SVector::zeros(), false));
+        assert_eq!(adaptive.hits().len(), 1);
+
+        assert!((0..1000).all(|_| domain.contains(&adaptive.sample())));
+    }
+}
+
+#[cfg(test)]
+mod test_stratified {
+    use nalgebra::SVector;
+
+    use crate::structs::{Domain, Sample};
+
+    use super::{SearchFactory, StratifiedSearch};
+
+    #[test]
+    fn each_pass_samples_every_cell_once() {
+        let domain = Domain::<2>::new(SVector::repeat(-1.0), SVector::repeat(1.0));
+        let mut search = StratifiedSearch::new(domain, 3, 0);
+        assert_eq!(search.cells().len(), 9);
+
+        for _ in 0..2 {
+            let mut counts = [0; 9];
+            for _ in 0..9 {
+                let p = search.sample();
+                let cell = search.cells().iter().position(|c| c.contains(&p));
+                counts[cell.expect("Sample outside of every cell")] += 1;
+            }
+            assert_eq!(counts, [1; 9]);
+        }
+    }
+
+    #[test]
+    fn refinement_concentrates_samples_near_hits() {
+        let mut search = StratifiedSearch::new(Domain::<2>::normalized(), 2, 0).with_refinement(8);
+        let hit = Sample::from_class(SVector::from([0.1, 0.1]), true);
+        let miss = Sample::from_class(SVector::from([0.9, 0.9]), false);
+        for _ in 0..10 {
+            search.observe(&hit);
+            search.observe(&miss);
+        }
+
+        assert_eq!(search.cells().len(), 8);
+        let total_volume: f64 = search.cells().iter().map(|c| c.volume()).sum();
+        assert!((total_volume - 1.0).abs() < 1e-10);
+
+        // 3 of the 8 cells partition the 1/16th of the domain around the hit
+        let n_near = (0..80)
+            .map(|_| search.sample())
+            .filter(|p| p[0] <= 0.25 && p[1] <= 0.25)
+            .count();
+        assert_eq!(n_near, 30);
+    }
+}
+
+#[cfg(test)]
+mod test_boundary_guided {
+    use nalgebra::SVector;
+
+    use crate::{structs::Domain, test_utils::sphere_boundary};
+
+    use super::{BoundaryGuidedSearch, MonteCarloSearch, SearchFactory};
+
+    const RADIUS: f64 = 0.3;
+
+    fn dist_to_surface(p: &SVector<f64, 3>) -> f64 {
+        ((p - SVector::repeat(0.5)).norm() - RADIUS).abs()
+    }
+
+    #[test]
+    fn concentrates_samples_near_prior_boundary() {
+        let prior = sphere_boundary(SVector::repeat(0.5), RADIUS, 200);
+
+        let mut guided = BoundaryGuidedSearch::new(Domain::normalized(), &prior, 0.02, 0.8, 0);
+        let mut mc = MonteCarloSearch::new(Domain::normalized(), 0);
+        let n_near = |search: &mut dyn SearchFactory<3>| {
+            (0..1000)
+                .filter(|_| dist_to_surface(&search.sample()) < 0.05)
+                .count()
+        };
+
+        let n_guided = n_near(&mut guided);
+        let n_mc = n_near(&mut mc);
+        assert!(n_guided > 700, "Too few samples near prior: {n_guided}");
+        assert!(n_guided > 3 * n_mc, "Guided: {n_guided}, MC: {n_mc}");
+    }
+
+    #[test]
+    fn empty_prior_samples_uniformly() {
+        let mut guided = BoundaryGuidedSearch::new(Domain::<3>::normalized(), &[], 0.02, 1.0, 0);
+        assert!((0..100).all(|_| Domain::normalized().contains(&guided.sample())));
+    }
+}
@@ -1,14 +1,25 @@
 use nalgebra::SVector;
 use surfacing::binary_surface_search;
 
+#[cfg(feature = "global_search")]
+use crate::structs::{Sample, SamplingError};
 use crate::{
     extensions::Queue,
-    structs::{BoundaryPair, Classifier, Domain, OutOfMode, Result, WithinMode},
+    structs::{BoundaryPair, Classifier, OutOfMode, Region, Result, WithinMode},
 };
+#[cfg(feature = "global_search")]
+use global_search::SearchFactory;
 
+pub mod active_learning;
+#[cfg(feature = "global_search")]
+pub mod coverage;
+#[cfg(feature = "global_search")]
+pub mod falsification;
 #[cfg(feature = "global_search")]
 pub mod global_search;
 
+#[cfg(all(feature = "global_search", feature = "surfacing"))]
+pub mod multi_root;
 #[cfg(feature = "surfacing")]
 pub mod surfacing;
 
@@ -16,8 +27,8 @@ pub mod surfacing;
 /// * Full : Searches every recurrent mid-point between a and b.
 /// * Nearest : Searches the points closer to a from the midpoint between a and b,
 ///   ignoring the points that fall closer to b.
-///     Efficient when you are trying to re-acquire the same performance space as point
-///     a, since the space beyond the first mid-point will be ignored entirely.
+///   Efficient when you are trying to re-acquire the same performance space as point
+///   a, since the space beyond the first mid-point will be ignored entirely.
 pub enum SearchMode {
     Full,
     Nearest,
@@ -74,6 +85,52 @@ pub fn binary_search_between<const N: usize, C: Classifier<N>>(
     None
 }
 
+/// Samples the points generated by @search until both a WithinMode and an OutOfMode
+/// sample have been found, forming the initial boundary pair for surfacing.
+/// ## Arguments
+/// * search : The global search to draw points from.
+/// * classifier : The FUT.
+/// * max_samples : The maximum number of samples to take.
+/// ## Return (Ok((pair, history)))
+/// * pair : The first WithinMode and first OutOfMode samples found.
+/// * history : Every sample taken, in order.
+/// ## Error (Err)
+/// * MaxSamplesExceeded : If either class was not found within @max_samples.
+/// * SamplingError : If the classifier fails.
+#[cfg(feature = "global_search")]
+pub fn find_initial_boundary_pair<const N: usize, S, C>(
+    search: &mut S,
+    classifier: &mut C,
+    max_samples: u32,
+) -> Result<(BoundaryPair<N>, Vec<Sample<N>>)>
+where
+    S: SearchFactory<N> + ?Sized,
+    C: Classifier<N>,
+{
+    let mut t0 = None;
+    let mut x0 = None;
+    let mut history = vec![];
+
+    for _ in 0..max_samples {
+        let sample = classifier.classify(search.sample())?;
+        history.push(sample);
+        match sample {
+            Sample::WithinMode(t) => {
+                t0.get_or_insert(t);
+            }
+            Sample::OutOfMode(x) => {
+                x0.get_or_insert(x);
+            }
+        }
+
+        if let (Some(t), Some(x)) = (t0, x0) {
+            return Ok((BoundaryPair::new(t, x), history));
+        }
+    }
+
+    Err(SamplingError::MaxSamplesExceeded)
+}
+
 /// Finds a boundary point on the opposite side of an envelope given a starting
 /// boundary point and directional vector of the chord between these boundary points.
 /// **Note: If there is no Out-of-Mode samples between @b and the edge of the domain,
@@ -86,7 +143,7 @@ pub fn binary_search_between<const N: usize, C: Classifier<N>>(
 /// * v : A unit vector describing the direction of the chord. Note: v must point
 ///   TOWARDS the envelope, not away. i.e. its dot product with the OSV should be
 ///   negative, n.dot(v) < 0,
-/// * domain : The domain to constrain exploration to be within.
+/// * domain : The domain (or other Region) to constrain exploration to be within.
 /// * classifier : The classifier for the FUT.
 ////// ## Returns (Ok)
 /// * Ok(b2) : The point within the envelope opposite that of @b.
@@ -98,11 +155,11 @@ pub fn binary_search_between<const N: usize, C: Classifier<N>>(
 /// * If @v is not facing the geometry, it may still return a boundary point with a
 ///   sufficiently large @num_checks or innaccurate @b. This is because it will
 ///   converge upon the same side of the geometry as @b.
-pub fn find_opposing_boundary<const N: usize, C: Classifier<N>>(
+pub fn find_opposing_boundary<const N: usize, C: Classifier<N>, R: Region<N> + ?Sized>(
     max_err: f64,
     t0: WithinMode<N>,
     v: SVector<f64, N>,
-    domain: &Domain<N>,
+    domain: &R,
     classifier: &mut C,
     num_checks: u32,
     num_iter: u32,
@@ -156,6 +213,84 @@ pub fn find_opposing_boundary<const N: usize, C: Classifier<N>>(
     Ok(b)
 }
 
+#[cfg(all(test, feature = "global_search"))]
+mod initial_boundary_pair {
+    use nalgebra::SVector;
+
+    use crate::structs::{Domain, FunctionClassifier, Result, SamplingError};
+
+    use super::{find_initial_boundary_pair, global_search::MonteCarloSearch};
+
+    #[test]
+    fn finds_first_of_each_class() {
+        let mut search = MonteCarloSearch::new(Domain::<3>::normalized(), 1);
+        let mut classifier = FunctionClassifier::new(|p: SVector<f64, 3>| Ok(p[0] < 0.2));
+
+        let (pair, history) = find_initial_boundary_pair(&mut search, &mut classifier, 100)
+            .expect("Failed to find boundary pair");
+
+        assert!(pair.t()[0] < 0.2 && pair.x()[0] >= 0.2);
+        let last = history.last().expect("History must be non-empty");
+        assert!(**last == **pair.t() || **last == **pair.x());
+        // Only the last sample completes the pair
+        let n_within = history.iter().filter(|s| s.class()).count();
+        assert!(n_within == 1 || n_within == history.len() - 1);
+    }
+
+    #[test]
+    fn fails_when_mode_is_absent() {
+        let mut search = MonteCarloSearch::new(Domain::<3>::normalized(), 1);
+        let mut n_samples = 0;
+        let mut classifier = FunctionClassifier::new(|_| -> Result<bool> {
+            n_samples += 1;
+            Ok(false)
+        });
+
+        assert_eq!(
+            find_initial_boundary_pair(&mut search, &mut classifier, 50).map(|_| ()),
+            Err(SamplingError::MaxSamplesExceeded)
+        );
+        assert_eq!(n_samples, 50);
+    }
+}
+
+#[cfg(test)]
+mod opposing_boundary_in_region {
+    use std::f64::consts::FRAC_PI_4;
+
+    use nalgebra::{vector, Rotation2, SVector};
+
+    use crate::structs::{Domain, FunctionClassifier, ObliqueDomain, Region, WithinMode};
+
+    use super::find_opposing_boundary;
+
+    #[test]
+    fn stops_at_edge_of_rotated_slab() {
+        let slab = ObliqueDomain::from_affine(
+            &Domain::new(vector![-0.5, -0.1], vector![0.5, 0.1]),
+            *Rotation2::new(FRAC_PI_4).matrix(),
+            vector![0.5, 0.5],
+        );
+        // The entire slab is within the envelope
+        let mut classifier = FunctionClassifier::new(|_: SVector<f64, 2>| Ok(true));
+        let along = vector![1.0, 1.0].normalize();
+
+        let b = find_opposing_boundary(
+            0.01,
+            WithinMode(vector![0.5, 0.5]),
+            along,
+            &slab,
+            &mut classifier,
+            10,
+            10,
+        )
+        .unwrap();
+
+        assert!(slab.contains(&b));
+        assert!((*b - vector![0.5, 0.5]).norm() > 0.49);
+    }
+}
+
 #[cfg(all(test, feature = "sps"))]
 mod search_tests {
     use super::*;
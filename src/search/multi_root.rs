@@ -0,0 +1,192 @@
+use crate::{
+    adherer_core::AdhererFactory,
+    boundary_tools::{
+        estimation::{approx_prediction, approx_surface},
+        falls_on_boundary, get_rtree_from_boundary,
+    },
+    explorer_core::Explorer,
+    explorers::MeshExplorer,
+    search::{global_search::SearchFactory, surfacing::binary_surface_search},
+    structs::{
+        BoundaryPair, BoundaryRTree, Budget, BudgetTracker, Classifier, Halfspace, OutOfMode,
+        Result, Sample, SamplingError, WithinMode,
+    },
+};
+use nalgebra::SVector;
+
+/// The max number of samples taken while surfacing a boundary pair.
+const MAX_SURFACING_SAMPLES: u32 = 100;
+
+/// Spends the budget on each classification, failing with MaxSamplesExceeded once
+/// the samples or time have run out.
+struct BudgetedClassifier<'a, C> {
+    classifier: &'a mut C,
+    budget: BudgetTracker,
+}
+
+impl<const N: usize, C: Classifier<N>> Classifier<N> for BudgetedClassifier<'_, C> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        if self.budget.exhausted(0).is_some() {
+            return Err(SamplingError::MaxSamplesExceeded);
+        }
+        self.budget.spend_sample();
+        self.classifier.classify(p)
+    }
+}
+
+/// Explores each of the disjoint envelopes found within the search domain.
+/// Alternates between global search, surfacing, and a MeshExplorer for each newly
+/// discovered envelope, until the budget runs out. Global search samples that are
+/// predicted to fall within an explored envelope are not surfaced, and surfaced
+/// roots that already lie on an explored boundary (see `falls_on_boundary()`)
+/// are skipped.
+/// ## Arguments
+/// * d : The jump distance of the explorers, also used as the max error while
+///   surfacing.
+/// * margin : The margin of the explorers. See `MeshExplorer::new()`.
+/// * search : The global search used to find envelopes.
+/// * adherer_f : The adherer factory of the explorers.
+/// * classifier : The FUT.
+/// * budget : The budget for the full process. Must limit the number of samples or
+///   the duration, since global search alone never completes. A boundary point
+///   limit applies to the total across envelopes.
+/// ## Return
+/// * envelopes : The boundary of each discovered envelope, in the order they were
+///   found. The last boundary may be incomplete if the budget ran out during its
+///   exploration.
+pub fn explore_envelopes<const N: usize, S, F, C>(
+    d: f64,
+    margin: f64,
+    search: &mut S,
+    adherer_f: F,
+    classifier: &mut C,
+    budget: Budget,
+) -> Vec<Vec<Halfspace<N>>>
+where
+    S: SearchFactory<N>,
+    F: AdhererFactory<N>,
+    C: Classifier<N>,
+{
+    assert!(
+        budget.max_samples.is_some() || budget.max_duration.is_some(),
+        "Budget must limit the number of samples or the duration!"
+    );
+
+    let mut classifier = BudgetedClassifier {
+        classifier,
+        budget: budget.start(),
+    };
+    let mut envelopes: Vec<(Vec<Halfspace<N>>, BoundaryRTree<N>)> = vec![];
+    let mut candidates: Vec<WithinMode<N>> = vec![];
+    let mut x0: Option<OutOfMode<N>> = None;
+    let b_count = |envelopes: &Vec<(Vec<Halfspace<N>>, _)>| -> usize {
+        envelopes.iter().map(|(b, _)| b.len()).sum()
+    };
+
+    while classifier.budget.exhausted(b_count(&envelopes)).is_none() {
+        let p = search.sample();
+        match classifier.classify(p) {
+            Ok(Sample::OutOfMode(x)) => x0 = Some(x),
+            Ok(Sample::WithinMode(t)) => {
+                let explored = envelopes
+                    .iter()
+                    .any(|(b, btree)| approx_prediction(*t, b, btree, 1).class());
+                if !explored {
+                    candidates.push(t);
+                }
+            }
+            Err(SamplingError::MaxSamplesExceeded) => break,
+            Err(_) => continue,
+        }
+
+        let (Some(x), Some(t)) = (x0, candidates.pop()) else {
+            continue;
+        };
+
+        let root = match binary_surface_search(
+            d,
+            &BoundaryPair::new(t, x),
+            MAX_SURFACING_SAMPLES,
+            &mut classifier,
+        ) {
+            Ok(root) => root,
+            Err(_) => continue,
+        };
+        let root = match approx_surface(d, root, &adherer_f, &mut classifier) {
+            Ok((hs, _, _)) => hs,
+            Err(_) => root,
+        };
+
+        if envelopes
+            .iter()
+            .any(|(b, btree)| falls_on_boundary(d, &root, b, btree))
+        {
+            continue;
+        }
+
+        let max_b_count = budget
+            .max_boundary_points
+            .map(|max| max - b_count(&envelopes));
+        let mut expl = MeshExplorer::new(d, root, margin, adherer_f);
+        loop {
+            if max_b_count.is_some_and(|max| expl.boundary_count() >= max) {
+                break;
+            }
+            match expl.step(&mut classifier) {
+                Ok(None) | Err(SamplingError::MaxSamplesExceeded) => break,
+                _ => (),
+            }
+        }
+
+        let boundary = expl.boundary_owned();
+        let btree = get_rtree_from_boundary(&boundary);
+        envelopes.push((boundary, btree));
+    }
+
+    envelopes.into_iter().map(|(b, _)| b).collect()
+}
+
+#[cfg(all(test, feature = "sps"))]
+mod multi_root_exploration {
+    use nalgebra::vector;
+
+    use super::*;
+    use crate::{
+        prelude::{ConstantAdhererFactory, Domain},
+        search::global_search::MonteCarloSearch,
+        sps::{Sphere, SphereCluster},
+    };
+
+    #[test]
+    fn explores_each_disjoint_envelope() {
+        let spheres = vec![
+            Sphere::new(vector![0.25, 0.25], 0.15, None),
+            Sphere::new(vector![0.7, 0.7], 0.2, None),
+        ];
+        let mut classifier = SphereCluster::new(spheres.clone(), Some(Domain::normalized()));
+        let mut search = MonteCarloSearch::new(Domain::normalized(), 1);
+
+        let envelopes = explore_envelopes(
+            0.05,
+            0.04,
+            &mut search,
+            ConstantAdhererFactory::new(0.2, None),
+            &mut classifier,
+            Budget::new().with_max_samples(3000),
+        );
+
+        assert_eq!(envelopes.len(), 2);
+        let mut found: Vec<usize> = envelopes
+            .iter()
+            .map(|boundary| {
+                let b = *boundary[0].b;
+                spheres
+                    .iter()
+                    .position(|s| ((b - s.center()).norm() - s.radius()).abs() < 0.05)
+                    .expect("Boundary does not belong to either sphere?")
+            })
+            .collect();
+        found.sort();
+        assert_eq!(found, vec![0, 1]);
+    }
+}
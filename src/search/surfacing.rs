@@ -3,6 +3,19 @@ use crate::{
     structs::{BoundaryPair, Classifier, Halfspace, Result, SamplingError, WithinMode},
 };
 
+/// The outcome of a binary surface search. See
+/// `binary_surface_search_with_history()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceSearchResult<const N: usize> {
+    /// The acquired boundary halfspace.
+    pub hs: Halfspace<N>,
+    /// Every sample taken, in order.
+    pub samples: Vec<Sample<N>>,
+    /// The distance between the final WithinMode and OutOfMode points, an upper
+    /// bound on the distance from @hs to the boundary along the searched chord.
+    pub err_bound: f64,
+}
+
 /// Finds the surface of an envelope, i.e. the initial halfspace for beginning
 /// surface exploration by iteratively splitting the space in half until a desireable
 /// distance from the boundary has been reached.
@@ -17,16 +30,42 @@ pub fn binary_surface_search<const N: usize, C: Classifier<N>>(
     max_samples: u32,
     classifier: &mut C,
 ) -> Result<Halfspace<N>> {
+    binary_surface_search_with_history(max_err, b_pair, max_samples, classifier)
+        .map(|result| result.hs)
+}
+
+/// Performs a `binary_surface_search()`, also returning the samples taken and the
+/// distance bound achieved, e.g. for logging convergence or judging how
+/// trustworthy the resulting halfspace is.
+/// ## Arguments
+/// * `max_err` The desired maximum distance from the boundary.
+/// * `t0` A target sample
+/// * `x0` A non-target sample
+/// * `max_samples` The maximum number of samples before the failing the process.
+/// ## Return (Ok)
+/// * result : The halfspace, samples and achieved distance bound.
+/// ## Error (Err)
+/// * MaxSamplesExceeded : If @max_err was not reached within @max_samples.
+/// * SamplingError : If the classifier fails.
+pub fn binary_surface_search_with_history<const N: usize, C: Classifier<N>>(
+    max_err: f64,
+    b_pair: &BoundaryPair<N>,
+    max_samples: u32,
+    classifier: &mut C,
+) -> Result<SurfaceSearchResult<N>> {
     let mut p_t = b_pair.t().0;
     let mut p_x = b_pair.x().0;
     let mut s = p_x - p_t;
     let mut i = 0;
+    let mut samples = vec![];
 
     while s.norm() > max_err && i < max_samples {
         s = (p_x - p_t) / 2.0;
         i += 1;
 
-        match classifier.classify(p_t + s)? {
+        let sample = classifier.classify(p_t + s)?;
+        samples.push(sample);
+        match sample {
             Sample::WithinMode(_) => p_t += s,
             Sample::OutOfMode(_) => p_x -= s,
         }
@@ -39,9 +78,13 @@ pub fn binary_surface_search<const N: usize, C: Classifier<N>>(
 
     let n = s.normalize();
 
-    Ok(Halfspace {
-        b: WithinMode(p_t),
-        n,
+    Ok(SurfaceSearchResult {
+        hs: Halfspace {
+            b: WithinMode(p_t),
+            n,
+        },
+        samples,
+        err_bound: (p_x - p_t).norm(),
     })
 }
 
@@ -55,10 +98,9 @@ mod test_surfacer {
         structs::{BoundaryPair, Classifier, Domain, OutOfMode, SamplingError, WithinMode},
     };
 
-    use super::binary_surface_search;
+    use super::{binary_surface_search, binary_surface_search_with_history};
 
     const RADIUS: f64 = 0.25;
-    const DIAMETER: f64 = 2.0 * RADIUS;
 
     fn setup_sphere<const N: usize>() -> Sphere<N> {
         let radius = 0.25;
@@ -188,4 +230,24 @@ mod test_surfacer {
             "Got a distance from boundary greater than max dist: {dist} > {max_err}"
         );
     }
+
+    #[test]
+    fn history_records_each_bisection() {
+        let max_err = 0.01;
+        let mut classifier = FunctionClassifier::new(|x: SVector<f64, 3>| Ok(x[0] < 0.7));
+        let b_pair = BoundaryPair::new(
+            WithinMode(vector![0.0, 0.5, 0.5]),
+            OutOfMode(vector![1.0, 0.5, 0.5]),
+        );
+
+        let result = binary_surface_search_with_history(max_err, &b_pair, 20, &mut classifier)
+            .expect("Got error when expecting results from BSS");
+
+        // The chord of length 1 is halved until within max_err
+        assert_eq!(result.samples.len(), (1.0 / max_err).log2().ceil() as usize);
+        assert!(result.err_bound <= max_err);
+        assert!((result.hs.b[0] - 0.7).abs() <= result.err_bound);
+        let last_t = result.samples.iter().rev().find(|s| s.class()).unwrap();
+        assert_eq!(**last_t, *result.hs.b);
+    }
 }
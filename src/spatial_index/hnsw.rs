@@ -0,0 +1,223 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+use crate::{prelude::KnnNode, utils::array_distance};
+
+use super::{Candidate, SpatialIndex};
+
+/// An approximate nearest-neighbor index based on Hierarchical Navigable Small
+/// World graphs (Malkov & Yashunin, 2016). Queries greedily descend a hierarchy of
+/// proximity graphs, so their cost grows roughly logarithmically with the number
+/// of nodes regardless of dimensionality. Results are not guaranteed to be exact.
+///
+/// Layers are assigned pseudo-randomly from a fixed seed, so an index built from
+/// the same insertions is always the same.
+#[derive(Debug, Clone)]
+pub struct HnswIndex<const N: usize> {
+    items: Vec<KnnNode<N>>,
+    /// The neighbors of each item, for each layer the item is present in.
+    links: Vec<Vec<Vec<usize>>>,
+    entry: Option<usize>,
+    m: usize,
+    ef: usize,
+    level_mult: f64,
+    rng_state: u64,
+}
+
+impl<const N: usize> HnswIndex<N> {
+    /// Creates an empty HnswIndex.
+    /// ## Arguments
+    /// * m : The number of neighbors each node links to per layer (twice as many on
+    ///   the bottom layer). Higher values improve accuracy at the cost of memory
+    ///   and insertion time. 16 is a good default.
+    /// * ef : The number of candidates considered during insertion and queries.
+    ///   Higher values improve accuracy at the cost of speed. Must be at least m;
+    ///   64 is a good default.
+    pub fn new(m: usize, ef: usize) -> Self {
+        assert!(m >= 2, "m must be at least 2!");
+        assert!(ef >= m, "ef must be at least m!");
+
+        HnswIndex {
+            items: vec![],
+            links: vec![],
+            entry: None,
+            m,
+            ef,
+            level_mult: 1.0 / (m as f64).ln(),
+            rng_state: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    fn dist(&self, p: &[f64; N], id: usize) -> f64 {
+        array_distance(p, self.items[id].geom())
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            2 * self.m
+        } else {
+            self.m
+        }
+    }
+
+    /// Draws the top layer of a new node from an exponentially decaying
+    /// distribution, using a splitmix64 generator.
+    fn random_level(&mut self) -> usize {
+        self.rng_state = self.rng_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        let u = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        (-u.ln() * self.level_mult).floor() as usize
+    }
+
+    /// Greedily searches @layer for the @ef nearest nodes to @p, starting from
+    /// @entries.
+    /// ## Return
+    /// * candidates : The nearest nodes found, ordered from nearest to farthest.
+    fn search_layer(
+        &self,
+        p: &[f64; N],
+        entries: &[Candidate],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entries.iter().map(|c| c.id).collect();
+        let mut frontier: BinaryHeap<Reverse<Candidate>> =
+            entries.iter().copied().map(Reverse).collect();
+        let mut nearest: BinaryHeap<Candidate> = entries.iter().copied().collect();
+
+        while let Some(Reverse(current)) = frontier.pop() {
+            let farthest = nearest.peek().map_or(f64::INFINITY, |c| c.dist);
+            if current.dist > farthest && nearest.len() >= ef {
+                break;
+            }
+
+            for &id in self.links[current.id][layer].iter() {
+                if !visited.insert(id) {
+                    continue;
+                }
+
+                let dist = self.dist(p, id);
+                let farthest = nearest.peek().map_or(f64::INFINITY, |c| c.dist);
+                if nearest.len() < ef || dist < farthest {
+                    frontier.push(Reverse(Candidate { dist, id }));
+                    nearest.push(Candidate { dist, id });
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+
+    /// Descends from the entry point to @layer, keeping only the nearest node to
+    /// @p on each layer above it.
+    fn descend(&self, p: &[f64; N], layer: usize) -> Option<Candidate> {
+        let entry = self.entry?;
+        let mut nearest = Candidate {
+            dist: self.dist(p, entry),
+            id: entry,
+        };
+
+        for l in (layer + 1..self.links[entry].len()).rev() {
+            nearest = self.search_layer(p, &[nearest], 1, l)[0];
+        }
+
+        Some(nearest)
+    }
+
+    /// Links @id to its @neighbors on @layer in both directions, pruning each
+    /// neighbor's links to its nearest max_links(@layer).
+    fn connect(&mut self, id: usize, neighbors: &[Candidate], layer: usize) {
+        let max_links = self.max_links(layer);
+        self.links[id][layer] = neighbors.iter().take(max_links).map(|c| c.id).collect();
+
+        for c in neighbors.iter().take(max_links) {
+            self.links[c.id][layer].push(id);
+            if self.links[c.id][layer].len() > max_links {
+                let p = *self.items[c.id].geom();
+                let mut links: Vec<Candidate> = self.links[c.id][layer]
+                    .iter()
+                    .map(|&other| Candidate {
+                        dist: self.dist(&p, other),
+                        id: other,
+                    })
+                    .collect();
+                links.sort();
+                self.links[c.id][layer] = links.iter().take(max_links).map(|c| c.id).collect();
+            }
+        }
+    }
+}
+
+impl<const N: usize> SpatialIndex<N> for HnswIndex<N> {
+    fn insert(&mut self, node: KnnNode<N>) {
+        let id = self.items.len();
+        let level = self.random_level();
+        let p = *node.geom();
+
+        self.items.push(node);
+        self.links.push(vec![vec![]; level + 1]);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(id);
+            return;
+        };
+
+        let top = self.links[entry].len() - 1;
+        let mut entries = vec![self
+            .descend(&p, level.min(top))
+            .expect("Entry point must exist")];
+
+        for layer in (0..=level.min(top)).rev() {
+            let neighbors = self.search_layer(&p, &entries, self.ef, layer);
+            self.connect(id, &neighbors, layer);
+            entries = neighbors;
+        }
+
+        if level > top {
+            self.entry = Some(id);
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    fn nearest_neighbors(&self, p: &[f64; N], k: usize) -> Vec<KnnNode<N>> {
+        let Some(entry) = self.descend(p, 0) else {
+            return vec![];
+        };
+
+        self.search_layer(p, &[entry], self.ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|c| self.items[c.id])
+            .collect()
+    }
+
+    /// Approximate: queries increasingly many neighbors until one falls beyond @r.
+    fn within_distance(&self, p: &[f64; N], r: f64) -> Vec<KnnNode<N>> {
+        let mut k = self.m;
+        loop {
+            let nodes = self.nearest_neighbors(p, k);
+            let exhausted = nodes.len() < k;
+            let within: Vec<KnnNode<N>> = nodes
+                .into_iter()
+                .take_while(|node| array_distance(p, node.geom()) <= r)
+                .collect();
+
+            if exhausted || within.len() < k {
+                return within;
+            }
+            k *= 2;
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BinaryHeap;
+
+use crate::{prelude::KnnNode, utils::array_distance};
+
+use super::{Candidate, SpatialIndex};
+
+/// An exact k-d tree. Nodes are inserted incrementally, and the tree is rebuilt
+/// into a balanced tree whenever an insertion lands too deep, which keeps the
+/// grid-like insertion order of exploration from degrading queries.
+#[derive(Debug, Clone, Default)]
+pub struct KdTree<const N: usize> {
+    nodes: Vec<KdNode<N>>,
+    root: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct KdNode<const N: usize> {
+    item: KnnNode<N>,
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<const N: usize> KdTree<N> {
+    pub fn new() -> Self {
+        KdTree {
+            nodes: vec![],
+            root: None,
+        }
+    }
+
+    /// Creates a balanced KdTree from @items.
+    pub fn bulk_load(items: Vec<KnnNode<N>>) -> Self {
+        let mut tree = KdTree::new();
+        tree.rebuild(items);
+        tree
+    }
+
+    fn rebuild(&mut self, mut items: Vec<KnnNode<N>>) {
+        self.nodes = Vec::with_capacity(items.len());
+        self.root = self.build(&mut items, 0);
+    }
+
+    fn build(&mut self, items: &mut [KnnNode<N>], depth: usize) -> Option<usize> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let axis = depth % N;
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |a, b| a.geom()[axis].total_cmp(&b.geom()[axis]));
+
+        let id = self.nodes.len();
+        self.nodes.push(KdNode {
+            item: items[mid],
+            axis,
+            left: None,
+            right: None,
+        });
+
+        let (left, rest) = items.split_at_mut(mid);
+        self.nodes[id].left = self.build(left, depth + 1);
+        self.nodes[id].right = self.build(&mut rest[1..], depth + 1);
+
+        Some(id)
+    }
+
+    /// Visits the subtree at @id, nearest side first, calling @visit with each
+    /// node's id and its distance from @p. @visit returns the current search radius,
+    /// beyond which subtrees are pruned.
+    fn search<V>(&self, id: Option<usize>, p: &[f64; N], visit: &mut V, radius: &mut f64)
+    where
+        V: FnMut(usize, f64) -> f64,
+    {
+        let Some(id) = id else {
+            return;
+        };
+        let node = &self.nodes[id];
+
+        *radius = visit(id, array_distance(p, node.item.geom()));
+
+        let diff = p[node.axis] - node.item.geom()[node.axis];
+        let (near, far) = if diff < 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+
+        self.search(near, p, visit, radius);
+        if diff.abs() <= *radius {
+            self.search(far, p, visit, radius);
+        }
+    }
+}
+
+impl<const N: usize> SpatialIndex<N> for KdTree<N> {
+    fn insert(&mut self, node: KnnNode<N>) {
+        let id = self.nodes.len();
+        let mut depth = 0;
+        let mut parent = None;
+        let mut current = self.root;
+
+        while let Some(i) = current {
+            let kd_node = &self.nodes[i];
+            let go_left = node.geom()[kd_node.axis] < kd_node.item.geom()[kd_node.axis];
+            parent = Some((i, go_left));
+            current = if go_left { kd_node.left } else { kd_node.right };
+            depth += 1;
+        }
+
+        self.nodes.push(KdNode {
+            item: node,
+            axis: depth % N,
+            left: None,
+            right: None,
+        });
+
+        match parent {
+            Some((i, true)) => self.nodes[i].left = Some(id),
+            Some((i, false)) => self.nodes[i].right = Some(id),
+            None => self.root = Some(id),
+        }
+
+        // Rebalance once the tree is far deeper than a balanced tree would be.
+        let max_depth = 3 * (usize::BITS - self.nodes.len().leading_zeros()) as usize + 8;
+        if depth > max_depth {
+            let items = self.nodes.iter().map(|kd_node| kd_node.item).collect();
+            self.rebuild(items);
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn nearest_neighbors(&self, p: &[f64; N], k: usize) -> Vec<KnnNode<N>> {
+        if k == 0 {
+            return vec![];
+        }
+
+        let mut heap = BinaryHeap::new();
+        let mut radius = f64::INFINITY;
+        self.search(
+            self.root,
+            p,
+            &mut |id, dist| {
+                heap.push(Candidate { dist, id });
+                if heap.len() > k {
+                    heap.pop();
+                }
+                match heap.peek() {
+                    Some(farthest) if heap.len() == k => farthest.dist,
+                    _ => f64::INFINITY,
+                }
+            },
+            &mut radius,
+        );
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|c| self.nodes[c.id].item)
+            .collect()
+    }
+
+    fn within_distance(&self, p: &[f64; N], r: f64) -> Vec<KnnNode<N>> {
+        let mut found = vec![];
+        let mut radius = r;
+        self.search(
+            self.root,
+            p,
+            &mut |id, dist| {
+                if dist <= r {
+                    found.push(Candidate { dist, id });
+                }
+                r
+            },
+            &mut radius,
+        );
+
+        found.sort();
+        found.into_iter().map(|c| self.nodes[c.id].item).collect()
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+
+use rstar::RTree;
+
+use crate::{prelude::KnnNode, utils::array_distance};
+
+pub mod hnsw;
+pub mod kd_tree;
+
+pub use hnsw::*;
+pub use kd_tree::*;
+
+/// A nearest-neighbor index over boundary points, used by the explorers to reject
+/// overlapping paths and by the prediction tools to find the halfspaces nearest
+/// to a point. Each node stores a boundary point and its index in the boundary.
+///
+/// RTree is the default backend. Its nearest-neighbor performance degrades quickly
+/// beyond ~20 dimensions, where KdTree or the approximate HnswIndex may be
+/// preferable. See `SpatialIndexKind`.
+pub trait SpatialIndex<const N: usize>: Send + Sync {
+    /// Adds a node to the index.
+    fn insert(&mut self, node: KnnNode<N>);
+
+    /// The number of nodes in the index.
+    fn size(&self) -> usize;
+
+    /// The nearest node to @p, or None if the index is empty.
+    fn nearest_neighbor(&self, p: &[f64; N]) -> Option<KnnNode<N>> {
+        self.nearest_neighbors(p, 1).into_iter().next()
+    }
+
+    /// The @k nearest nodes to @p, ordered from nearest to farthest.
+    fn nearest_neighbors(&self, p: &[f64; N], k: usize) -> Vec<KnnNode<N>>;
+
+    /// All nodes within @r distance of @p, ordered from nearest to farthest.
+    fn within_distance(&self, p: &[f64; N], r: f64) -> Vec<KnnNode<N>>;
+}
+
+/// The available SpatialIndex backends, for selecting an explorer's index at
+/// construction (e.g. `MeshExplorer::with_spatial_index()`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SpatialIndexKind {
+    /// An exact R*-tree. Fast in low dimensions.
+    #[default]
+    RTree,
+    /// An exact k-d tree. Degrades more gracefully than RTree with dimensionality.
+    KdTree,
+    /// An approximate Hierarchical Navigable Small World graph. Nearest neighbors
+    /// may occasionally be missed, in exchange for fast queries in high
+    /// dimensions. See `HnswIndex::new()` for the parameters.
+    Hnsw { m: usize, ef: usize },
+}
+
+impl SpatialIndexKind {
+    /// Creates an empty index of this kind.
+    pub fn build<const N: usize>(&self) -> Box<dyn SpatialIndex<N>> {
+        match *self {
+            SpatialIndexKind::RTree => Box::new(RTree::<KnnNode<N>>::new()),
+            SpatialIndexKind::KdTree => Box::new(KdTree::<N>::new()),
+            SpatialIndexKind::Hnsw { m, ef } => Box::new(HnswIndex::<N>::new(m, ef)),
+        }
+    }
+}
+
+impl<const N: usize> SpatialIndex<N> for RTree<KnnNode<N>> {
+    fn insert(&mut self, node: KnnNode<N>) {
+        RTree::insert(self, node);
+    }
+
+    fn size(&self) -> usize {
+        RTree::size(self)
+    }
+
+    fn nearest_neighbor(&self, p: &[f64; N]) -> Option<KnnNode<N>> {
+        RTree::nearest_neighbor(self, p).copied()
+    }
+
+    fn nearest_neighbors(&self, p: &[f64; N], k: usize) -> Vec<KnnNode<N>> {
+        self.nearest_neighbor_iter(p).take(k).copied().collect()
+    }
+
+    fn within_distance(&self, p: &[f64; N], r: f64) -> Vec<KnnNode<N>> {
+        self.nearest_neighbor_iter(p)
+            .take_while(|node| array_distance(p, node.geom()) <= r)
+            .copied()
+            .collect()
+    }
+}
+
+/// A node reference paired with its distance from a query point, ordered by
+/// distance for use in the backends' priority queues.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    dist: f64,
+    id: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist
+            .total_cmp(&other.dist)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+#[cfg(test)]
+mod spatial_index_tests {
+    use super::*;
+
+    const KINDS: [SpatialIndexKind; 3] = [
+        SpatialIndexKind::RTree,
+        SpatialIndexKind::KdTree,
+        SpatialIndexKind::Hnsw { m: 16, ef: 64 },
+    ];
+
+    /// Deterministic pseudo-random points within the unit hypercube.
+    fn get_points<const N: usize>(count: usize) -> Vec<[f64; N]> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count)
+            .map(|_| {
+                [0.0; N].map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state >> 11) as f64 / (1u64 << 53) as f64
+                })
+            })
+            .collect()
+    }
+
+    fn build_index<const N: usize>(
+        kind: SpatialIndexKind,
+        points: &[[f64; N]],
+    ) -> Box<dyn SpatialIndex<N>> {
+        let mut index = kind.build();
+        for (i, p) in points.iter().enumerate() {
+            index.insert(KnnNode::new(*p, i));
+        }
+        index
+    }
+
+    fn brute_force<const N: usize>(points: &[[f64; N]], p: &[f64; N], k: usize) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..points.len()).collect();
+        ids.sort_by(|&a, &b| {
+            array_distance(p, &points[a]).total_cmp(&array_distance(p, &points[b]))
+        });
+        ids.truncate(k);
+        ids
+    }
+
+    #[test]
+    fn empty_index_has_no_neighbors() {
+        for kind in KINDS {
+            let index = kind.build::<3>();
+            assert_eq!(index.size(), 0);
+            assert!(index.nearest_neighbor(&[0.5; 3]).is_none(), "{kind:?}");
+            assert!(index.within_distance(&[0.5; 3], 1.0).is_empty(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn exact_backends_match_brute_force() {
+        let points = get_points::<25>(500);
+        let queries = get_points::<25>(520).split_off(500);
+
+        for kind in [SpatialIndexKind::RTree, SpatialIndexKind::KdTree] {
+            let index = build_index(kind, &points);
+            assert_eq!(index.size(), points.len());
+
+            for q in queries.iter() {
+                let expected = brute_force(&points, q, 5);
+                let ids: Vec<usize> = index
+                    .nearest_neighbors(q, 5)
+                    .iter()
+                    .map(|node| node.data)
+                    .collect();
+                assert_eq!(ids, expected, "{kind:?} returned the wrong neighbors");
+            }
+        }
+    }
+
+    #[test]
+    fn within_distance_is_sorted_and_bounded() {
+        let points = get_points::<3>(300);
+        let q = [0.5; 3];
+        let r = 0.2;
+        let expected = points.iter().filter(|p| array_distance(&q, p) <= r).count();
+
+        for kind in KINDS {
+            let index = build_index(kind, &points);
+            let nodes = index.within_distance(&q, r);
+            let dists: Vec<f64> = nodes
+                .iter()
+                .map(|node| array_distance(&q, node.geom()))
+                .collect();
+
+            assert!(dists.iter().all(|&dist| dist <= r), "{kind:?}");
+            assert!(dists.windows(2).all(|w| w[0] <= w[1]), "{kind:?}");
+            if kind != (SpatialIndexKind::Hnsw { m: 16, ef: 64 }) {
+                assert_eq!(nodes.len(), expected, "{kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn hnsw_has_high_recall() {
+        let points = get_points::<30>(400);
+        let queries = get_points::<30>(450).split_off(400);
+        let index = build_index(SpatialIndexKind::Hnsw { m: 8, ef: 32 }, &points);
+
+        let hits = queries
+            .iter()
+            .filter(|q| {
+                index.nearest_neighbor(q).map(|node| node.data)
+                    == Some(brute_force(&points, q, 1)[0])
+            })
+            .count();
+
+        assert!(
+            hits as f64 >= 0.9 * queries.len() as f64,
+            "HNSW found only {hits} of {} nearest neighbors",
+            queries.len()
+        );
+    }
+}
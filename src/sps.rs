@@ -1,10 +1,13 @@
 use nalgebra::SVector;
 
+#[cfg(feature = "metrics")]
+use crate::metrics::experiment::GroundTruth;
 use crate::{
     prelude::{Result, Sample},
     structs::{Classifier, Domain},
 };
 
+#[derive(Debug, Clone)]
 pub struct Sphere<const N: usize> {
     center: SVector<f64, N>,
     radius: f64,
@@ -48,6 +51,25 @@ impl<const N: usize> Classifier<N> for Sphere<N> {
     }
 }
 
+#[cfg(feature = "metrics")]
+impl<const N: usize> GroundTruth<N> for Sphere<N> {
+    fn true_normal(&self, p: &SVector<f64, N>) -> SVector<f64, N> {
+        (p - self.center).normalize()
+    }
+
+    /// The volume of the N-ball, ignoring any part that falls outside the domain.
+    fn true_volume(&self) -> f64 {
+        // V(n) = V(n - 2) * 2 pi r^2 / n
+        let r = self.radius;
+        let mut volumes = [1.0, 2.0 * r];
+        for i in 2..=N {
+            volumes[i % 2] *= 2.0 * std::f64::consts::PI * r * r / i as f64;
+        }
+        volumes[N % 2]
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Cube<const N: usize> {
     shape: Domain<N>,
     domain: Option<Domain<N>>,
@@ -86,6 +108,34 @@ impl<const N: usize> Classifier<N> for Cube<N> {
     }
 }
 
+#[cfg(feature = "metrics")]
+impl<const N: usize> GroundTruth<N> for Cube<N> {
+    fn true_normal(&self, p: &SVector<f64, N>) -> SVector<f64, N> {
+        let mut n = SVector::zeros();
+        let mut min_dist = f64::INFINITY;
+        for i in 0..N {
+            let to_low = (p[i] - self.shape.low()[i]).abs();
+            let to_high = (self.shape.high()[i] - p[i]).abs();
+            if to_low < min_dist {
+                min_dist = to_low;
+                n = SVector::zeros();
+                n[i] = -1.0;
+            }
+            if to_high < min_dist {
+                min_dist = to_high;
+                n = SVector::zeros();
+                n[i] = 1.0;
+            }
+        }
+        n
+    }
+
+    fn true_volume(&self) -> f64 {
+        self.shape.volume()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SphereCluster<const N: usize> {
     spheres: Vec<Sphere<N>>,
     domain: Option<Domain<N>>,
@@ -1,5 +1,7 @@
 use nalgebra::SVector;
 use rstar::{primitives::GeomWithData, RTree};
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
 
 use super::{OutOfMode, Sample, WithinMode};
 
@@ -12,6 +14,7 @@ pub type BoundaryRTree<const N: usize> = RTree<KnnNode<N>>;
 /// falls outside of the performance mode. When a boundary pair exists, a boundary
 /// must exist between t and x.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct BoundaryPair<const N: usize> {
     t: WithinMode<N>,
     x: OutOfMode<N>,
@@ -21,6 +24,7 @@ pub struct BoundaryPair<const N: usize> {
 /// describes the location (the boundary point, b) and the direction of the surface
 /// (the ortho[n]ormal surface vector, n).
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct Halfspace<const N: usize> {
     pub b: WithinMode<N>,
     pub n: SVector<f64, N>,
@@ -66,3 +70,65 @@ pub mod backprop {
         fn backprop(&mut self, id: NodeIndex, margin: f64);
     }
 }
+
+#[cfg(all(test, feature = "io"))]
+mod serde_tests {
+    use nalgebra::vector;
+
+    use crate::structs::Domain;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_core_structs_through_json() {
+        let boundary = vec![
+            Halfspace {
+                b: WithinMode(vector![0.25, 0.5, 0.75]),
+                n: vector![0.0, 0.6, 0.8],
+            },
+            Halfspace {
+                b: WithinMode(vector![0.1, 0.2, 0.3]),
+                n: vector![1.0, 0.0, 0.0],
+            },
+        ];
+        let json = serde_json::to_string(&boundary).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<Halfspace<3>>>(&json).unwrap(),
+            boundary
+        );
+
+        let samples = vec![
+            Sample::WithinMode(WithinMode(vector![0.1, 0.2])),
+            Sample::OutOfMode(OutOfMode(vector![0.3, 0.4])),
+        ];
+        let json = serde_json::to_string(&samples).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<Sample<2>>>(&json).unwrap(),
+            samples
+        );
+
+        let pair = BoundaryPair::new(WithinMode(vector![0.1, 0.2]), OutOfMode(vector![0.3, 0.4]));
+        let json = serde_json::to_string(&pair).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BoundaryPair<2>>(&json).unwrap(),
+            pair
+        );
+
+        let domain = Domain::new(vector![0.0, -1.0], vector![2.0, 1.0]);
+        let json = serde_json::to_string(&domain).unwrap();
+        assert_eq!(serde_json::from_str::<Domain<2>>(&json).unwrap(), domain);
+    }
+
+    #[test]
+    fn vectors_serialize_as_flat_arrays() {
+        let hs = Halfspace {
+            b: WithinMode(vector![0.25, 0.5]),
+            n: vector![1.0, 0.0],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&hs).unwrap(),
+            r#"{"b":[0.25,0.5],"n":[1.0,0.0]}"#
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+use super::Sample;
+
+/// Limits on the resources an exploration may spend. Any combination of limits
+/// may be set, and exploration ends once any one of them is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct Budget {
+    /// The number of samples to take.
+    pub max_samples: Option<usize>,
+    /// The number of boundary points to acquire.
+    pub max_boundary_points: Option<usize>,
+    /// The wall-clock time to spend, measured from `Budget::start()`.
+    pub max_duration: Option<Duration>,
+    /// Ends exploration once it stops making progress. See
+    /// `Budget::with_convergence()`.
+    #[cfg_attr(feature = "io", serde(default))]
+    pub convergence: Option<Convergence>,
+}
+
+/// A termination criterion based on the rate at which new boundary points are
+/// acquired, which plateaus as the explored boundary approaches full coverage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct Convergence {
+    /// The number of most recent samples the rate is measured over.
+    pub window: usize,
+    /// The rate of new boundary points per sample below which exploration is
+    /// considered converged.
+    pub min_rate: f64,
+}
+
+/// The limit of a Budget that was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub enum BudgetExhausted {
+    MaxSamples,
+    MaxBoundaryPoints,
+    MaxDuration,
+    Converged,
+}
+
+/// The outcome of a budgeted exploration step. See `Explorer::step_within()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOutcome<const N: usize> {
+    /// A sample was taken.
+    Sampled(Sample<N>),
+    /// The explorer ran out of boundary to explore.
+    Complete,
+    /// The budget was exhausted before the step was taken.
+    Terminated(BudgetExhausted),
+}
+
+/// Tracks the resources spent against a Budget.
+#[derive(Debug, Clone)]
+pub struct BudgetTracker {
+    budget: Budget,
+    start: Instant,
+    n_samples: usize,
+    /// The sample and boundary counts at each of the most recent records, for
+    /// measuring convergence.
+    b_counts: VecDeque<(usize, usize)>,
+}
+
+impl Budget {
+    /// A budget without limits.
+    pub fn new() -> Self {
+        Budget::default()
+    }
+
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        self.max_samples = Some(max_samples);
+        self
+    }
+
+    pub fn with_max_boundary_points(mut self, max_boundary_points: usize) -> Self {
+        self.max_boundary_points = Some(max_boundary_points);
+        self
+    }
+
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Ends exploration once fewer than @min_rate new boundary points per sample
+    /// were acquired over the last @window samples, i.e. once coverage plateaus,
+    /// rather than after a fixed number of samples. Exploration that has yet to
+    /// take @window samples never converges.
+    /// ## Arguments
+    /// * window : The number of most recent samples to measure the rate over.
+    ///   Should span several adherences, e.g. 20x the typical samples per
+    ///   boundary point.
+    /// * min_rate : The rate below which exploration has converged, 0 < min_rate
+    ///   <= 1.
+    pub fn with_convergence(mut self, window: usize, min_rate: f64) -> Self {
+        assert!(window > 0, "Window must be non-zero!");
+        assert!(
+            0.0 < min_rate && min_rate <= 1.0,
+            "Minimum rate must be in (0, 1]!"
+        );
+        self.convergence = Some(Convergence { window, min_rate });
+        self
+    }
+
+    /// Starts spending the budget, beginning the wall-clock timer.
+    pub fn start(self) -> BudgetTracker {
+        BudgetTracker {
+            budget: self,
+            start: Instant::now(),
+            n_samples: 0,
+            b_counts: VecDeque::new(),
+        }
+    }
+}
+
+impl BudgetTracker {
+    pub fn budget(&self) -> &Budget {
+        &self.budget
+    }
+
+    /// The number of samples spent so far.
+    pub fn n_samples(&self) -> usize {
+        self.n_samples
+    }
+
+    /// The time elapsed since the budget was started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Records a sample against the budget.
+    pub fn spend_sample(&mut self) {
+        self.n_samples += 1;
+    }
+
+    /// Records the boundary count after the samples spent so far, from which
+    /// convergence is measured. May be recorded once per sample or once per batch
+    /// of samples. Only needed if the budget has a convergence criterion.
+    pub fn record_boundary_count(&mut self, b_count: usize) {
+        let Some(convergence) = self.budget.convergence else {
+            return;
+        };
+
+        self.b_counts.push_back((self.n_samples, b_count));
+        // Keeps the most recent record that is at least a window old.
+        while self
+            .b_counts
+            .get(1)
+            .is_some_and(|(n, _)| self.n_samples - n >= convergence.window)
+        {
+            self.b_counts.pop_front();
+        }
+    }
+
+    /// The rate of new boundary points per sample over the convergence window, or
+    /// None if the window has yet to be filled.
+    pub fn progress_rate(&self) -> Option<f64> {
+        let window = self.budget.convergence?.window;
+        let (n0, b0) = *self.b_counts.front()?;
+        let (n1, b1) = *self.b_counts.back()?;
+        if n1 - n0 < window {
+            return None;
+        }
+
+        Some(b1.saturating_sub(b0) as f64 / (n1 - n0) as f64)
+    }
+
+    /// Checks whether any limit has been reached.
+    /// ## Arguments
+    /// * b_count : The number of boundary points acquired so far.
+    /// ## Return
+    /// * Some(limit) : The first limit found to be reached.
+    /// * None : If the budget has not been exhausted.
+    pub fn exhausted(&self, b_count: usize) -> Option<BudgetExhausted> {
+        if self
+            .budget
+            .max_samples
+            .is_some_and(|max| self.n_samples >= max)
+        {
+            Some(BudgetExhausted::MaxSamples)
+        } else if self
+            .budget
+            .max_boundary_points
+            .is_some_and(|max| b_count >= max)
+        {
+            Some(BudgetExhausted::MaxBoundaryPoints)
+        } else if self
+            .budget
+            .max_duration
+            .is_some_and(|max| self.elapsed() >= max)
+        {
+            Some(BudgetExhausted::MaxDuration)
+        } else if self
+            .budget
+            .convergence
+            .zip(self.progress_rate())
+            .is_some_and(|(convergence, rate)| rate < convergence.min_rate)
+        {
+            Some(BudgetExhausted::Converged)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod budget_tests {
+    use super::*;
+
+    #[test]
+    fn converges_once_progress_plateaus() {
+        let mut tracker = Budget::new().with_convergence(4, 0.5).start();
+
+        for b_count in [1, 2, 3, 4, 5, 5, 5] {
+            assert_eq!(tracker.exhausted(b_count), None);
+            tracker.spend_sample();
+            tracker.record_boundary_count(b_count);
+        }
+        assert_eq!(tracker.progress_rate(), Some(0.5));
+
+        tracker.spend_sample();
+        tracker.record_boundary_count(5);
+        assert_eq!(tracker.progress_rate(), Some(0.25));
+        assert_eq!(tracker.exhausted(5), Some(BudgetExhausted::Converged));
+    }
+
+    #[test]
+    fn measures_progress_over_batches_of_samples() {
+        let mut tracker = Budget::new().with_convergence(4, 0.5).start();
+        tracker.record_boundary_count(0);
+
+        for b_count in [1, 2] {
+            for _ in 0..3 {
+                tracker.spend_sample();
+            }
+            tracker.record_boundary_count(b_count);
+        }
+        // The most recent record at least 4 samples old is the start.
+        assert_eq!(tracker.progress_rate(), Some(2.0 / 6.0));
+
+        for _ in 0..3 {
+            tracker.spend_sample();
+        }
+        tracker.record_boundary_count(5);
+        assert_eq!(tracker.progress_rate(), Some(4.0 / 6.0));
+    }
+
+    #[test]
+    fn without_convergence_progress_is_not_tracked() {
+        let mut tracker = Budget::new().start();
+        for _ in 0..10 {
+            tracker.spend_sample();
+            tracker.record_boundary_count(0);
+        }
+
+        assert_eq!(tracker.progress_rate(), None);
+        assert_eq!(tracker.exhausted(0), None);
+    }
+}
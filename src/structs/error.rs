@@ -5,8 +5,26 @@ pub enum SamplingError {
     OutOfBounds,
     MaxSamplesExceeded,
     InvalidClassifierResponse(String),
+    /// The connection to a remote classifier was lost. The request can be retried
+    /// once the connection is re-established.
+    Disconnected,
+    /// The remote classifier did not respond within the configured timeout.
+    Timeout,
+    /// The classification has been requested but not yet provided, e.g. by an
+    /// AskTellExplorer's caller. The request can be retried once it is provided.
+    Pending,
 }
 
+impl SamplingError {
+    /// Whether the request can be retried, in which case explorers keep the active
+    /// path for the next step.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, SamplingError::Disconnected | SamplingError::Pending)
+    }
+}
+
+/// An error from invalid parameters, e.g. when configuring an adherer.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParameterError {
     Invalid(String),
     OutOfRange,
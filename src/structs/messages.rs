@@ -1,3 +1,8 @@
+use std::{fmt, time::Duration};
+
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
 pub const MSG_OK: &str = "OK";
 pub const MSG_ERR: &str = "ERROR";
 pub const MSG_END: &str = "END";
@@ -5,3 +10,228 @@ pub const MSG_CONTINUE: &str = "CONT";
 pub const MSG_PHASE_GLOBAL_SEARCH: &str = "GS";
 pub const MSG_PHASE_SURFACE_SEARCH: &str = "SS";
 pub const MSG_PHASE_BOUNDARY_EXPL: &str = "BE";
+pub const MSG_PING: &str = "PING";
+pub const MSG_PONG: &str = "PONG";
+pub const MSG_REACQUIRE: &str = "REACQ";
+pub const MSG_PROGRESS: &str = "PROGRESS";
+pub const MSG_MODE_BEGIN: &str = "MODE_BEGIN";
+pub const MSG_MODE_END: &str = "MODE_END";
+
+/// The exploration phase SEMBAS is in, sent to the FUT prior to each request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub enum Phase {
+    GlobalSearch,
+    SurfaceSearch,
+    BoundaryExploration,
+}
+
+/// A message exchanged between SEMBAS and the FUT during a `SembasSession`.
+/// Messages outside of the known set are preserved as Custom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub enum SessionMessage {
+    Phase(Phase),
+    Reacquire,
+    Continue,
+    End,
+    Progress(Progress),
+    /// The FUT should switch to the performance mode with the given id, sent as
+    /// `MODE_BEGIN <id>`.
+    ModeBegin(String),
+    /// The performance mode with the given id is complete, sent as `MODE_END <id>`.
+    ModeEnd(String),
+    Custom(String),
+}
+
+/// A progress update pushed to the FUT during a `SembasSession`, e.g. for display
+/// on a dashboard. Sent as a single line of space separated key=value pairs:
+/// `PROGRESS phase=BE b_count=120 n_samples=5000 eta=35.5`, where eta (seconds)
+/// is omitted when unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct Progress {
+    /// The wire representation of the current phase.
+    pub phase: String,
+    /// The number of boundary points found so far.
+    pub b_count: usize,
+    /// The number of samples classified so far.
+    pub n_samples: usize,
+    /// The estimated time remaining, if known.
+    pub eta: Option<Duration>,
+}
+
+impl Progress {
+    /// Parses a progress message, returning None if @msg is not a valid progress
+    /// message.
+    pub fn parse(msg: &str) -> Option<Self> {
+        let mut tokens = msg.split_whitespace();
+        if tokens.next()? != MSG_PROGRESS {
+            return None;
+        }
+
+        let (mut phase, mut b_count, mut n_samples, mut eta) = (None, None, None, None);
+        for token in tokens {
+            let (key, value) = token.split_once('=')?;
+            match key {
+                "phase" => phase = Some(value.to_string()),
+                "b_count" => b_count = Some(value.parse().ok()?),
+                "n_samples" => n_samples = Some(value.parse().ok()?),
+                "eta" => eta = Some(Duration::try_from_secs_f64(value.parse().ok()?).ok()?),
+                _ => (),
+            }
+        }
+
+        Some(Progress {
+            phase: phase?,
+            b_count: b_count?,
+            n_samples: n_samples?,
+            eta,
+        })
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{MSG_PROGRESS} phase={} b_count={} n_samples={}",
+            self.phase, self.b_count, self.n_samples
+        )?;
+        if let Some(eta) = self.eta {
+            write!(f, " eta={}", eta.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
+
+impl Phase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::GlobalSearch => MSG_PHASE_GLOBAL_SEARCH,
+            Phase::SurfaceSearch => MSG_PHASE_SURFACE_SEARCH,
+            Phase::BoundaryExploration => MSG_PHASE_BOUNDARY_EXPL,
+        }
+    }
+}
+
+impl SessionMessage {
+    /// The wire representation of the message.
+    pub fn to_wire(&self) -> String {
+        match self {
+            SessionMessage::Progress(progress) => progress.to_string(),
+            SessionMessage::ModeBegin(id) => format!("{MSG_MODE_BEGIN} {id}"),
+            SessionMessage::ModeEnd(id) => format!("{MSG_MODE_END} {id}"),
+            msg => msg.as_str().to_string(),
+        }
+    }
+
+    /// The wire representation of the message, or the message type for messages
+    /// that carry data (e.g. Progress). See `to_wire()`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SessionMessage::Phase(phase) => phase.as_str(),
+            SessionMessage::Reacquire => MSG_REACQUIRE,
+            SessionMessage::Continue => MSG_CONTINUE,
+            SessionMessage::End => MSG_END,
+            SessionMessage::Progress(_) => MSG_PROGRESS,
+            SessionMessage::ModeBegin(_) => MSG_MODE_BEGIN,
+            SessionMessage::ModeEnd(_) => MSG_MODE_END,
+            SessionMessage::Custom(msg) => msg,
+        }
+    }
+}
+
+impl From<&str> for SessionMessage {
+    fn from(msg: &str) -> Self {
+        match msg {
+            MSG_PHASE_GLOBAL_SEARCH => SessionMessage::Phase(Phase::GlobalSearch),
+            MSG_PHASE_SURFACE_SEARCH => SessionMessage::Phase(Phase::SurfaceSearch),
+            MSG_PHASE_BOUNDARY_EXPL => SessionMessage::Phase(Phase::BoundaryExploration),
+            MSG_REACQUIRE => SessionMessage::Reacquire,
+            MSG_CONTINUE => SessionMessage::Continue,
+            MSG_END => SessionMessage::End,
+            _ => match msg.split_once(' ') {
+                Some((MSG_MODE_BEGIN, id)) if is_mode_id(id) => {
+                    SessionMessage::ModeBegin(id.to_string())
+                }
+                Some((MSG_MODE_END, id)) if is_mode_id(id) => {
+                    SessionMessage::ModeEnd(id.to_string())
+                }
+                _ => match Progress::parse(msg) {
+                    Some(progress) => SessionMessage::Progress(progress),
+                    None => SessionMessage::Custom(msg.to_string()),
+                },
+            },
+        }
+    }
+}
+
+/// Whether @id can be sent as a performance mode id, i.e. it is non-empty and
+/// contains no whitespace.
+pub fn is_mode_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains(char::is_whitespace)
+}
+
+impl From<String> for SessionMessage {
+    fn from(msg: String) -> Self {
+        SessionMessage::from(msg.as_str())
+    }
+}
+
+impl From<Phase> for SessionMessage {
+    fn from(phase: Phase) -> Self {
+        SessionMessage::Phase(phase)
+    }
+}
+
+impl fmt::Display for SessionMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_wire())
+    }
+}
+
+#[cfg(test)]
+mod session_messages {
+    use super::*;
+
+    #[test]
+    fn messages_survive_wire_round_trip() {
+        let messages = [
+            SessionMessage::Phase(Phase::GlobalSearch),
+            SessionMessage::Phase(Phase::SurfaceSearch),
+            SessionMessage::Phase(Phase::BoundaryExploration),
+            SessionMessage::Reacquire,
+            SessionMessage::Continue,
+            SessionMessage::End,
+            SessionMessage::Progress(Progress {
+                phase: MSG_PHASE_BOUNDARY_EXPL.to_string(),
+                b_count: 120,
+                n_samples: 5000,
+                eta: Some(Duration::from_millis(35500)),
+            }),
+            SessionMessage::Progress(Progress {
+                phase: MSG_PHASE_GLOBAL_SEARCH.to_string(),
+                b_count: 0,
+                n_samples: 12,
+                eta: None,
+            }),
+            SessionMessage::ModeBegin("lane_keeping".to_string()),
+            SessionMessage::ModeEnd("lane_keeping".to_string()),
+            SessionMessage::Custom("RESET".to_string()),
+        ];
+
+        for msg in messages {
+            assert_eq!(SessionMessage::from(msg.to_wire()), msg);
+        }
+    }
+
+    #[test]
+    fn malformed_progress_is_custom() {
+        let msg = "PROGRESS phase=BE b_count=many";
+        assert_eq!(
+            SessionMessage::from(msg),
+            SessionMessage::Custom(msg.to_string())
+        );
+    }
+}
@@ -1,7 +0,0 @@
-pub const MSG_OK: &str = "OK";
-pub const MSG_ERR: &str = "ERROR";
-pub const MSG_END: &str = "END";
-pub const MSG_CONTINUE: &str = "CONT";
-pub const MSG_PHASE_GLOBAL_SEARCH: &str = "GS";
-pub const MSG_PHASE_SURFACE_SEARCH: &str = "SS";
-pub const MSG_PHASE_BOUNDARY_EXPL: &str = "BE";
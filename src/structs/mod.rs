@@ -1,17 +1,33 @@
 pub mod boundary;
+pub mod budget;
 pub mod error;
 #[cfg(feature = "api")]
-pub mod messagse;
+pub mod messages;
+/// Misspelled alias of `messages`, kept so existing imports continue to compile.
+#[cfg(feature = "api")]
+#[deprecated(note = "use `structs::messages` instead")]
+pub mod messagse {
+    pub use super::messages::*;
+}
+pub mod parameter_space;
+pub mod region;
 pub mod report;
 pub mod sampling;
+pub mod subspace;
 
 pub use boundary::*;
+pub use budget::*;
 pub use error::*;
+pub use parameter_space::*;
+pub use region::*;
 pub use sampling::*;
 
 use core::fmt;
+use std::iter::Filter;
 
 use nalgebra::{Const, OMatrix, SVector};
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
 
 use crate::utils::vector_to_string;
 
@@ -27,6 +43,7 @@ pub struct Span<const N: usize> {
 /// and high). Used to define a valid input region to sample from for a system under
 /// test.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct Domain<const N: usize> {
     low: SVector<f64, N>,
     high: SVector<f64, N>,
@@ -208,6 +225,12 @@ impl<const N: usize> Domain<N> {
     }
 
     pub fn clip_vector(&self, p: &SVector<f64, N>) -> SVector<f64, N> {
+        self.clamp(p)
+    }
+
+    /// The nearest point within the domain to @p, i.e. @p with each component
+    /// clamped to the domain's bounds.
+    pub fn clamp(&self, p: &SVector<f64, N>) -> SVector<f64, N> {
         SVector::<f64, N>::from_iterator(self.low.iter().zip(self.high.iter()).zip(p.iter()).map(
             |((li, hi), pi)| {
                 if pi < li {
@@ -220,8 +243,102 @@ impl<const N: usize> Domain<N> {
             },
         ))
     }
+
+    /// The points of an evenly spaced lattice across the domain, e.g. for
+    /// rasterizing predictions or exhaustively testing a low-dimensional FUT.
+    /// ## Arguments
+    /// * resolution : The number of points along each dimension, including both of
+    ///   its bounds. A resolution of 1 yields only the center of the domain.
+    /// ## Returns
+    /// * grid : An iterator over the resolution^N points, varying the first
+    ///   dimension fastest.
+    pub fn grid(&self, resolution: usize) -> DomainGrid<N> {
+        DomainGrid {
+            low: self.low,
+            step: if resolution > 1 {
+                self.dimensions() / (resolution - 1) as f64
+            } else {
+                SVector::zeros()
+            },
+            offset: if resolution == 1 {
+                self.dimensions() / 2.0
+            } else {
+                SVector::zeros()
+            },
+            resolution,
+            index: 0,
+            len: resolution.pow(N as u32),
+        }
+    }
+
+    /// The points of `grid()` for which @mask is true, e.g. to skip a region that
+    /// is already known or infeasible.
+    pub fn grid_masked<P>(&self, resolution: usize, mask: P) -> Filter<DomainGrid<N>, P>
+    where
+        P: FnMut(&SVector<f64, N>) -> bool,
+    {
+        self.grid(resolution).filter(mask)
+    }
+
+    /// Slides the displacement @v from @p along the walls of the domain, rather than
+    /// through them. Components of @v that would carry the point past a wall are
+    /// shortened to end on that wall, so a displacement from a point on a wall
+    /// loses its outward component and travels along the wall instead.
+    /// ## Arguments
+    /// * p : The point the displacement starts from, which should be within the
+    ///   domain.
+    /// * v : The displacement to project.
+    /// ## Returns
+    /// * v' : The projected displacement, where @p + v' is within the domain.
+    ///   Equal to @v if @p + @v is already within the domain.
+    pub fn project_onto_surface(
+        &self,
+        p: &SVector<f64, N>,
+        v: &SVector<f64, N>,
+    ) -> SVector<f64, N> {
+        self.clamp(&(p + v)) - p
+    }
 }
 
+/// An iterator over the points of an evenly spaced lattice across a domain. See
+/// `Domain::grid()`.
+#[derive(Debug, Clone)]
+pub struct DomainGrid<const N: usize> {
+    low: SVector<f64, N>,
+    step: SVector<f64, N>,
+    offset: SVector<f64, N>,
+    resolution: usize,
+    index: usize,
+    len: usize,
+}
+
+impl<const N: usize> Iterator for DomainGrid<N> {
+    type Item = SVector<f64, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let mut rem = self.index;
+        let p = SVector::<f64, N>::from_fn(|i, _| {
+            let k = rem % self.resolution;
+            rem /= self.resolution;
+            self.low[i] + self.offset[i] + k as f64 * self.step[i]
+        });
+        self.index += 1;
+
+        Some(p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for DomainGrid<N> {}
+
 impl<const N: usize> fmt::Display for Span<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -418,6 +535,90 @@ mod domain_tests {
         assert!(p == d.high)
     }
 
+    #[test]
+    fn clamp_keeps_in_domain_point() {
+        let d = Domain::<3>::normalized();
+        let p = vector![0.2, 0.5, 0.9];
+
+        assert_eq!(d.clamp(&p), p);
+    }
+
+    #[test]
+    fn clamp_finds_nearest_point_in_domain() {
+        let d = Domain::<3>::normalized();
+        let p = vector![-0.5, 0.5, 1.5];
+
+        assert_eq!(d.clamp(&p), vector![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn projection_within_domain_is_unchanged() {
+        let d = Domain::<2>::normalized();
+        let p = vector![0.5, 0.5];
+        let v = vector![0.1, -0.2];
+
+        assert!(is_near(&d.project_onto_surface(&p, &v), &v, ATOL));
+    }
+
+    #[test]
+    fn projection_slides_along_wall() {
+        let d = Domain::<2>::normalized();
+        let p = vector![1.0, 0.5];
+        let v = vector![0.1, 0.1];
+
+        let v1 = d.project_onto_surface(&p, &v);
+
+        assert!(is_near(&v1, &vector![0.0, 0.1], ATOL));
+        assert!(d.contains(&(p + v1)));
+    }
+
+    #[test]
+    fn projection_stops_at_wall() {
+        let d = Domain::<2>::normalized();
+        let p = vector![0.95, 0.05];
+        let v = vector![0.1, -0.1];
+
+        let v1 = d.project_onto_surface(&p, &v);
+
+        assert!(is_near(&(p + v1), &vector![1.0, 0.0], ATOL));
+    }
+
+    #[test]
+    fn grid_spans_domain() {
+        let d = Domain::<2>::new(vector![1.0, -1.0], vector![2.0, 1.0]);
+        let grid = d.grid(3);
+        assert_eq!(grid.len(), 9);
+
+        let points: Vec<_> = grid.collect();
+        assert_eq!(points[0], vector![1.0, -1.0]);
+        assert_eq!(points[1], vector![1.5, -1.0]);
+        assert_eq!(points[3], vector![1.0, 0.0]);
+        assert_eq!(points[8], vector![2.0, 1.0]);
+        assert!(points.iter().all(|p| d.contains(p)));
+    }
+
+    #[test]
+    fn grid_of_one_is_center() {
+        let d = Domain::<3>::normalized();
+        let points: Vec<_> = d.grid(1).collect();
+
+        assert_eq!(points, vec![vector![0.5, 0.5, 0.5]]);
+    }
+
+    #[test]
+    fn grid_of_zero_is_empty() {
+        assert_eq!(Domain::<3>::normalized().grid(0).count(), 0);
+    }
+
+    #[test]
+    fn masked_grid_skips_points() {
+        let d = Domain::<2>::normalized();
+        let points: Vec<_> = d.grid_masked(5, |p| p[0] < 0.5).collect();
+
+        assert_eq!(points.len(), 10);
+        assert!(points.iter().all(|p| p[0] < 0.5));
+    }
+
     #[test]
     fn clip_vector_keeps_in_domain_elements() {
         let d = Domain::<3>::new(vector![4.0, 2.5, 6.0], vector![1.0, 5.0, 3.5]);
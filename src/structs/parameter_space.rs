@@ -0,0 +1,200 @@
+use std::fmt;
+
+use nalgebra::SVector;
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
+
+use super::Domain;
+
+/// A named input dimension of the system under test, with its unit and physical
+/// bounds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct Parameter {
+    pub name: String,
+    pub unit: String,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Parameter {
+    pub fn new(name: &str, unit: &str, low: f64, high: f64) -> Self {
+        Parameter {
+            name: name.to_string(),
+            unit: unit.to_string(),
+            low,
+            high,
+        }
+    }
+}
+
+/// The value of a named parameter, e.g. for reports. Displayed as `name=value`
+/// followed by the unit, if any, e.g. `speed=12.5m/s`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct NamedValue {
+    pub name: String,
+    pub unit: String,
+    pub value: f64,
+}
+
+impl fmt::Display for NamedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}{}", self.name, self.value, self.unit)
+    }
+}
+
+/// Names each dimension of an input space and carries its unit and physical
+/// bounds. Exploration takes place in normalized coordinates, i.e.
+/// `Domain::normalized()`, which the space converts to and from physical values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSpace<const N: usize> {
+    parameters: Vec<Parameter>,
+    domain: Domain<N>,
+}
+
+impl<const N: usize> ParameterSpace<N> {
+    /// ## Panic
+    /// * When two parameters share a name.
+    pub fn new(parameters: [Parameter; N]) -> Self {
+        for (i, p) in parameters.iter().enumerate() {
+            assert!(
+                parameters[..i].iter().all(|q| q.name != p.name),
+                "Duplicate parameter name: {}",
+                p.name
+            );
+        }
+
+        let domain = Domain::new(
+            SVector::from_fn(|i, _| parameters[i].low),
+            SVector::from_fn(|i, _| parameters[i].high),
+        );
+
+        ParameterSpace {
+            parameters: parameters.into(),
+            domain,
+        }
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// The physical bounds of the space.
+    pub fn domain(&self) -> &Domain<N> {
+        &self.domain
+    }
+
+    /// The index of the dimension named @name, if any.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.parameters.iter().position(|p| p.name == name)
+    }
+
+    /// Converts a point in physical units to normalized coordinates.
+    pub fn to_normalized(&self, p: &SVector<f64, N>) -> SVector<f64, N> {
+        Domain::project_point_domains(p, &self.domain, &Domain::normalized())
+    }
+
+    /// Converts a point in normalized coordinates to physical units.
+    pub fn to_physical(&self, q: &SVector<f64, N>) -> SVector<f64, N> {
+        Domain::project_point_domains(q, &Domain::normalized(), &self.domain)
+    }
+
+    /// Names the values of a normalized point in physical units.
+    /// ## Returns
+    /// * values : The physical value of each dimension, in dimension order.
+    pub fn named(&self, q: &SVector<f64, N>) -> Vec<NamedValue> {
+        let p = self.to_physical(q);
+        self.parameters
+            .iter()
+            .zip(p.iter())
+            .map(|(param, &value)| NamedValue {
+                name: param.name.clone(),
+                unit: param.unit.clone(),
+                value,
+            })
+            .collect()
+    }
+
+    /// Formats a normalized point as space separated `name=value` pairs in physical
+    /// units, e.g. for messages to the FUT: `speed=12.5 heading=0.3`.
+    pub fn to_wire(&self, q: &SVector<f64, N>) -> String {
+        self.named(q)
+            .iter()
+            .map(|v| format!("{}={}", v.name, v.value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Parses space separated `name=value` pairs in physical units, in any order,
+    /// as formatted by `to_wire()`.
+    /// ## Returns
+    /// * q : The normalized point, or None if a parameter is missing, unknown or
+    ///   not a number.
+    pub fn parse_wire(&self, msg: &str) -> Option<SVector<f64, N>> {
+        let mut values: [Option<f64>; N] = [None; N];
+        for token in msg.split_whitespace() {
+            let (name, value) = token.split_once('=')?;
+            values[self.index_of(name)?] = Some(value.parse().ok()?);
+        }
+
+        let p = SVector::from_iterator(values.into_iter().collect::<Option<Vec<f64>>>()?);
+        Some(self.to_normalized(&p))
+    }
+}
+
+#[cfg(test)]
+mod parameter_space_tests {
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn space() -> ParameterSpace<2> {
+        ParameterSpace::new([
+            Parameter::new("speed", "m/s", 10.0, 30.0),
+            Parameter::new("heading", "rad", -0.5, 0.5),
+        ])
+    }
+
+    #[test]
+    fn converts_between_physical_and_normalized() {
+        let space = space();
+        let q = vector![0.25, 0.5];
+        let p = space.to_physical(&q);
+
+        assert!((p - vector![15.0, 0.0]).norm() < 1e-10);
+        assert!((space.to_normalized(&p) - q).norm() < 1e-10);
+        assert_eq!(space.index_of("heading"), Some(1));
+        assert_eq!(space.index_of("pitch"), None);
+    }
+
+    #[test]
+    fn names_values_in_physical_units() {
+        let space = space();
+        let named = space.named(&vector![0.25, 1.0]);
+
+        assert_eq!(named[0].to_string(), "speed=15m/s");
+        assert_eq!(named[1].to_string(), "heading=0.5rad");
+        assert_eq!(space.to_wire(&vector![0.25, 1.0]), "speed=15 heading=0.5");
+    }
+
+    #[test]
+    fn parses_wire_format() {
+        let space = space();
+        let q = space.parse_wire("heading=-0.5 speed=20").unwrap();
+
+        assert!((q - vector![0.5, 0.0]).norm() < 1e-10);
+        assert_eq!(space.parse_wire("speed=20"), None);
+        assert_eq!(space.parse_wire("speed=20 heading=0 pitch=1"), None);
+        assert_eq!(space.parse_wire("speed=fast heading=0"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate parameter name")]
+    fn rejects_duplicate_names() {
+        ParameterSpace::new([
+            Parameter::new("speed", "m/s", 0.0, 1.0),
+            Parameter::new("speed", "km/h", 0.0, 1.0),
+        ]);
+    }
+}
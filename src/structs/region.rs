@@ -0,0 +1,238 @@
+use nalgebra::{Const, OMatrix, SVector};
+
+use super::{Domain, Result, SamplingError};
+
+/// A valid input region of a system under test, e.g. an axis-aligned Domain or an
+/// ObliqueDomain. Describes where samples may be taken.
+pub trait Region<const N: usize> {
+    /// Checks if the given vector is within the region.
+    fn contains(&self, p: &SVector<f64, N>) -> bool;
+
+    /// Finds the distance between the edge of the region from a point in the
+    /// direction of the provided vector.
+    /// ## Arguments
+    /// * p: A point that the ray starts from
+    /// * v: The direction the ray travels
+    /// ## Returns
+    /// * t: The linear distance between p and the edge of the region in the
+    ///   direction v
+    /// ## Error (Err)
+    /// * OutOfBounds : If @p falls outside of the region.
+    fn distance_to_edge(&self, p: &SVector<f64, N>, v: &SVector<f64, N>) -> Result<f64>;
+
+    /// The smallest axis-aligned Domain that contains the region, e.g. to draw
+    /// samples from.
+    fn bounding_box(&self) -> Domain<N>;
+}
+
+impl<const N: usize> Region<N> for Domain<N> {
+    fn contains(&self, p: &SVector<f64, N>) -> bool {
+        Domain::contains(self, p)
+    }
+
+    fn distance_to_edge(&self, p: &SVector<f64, N>, v: &SVector<f64, N>) -> Result<f64> {
+        Domain::distance_to_edge(self, p, v)
+    }
+
+    fn bounding_box(&self) -> Domain<N> {
+        self.clone()
+    }
+}
+
+/// Tolerance on the faces of an ObliqueDomain, so that points on a face are within
+/// the region despite round-off.
+const FACE_TOLERANCE: f64 = 1e-12;
+
+/// A convex polytope, described by the intersection of halfspaces n . p <= offset,
+/// e.g. a rotated or sheared box. Used when the valid input region is not aligned
+/// with the input dimensions, such as a rotated slab.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObliqueDomain<const N: usize> {
+    faces: Vec<(SVector<f64, N>, f64)>,
+    bounds: Domain<N>,
+}
+
+impl<const N: usize> ObliqueDomain<N> {
+    /// Returns the region within @bounds that satisfies every constraint.
+    /// ## Arguments
+    /// * bounds : An axis-aligned domain that limits the region.
+    /// * constraints : Each (n, offset) limits the region to n . p <= offset.
+    pub fn from_constraints(bounds: Domain<N>, constraints: &[(SVector<f64, N>, f64)]) -> Self {
+        let mut faces = vec![];
+        for i in 0..N {
+            let mut n = SVector::zeros();
+            n[i] = 1.0;
+            faces.push((n, bounds.high()[i]));
+            faces.push((-n, -bounds.low()[i]));
+        }
+        faces.extend(
+            constraints
+                .iter()
+                .map(|(n, offset)| (n / n.norm(), offset / n.norm())),
+        );
+
+        ObliqueDomain { faces, bounds }
+    }
+
+    /// Returns the image of @domain under the affine transform p = @transform * q +
+    /// @translation, e.g. a rotated box.
+    /// ## Panic
+    /// * When @transform is not invertible.
+    pub fn from_affine(
+        domain: &Domain<N>,
+        transform: OMatrix<f64, Const<N>, Const<N>>,
+        translation: SVector<f64, N>,
+    ) -> Self {
+        let inverse = transform
+            .try_inverse()
+            .expect("The transform must be invertible!");
+
+        // low_i <= r_i . (p - translation) <= high_i, for each row r_i of the inverse
+        let mut faces = vec![];
+        for i in 0..N {
+            let r: SVector<f64, N> = inverse.row(i).transpose();
+            let (norm, shift) = (r.norm(), r.dot(&translation));
+            faces.push((r / norm, (domain.high()[i] + shift) / norm));
+            faces.push((-r / norm, -(domain.low()[i] + shift) / norm));
+        }
+
+        let low = SVector::from_fn(|j, _| {
+            (0..N)
+                .map(|k| {
+                    let m = transform[(j, k)];
+                    (m * domain.low()[k]).min(m * domain.high()[k])
+                })
+                .sum::<f64>()
+                + translation[j]
+        });
+        let high = SVector::from_fn(|j, _| {
+            (0..N)
+                .map(|k| {
+                    let m = transform[(j, k)];
+                    (m * domain.low()[k]).max(m * domain.high()[k])
+                })
+                .sum::<f64>()
+                + translation[j]
+        });
+
+        ObliqueDomain {
+            faces,
+            bounds: Domain::new(low, high),
+        }
+    }
+
+    /// The unit normal and offset of each face, n . p <= offset.
+    pub fn faces(&self) -> &[(SVector<f64, N>, f64)] {
+        &self.faces
+    }
+}
+
+impl<const N: usize> Region<N> for ObliqueDomain<N> {
+    fn contains(&self, p: &SVector<f64, N>) -> bool {
+        self.faces
+            .iter()
+            .all(|(n, offset)| n.dot(p) <= offset + FACE_TOLERANCE)
+    }
+
+    fn distance_to_edge(&self, p: &SVector<f64, N>, v: &SVector<f64, N>) -> Result<f64> {
+        if !self.contains(p) {
+            return Err(SamplingError::OutOfBounds);
+        }
+
+        self.faces
+            .iter()
+            .filter_map(|(n, offset)| {
+                let rate = n.dot(v);
+                (rate > 0.0).then(|| ((offset - n.dot(p)) / rate).max(0.0))
+            })
+            .min_by(|a, b| a.total_cmp(b))
+            .ok_or(SamplingError::OutOfBounds)
+    }
+
+    fn bounding_box(&self) -> Domain<N> {
+        self.bounds.clone()
+    }
+}
+
+#[cfg(test)]
+mod oblique_domain_tests {
+    use std::f64::consts::FRAC_PI_4;
+
+    use nalgebra::{vector, Rotation2};
+
+    use super::*;
+
+    /// A 1 x 0.2 slab centered on (0.5, 0.5), rotated by 45 degrees.
+    fn rotated_slab() -> ObliqueDomain<2> {
+        ObliqueDomain::from_affine(
+            &Domain::new(vector![-0.5, -0.1], vector![0.5, 0.1]),
+            *Rotation2::new(FRAC_PI_4).matrix(),
+            vector![0.5, 0.5],
+        )
+    }
+
+    #[test]
+    fn rotated_slab_contains_its_interior() {
+        let slab = rotated_slab();
+
+        assert!(slab.contains(&vector![0.5, 0.5]));
+        assert!(slab.contains(&vector![0.8, 0.8]));
+        assert!(!slab.contains(&vector![0.9, 0.1]));
+        // Within the bounding box, but outside of the slab
+        assert!(slab.bounding_box().contains(&vector![0.8, 0.5]));
+        assert!(!slab.contains(&vector![0.8, 0.5]));
+    }
+
+    #[test]
+    fn distance_to_edge_of_rotated_slab() {
+        let slab = rotated_slab();
+        let c = vector![0.5, 0.5];
+        let (along, across) = (
+            vector![1.0, 1.0].normalize(),
+            vector![-1.0, 1.0].normalize(),
+        );
+
+        assert!((slab.distance_to_edge(&c, &along).unwrap() - 0.5).abs() < 1e-10);
+        assert!((slab.distance_to_edge(&c, &across).unwrap() - 0.1).abs() < 1e-10);
+        assert!((slab.distance_to_edge(&c, &(2.0 * across)).unwrap() - 0.05).abs() < 1e-10);
+        assert_eq!(
+            slab.distance_to_edge(&vector![0.9, 0.1], &along),
+            Err(SamplingError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn bounding_box_of_rotated_slab() {
+        let half_width = 0.6 * FRAC_PI_4.cos();
+        let bounds = rotated_slab().bounding_box();
+
+        assert!((bounds.low() - vector![0.5 - half_width, 0.5 - half_width]).norm() < 1e-10);
+        assert!((bounds.high() - vector![0.5 + half_width, 0.5 + half_width]).norm() < 1e-10);
+    }
+
+    #[test]
+    fn constraints_cut_bounds() {
+        // The lower triangle of the unit square
+        let triangle =
+            ObliqueDomain::from_constraints(Domain::normalized(), &[(vector![-2.0, 2.0], 0.0)]);
+
+        assert!(triangle.contains(&vector![0.7, 0.2]));
+        assert!(!triangle.contains(&vector![0.2, 0.7]));
+        assert!(!triangle.contains(&vector![1.2, 0.1]));
+        assert!(
+            (triangle
+                .distance_to_edge(&vector![0.5, 0.0], &vector![0.0, 1.0])
+                .unwrap()
+                - 0.5)
+                .abs()
+                < 1e-10
+        );
+        assert_eq!(triangle.bounding_box(), Domain::normalized());
+
+        let domain = Domain::<2>::normalized();
+        assert_eq!(
+            Region::contains(&domain, &vector![0.5, 0.5]),
+            domain.contains(&vector![0.5, 0.5])
+        );
+    }
+}
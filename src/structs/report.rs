@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     fs::File,
     io::{self, BufWriter, Write},
+    time::Duration,
 };
 
 use nalgebra::SVector;
@@ -9,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::prelude::AdhererFactory;
 
-use super::{Boundary, Halfspace, WithinMode};
+use super::{Boundary, Halfspace, Parameter, ParameterSpace, Sample, WithinMode};
 
 #[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct ExplorationStatus<const N: usize, F>
@@ -23,6 +24,21 @@ where
     b_count: usize,
     boundary_points: Vec<Vec<f64>>,
     boundary_surface: Vec<Vec<f64>>,
+    #[cfg_attr(
+        feature = "io",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    sample_points: Option<Vec<Vec<f64>>>,
+    #[cfg_attr(
+        feature = "io",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    sample_classes: Option<Vec<bool>>,
+    #[cfg_attr(
+        feature = "io",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    parameters: Option<Vec<Parameter>>,
     notes: Option<String>,
 }
 
@@ -54,10 +70,33 @@ where
             b_count: b_points.len(),
             boundary_points: b_points,
             boundary_surface: n_points,
+            sample_points: None,
+            sample_classes: None,
+            parameters: None,
             notes: notes.map(|s| s.to_string()),
         }
     }
 
+    /// Includes the history of samples taken during exploration, e.g. from
+    /// `Explorer::samples()`.
+    pub fn with_samples(mut self, samples: &[Sample<N>]) -> Self {
+        self.sample_points = Some(
+            samples
+                .iter()
+                .map(|s| s.into_inner().iter().copied().collect())
+                .collect(),
+        );
+        self.sample_classes = Some(samples.iter().map(|s| s.class()).collect());
+        self
+    }
+
+    /// Names the dimensions of the boundary, so that the report can be read in
+    /// terms of the FUT's parameters. See `physical_boundary_points()`.
+    pub fn with_parameter_space(mut self, space: &ParameterSpace<N>) -> Self {
+        self.parameters = Some(space.parameters().to_vec());
+        self
+    }
+
     pub fn as_state(self) -> (Vec<Halfspace<N>>, A) {
         let boundary = self
             .boundary_points
@@ -80,6 +119,10 @@ where
         &self.adherer_type
     }
 
+    pub fn explorer_parameters(&self) -> &HashMap<String, f64> {
+        &self.explorer_parameters
+    }
+
     pub fn adherer_parameters(&self) -> &A {
         &self.adherer_parameters
     }
@@ -92,6 +135,47 @@ where
         &self.boundary_surface
     }
 
+    pub fn sample_points(&self) -> Option<&[Vec<f64>]> {
+        self.sample_points.as_deref()
+    }
+
+    pub fn sample_classes(&self) -> Option<&[bool]> {
+        self.sample_classes.as_deref()
+    }
+
+    /// The history of samples taken during exploration, or None if it was not
+    /// retained.
+    pub fn samples(&self) -> Option<Vec<Sample<N>>> {
+        let points = self.sample_points.as_ref()?;
+        let classes = self.sample_classes.as_ref()?;
+
+        Some(
+            points
+                .iter()
+                .zip(classes.iter())
+                .map(|(p, &cls)| Sample::from_class(SVector::from_column_slice(p), cls))
+                .collect(),
+        )
+    }
+
+    /// The named dimensions of the boundary, if provided.
+    pub fn parameters(&self) -> Option<&[Parameter]> {
+        self.parameters.as_deref()
+    }
+
+    /// The boundary points in the physical units of the named dimensions, or None
+    /// if the dimensions were not named.
+    pub fn physical_boundary_points(&self) -> Option<Vec<SVector<f64, N>>> {
+        let params: [Parameter; N] = self.parameters.clone()?.try_into().ok()?;
+        let space = ParameterSpace::new(params);
+        Some(
+            self.boundary_points
+                .iter()
+                .map(|b| space.to_physical(&SVector::from_column_slice(b)))
+                .collect(),
+        )
+    }
+
     pub fn notes(&self) -> Option<&String> {
         self.notes.as_ref()
     }
@@ -116,3 +200,199 @@ where
         Ok(())
     }
 }
+
+/// The resources spent by one phase of an exploration, e.g. surfacing or boundary
+/// exploration. See `EfficiencyReport`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct PhaseReport {
+    pub name: String,
+    /// The number of samples taken.
+    pub n_samples: usize,
+    /// The number of boundary points acquired.
+    pub n_boundary_points: usize,
+    /// The number of steps that lost the boundary (BLE).
+    pub n_boundary_lost: usize,
+    /// The number of steps that left the domain (OOB).
+    pub n_out_of_bounds: usize,
+    /// The wall-clock time spent.
+    pub duration: Duration,
+}
+
+impl PhaseReport {
+    pub fn new(name: &str) -> Self {
+        PhaseReport {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The boundary sampling efficiency of the phase, i.e. the number of boundary
+    /// points acquired per sample, or 0 if no samples were taken.
+    pub fn bse(&self) -> f64 {
+        if self.n_samples == 0 {
+            0.0
+        } else {
+            self.n_boundary_points as f64 / self.n_samples as f64
+        }
+    }
+}
+
+/// Summarizes how efficiently an exploration used its samples and time, overall
+/// and per phase. See `Explorer::explore_with_report()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct EfficiencyReport {
+    /// The number of samples taken.
+    pub n_samples: usize,
+    /// The number of boundary points acquired.
+    pub n_boundary_points: usize,
+    /// The boundary sampling efficiency, i.e. boundary points per sample.
+    pub bse: f64,
+    /// The number of steps that lost the boundary (BLE).
+    pub n_boundary_lost: usize,
+    /// The number of steps that left the domain (OOB).
+    pub n_out_of_bounds: usize,
+    /// The wall-clock time spent.
+    pub duration: Duration,
+    /// The breakdown of the totals by phase, in the order the phases ran.
+    pub phases: Vec<PhaseReport>,
+}
+
+impl EfficiencyReport {
+    pub fn new() -> Self {
+        EfficiencyReport::default()
+    }
+
+    /// Adds @phase to the report, e.g. the samples spent by surfacing prior to the
+    /// exploration, and includes it in the totals.
+    pub fn with_phase(mut self, phase: PhaseReport) -> Self {
+        self.n_samples += phase.n_samples;
+        self.n_boundary_points += phase.n_boundary_points;
+        self.n_boundary_lost += phase.n_boundary_lost;
+        self.n_out_of_bounds += phase.n_out_of_bounds;
+        self.duration += phase.duration;
+        self.bse = if self.n_samples == 0 {
+            0.0
+        } else {
+            self.n_boundary_points as f64 / self.n_samples as f64
+        };
+        self.phases.push(phase);
+        self
+    }
+
+    /// The phase named @name, if any.
+    pub fn phase(&self, name: &str) -> Option<&PhaseReport> {
+        self.phases.iter().find(|p| p.name == name)
+    }
+}
+
+#[cfg(feature = "io")]
+impl EfficiencyReport {
+    pub fn load(path: &str) -> io::Result<Self> {
+        let f = File::open(path)?;
+        let report = serde_json::from_reader(f)?;
+        Ok(report)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let f = File::create(path)?;
+        let mut writer = BufWriter::new(f);
+        serde_json::to_writer(&mut writer, &self)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod efficiency_report_tests {
+    use std::time::Duration;
+
+    use super::{EfficiencyReport, PhaseReport};
+
+    #[test]
+    fn totals_sum_phases() {
+        let surfacing = PhaseReport {
+            n_samples: 20,
+            n_boundary_points: 1,
+            duration: Duration::from_millis(5),
+            ..PhaseReport::new("surfacing")
+        };
+        let exploration = PhaseReport {
+            n_samples: 180,
+            n_boundary_points: 49,
+            n_boundary_lost: 2,
+            n_out_of_bounds: 3,
+            duration: Duration::from_millis(20),
+            ..PhaseReport::new("exploration")
+        };
+
+        let report = EfficiencyReport::new()
+            .with_phase(surfacing)
+            .with_phase(exploration.clone());
+
+        assert_eq!(report.n_samples, 200);
+        assert_eq!(report.n_boundary_points, 50);
+        assert_eq!(report.bse, 0.25);
+        assert_eq!((report.n_boundary_lost, report.n_out_of_bounds), (2, 3));
+        assert_eq!(report.duration, Duration::from_millis(25));
+        assert_eq!(report.phase("exploration"), Some(&exploration));
+        assert!((exploration.bse() - 49.0 / 180.0).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn round_trips_through_json() {
+        let report = EfficiencyReport::new().with_phase(PhaseReport {
+            n_samples: 10,
+            n_boundary_points: 4,
+            duration: Duration::from_millis(3),
+            ..PhaseReport::new("exploration")
+        });
+
+        let json = serde_json::to_string(&report).unwrap();
+        let loaded: EfficiencyReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, report);
+    }
+}
+
+#[cfg(test)]
+mod parameter_report_tests {
+    use std::collections::HashMap;
+
+    use nalgebra::vector;
+
+    use crate::{
+        adherers::const_adherer::ConstantAdhererFactory,
+        structs::{Halfspace, Parameter, ParameterSpace, WithinMode},
+    };
+
+    use super::ExplorationStatus;
+
+    #[test]
+    fn reports_boundary_in_physical_units() {
+        let space = ParameterSpace::new([
+            Parameter::new("speed", "m/s", 10.0, 30.0),
+            Parameter::new("heading", "rad", -0.5, 0.5),
+        ]);
+        let boundary = vec![Halfspace {
+            b: WithinMode(vector![0.5, 0.25]),
+            n: vector![1.0, 0.0],
+        }];
+
+        let status = ExplorationStatus::new(
+            "MeshExplorer",
+            "ConstantAdherer",
+            HashMap::new(),
+            ConstantAdhererFactory::new(0.1, None),
+            &boundary,
+            None,
+        );
+        assert_eq!(status.physical_boundary_points(), None);
+
+        let status = status.with_parameter_space(&space);
+        assert_eq!(status.parameters(), Some(space.parameters()));
+        let physical = status.physical_boundary_points().unwrap();
+        assert!((physical[0] - vector![20.0, -0.25]).norm() < 1e-10);
+    }
+}
@@ -1,9 +1,12 @@
 use std::{
     fmt,
     ops::{Add, Deref, Sub},
+    time::SystemTime,
 };
 
 use nalgebra::SVector;
+#[cfg(feature = "io")]
+use serde::{Deserialize, Serialize};
 
 use crate::structs::Result;
 
@@ -11,6 +14,55 @@ use crate::structs::Result;
 /// behavior. For example, safe/unsafe.
 pub trait Classifier<const N: usize> {
     fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>>;
+
+    /// Classifies several points at once. Override this for FUTs that can evaluate
+    /// points in parallel or that have a high per-request overhead.
+    /// ## Arguments
+    /// * points : The points to classify.
+    /// ## Return (Ok)
+    /// * samples : The samples, in the same order as @points.
+    /// ## Error (Err)
+    /// * SamplingError : The first error encountered, if any point failed.
+    fn classify_batch(&mut self, points: &[SVector<f64, N>]) -> Result<Vec<Sample<N>>> {
+        points.iter().map(|&p| self.classify(p)).collect()
+    }
+
+    /// Classifies @p along with the confidence in its class, if the FUT reports
+    /// one. Noise-aware components, e.g. NoiseTolerantAdherer, use the confidence
+    /// to save samples. See `ProbabilisticClassifier`.
+    /// ## Return (Ok((sample, confidence)))
+    /// * sample : The classified sample.
+    /// * confidence : 0 <= confidence <= 1, the probability that the class of
+    ///   @sample is correct, or None if unknown.
+    fn classify_with_confidence(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, Option<f64>)> {
+        Ok((self.classify(p)?, None))
+    }
+}
+
+/// A system under test that reports how confident it is in each classification,
+/// e.g. an ML model with calibrated scores. Wrap it in a
+/// `classifiers::ProbabilisticAdapter` to use it as a Classifier.
+pub trait ProbabilisticClassifier<const N: usize> {
+    /// Classifies @p and reports the confidence in its class.
+    /// ## Return (Ok((sample, confidence)))
+    /// * sample : The classified sample.
+    /// * confidence : 0 <= confidence <= 1, the probability that the class of
+    ///   @sample is correct.
+    fn classify_probabilistic(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, f64)>;
+}
+
+/// A system under test that, alongside each classification, reports a scalar proxy
+/// for how close the point is to the target performance mode, e.g. the minimum
+/// distance between two vehicles when the target mode is a collision. Lower scores
+/// are closer to the target mode. Guides optimization-driven search (see
+/// `search::falsification`) when the target mode is too rare for random search.
+pub trait ScoredClassifier<const N: usize> {
+    /// Classifies @p and scores its proximity to the target performance mode.
+    /// ## Return (Ok((sample, score)))
+    /// * sample : The classified sample.
+    /// * score : The proxy for the distance to the target performance mode, lower
+    ///   is closer.
+    fn classify_scored(&mut self, p: SVector<f64, N>) -> Result<(Sample<N>, f64)>;
 }
 
 /// A Classifier defined by a function (p: SVector) -> Result<bool>
@@ -42,21 +94,127 @@ where
 /// A point that falls within the target performance mode, i.e. when classifying this
 /// point results in true classification.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct WithinMode<const N: usize>(pub SVector<f64, N>);
 
 /// A point that falls outside the target performance mode, i.e. when classifying
 /// this point results in false classification.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub struct OutOfMode<const N: usize>(pub SVector<f64, N>);
 
 /// A sample from the system under test's input space with a corresponding target
 /// performance classification.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
 pub enum Sample<const N: usize> {
     WithinMode(WithinMode<N>),
     OutOfMode(OutOfMode<N>),
 }
 
+/// A sample with auxiliary scalars reported by the FUT alongside its class, e.g.
+/// the lap time or collision severity of a simulation. Allows boundary position to
+/// be correlated with performance magnitude.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichSample<const N: usize> {
+    pub sample: Sample<N>,
+    pub metadata: Vec<f64>,
+}
+
+impl<const N: usize> RichSample<N> {
+    pub fn new(sample: Sample<N>, metadata: Vec<f64>) -> Self {
+        RichSample { sample, metadata }
+    }
+
+    /// Discards the metadata, returning the classified sample.
+    pub fn into_sample(self) -> Sample<N> {
+        self.sample
+    }
+}
+
+impl<const N: usize> Deref for RichSample<N> {
+    type Target = Sample<N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sample
+    }
+}
+
+/// The phase of an exploration that a sample was taken during. See
+/// `AnnotatedSample`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub enum SamplePhase {
+    /// Searching for an initial boundary pair, e.g. by MonteCarloSearch.
+    GlobalSearch,
+    /// Narrowing a boundary pair down to a boundary point.
+    Surfacing,
+    /// Adhering to the boundary during exploration.
+    Adherence,
+}
+
+/// A sample with optional metadata describing when and why it was taken, for
+/// post-hoc analysis of an exploration, e.g. which phase consumed the FUT budget.
+/// See `classifiers::AnnotatingClassifier`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "io", derive(Serialize, Deserialize))]
+pub struct AnnotatedSample<const N: usize> {
+    pub sample: Sample<N>,
+    /// The order in which the sample was taken, starting from 0.
+    pub sequence: u64,
+    /// When the sample was classified.
+    pub timestamp: Option<SystemTime>,
+    /// The phase the sample was taken during.
+    pub phase: Option<SamplePhase>,
+    /// User defined labels, e.g. the name of the FUT configuration.
+    pub tags: Vec<String>,
+}
+
+impl<const N: usize> AnnotatedSample<N> {
+    pub fn new(sample: Sample<N>, sequence: u64) -> Self {
+        AnnotatedSample {
+            sample,
+            sequence,
+            timestamp: None,
+            phase: None,
+            tags: vec![],
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_phase(mut self, phase: SamplePhase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Whether the sample is labeled with @tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Discards the metadata, returning the classified sample.
+    pub fn into_sample(self) -> Sample<N> {
+        self.sample
+    }
+}
+
+impl<const N: usize> Deref for AnnotatedSample<N> {
+    type Target = Sample<N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sample
+    }
+}
+
 impl<const N: usize> Sample<N> {
     pub fn from_class(p: SVector<f64, N>, cls: bool) -> Self {
         if cls {
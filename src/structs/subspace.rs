@@ -0,0 +1,121 @@
+use std::cmp::Ordering;
+
+use nalgebra::{Const, OMatrix, SVector};
+
+use super::{Halfspace, Sample, WithinMode};
+use crate::utils::sorted_eigen;
+
+/// An M-dimensional affine subspace of an N-dimensional input space, described by
+/// an origin and M orthonormal basis vectors. The origin fixes the value of the
+/// complement of the subspace, i.e. the dimensions that are not explored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subspace<const N: usize, const M: usize> {
+    origin: SVector<f64, N>,
+    basis: OMatrix<f64, Const<N>, Const<M>>,
+}
+
+impl<const N: usize, const M: usize> Subspace<N, M> {
+    /// Constructs a Subspace from user-provided directions, which are
+    /// orthonormalized in order (Gram Schmidt Orthonormalization).
+    /// ## Panic
+    /// * If the directions are linearly dependent.
+    pub fn from_directions(origin: SVector<f64, N>, directions: [SVector<f64, N>; M]) -> Self {
+        let mut basis = OMatrix::<f64, Const<N>, Const<M>>::zeros();
+        for (i, v) in directions.iter().enumerate() {
+            let mut u = *v;
+            for j in 0..i {
+                let prev = basis.column(j).into_owned();
+                u -= prev * prev.dot(&u);
+            }
+            assert!(
+                u.norm() > 1e-10,
+                "Subspace directions must be linearly independent!"
+            );
+            basis.set_column(i, &u.normalize());
+        }
+
+        Subspace { origin, basis }
+    }
+
+    /// Identifies the M-dimensional active subspace from classified samples, such
+    /// as those acquired during global search. Each WithinMode sample is paired
+    /// with its nearest OutOfMode sample, and the principal components of the
+    /// displacements between them form the basis. Since the displacements cross
+    /// the boundary, they approximate its surface vectors (normals): the basis
+    /// spans the directions in which the class changes.
+    /// ## Arguments
+    /// * origin : The point that fixes the complement of the subspace.
+    /// * samples : Classified samples containing both classes.
+    /// ## Returns
+    /// * Some(subspace) : The subspace spanned by the M dominant directions.
+    /// * None : If @samples does not contain both WithinMode and OutOfMode samples.
+    pub fn from_samples(origin: SVector<f64, N>, samples: &[Sample<N>]) -> Option<Self> {
+        let within: Vec<_> = samples.iter().filter(|s| s.class()).collect();
+        let outside: Vec<_> = samples.iter().filter(|s| !s.class()).collect();
+
+        let mut moment = OMatrix::<f64, Const<N>, Const<N>>::zeros();
+        for t in within.iter() {
+            let nearest = outside.iter().min_by(|a, b| {
+                let da = (a.into_inner() - t.into_inner()).norm();
+                let db = (b.into_inner() - t.into_inner()).norm();
+                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+            })?;
+            let v = (nearest.into_inner() - t.into_inner()).normalize();
+            moment += v * v.transpose();
+        }
+
+        if within.is_empty() {
+            return None;
+        }
+
+        Some(Self::from_principal_components(origin, moment))
+    }
+
+    /// Constructs the subspace spanned by the M eigenvectors of @moment with the
+    /// largest eigenvalues.
+    pub fn from_principal_components(
+        origin: SVector<f64, N>,
+        moment: OMatrix<f64, Const<N>, Const<N>>,
+    ) -> Self {
+        let mut basis = OMatrix::<f64, Const<N>, Const<M>>::zeros();
+        for (i, (_, v)) in sorted_eigen(&moment).iter().take(M).enumerate() {
+            basis.set_column(i, v);
+        }
+
+        Subspace { origin, basis }
+    }
+
+    /// The point that fixes the complement of the subspace.
+    pub fn origin(&self) -> &SVector<f64, N> {
+        &self.origin
+    }
+
+    /// The orthonormal basis vectors of the subspace, as columns.
+    pub fn basis(&self) -> &OMatrix<f64, Const<N>, Const<M>> {
+        &self.basis
+    }
+
+    /// Maps a point in subspace coordinates to the full input space.
+    pub fn lift(&self, q: &SVector<f64, M>) -> SVector<f64, N> {
+        self.origin + self.basis * q
+    }
+
+    /// Maps a point in the full input space to subspace coordinates, discarding
+    /// the component that lies outside of the subspace.
+    pub fn project(&self, p: &SVector<f64, N>) -> SVector<f64, M> {
+        self.basis.transpose() * (p - self.origin)
+    }
+
+    /// Maps a halfspace explored within the subspace to the full input space.
+    pub fn lift_halfspace(&self, hs: &Halfspace<M>) -> Halfspace<N> {
+        Halfspace {
+            b: WithinMode(self.lift(&hs.b)),
+            n: (self.basis * hs.n).normalize(),
+        }
+    }
+
+    /// Maps a boundary explored within the subspace to the full input space.
+    pub fn lift_boundary(&self, boundary: &[Halfspace<M>]) -> Vec<Halfspace<N>> {
+        boundary.iter().map(|hs| self.lift_halfspace(hs)).collect()
+    }
+}
@@ -0,0 +1,467 @@
+use std::{
+    fs, io,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::{report::ExplorationStatus, AdhererFactory, Explorer, Halfspace},
+    structs::{Classifier, Result, SamplingError},
+};
+
+/// Configures how a Supervisor checkpoints and recovers an exploration.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Where to save checkpoints. Checkpoints are only kept in memory if None.
+    pub checkpoint_path: Option<String>,
+    /// The number of steps between checkpoints.
+    pub checkpoint_interval: usize,
+    /// The number of consecutive classifier failures before the classifier is
+    /// considered unhealthy and is restarted.
+    pub max_consecutive_failures: usize,
+    /// The number of restarts allowed before the run is aborted.
+    pub max_restarts: usize,
+    /// The number of steps to take before ending the run.
+    pub max_steps: Option<usize>,
+    /// The number of boundary points to acquire before ending the run.
+    pub target_boundary_count: Option<usize>,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        SupervisorConfig {
+            checkpoint_path: None,
+            checkpoint_interval: 100,
+            max_consecutive_failures: 5,
+            max_restarts: 10,
+            max_steps: None,
+            target_boundary_count: None,
+        }
+    }
+}
+
+/// Why a supervised run ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SupervisorOutcome {
+    /// The explorer ran out of boundary to explore.
+    Completed,
+    /// The target boundary count was reached.
+    TargetReached,
+    /// The step limit was reached.
+    StepLimit,
+    /// The classifier could not be recovered.
+    Aborted(String),
+}
+
+/// The consolidated report of a supervised run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorReport {
+    pub outcome: SupervisorOutcome,
+    pub steps: usize,
+    pub b_count: usize,
+    pub restarts: usize,
+    pub checkpoints: usize,
+    /// The number of checkpoints that could not be saved to the checkpoint path.
+    #[serde(default)]
+    pub checkpoint_failures: usize,
+    pub boundary_lost_errors: usize,
+    pub out_of_bounds_errors: usize,
+    pub classifier_failures: usize,
+    pub panics: usize,
+    pub elapsed: Duration,
+}
+
+/// Handles a failure to save a checkpoint to the given path.
+type CheckpointErrorHandler = Box<dyn FnMut(&str, &io::Error)>;
+
+/// Wraps the exploration process with checkpointing, classifier health
+/// monitoring, and automatic restart-and-resume after failures. Intended for long
+/// running campaigns where the FUT may crash, hang up, or respond erroneously.
+pub struct Supervisor<const N: usize, F, E, C, R>
+where
+    F: AdhererFactory<N>,
+    E: Explorer<N, F>,
+    C: Classifier<N>,
+    R: FnMut() -> Result<C>,
+{
+    explorer: E,
+    classifier: Option<C>,
+    connect: R,
+    config: SupervisorConfig,
+    checkpoint: Vec<Halfspace<N>>,
+    on_checkpoint_error: Option<CheckpointErrorHandler>,
+    _adherer_f: PhantomData<F>,
+}
+
+impl<const N: usize, F, E, C, R> Supervisor<N, F, E, C, R>
+where
+    F: AdhererFactory<N> + Serialize + for<'a> Deserialize<'a>,
+    E: Explorer<N, F>,
+    C: Classifier<N>,
+    R: FnMut() -> Result<C>,
+{
+    /// Creates a Supervisor for @explorer. If a checkpoint exists at the configured
+    /// path, the explorer resumes from its boundary.
+    /// ## Arguments
+    /// * explorer : The explorer to supervise.
+    /// * connect : Creates a new classifier, e.g. by (re)connecting to the FUT.
+    ///   Called once at startup and again for each restart.
+    /// * config : The checkpointing and recovery settings.
+    /// ## Error (Err)
+    /// * io::Error : If a checkpoint exists but cannot be read. Remove it to start
+    ///   a new run instead.
+    pub fn new(mut explorer: E, connect: R, config: SupervisorConfig) -> io::Result<Self> {
+        assert!(
+            config.checkpoint_interval > 0,
+            "Checkpoint interval must be non-zero!"
+        );
+
+        if let Some(path) = &config.checkpoint_path {
+            if Path::new(path).exists() {
+                let status = ExplorationStatus::<N, F>::load(path)?;
+                let (boundary, _) = status.as_state();
+                if !boundary.is_empty() {
+                    explorer.load_boundary(boundary);
+                }
+            }
+        }
+
+        let checkpoint = explorer.boundary().clone();
+
+        Ok(Supervisor {
+            explorer,
+            classifier: None,
+            connect,
+            config,
+            checkpoint,
+            on_checkpoint_error: None,
+            _adherer_f: PhantomData,
+        })
+    }
+
+    /// Calls @handler with the path and error whenever a checkpoint fails to save,
+    /// e.g. to log it. The run continues regardless, and the failure is counted
+    /// in the report's checkpoint_failures.
+    pub fn with_checkpoint_error_handler(
+        mut self,
+        handler: impl FnMut(&str, &io::Error) + 'static,
+    ) -> Self {
+        self.on_checkpoint_error = Some(Box::new(handler));
+        self
+    }
+
+    pub fn explorer(&self) -> &E {
+        &self.explorer
+    }
+
+    pub fn into_explorer(self) -> E {
+        self.explorer
+    }
+
+    /// Runs the exploration until it completes, reaches one of the configured
+    /// limits, or the classifier cannot be recovered.
+    /// ## Returns
+    /// * report : A summary of the run, including its failures and recoveries.
+    pub fn run(&mut self) -> SupervisorReport {
+        let start = Instant::now();
+        let mut report = SupervisorReport {
+            outcome: SupervisorOutcome::Completed,
+            steps: 0,
+            b_count: 0,
+            restarts: 0,
+            checkpoints: 0,
+            checkpoint_failures: 0,
+            boundary_lost_errors: 0,
+            out_of_bounds_errors: 0,
+            classifier_failures: 0,
+            panics: 0,
+            elapsed: Duration::ZERO,
+        };
+        let mut consecutive_failures = 0;
+
+        report.outcome = loop {
+            if self
+                .config
+                .target_boundary_count
+                .is_some_and(|target| self.explorer.boundary_count() >= target)
+            {
+                break SupervisorOutcome::TargetReached;
+            }
+            if self
+                .config
+                .max_steps
+                .is_some_and(|max_steps| report.steps >= max_steps)
+            {
+                break SupervisorOutcome::StepLimit;
+            }
+
+            if self.classifier.is_none() {
+                match (self.connect)() {
+                    Ok(c) => self.classifier = Some(c),
+                    Err(e) => {
+                        if let Err(msg) = self.restart(&mut report, &format!("{e:?}")) {
+                            break SupervisorOutcome::Aborted(msg);
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            let classifier = self.classifier.as_mut().expect("Classifier missing?");
+            let explorer = &mut self.explorer;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| explorer.step(classifier)));
+            report.steps += 1;
+
+            match result {
+                Ok(Ok(None)) => break SupervisorOutcome::Completed,
+                Ok(Ok(Some(_))) => consecutive_failures = 0,
+                Ok(Err(SamplingError::BoundaryLost)) => report.boundary_lost_errors += 1,
+                Ok(Err(SamplingError::OutOfBounds)) => report.out_of_bounds_errors += 1,
+                Ok(Err(e)) => {
+                    report.classifier_failures += 1;
+                    consecutive_failures += 1;
+                    if consecutive_failures >= self.config.max_consecutive_failures {
+                        consecutive_failures = 0;
+                        if let Err(msg) = self.restart(&mut report, &format!("{e:?}")) {
+                            break SupervisorOutcome::Aborted(msg);
+                        }
+                    }
+                }
+                Err(_) => {
+                    // The explorer's state can't be trusted after a panic, so we
+                    // fall back to the last checkpoint.
+                    report.panics += 1;
+                    consecutive_failures = 0;
+                    self.explorer.load_boundary(self.checkpoint.clone());
+                    if let Err(msg) = self.restart(&mut report, "Panicked during step") {
+                        break SupervisorOutcome::Aborted(msg);
+                    }
+                }
+            }
+
+            if report.steps.is_multiple_of(self.config.checkpoint_interval) {
+                self.save_checkpoint(&mut report);
+            }
+        };
+
+        self.save_checkpoint(&mut report);
+        report.b_count = self.explorer.boundary_count();
+        report.elapsed = start.elapsed();
+        report
+    }
+
+    fn restart(
+        &mut self,
+        report: &mut SupervisorReport,
+        reason: &str,
+    ) -> std::result::Result<(), String> {
+        self.classifier = None;
+        if report.restarts >= self.config.max_restarts {
+            return Err(format!(
+                "Exceeded {} restarts. Last failure: {reason}",
+                self.config.max_restarts
+            ));
+        }
+        report.restarts += 1;
+        Ok(())
+    }
+
+    fn save_checkpoint(&mut self, report: &mut SupervisorReport) {
+        self.checkpoint = self.explorer.boundary().clone();
+        report.checkpoints += 1;
+
+        if let Some(path) = &self.config.checkpoint_path {
+            let status = self.explorer.describe();
+            if let Err(e) = save_atomic(&status, path) {
+                report.checkpoint_failures += 1;
+                if let Some(handler) = self.on_checkpoint_error.as_mut() {
+                    handler(path, &e);
+                }
+            }
+        }
+    }
+}
+
+/// Saves @status to a temporary file beside @path before moving it into place, so
+/// that a crash mid-write leaves the previous checkpoint intact.
+fn save_atomic<const N: usize, F>(status: &ExplorationStatus<N, F>, path: &str) -> io::Result<()>
+where
+    F: AdhererFactory<N> + Serialize + for<'a> Deserialize<'a>,
+{
+    let tmp_path = format!("{path}.tmp");
+    status.save(&tmp_path)?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(all(test, feature = "sps"))]
+mod supervised_exploration {
+    use nalgebra::SVector;
+
+    use crate::{
+        prelude::{ConstantAdhererFactory, Domain, Halfspace, MeshExplorer, Sample, WithinMode},
+        sps::Sphere,
+        structs::{Classifier, Result, SamplingError},
+    };
+
+    use super::*;
+
+    const D: f64 = 0.1;
+
+    /// A sphere that stops responding after a number of classifications.
+    struct FlakySphere {
+        sphere: Sphere<3>,
+        remaining: usize,
+        panics: bool,
+    }
+
+    impl Classifier<3> for FlakySphere {
+        fn classify(&mut self, p: SVector<f64, 3>) -> Result<Sample<3>> {
+            if self.remaining == 0 {
+                if self.panics {
+                    panic!("FUT crashed");
+                }
+                return Err(SamplingError::InvalidClassifierResponse(
+                    "Connection lost".to_string(),
+                ));
+            }
+            self.remaining -= 1;
+            self.sphere.classify(p)
+        }
+    }
+
+    fn setup_explorer() -> MeshExplorer<3, ConstantAdhererFactory<3>> {
+        let root = Halfspace {
+            b: WithinMode(SVector::from_fn(|i, _| if i == 0 { 0.74 } else { 0.5 })),
+            n: SVector::from_fn(|i, _| if i == 0 { 1.0 } else { 0.0 }),
+        };
+        MeshExplorer::new(D, root, D * 0.85, ConstantAdhererFactory::new(0.26, None))
+    }
+
+    fn sphere() -> Sphere<3> {
+        Sphere::new(SVector::repeat(0.5), 0.25, Some(Domain::normalized()))
+    }
+
+    #[test]
+    fn recovers_from_failures_and_completes() {
+        let mut connections = 0;
+        let connect = || {
+            connections += 1;
+            Ok(FlakySphere {
+                sphere: sphere(),
+                remaining: 50,
+                panics: connections % 2 == 0,
+            })
+        };
+        let config = SupervisorConfig {
+            max_restarts: 100,
+            checkpoint_interval: 10,
+            ..Default::default()
+        };
+
+        let mut supervisor = Supervisor::new(setup_explorer(), connect, config).unwrap();
+        let report = supervisor.run();
+
+        assert_eq!(report.outcome, SupervisorOutcome::Completed);
+        assert!(
+            report.restarts > 0 && report.panics > 0,
+            "No failures? {report:?}"
+        );
+        assert!(report.b_count > 50, "Boundary not explored? {report:?}");
+    }
+
+    #[test]
+    fn aborts_after_max_restarts() {
+        let connect = || -> Result<FlakySphere> {
+            Err(SamplingError::InvalidClassifierResponse(
+                "Connection refused".to_string(),
+            ))
+        };
+        let config = SupervisorConfig {
+            max_restarts: 3,
+            ..Default::default()
+        };
+
+        let mut supervisor = Supervisor::new(setup_explorer(), connect, config).unwrap();
+        let report = supervisor.run();
+
+        assert!(matches!(report.outcome, SupervisorOutcome::Aborted(_)));
+        assert_eq!(report.restarts, 3);
+    }
+
+    #[test]
+    fn resumes_from_checkpoint() {
+        let path = std::env::temp_dir().join("sembas-supervisor-checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let config = SupervisorConfig {
+            checkpoint_path: Some(path.clone()),
+            target_boundary_count: Some(20),
+            ..Default::default()
+        };
+        let mut supervisor =
+            Supervisor::new(setup_explorer(), || Ok(sphere()), config.clone()).unwrap();
+        let first = supervisor.run();
+        assert_eq!(first.outcome, SupervisorOutcome::TargetReached);
+
+        let mut supervisor = Supervisor::new(
+            setup_explorer(),
+            || Ok(sphere()),
+            SupervisorConfig {
+                target_boundary_count: None,
+                ..config
+            },
+        )
+        .unwrap();
+        assert_eq!(supervisor.explorer().boundary_count(), first.b_count);
+        let second = supervisor.run();
+        assert_eq!(second.outcome, SupervisorOutcome::Completed);
+        assert!(second.b_count > first.b_count);
+        assert!(!Path::new(&format!("{path}.tmp")).exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reports_unreadable_checkpoint() {
+        let path = std::env::temp_dir().join("sembas-supervisor-corrupt-checkpoint.json");
+        std::fs::write(&path, "{\"boundary\": [").unwrap();
+
+        let config = SupervisorConfig {
+            checkpoint_path: Some(path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        let result = Supervisor::new(setup_explorer(), || Ok(sphere()), config);
+        assert!(result.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reports_checkpoint_failures() {
+        let path = std::env::temp_dir()
+            .join("sembas-supervisor-missing-dir")
+            .join("checkpoint.json");
+        let config = SupervisorConfig {
+            checkpoint_path: Some(path.to_str().unwrap().to_string()),
+            max_steps: Some(10),
+            checkpoint_interval: 5,
+            ..Default::default()
+        };
+
+        let failures = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = failures.clone();
+        let mut supervisor = Supervisor::new(setup_explorer(), || Ok(sphere()), config)
+            .unwrap()
+            .with_checkpoint_error_handler(move |_, _| counter.set(counter.get() + 1));
+        let report = supervisor.run();
+
+        assert_eq!(report.outcome, SupervisorOutcome::StepLimit);
+        assert_eq!(report.checkpoint_failures, 3);
+        assert_eq!(failures.get(), 3);
+    }
+}
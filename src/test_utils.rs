@@ -0,0 +1,50 @@
+use std::f64::consts::PI;
+
+use nalgebra::{vector, SVector};
+
+use crate::structs::{Halfspace, WithinMode};
+
+/// @n halfspaces evenly spread over a sphere by the Fibonacci lattice, with
+/// surface vectors pointing outwards.
+pub fn sphere_boundary(center: SVector<f64, 3>, radius: f64, n: usize) -> Vec<Halfspace<3>> {
+    let golden_angle = PI * (3.0 - 5.0f64.sqrt());
+    (0..n)
+        .map(|i| {
+            let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+            let r = (1.0 - z * z).sqrt();
+            let theta = golden_angle * i as f64;
+            let n = vector![r * theta.cos(), r * theta.sin(), z];
+            Halfspace {
+                b: WithinMode(center + radius * n),
+                n,
+            }
+        })
+        .collect()
+}
+
+/// @n halfspaces evenly spaced along the arc of a circle from angle @from up to,
+/// but excluding, @to, with surface vectors pointing outwards.
+pub fn arc_boundary(
+    center: SVector<f64, 2>,
+    radius: f64,
+    from: f64,
+    to: f64,
+    n: usize,
+) -> Vec<Halfspace<2>> {
+    (0..n)
+        .map(|i| {
+            let theta = from + (to - from) * i as f64 / n as f64;
+            let n = vector![theta.cos(), theta.sin()];
+            Halfspace {
+                b: WithinMode(center + radius * n),
+                n,
+            }
+        })
+        .collect()
+}
+
+/// @n halfspaces evenly spaced around a circle, with surface vectors pointing
+/// outwards.
+pub fn circle_boundary(center: SVector<f64, 2>, radius: f64, n: usize) -> Vec<Halfspace<2>> {
+    arc_boundary(center, radius, 0.0, 2.0 * PI, n)
+}
@@ -1,5 +1,5 @@
-use nalgebra::SVector;
-use std::fmt::Write;
+use nalgebra::{Const, DMatrix, OMatrix, SVector, SymmetricEigen};
+use std::{cmp::Ordering, fmt::Write};
 
 pub fn array_distance<const N: usize>(a1: &[f64; N], a2: &[f64; N]) -> f64 {
     let v1: SVector<f64, N> = unsafe {
@@ -15,6 +15,28 @@ pub fn array_distance<const N: usize>(a1: &[f64; N], a2: &[f64; N]) -> f64 {
     (v2 - v1).norm()
 }
 
+/// A hashable key for @p that matches only bitwise-identical points.
+pub fn point_key<const N: usize>(p: &SVector<f64, N>) -> [u64; N] {
+    std::array::from_fn(|i| p[i].to_bits())
+}
+
+/// The eigenvalues and eigenvectors of the symmetric matrix @m, sorted from the
+/// largest eigenvalue to the smallest.
+pub fn sorted_eigen<const N: usize>(
+    m: &OMatrix<f64, Const<N>, Const<N>>,
+) -> Vec<(f64, SVector<f64, N>)> {
+    // Eigen decomposition is not available for generic const dims.
+    let eigen = SymmetricEigen::new(DMatrix::from_column_slice(N, N, m.as_slice()));
+    let mut pairs: Vec<(f64, SVector<f64, N>)> = (0..N)
+        .map(|i| {
+            let v = SVector::from_iterator(eigen.eigenvectors.column(i).iter().copied());
+            (eigen.eigenvalues[i], v)
+        })
+        .collect();
+    pairs.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    pairs
+}
+
 pub fn vector_to_string<const N: usize>(v: &SVector<f64, N>) -> String {
     let mut result = String::new();
     write!(result, "[").unwrap();
@@ -64,6 +64,10 @@ fn const_adh_loses_boundary_when_out_of_reach() {
                 SamplingError::BoundaryLost,
                 "Unexpected error type? Expected BSE got {e:?}"
             );
+            // The probes are retained for diagnosing the failure
+            assert_eq!(adh.samples().len(), i as usize + 1);
+            assert!(adh.samples().iter().all(|s| s.class()));
+            assert!((adh.displacement().norm() - dist).abs() < 1e-10);
             return;
         }
         if i > max_steps + 1 {
@@ -123,6 +127,8 @@ fn bs_adh_loses_boundary_when_out_of_reach() {
                 SamplingError::BoundaryLost,
                 "Unexpected error type? Expected BSE got {e:?}"
             );
+            assert_eq!(adh.samples().len(), n_iter as usize);
+            assert!((adh.displacement().norm() - dist).abs() < 1e-10);
             return;
         }
     }
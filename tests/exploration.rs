@@ -1,21 +1,25 @@
 #![cfg(feature = "sps")]
 
 use std::{
+    cell::RefCell,
     f64::consts::PI,
+    rc::Rc,
     time::{Duration, Instant},
 };
 
 use nalgebra::{vector, SVector};
 use petgraph::graph::NodeIndex;
 use sembas::{
+    adherer_core::AdherenceFailure,
     adherers::const_adherer::ConstantAdhererFactory,
     boundary_tools::estimation::approx_prediction,
-    explorer_core::Explorer,
-    explorers::MeshExplorer,
-    sps::Sphere,
+    explorer_core::{ExplorationObserver, Explorer},
+    explorers::{CurvatureExplorer, Goal, MeshExplorer},
+    spatial_index::SpatialIndexKind,
+    sps::{Cube, Sphere},
     structs::{
-        backprop::Backpropagation, Classifier, Domain, Halfspace, Result, Sample, SamplingError,
-        WithinMode,
+        backprop::Backpropagation, Budget, BudgetExhausted, Classifier, Domain, Halfspace, Result,
+        Sample, SamplingError, StepOutcome, WithinMode,
     },
 };
 
@@ -51,6 +55,38 @@ fn setup_sphere<const N: usize>() -> Sphere<N> {
     Sphere::new(center, radius, Some(domain))
 }
 
+/// Counts the samples classified by the wrapped classifier.
+struct CountingClassifier<C> {
+    classifier: C,
+    n_samples: usize,
+}
+
+impl<C> CountingClassifier<C> {
+    fn new(classifier: C) -> Self {
+        CountingClassifier {
+            classifier,
+            n_samples: 0,
+        }
+    }
+}
+
+impl<const N: usize, C: Classifier<N>> Classifier<N> for CountingClassifier<C> {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        let sample = self.classifier.classify(p)?;
+        self.n_samples += 1;
+        Ok(sample)
+    }
+}
+
+/// Everything is in-mode, so every adherence loses the boundary.
+struct Everywhere;
+
+impl<const N: usize> Classifier<N> for Everywhere {
+    fn classify(&mut self, p: SVector<f64, N>) -> Result<Sample<N>> {
+        Ok(Sample::from_class(p, true))
+    }
+}
+
 fn average_vectors<const N: usize>(vectors: &Vec<SVector<f64, N>>) -> Option<SVector<f64, N>> {
     if vectors.is_empty() {
         return None; // Return None if the input vector is empty
@@ -117,6 +153,29 @@ fn fully_explores_sphere() {
     );
 }
 
+#[test]
+fn reports_sphere_exploration_efficiency() {
+    let mut sphere = setup_sphere::<D>();
+    let mut expl = setup_mesh_expl(&sphere);
+
+    let mut budget = Budget::new()
+        .with_max_duration(Duration::from_secs(5))
+        .start();
+    let (limit, report) = expl
+        .explore_with_report(&mut sphere, &mut budget)
+        .expect("Unexpected sampling error");
+    assert_eq!(
+        limit, None,
+        "Test exceeded expected time to completion. Mesh explorer got stuck?"
+    );
+    assert_eq!(report.n_samples, budget.n_samples());
+    assert_eq!(report.n_boundary_points, expl.boundary_count() - 1);
+    assert_eq!(
+        report.bse,
+        report.n_boundary_points as f64 / report.n_samples as f64
+    );
+}
+
 #[cfg(feature = "io")]
 #[test]
 fn saves_and_loads_results_correctly() {
@@ -331,3 +390,649 @@ fn prediction_tests() {
         }
     }
 }
+
+#[test]
+fn surrogate_explorer_reduces_real_samples() {
+    use sembas::{adherers::bs_adherer::BinarySearchAdhererFactory, explorers::SurrogateExplorer};
+
+    let d = 0.05;
+    let mut sphere = setup_sphere::<3>();
+    let center = *sphere.center();
+    let radius = sphere.radius();
+    let root = Halfspace {
+        b: WithinMode(center + vector![radius - 0.01, 0.0, 0.0]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    let adherer_f = BinarySearchAdhererFactory::new(PI / 2.0, 4);
+
+    let mut plain = MeshExplorer::new(d, root, d * 0.85, adherer_f);
+    let mut plain_samples = 0;
+    loop {
+        match plain.step(&mut sphere) {
+            Ok(None) => break,
+            _ => plain_samples += 1,
+        }
+    }
+
+    let mut expl = SurrogateExplorer::new(
+        MeshExplorer::new(d, root, d * 0.85, adherer_f),
+        d * 0.5,
+        10,
+        8,
+    );
+
+    let timeout = Duration::from_secs(5);
+    let start_time = Instant::now();
+    loop {
+        if let Ok(None) = expl.step(&mut sphere) {
+            break;
+        }
+        if start_time.elapsed() > timeout {
+            panic!("Test exceeded expected time to completion. Surrogate explorer got stuck?");
+        }
+    }
+
+    let stats = expl.stats();
+    assert!(
+        stats.real_samples < plain_samples,
+        "Surrogate did not reduce real samples: {} >= {plain_samples}",
+        stats.real_samples
+    );
+
+    let boundary_points = expl.boundary().iter().map(|x| *x.b).collect();
+    let center_of_mass = average_vectors(&boundary_points).expect("Empty boundary?");
+    assert!(
+        (center_of_mass - center).norm() < radius / 2.0,
+        "Surrogate-assisted exploration did not cover the sphere."
+    );
+}
+
+#[test]
+fn surrogate_explorer_forwards_history_and_stats() {
+    use sembas::explorers::SurrogateExplorer;
+
+    let mut sphere = setup_sphere::<3>();
+    let inner = setup_mesh_expl(&sphere)
+        .with_sample_history()
+        .with_dedup_tolerance(JUMP_DISTANCE * 0.5);
+    let mut expl = SurrogateExplorer::new(inner, JUMP_DISTANCE * 0.5, 10, 8);
+    while !matches!(expl.step(&mut sphere), Ok(None)) {}
+
+    let inner = expl.inner();
+    assert!(!expl.samples().unwrap().is_empty());
+    assert_eq!(expl.samples(), inner.samples());
+    assert_eq!(expl.adherence_stats().unwrap().len(), expl.boundary_count());
+    assert_eq!(expl.adherence_stats(), inner.adherence_stats());
+}
+
+#[test]
+fn curvature_explorer_prioritizes_edges() {
+    const BUDGET: usize = 200;
+    let mut cube = Cube::<3>::from_size(0.5, SVector::repeat(0.5), Some(Domain::normalized()));
+    let root = Halfspace {
+        b: WithinMode(vector![0.74, 0.5, 0.5]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE));
+    let near_edge = |b: &Vec<Halfspace<3>>| {
+        b.iter()
+            .filter(|hs| {
+                hs.b.iter()
+                    .filter(|&&x| (x - 0.25).abs() < 0.05 || (x - 0.75).abs() < 0.05)
+                    .count()
+                    >= 2
+            })
+            .count()
+    };
+
+    let mut mesh = MeshExplorer::new(0.05, root, 0.045, adherer_f);
+    while mesh.boundary_count() < BUDGET && !matches!(mesh.step(&mut cube), Ok(None)) {}
+
+    let mut curvature = CurvatureExplorer::new(0.05, root, 0.045, adherer_f);
+    while curvature.boundary_count() < BUDGET && !matches!(curvature.step(&mut cube), Ok(None)) {}
+
+    let (mesh_edges, curvature_edges) =
+        (near_edge(mesh.boundary()), near_edge(curvature.boundary()));
+    println!("Near edge, mesh: {mesh_edges}, curvature: {curvature_edges}");
+    assert!(curvature_edges > mesh_edges);
+}
+
+#[test]
+fn restricted_mesh_explorer_stays_in_subspace() {
+    let mut sphere = setup_sphere::<D>();
+    let center = *sphere.center();
+    let radius = sphere.radius();
+    let dims = [0, 4, 7];
+    let mut expl = setup_mesh_expl(&sphere).with_dimensions(&dims);
+
+    while !matches!(expl.step(&mut sphere), Ok(None)) {}
+
+    assert!(expl.boundary_count() > 10);
+    for hs in expl.boundary() {
+        for i in (0..D).filter(|i| !dims.contains(i)) {
+            let root_value = if i == 0 { 0.49 + radius } else { 0.5 };
+            assert_eq!(hs.b[i], root_value, "Left the subspace along dim {i}?");
+        }
+        assert!(((hs.b - center).norm() - radius).abs() < JUMP_DISTANCE);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_mesh_explorer_is_deterministic() {
+    let sphere = setup_sphere::<3>();
+    let center = *sphere.center();
+    let radius = sphere.radius();
+
+    let explore = || {
+        let mut classifiers = vec![sphere.clone(); 4];
+        let mut expl = setup_mesh_expl(&sphere);
+        while let Some(adherences) = expl.step_parallel(&mut classifiers) {
+            assert!(!adherences.is_empty() && adherences.len() <= 4);
+        }
+        expl.boundary_owned()
+    };
+
+    let mut sequential = setup_mesh_expl(&sphere);
+    let mut classifier = sphere.clone();
+    while !matches!(sequential.step(&mut classifier), Ok(None)) {}
+
+    let boundary = explore();
+    println!(
+        "Parallel: {}, sequential: {}",
+        boundary.len(),
+        sequential.boundary_count()
+    );
+    assert!(boundary.len() as f64 >= 0.8 * sequential.boundary_count() as f64);
+    assert_eq!(boundary, explore());
+
+    let boundary_points = boundary.iter().map(|hs| *hs.b).collect();
+    let center_of_mass = average_vectors(&boundary_points).expect("Empty boundary?");
+    assert!((center_of_mass - center).norm() < radius / 2.0);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_mesh_explorer_reports_resumed_samples_once() {
+    let sphere = setup_sphere::<3>();
+    let counts = Rc::new(RefCell::new(EventCounts::default()));
+    let mut expl = setup_mesh_expl(&sphere).with_sample_history();
+    expl.add_observer(Box::new(CountingObserver(counts.clone())));
+
+    // Leaves an adherence in progress for step_parallel() to complete, one sample
+    // after the first halfspace is acquired.
+    let mut classifier = CountingClassifier::new(sphere.clone());
+    while expl.boundary_count() < 2 {
+        expl.step(&mut classifier).unwrap();
+    }
+    expl.step(&mut classifier).unwrap();
+
+    let mut classifiers: Vec<_> = (0..4)
+        .map(|_| CountingClassifier::new(sphere.clone()))
+        .collect();
+    let adherences = expl.step_parallel(&mut classifiers).unwrap();
+
+    let n_samples = classifier.n_samples + classifiers.iter().map(|c| c.n_samples).sum::<usize>();
+    let n_parallel: usize = adherences.iter().map(|a| a.samples.len()).sum();
+    assert_eq!(n_parallel + classifier.n_samples, n_samples);
+    assert_eq!(counts.borrow().samples, n_samples);
+    assert_eq!(expl.samples().unwrap().len(), n_samples);
+}
+
+#[test]
+fn dedup_prevents_near_duplicate_halfspaces() {
+    let tolerance = JUMP_DISTANCE * 0.5;
+    let min_distance = |boundary: &Vec<Halfspace<3>>| {
+        let mut min = f64::INFINITY;
+        for (i, a) in boundary.iter().enumerate() {
+            for b in &boundary[i + 1..] {
+                min = min.min((*a.b - *b.b).norm());
+            }
+        }
+        min
+    };
+
+    let mut sphere = setup_sphere::<3>();
+    let mut expl = setup_mesh_expl(&sphere).with_dedup_tolerance(tolerance);
+    while !matches!(expl.step(&mut sphere), Ok(None)) {}
+    assert!(min_distance(expl.boundary()) > tolerance);
+
+    let mut expl = setup_mesh_expl(&sphere);
+    while !matches!(expl.step(&mut sphere), Ok(None)) {}
+    let count = expl.boundary_count();
+    let total_samples = |expl: &MeshExplorer<3, ConstantAdhererFactory<3>>| {
+        let stats = expl.adherence_stats().unwrap();
+        assert_eq!(stats.len(), expl.boundary_count());
+        stats.iter().map(|s| s.n_samples).sum::<usize>()
+    };
+    let n_samples = total_samples(&expl);
+    let n_merged = expl.compact(MARGIN);
+    println!("Merged {n_merged} of {count}");
+    assert!(n_merged > 0);
+    assert_eq!(expl.boundary_count(), count - n_merged);
+    // The adherence statistics survive compaction
+    assert_eq!(total_samples(&expl), n_samples);
+    assert!(min_distance(expl.boundary()) > MARGIN);
+    assert!(expl
+        .boundary()
+        .iter()
+        .all(|hs| (hs.n.norm() - 1.0).abs() < 1e-10));
+}
+
+#[test]
+fn budget_terminates_exploration() {
+    let mut sphere = setup_sphere::<3>();
+
+    let mut expl = setup_mesh_expl(&sphere);
+    let mut budget = Budget::new().with_max_samples(25).start();
+    let limit = expl.explore(&mut sphere, &mut budget).unwrap();
+    assert_eq!(limit, Some(BudgetExhausted::MaxSamples));
+    assert_eq!(budget.n_samples(), 25);
+    assert_eq!(
+        expl.step_within(&mut sphere, &mut budget),
+        Ok(StepOutcome::Terminated(BudgetExhausted::MaxSamples))
+    );
+
+    let mut expl = setup_mesh_expl(&sphere);
+    let mut budget = Budget::new().with_max_boundary_points(5).start();
+    let limit = expl.explore(&mut sphere, &mut budget).unwrap();
+    assert_eq!(limit, Some(BudgetExhausted::MaxBoundaryPoints));
+    assert_eq!(expl.boundary_count(), 5);
+
+    let mut expl = setup_mesh_expl(&sphere);
+    let mut budget = Budget::new()
+        .with_max_duration(Duration::from_secs(60))
+        .start();
+    assert_eq!(expl.explore(&mut sphere, &mut budget), Ok(None));
+}
+
+#[test]
+fn budget_charges_samples_of_lost_boundaries() {
+    let mut classifier = CountingClassifier::new(Everywhere);
+    let mut expl = setup_mesh_expl(&setup_sphere::<3>());
+    // The boundary is first lost on the 14th sample
+    let mut budget = Budget::new().with_max_samples(14).start();
+
+    let limit = expl.explore(&mut classifier, &mut budget).unwrap();
+    assert_eq!(limit, Some(BudgetExhausted::MaxSamples));
+    assert_eq!(classifier.n_samples, 14);
+    assert_eq!(budget.n_samples(), 14);
+}
+
+#[test]
+fn convergence_terminates_exploration() {
+    let mut sphere = setup_sphere::<3>();
+
+    // Never converges while each adherence takes fewer than 10 samples.
+    let mut expl = setup_mesh_expl(&sphere);
+    let mut budget = Budget::new().with_convergence(50, 0.1).start();
+    assert_eq!(expl.explore(&mut sphere, &mut budget), Ok(None));
+
+    let mut expl = setup_mesh_expl(&sphere);
+    let mut budget = Budget::new().with_convergence(20, 0.9).start();
+    let limit = expl.explore(&mut sphere, &mut budget).unwrap();
+    assert_eq!(limit, Some(BudgetExhausted::Converged));
+    assert!(budget.n_samples() > 20);
+    assert!(budget.progress_rate().unwrap() < 0.9);
+}
+
+#[test]
+fn iterates_until_fully_explored() {
+    let mut sphere = setup_sphere::<3>();
+
+    let mut stepped = setup_mesh_expl(&sphere);
+    let mut n_steps = 0;
+    while !matches!(stepped.step(&mut sphere), Ok(None)) {
+        n_steps += 1;
+    }
+
+    let mut expl = setup_mesh_expl(&sphere);
+    let results: Vec<Result<Sample<3>>> = expl.iter(&mut sphere).collect();
+    assert_eq!(results.len(), n_steps);
+    assert_eq!(expl.boundary(), stepped.boundary());
+    assert_eq!(expl.iter(&mut sphere).next(), None);
+}
+
+#[derive(Default)]
+struct EventCounts {
+    samples: usize,
+    boundary_found: usize,
+    pruned: usize,
+    failed: usize,
+    errors: usize,
+}
+
+struct CountingObserver(Rc<RefCell<EventCounts>>);
+
+impl ExplorationObserver<3> for CountingObserver {
+    fn on_sample(&mut self, _sample: &Sample<3>) {
+        self.0.borrow_mut().samples += 1;
+    }
+
+    fn on_boundary_found(&mut self, _hs: &Halfspace<3>) {
+        self.0.borrow_mut().boundary_found += 1;
+    }
+
+    fn on_branch_pruned(&mut self, _parent: &Halfspace<3>, _reason: &SamplingError) {
+        self.0.borrow_mut().pruned += 1;
+    }
+
+    fn on_adherence_failed(&mut self, _failure: &AdherenceFailure<3>) {
+        self.0.borrow_mut().failed += 1;
+    }
+
+    fn on_error(&mut self, _error: &SamplingError) {
+        self.0.borrow_mut().errors += 1;
+    }
+}
+
+#[test]
+fn observers_receive_exploration_events() {
+    let mut sphere = CountingClassifier::new(setup_sphere::<3>());
+    let counts = Rc::new(RefCell::new(EventCounts::default()));
+    let mut expl = setup_mesh_expl(&sphere.classifier);
+    expl.add_observer(Box::new(CountingObserver(counts.clone())));
+
+    let n_errors = expl.iter(&mut sphere).filter(|r| r.is_err()).count();
+
+    let counts = counts.borrow();
+    assert_eq!(counts.samples, sphere.n_samples);
+    assert_eq!(counts.boundary_found, expl.boundary_count() - 1);
+    assert_eq!(counts.errors, n_errors);
+    assert_eq!(counts.pruned, n_errors);
+    assert_eq!(counts.failed, n_errors);
+}
+
+#[test]
+fn adaptive_jump_refines_edges_of_cube() {
+    let mut cube = Cube::<3>::from_size(0.5, SVector::repeat(0.5), Some(Domain::normalized()));
+    let root = Halfspace {
+        b: WithinMode(vector![0.74, 0.5, 0.5]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE));
+    let explore = |d: f64, adaptive: bool, cube: &mut Cube<3>| {
+        let mut expl = MeshExplorer::new(d, root, d * 0.85, adherer_f);
+        if adaptive {
+            expl = expl.with_adaptive_jump(d / 2.0, d * 2.0, 0.1);
+        }
+        while !matches!(expl.step(cube), Ok(None)) {}
+        expl.boundary_owned()
+    };
+
+    let fine = explore(0.05, false, &mut cube);
+    let coarse = explore(0.1, false, &mut cube);
+    let adaptive = explore(0.1, true, &mut cube);
+    println!(
+        "Fine: {}, coarse: {}, adaptive: {}",
+        fine.len(),
+        coarse.len(),
+        adaptive.len()
+    );
+    assert!(coarse.len() < adaptive.len() && adaptive.len() < fine.len());
+}
+
+#[test]
+fn lost_boundary_retries_recover_edges_of_cube() {
+    let mut cube = Cube::<3>::from_size(0.5, SVector::repeat(0.5), Some(Domain::normalized()));
+    let root = Halfspace {
+        b: WithinMode(vector![0.74, 0.5, 0.5]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    // Too little rotation to turn most of the cube's edges at full jump distance
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(PI / 4.0));
+    let explore = |max_retries: u32, cube: &mut Cube<3>| {
+        let counts = Rc::new(RefCell::new(EventCounts::default()));
+        let mut expl =
+            MeshExplorer::new(0.1, root, 0.085, adherer_f).with_lost_boundary_retries(max_retries);
+        expl.add_observer(Box::new(CountingObserver(counts.clone())));
+        while !matches!(expl.step(cube), Ok(None)) {}
+
+        let stats = expl.adherence_stats().unwrap();
+        assert_eq!(stats.len(), expl.boundary_count());
+        assert!(stats.iter().all(|s| s.n_retries <= max_retries));
+        if max_retries > 0 {
+            assert!(stats.iter().any(|s| s.n_retries > 0));
+        }
+
+        let counts = counts.borrow();
+        (expl.boundary_count(), counts.pruned, counts.failed)
+    };
+
+    let (b_count, pruned, failed) = explore(0, &mut cube);
+    assert_eq!(pruned, failed);
+
+    let (retry_b_count, retry_pruned, retry_failed) = explore(3, &mut cube);
+    println!("Without retries: {b_count} ({pruned} pruned), with: {retry_b_count} ({retry_pruned} pruned)");
+    assert!(retry_failed > retry_pruned);
+    assert!(retry_b_count > b_count);
+}
+
+#[test]
+fn adherence_stats_account_for_every_sample() {
+    let mut sphere = setup_sphere::<3>();
+    let mut expl = setup_mesh_expl(&sphere);
+
+    let mut n_samples = 0;
+    for result in expl.iter(&mut sphere) {
+        result.expect("Unexpected sampling error");
+        n_samples += 1;
+    }
+
+    let stats = expl.adherence_stats().unwrap();
+    assert_eq!(stats.len(), expl.boundary_count());
+    assert_eq!(
+        stats[0].n_samples, 0,
+        "The root was not acquired by adherence"
+    );
+    assert_eq!(stats.iter().map(|s| s.n_samples).sum::<usize>(), n_samples);
+    assert!(stats[1..]
+        .iter()
+        .all(|s| s.n_samples > 1 && s.rotation > 0.0 && s.n_retries == 0));
+    assert!(stats[1..].iter().all(|s| s.bse() <= 0.5));
+}
+
+#[test]
+fn auto_margin_reduces_duplicates() {
+    let d = 0.05;
+    let mut sphere = setup_sphere::<3>();
+    let root = Halfspace {
+        b: WithinMode(vector![0.74, 0.5, 0.5]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE));
+    let n_duplicates = |boundary: &Vec<Halfspace<3>>| {
+        let mut count = 0;
+        for (i, a) in boundary.iter().enumerate() {
+            count += boundary[i + 1..]
+                .iter()
+                .filter(|b| (*a.b - *b.b).norm() < d * 0.5)
+                .count();
+        }
+        count
+    };
+
+    let mut fixed = MeshExplorer::new(d, root, d * 0.5, adherer_f);
+    while !matches!(fixed.step(&mut sphere), Ok(None)) {}
+
+    let mut auto = MeshExplorer::new(d, root, d * 0.5, adherer_f).with_auto_margin();
+    while !matches!(auto.step(&mut sphere), Ok(None)) {}
+
+    println!(
+        "Fixed: {} ({} duplicates), auto: {} ({} duplicates), margin: {}",
+        fixed.boundary_count(),
+        n_duplicates(fixed.boundary()),
+        auto.boundary_count(),
+        n_duplicates(auto.boundary()),
+        auto.margin()
+    );
+    assert!(auto.margin() > d * 0.5 && auto.margin() <= d * 0.95);
+    assert!(n_duplicates(auto.boundary()) < n_duplicates(fixed.boundary()));
+    assert_eq!(
+        auto.describe().explorer_parameters()["margin"],
+        auto.margin()
+    );
+}
+
+#[test]
+fn spatial_index_backends_explore_sphere() {
+    let mut sphere = setup_sphere::<3>();
+    let mut rtree = setup_mesh_expl(&sphere);
+    while !matches!(rtree.step(&mut sphere), Ok(None)) {}
+
+    // Exact backends find the same neighbors, so exploration is identical.
+    let mut kd_tree = setup_mesh_expl(&sphere).with_spatial_index(SpatialIndexKind::KdTree);
+    while !matches!(kd_tree.step(&mut sphere), Ok(None)) {}
+    assert_eq!(kd_tree.boundary(), rtree.boundary());
+
+    let mut hnsw =
+        setup_mesh_expl(&sphere).with_spatial_index(SpatialIndexKind::Hnsw { m: 8, ef: 32 });
+    while !matches!(hnsw.step(&mut sphere), Ok(None)) {}
+    assert_eq!(hnsw.knn_index().size(), hnsw.boundary_count());
+
+    let count = rtree.boundary_count() as f64;
+    assert!(
+        (hnsw.boundary_count() as f64 - count).abs() < 0.1 * count,
+        "HNSW explored {} halfspaces, RTree explored {count}",
+        hnsw.boundary_count()
+    );
+}
+
+#[test]
+fn goal_biased_explorer_reaches_goal_first() {
+    let d = 0.05;
+    let mut sphere = setup_sphere::<3>();
+    let goal = vector![0.5, 0.5, 0.0];
+    let root = Halfspace {
+        b: WithinMode(vector![0.74, 0.5, 0.5]),
+        n: vector![1.0, 0.0, 0.0],
+    };
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE));
+    let mean_goal_distance = |boundary: &Vec<Halfspace<3>>| {
+        boundary.iter().map(|hs| (*hs.b - goal).norm()).sum::<f64>() / boundary.len() as f64
+    };
+
+    let mut unbiased = MeshExplorer::new(d, root, d * 0.9, adherer_f);
+    let mut biased = MeshExplorer::new(d, root, d * 0.9, adherer_f).with_goal(Goal::Point(goal));
+    for _ in 0..300 {
+        let _ = unbiased.step(&mut sphere);
+        let _ = biased.step(&mut sphere);
+    }
+
+    println!(
+        "Unbiased: {}, biased: {}",
+        mean_goal_distance(unbiased.boundary()),
+        mean_goal_distance(biased.boundary())
+    );
+    assert!(mean_goal_distance(biased.boundary()) < mean_goal_distance(unbiased.boundary()));
+    assert_eq!(biased.describe().explorer_parameters()["goal_biased"], 1.0);
+
+    // The goal only changes the order, so the full boundary is still explored.
+    while !matches!(unbiased.step(&mut sphere), Ok(None)) {}
+    while !matches!(biased.step(&mut sphere), Ok(None)) {}
+    let count = unbiased.boundary_count() as f64;
+    assert!((biased.boundary_count() as f64 - count).abs() < 0.1 * count);
+}
+
+#[test]
+fn domain_walls_keep_paths_within_domain() {
+    let d = 0.1;
+    // Truncated by the domain's wall at z = 0
+    let mut sphere = Sphere::new(vector![0.5, 0.5, 0.3], 0.4, Some(Domain::normalized()));
+    let root = Halfspace {
+        b: WithinMode(vector![0.5, 0.5, 0.69]),
+        n: vector![0.0, 0.0, 1.0],
+    };
+    let adherer_f = ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE));
+
+    let explore = |mut expl: MeshExplorer<3, ConstantAdhererFactory<3>>, sphere: &mut Sphere<3>| {
+        let mut n_oob = 0;
+        loop {
+            match expl.step(sphere) {
+                Ok(None) => break,
+                Err(SamplingError::OutOfBounds) => n_oob += 1,
+                _ => (),
+            }
+        }
+        (expl, n_oob)
+    };
+
+    let (_, unbounded_oob) = explore(MeshExplorer::new(d, root, d * 0.9, adherer_f), &mut sphere);
+    let (walled, walled_oob) = explore(
+        MeshExplorer::new(d, root, d * 0.9, adherer_f).with_domain_walls(Domain::normalized()),
+        &mut sphere,
+    );
+
+    println!("OOB without walls: {unbounded_oob}, with walls: {walled_oob}");
+    assert!(walled_oob < unbounded_oob);
+    assert!(walled.boundary_count() > 1);
+    assert_eq!(walled.describe().explorer_parameters()["domain_walls"], 1.0);
+}
+
+#[test]
+fn sample_history_retains_every_sample() {
+    let mut sphere = CountingClassifier::new(setup_sphere::<3>());
+    let expl = setup_mesh_expl(&sphere.classifier);
+    assert!(expl.samples().is_none());
+    assert!(expl.describe().samples().is_none());
+
+    let mut expl = expl.with_sample_history();
+    let mut taken = vec![];
+    loop {
+        match expl.step(&mut sphere) {
+            Ok(Some(sample)) => taken.push(sample),
+            Ok(None) => break,
+            Err(_) => (),
+        }
+    }
+
+    let history = expl.samples().unwrap();
+    assert_eq!(history.len(), sphere.n_samples);
+    // Includes the samples returned by each step, in order
+    let mut remaining = history.iter();
+    assert!(taken.iter().all(|s| remaining.any(|h| h == s)));
+    assert_eq!(expl.describe().samples().as_deref(), Some(history));
+    assert_eq!(
+        expl.describe().sample_classes().map(|c| c.len()),
+        Some(sphere.n_samples)
+    );
+
+    let mut sphere = CountingClassifier::new(setup_sphere::<3>());
+    let mut expl = CurvatureExplorer::new(
+        JUMP_DISTANCE,
+        *setup_mesh_expl(&sphere.classifier)
+            .boundary()
+            .first()
+            .unwrap(),
+        MARGIN,
+        ConstantAdhererFactory::new(ADH_DELTA_ANGLE, Some(ADH_MAX_ANGLE)),
+    )
+    .with_sample_history();
+    for _ in 0..50 {
+        let _ = expl.step(&mut sphere);
+    }
+    assert_eq!(expl.samples().map(|s| s.len()), Some(sphere.n_samples));
+}
+
+#[test]
+fn sample_history_includes_failed_adherences() {
+    let mut classifier = CountingClassifier::new(Everywhere);
+    let counts = Rc::new(RefCell::new(EventCounts::default()));
+    let mut expl = setup_mesh_expl(&setup_sphere::<3>()).with_sample_history();
+    expl.add_observer(Box::new(CountingObserver(counts.clone())));
+
+    let n_lost = (0..50)
+        .filter(|_| matches!(expl.step(&mut classifier), Err(SamplingError::BoundaryLost)))
+        .count();
+    assert!(n_lost > 0);
+    assert_eq!(expl.samples().map(|s| s.len()), Some(classifier.n_samples));
+    assert_eq!(counts.borrow().samples, classifier.n_samples);
+
+    let mut classifier = CountingClassifier::new(Everywhere);
+    let mut expl = setup_mesh_expl(&setup_sphere::<3>());
+    let mut budget = Budget::new().with_max_samples(50).start();
+    let (_, report) = expl
+        .explore_with_report(&mut classifier, &mut budget)
+        .unwrap();
+    assert_eq!(report.n_samples, classifier.n_samples);
+}
@@ -7,10 +7,11 @@ use std::{
 use nalgebra::SVector;
 use sembas::{
     boundary_tools::estimation::{
-        approx_mc_volume, approx_mc_volume_intersection, approx_prediction, PredictionMode,
+        approx_mc_volume, approx_mc_volume_intersection, approx_prediction, approx_volume_with,
+        PredictionMode,
     },
     prelude::{ConstantAdhererFactory, Explorer, MeshExplorer},
-    search::global_search::{MonteCarloSearch, SearchFactory},
+    search::global_search::{MonteCarloSearch, SearchFactory, SobolSearch},
     sps::{Cube, Sphere},
     structs::{Classifier, Domain, Halfspace, WithinMode},
 };
@@ -132,6 +133,32 @@ fn volume_mc() {
     );
 }
 
+#[test]
+fn volume_with_sobol_search() {
+    const NDIM: usize = 3;
+    let mut sphere = Sphere::<NDIM>::new(SVector::repeat(0.5), 0.25, None);
+    let radius = sphere.radius();
+    let mut expl = setup_mesh_expl_sphere(&sphere);
+
+    while !matches!(expl.step(&mut sphere), Ok(None)) {}
+
+    let true_volume = 4.0 / 3.0 * PI * radius.powf(3.0);
+    let mut sobol = SobolSearch::new(Domain::normalized());
+    let est_vol = approx_volume_with(
+        PredictionMode::Intersection,
+        &[(expl.boundary(), expl.knn_index())],
+        1024,
+        1,
+        &mut sobol,
+    );
+
+    let perc_err = (est_vol - true_volume).abs() / true_volume;
+    assert!(
+        perc_err < 0.2,
+        "Excessive error in volume. err:{perc_err}, vol: {est_vol}, true vol: {true_volume}"
+    );
+}
+
 #[test]
 fn inscribed_sphere_has_no_distinct_volume() {
     const NDIM: usize = 3;